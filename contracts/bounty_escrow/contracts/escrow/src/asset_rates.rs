@@ -0,0 +1,142 @@
+//! # Cross-Asset Rate Table for Treasury Payouts
+//!
+//! A `TreasuryDestination`'s optional `payout_asset` (an extension this
+//! module assumes is wired onto that struct at the contract layer,
+//! mirroring how [`crate::multi_token`]'s allow-list is wired onto
+//! `Escrow.token` rather than owned there) lets a region request its
+//! distribution share in an asset other than the one the escrow itself is
+//! denominated in. This module owns the admin-set conversion-rate table
+//! (`set_asset_rate`/[`get_asset_rate`]) and the [`convert`] helper
+//! distribution uses to turn a destination's native-token share into its
+//! requested payout asset before transferring it.
+
+use soroban_sdk::{Address, Env};
+
+fn rate_key(from_asset: &Address, to_asset: &Address) -> crate::DataKey {
+    crate::DataKey::AssetRate(from_asset.clone(), to_asset.clone())
+}
+
+/// Admin-only: record that `1 from_asset == numerator/denominator
+/// to_asset`. Overwrites any previously configured rate for the same pair.
+/// Rejects a non-positive `numerator`/`denominator` - a rate that can't be
+/// divided by, or that would pay out nothing, would otherwise only fail
+/// later at conversion time instead of once here.
+pub fn set_asset_rate(
+    env: &Env,
+    admin: &Address,
+    from_asset: Address,
+    to_asset: Address,
+    numerator: i128,
+    denominator: i128,
+) -> Result<(), crate::Error> {
+    admin.require_auth();
+    let stored_admin: Address = env
+        .storage()
+        .instance()
+        .get(&crate::DataKey::Admin)
+        .ok_or(crate::Error::NotInitialized)?;
+    if *admin != stored_admin {
+        return Err(crate::Error::Unauthorized);
+    }
+    if numerator <= 0 || denominator <= 0 {
+        return Err(crate::Error::InvalidAmount);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&rate_key(&from_asset, &to_asset), &(numerator, denominator));
+
+    Ok(())
+}
+
+/// The configured `(numerator, denominator)` rate for `from_asset ->
+/// to_asset`, or `None` if [`set_asset_rate`] was never called for that
+/// exact ordered pair.
+pub fn get_asset_rate(env: &Env, from_asset: &Address, to_asset: &Address) -> Option<(i128, i128)> {
+    env.storage()
+        .persistent()
+        .get(&rate_key(from_asset, to_asset))
+}
+
+/// Convert `amount` of `from_asset` into `to_asset` using the stored rate.
+/// Same-asset conversions always succeed at 1:1 without needing a rate on
+/// file. Fails with `Error::AssetRateNotSet` instead of silently assuming
+/// parity when distribution needs a cross-asset rate no admin configured.
+pub fn convert(
+    env: &Env,
+    amount: i128,
+    from_asset: &Address,
+    to_asset: &Address,
+) -> Result<i128, crate::Error> {
+    if from_asset == to_asset {
+        return Ok(amount);
+    }
+
+    let (numerator, denominator) =
+        get_asset_rate(env, from_asset, to_asset).ok_or(crate::Error::AssetRateNotSet)?;
+
+    Ok((amount * numerator) / denominator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::testutils::Address as _;
+
+    fn setup(env: &Env) -> Address {
+        let admin = Address::generate(env);
+        env.storage().instance().set(&crate::DataKey::Admin, &admin);
+        admin
+    }
+
+    #[test]
+    fn test_convert_same_asset_is_identity_without_a_configured_rate() {
+        let env = Env::default();
+        let asset = Address::generate(&env);
+        assert_eq!(convert(&env, 100, &asset, &asset), Ok(100));
+    }
+
+    #[test]
+    fn test_convert_missing_rate_is_rejected() {
+        let env = Env::default();
+        let from_asset = Address::generate(&env);
+        let to_asset = Address::generate(&env);
+        assert_eq!(
+            convert(&env, 100, &from_asset, &to_asset),
+            Err(crate::Error::AssetRateNotSet)
+        );
+    }
+
+    #[test]
+    fn test_set_asset_rate_and_convert() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = setup(&env);
+        let from_asset = Address::generate(&env);
+        let to_asset = Address::generate(&env);
+
+        set_asset_rate(&env, &admin, from_asset.clone(), to_asset.clone(), 3, 2).unwrap();
+
+        assert_eq!(get_asset_rate(&env, &from_asset, &to_asset), Some((3, 2)));
+        // 100 units at a 3:2 rate -> 150
+        assert_eq!(convert(&env, 100, &from_asset, &to_asset), Ok(150));
+    }
+
+    #[test]
+    fn test_set_asset_rate_rejects_non_positive_values() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = setup(&env);
+        let from_asset = Address::generate(&env);
+        let to_asset = Address::generate(&env);
+
+        assert_eq!(
+            set_asset_rate(&env, &admin, from_asset.clone(), to_asset.clone(), 0, 2),
+            Err(crate::Error::InvalidAmount)
+        );
+        assert_eq!(
+            set_asset_rate(&env, &admin, from_asset, to_asset, 3, 0),
+            Err(crate::Error::InvalidAmount)
+        );
+    }
+}
@@ -0,0 +1,189 @@
+//! # Admin-Configurable Batch-Size Policy
+//!
+//! `batch_lock_funds`/`batch_release_funds` reject a batch above
+//! `MAX_BATCH_SIZE`, but that ceiling was a compile-time constant (20) -
+//! operators who want to tune batch throughput for a given network's
+//! resource limits had to redeploy the contract to change it. This module
+//! extracts the limit into a [`BatchPolicy`] stored under
+//! `DataKey::BatchPolicy`, with an admin-gated [`set_batch_limits`] setter.
+//! [`max_lock_batch_size`]/[`max_release_batch_size`] fall back to
+//! [`DEFAULT_MAX_BATCH_SIZE`] (20, matching the old constant) when no policy
+//! has ever been stored, so existing deployments keep their current
+//! behavior until an admin opts into a different limit.
+
+use soroban_sdk::{contracttype, Address, Env};
+
+/// `max_lock_batch_size`/`max_release_batch_size` fall back to this when
+/// [`set_batch_limits`] has never been called - the old `MAX_BATCH_SIZE`.
+pub const DEFAULT_MAX_BATCH_SIZE: u32 = 20;
+
+/// Hard safety ceiling [`set_batch_limits`] enforces regardless of what an
+/// admin asks for, so a mistaken or malicious call can't set a batch size
+/// that blows through the transaction's resource budget.
+pub const MAX_BATCH_SIZE_CEILING: u32 = 200;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchPolicy {
+    pub max_lock: u32,
+    pub max_release: u32,
+}
+
+/// Admin-only: set the batch-size ceilings `batch_lock_funds` and
+/// `batch_release_funds` validate against. Rejects 0 (a limit that accepts
+/// no batch isn't a policy, it's a lockout) and anything above
+/// [`MAX_BATCH_SIZE_CEILING`].
+pub fn set_batch_limits(
+    env: &Env,
+    admin: &Address,
+    max_lock: u32,
+    max_release: u32,
+) -> Result<(), crate::Error> {
+    admin.require_auth();
+    let stored_admin: Address = env
+        .storage()
+        .instance()
+        .get(&crate::DataKey::Admin)
+        .ok_or(crate::Error::NotInitialized)?;
+    if *admin != stored_admin {
+        return Err(crate::Error::Unauthorized);
+    }
+    if max_lock == 0 || max_release == 0 {
+        return Err(crate::Error::InvalidAmount);
+    }
+    if max_lock > MAX_BATCH_SIZE_CEILING || max_release > MAX_BATCH_SIZE_CEILING {
+        return Err(crate::Error::InvalidAmount);
+    }
+
+    env.storage().instance().set(
+        &crate::DataKey::BatchPolicy,
+        &BatchPolicy {
+            max_lock,
+            max_release,
+        },
+    );
+
+    Ok(())
+}
+
+/// The currently configured policy, or `None` if [`set_batch_limits`] was
+/// never called.
+fn stored_policy(env: &Env) -> Option<BatchPolicy> {
+    env.storage().instance().get(&crate::DataKey::BatchPolicy)
+}
+
+/// The batch-size ceiling `batch_lock_funds` should validate against:
+/// [`stored_policy`]'s `max_lock`, or [`DEFAULT_MAX_BATCH_SIZE`] if unset.
+pub fn max_lock_batch_size(env: &Env) -> u32 {
+    stored_policy(env)
+        .map(|p| p.max_lock)
+        .unwrap_or(DEFAULT_MAX_BATCH_SIZE)
+}
+
+/// The batch-size ceiling `batch_release_funds` should validate against:
+/// [`stored_policy`]'s `max_release`, or [`DEFAULT_MAX_BATCH_SIZE`] if unset.
+pub fn max_release_batch_size(env: &Env) -> u32 {
+    stored_policy(env)
+        .map(|p| p.max_release)
+        .unwrap_or(DEFAULT_MAX_BATCH_SIZE)
+}
+
+/// `batch_lock_funds`'s size check: empty or above [`max_lock_batch_size`]
+/// is `Error::InvalidBatchSize`.
+pub fn validate_lock_batch_size(env: &Env, len: u32) -> Result<(), crate::Error> {
+    if len == 0 || len > max_lock_batch_size(env) {
+        return Err(crate::Error::InvalidBatchSize);
+    }
+    Ok(())
+}
+
+/// `batch_release_funds`'s size check: empty or above
+/// [`max_release_batch_size`] is `Error::InvalidBatchSize`.
+pub fn validate_release_batch_size(env: &Env, len: u32) -> Result<(), crate::Error> {
+    if len == 0 || len > max_release_batch_size(env) {
+        return Err(crate::Error::InvalidBatchSize);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::testutils::Address as _;
+
+    fn setup_admin(env: &Env) -> Address {
+        let admin = Address::generate(env);
+        env.storage().instance().set(&crate::DataKey::Admin, &admin);
+        admin
+    }
+
+    #[test]
+    fn test_max_batch_sizes_default_without_config() {
+        let env = Env::default();
+        assert_eq!(max_lock_batch_size(&env), DEFAULT_MAX_BATCH_SIZE);
+        assert_eq!(max_release_batch_size(&env), DEFAULT_MAX_BATCH_SIZE);
+    }
+
+    #[test]
+    fn test_set_batch_limits_rejects_zero() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = setup_admin(&env);
+        assert_eq!(
+            set_batch_limits(&env, &admin, 0, 10),
+            Err(crate::Error::InvalidAmount)
+        );
+        assert_eq!(
+            set_batch_limits(&env, &admin, 10, 0),
+            Err(crate::Error::InvalidAmount)
+        );
+    }
+
+    #[test]
+    fn test_set_batch_limits_rejects_above_ceiling() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = setup_admin(&env);
+        assert_eq!(
+            set_batch_limits(&env, &admin, MAX_BATCH_SIZE_CEILING + 1, 10),
+            Err(crate::Error::InvalidAmount)
+        );
+    }
+
+    #[test]
+    fn test_set_batch_limits_rejects_non_admin() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let _admin = setup_admin(&env);
+        let other = Address::generate(&env);
+        assert_eq!(
+            set_batch_limits(&env, &other, 5, 5),
+            Err(crate::Error::Unauthorized)
+        );
+    }
+
+    #[test]
+    fn test_set_batch_limits_updates_stored_policy() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = setup_admin(&env);
+        set_batch_limits(&env, &admin, 50, 30).unwrap();
+        assert_eq!(max_lock_batch_size(&env), 50);
+        assert_eq!(max_release_batch_size(&env), 30);
+    }
+
+    #[test]
+    fn test_validate_batch_size_honours_configured_limit() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = setup_admin(&env);
+        set_batch_limits(&env, &admin, 2, 2).unwrap();
+
+        assert_eq!(validate_lock_batch_size(&env, 0), Err(crate::Error::InvalidBatchSize));
+        assert_eq!(validate_lock_batch_size(&env, 2), Ok(()));
+        assert_eq!(
+            validate_lock_batch_size(&env, 3),
+            Err(crate::Error::InvalidBatchSize)
+        );
+    }
+}
@@ -0,0 +1,442 @@
+//! # Capability Delegation (Attenuated Re-Delegation)
+//!
+//! `CapabilityIssued`/`CapabilityUsed`/`CapabilityRevoked` model a flat
+//! grant: the admin mints a capability straight to its holder, and that's
+//! the end of the tree. There's no object-capability-style way for the
+//! holder to hand a narrower slice of their own authority to someone else
+//! without going back to the admin, which is the re-delegation pattern
+//! NextGraph's capability model relies on. This module adds that layer on
+//! top of the existing flat grants.
+//!
+//! A [`DelegatedCapability`] is always strictly attenuated relative to its
+//! parent: same `action` (this crate's `CapabilityAction` has no
+//! broader/narrower relationship defined between variants yet, so "same or
+//! subset" reduces to equality until it does), `amount_limit` no greater
+//! than the parent's *remaining* amount, `max_uses` no greater than the
+//! parent's *remaining* uses, and `expires_at` no later than the parent's.
+//! Ids are minted from the same `DataKey::NextCapabilityId` counter the
+//! root issuance path uses, so root and delegated capabilities share one
+//! flat id space and `parent_capability_id` always resolves unambiguously.
+//!
+//! Spend accounting walks the whole delegated chain: [`use_delegated_capability`]
+//! decrements the used capability's own remaining budget, then walks up via
+//! `parent_capability_id`, decrementing every delegated ancestor's budget
+//! too, stopping as soon as it reaches an id with no [`DelegatedCapability`]
+//! entry (i.e. the root grant, whose own remaining budget the caller
+//! decrements directly through the existing `use_capability` path - this
+//! module only owns the attenuation layer above it).
+//!
+//! Revocation cascades the other way: [`revoke_capability_cascade`] walks
+//! down from a revoked id through `DataKey::CapabilityChildren` and marks
+//! every descendant revoked, returning the full affected set so the caller
+//! can emit one `CapabilityRevoked` per id - a parent can never stay usable
+//! once any ancestor in its chain is gone.
+
+use crate::CapabilityAction;
+use soroban_sdk::{contracttype, vec, Address, Env, Vec};
+
+/// A narrower capability re-delegated from `parent_capability_id` by its
+/// holder (the `delegator`) to a new `holder`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DelegatedCapability {
+    pub capability_id: u64,
+    pub parent_capability_id: u64,
+    pub delegator: Address,
+    pub holder: Address,
+    pub action: CapabilityAction,
+    pub amount_limit: i128,
+    pub remaining_amount: i128,
+    pub max_uses: u32,
+    pub remaining_uses: u32,
+    pub expires_at: u64,
+    pub revoked: bool,
+}
+
+/// The attenuation bounds a prospective child is checked against - the
+/// parent's action plus whatever budget it has *left*, not its original
+/// grant. The caller builds this from a root `Capability` record or from
+/// an existing [`DelegatedCapability`] via [`parent_view`]; either way the
+/// check is the same.
+#[derive(Clone, Debug)]
+pub struct ParentCapabilityView {
+    pub action: CapabilityAction,
+    pub remaining_amount: i128,
+    pub remaining_uses: u32,
+    pub expires_at: u64,
+}
+
+/// View a stored [`DelegatedCapability`] as a [`ParentCapabilityView`] so a
+/// second-generation delegation is checked against the same fields a
+/// first-generation one would be.
+pub fn parent_view(cap: &DelegatedCapability) -> ParentCapabilityView {
+    ParentCapabilityView {
+        action: cap.action.clone(),
+        remaining_amount: cap.remaining_amount,
+        remaining_uses: cap.remaining_uses,
+        expires_at: cap.expires_at,
+    }
+}
+
+fn next_capability_id(env: &Env) -> u64 {
+    let id: u64 = env
+        .storage()
+        .instance()
+        .get(&crate::DataKey::NextCapabilityId)
+        .unwrap_or(0)
+        + 1;
+    env.storage()
+        .instance()
+        .set(&crate::DataKey::NextCapabilityId, &id);
+    id
+}
+
+fn load_delegated(env: &Env, capability_id: u64) -> Option<DelegatedCapability> {
+    env.storage()
+        .persistent()
+        .get(&crate::DataKey::DelegatedCapability(capability_id))
+}
+
+fn store_delegated(env: &Env, cap: &DelegatedCapability) {
+    env.storage()
+        .persistent()
+        .set(&crate::DataKey::DelegatedCapability(cap.capability_id), cap);
+}
+
+fn children_of(env: &Env, capability_id: u64) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&crate::DataKey::CapabilityChildren(capability_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Mint a child capability attenuated from `parent`, requiring `delegator`'s
+/// auth (the holder re-delegating, not necessarily the original admin).
+/// Rejects any requested limit that isn't strictly within what `parent` has
+/// left. Returns the new capability id.
+pub fn delegate_capability(
+    env: &Env,
+    delegator: &Address,
+    parent_capability_id: u64,
+    parent: &ParentCapabilityView,
+    holder: Address,
+    action: CapabilityAction,
+    amount_limit: i128,
+    max_uses: u32,
+    expires_at: u64,
+) -> Result<u64, crate::Error> {
+    delegator.require_auth();
+
+    if action != parent.action {
+        return Err(crate::Error::Unauthorized);
+    }
+    if amount_limit <= 0 || amount_limit > parent.remaining_amount {
+        return Err(crate::Error::InvalidAmount);
+    }
+    if max_uses == 0 || max_uses > parent.remaining_uses {
+        return Err(crate::Error::InvalidAmount);
+    }
+    if expires_at > parent.expires_at {
+        return Err(crate::Error::InvalidAmount);
+    }
+
+    let child_id = next_capability_id(env);
+    let child = DelegatedCapability {
+        capability_id: child_id,
+        parent_capability_id,
+        delegator: delegator.clone(),
+        holder,
+        action,
+        amount_limit,
+        remaining_amount: amount_limit,
+        max_uses,
+        remaining_uses: max_uses,
+        expires_at,
+        revoked: false,
+    };
+    store_delegated(env, &child);
+
+    let mut children = children_of(env, parent_capability_id);
+    children.push_back(child_id);
+    env.storage().persistent().set(
+        &crate::DataKey::CapabilityChildren(parent_capability_id),
+        &children,
+    );
+
+    Ok(child_id)
+}
+
+/// Spend `amount` against `capability_id` and every delegated ancestor
+/// above it, leaving the root grant (outside this module's storage) for the
+/// caller to decrement through the existing `use_capability` path. Fails
+/// without mutating anything if the capability is revoked, expired-out of
+/// uses, or `amount` exceeds its remaining budget.
+pub fn use_delegated_capability(
+    env: &Env,
+    capability_id: u64,
+    amount: i128,
+) -> Result<(), crate::Error> {
+    let mut chain = Vec::new(env);
+    let mut current_id = capability_id;
+    loop {
+        let cap = load_delegated(env, current_id).ok_or(crate::Error::Unauthorized)?;
+        if cap.revoked {
+            return Err(crate::Error::Unauthorized);
+        }
+        if cap.remaining_uses == 0 || amount > cap.remaining_amount {
+            return Err(crate::Error::InvalidAmount);
+        }
+        let parent_id = cap.parent_capability_id;
+        chain.push_back(cap);
+
+        match load_delegated(env, parent_id) {
+            Some(_) => current_id = parent_id,
+            None => break,
+        }
+    }
+
+    for cap in chain.iter() {
+        let mut cap = cap;
+        cap.remaining_amount -= amount;
+        cap.remaining_uses -= 1;
+        store_delegated(env, &cap);
+    }
+
+    Ok(())
+}
+
+/// Mark `capability_id` and every descendant reachable through
+/// `DataKey::CapabilityChildren` as revoked, depth-first. Returns the full
+/// affected id set (including `capability_id` itself) so the caller can
+/// emit one `CapabilityRevoked` per id - the root revocation event for
+/// `capability_id` is the caller's own responsibility if it isn't itself a
+/// [`DelegatedCapability`].
+pub fn revoke_capability_cascade(env: &Env, capability_id: u64) -> Vec<u64> {
+    let mut affected = Vec::new(env);
+    let mut stack = vec![env, capability_id];
+
+    while let Some(id) = stack.pop_back() {
+        affected.push_back(id);
+
+        if let Some(mut cap) = load_delegated(env, id) {
+            cap.revoked = true;
+            store_delegated(env, &cap);
+        }
+
+        for child in children_of(env, id).iter() {
+            stack.push_back(child);
+        }
+    }
+
+    affected
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::testutils::Address as _;
+
+    fn root_view(action: CapabilityAction) -> ParentCapabilityView {
+        ParentCapabilityView {
+            action,
+            remaining_amount: 1_000,
+            remaining_uses: 5,
+            expires_at: 10_000,
+        }
+    }
+
+    #[test]
+    fn test_delegate_capability_rejects_limits_above_parent_remaining() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let delegator = Address::generate(&env);
+        let holder = Address::generate(&env);
+        let parent = root_view(CapabilityAction::Release);
+
+        assert_eq!(
+            delegate_capability(
+                &env,
+                &delegator,
+                1,
+                &parent,
+                holder.clone(),
+                CapabilityAction::Release,
+                1_001,
+                1,
+                5_000,
+            ),
+            Err(crate::Error::InvalidAmount)
+        );
+        assert_eq!(
+            delegate_capability(
+                &env,
+                &delegator,
+                1,
+                &parent,
+                holder.clone(),
+                CapabilityAction::Release,
+                100,
+                6,
+                5_000,
+            ),
+            Err(crate::Error::InvalidAmount)
+        );
+        assert_eq!(
+            delegate_capability(
+                &env,
+                &delegator,
+                1,
+                &parent,
+                holder,
+                CapabilityAction::Release,
+                100,
+                1,
+                10_001,
+            ),
+            Err(crate::Error::InvalidAmount)
+        );
+    }
+
+    #[test]
+    fn test_delegate_capability_rejects_mismatched_action() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let delegator = Address::generate(&env);
+        let holder = Address::generate(&env);
+        let parent = root_view(CapabilityAction::Release);
+
+        assert_eq!(
+            delegate_capability(
+                &env,
+                &delegator,
+                1,
+                &parent,
+                holder,
+                CapabilityAction::Refund,
+                100,
+                1,
+                5_000,
+            ),
+            Err(crate::Error::Unauthorized)
+        );
+    }
+
+    #[test]
+    fn test_use_delegated_capability_decrements_whole_chain() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let delegator = Address::generate(&env);
+        let holder_a = Address::generate(&env);
+        let holder_b = Address::generate(&env);
+        let parent = root_view(CapabilityAction::Release);
+
+        let child_id = delegate_capability(
+            &env,
+            &delegator,
+            1,
+            &parent,
+            holder_a,
+            CapabilityAction::Release,
+            500,
+            3,
+            5_000,
+        )
+        .unwrap();
+        let child = load_delegated(&env, child_id).unwrap();
+        let grandchild_id = delegate_capability(
+            &env,
+            &delegator,
+            child_id,
+            &parent_view(&child),
+            holder_b,
+            CapabilityAction::Release,
+            200,
+            2,
+            4_000,
+        )
+        .unwrap();
+
+        use_delegated_capability(&env, grandchild_id, 50).unwrap();
+
+        let grandchild = load_delegated(&env, grandchild_id).unwrap();
+        assert_eq!(grandchild.remaining_amount, 150);
+        assert_eq!(grandchild.remaining_uses, 1);
+
+        let child_after = load_delegated(&env, child_id).unwrap();
+        assert_eq!(child_after.remaining_amount, 450);
+        assert_eq!(child_after.remaining_uses, 2);
+    }
+
+    #[test]
+    fn test_use_delegated_capability_rejects_amount_over_budget() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let delegator = Address::generate(&env);
+        let holder = Address::generate(&env);
+        let parent = root_view(CapabilityAction::Release);
+
+        let child_id = delegate_capability(
+            &env,
+            &delegator,
+            1,
+            &parent,
+            holder,
+            CapabilityAction::Release,
+            100,
+            1,
+            5_000,
+        )
+        .unwrap();
+
+        assert_eq!(
+            use_delegated_capability(&env, child_id, 101),
+            Err(crate::Error::InvalidAmount)
+        );
+    }
+
+    #[test]
+    fn test_revoke_capability_cascade_marks_whole_subtree() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let delegator = Address::generate(&env);
+        let holder_a = Address::generate(&env);
+        let holder_b = Address::generate(&env);
+        let parent = root_view(CapabilityAction::Release);
+
+        let child_id = delegate_capability(
+            &env,
+            &delegator,
+            1,
+            &parent,
+            holder_a,
+            CapabilityAction::Release,
+            500,
+            3,
+            5_000,
+        )
+        .unwrap();
+        let child = load_delegated(&env, child_id).unwrap();
+        let grandchild_id = delegate_capability(
+            &env,
+            &delegator,
+            child_id,
+            &parent_view(&child),
+            holder_b,
+            CapabilityAction::Release,
+            200,
+            2,
+            4_000,
+        )
+        .unwrap();
+
+        let affected = revoke_capability_cascade(&env, child_id);
+        assert_eq!(affected.len(), 2);
+        assert!(load_delegated(&env, child_id).unwrap().revoked);
+        assert!(load_delegated(&env, grandchild_id).unwrap().revoked);
+
+        assert_eq!(
+            use_delegated_capability(&env, grandchild_id, 10),
+            Err(crate::Error::Unauthorized)
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//! # Foundation Clawback (Escrow Termination)
+//!
+//! Neither [`crate::vesting::claim_vested`] nor a plain refund is fair when
+//! a bounty is cancelled mid-work: the contributor has already earned
+//! whatever vested so far, but the depositor shouldn't have to wait out the
+//! full schedule to get the rest back. Borrowing the foundation
+//! `terminate_vesting` model this module is named after,
+//! [`terminate_escrow`] freezes the schedule as of `now` (via
+//! [`crate::vesting::terminate_vesting`]) and atomically splits what's left
+//! in escrow: the vested-but-unclaimed portion stays claimable by the
+//! contributor, and the never-to-vest remainder is handed back to the
+//! entrypoint as `depositor_refund` to transfer out immediately.
+//!
+//! This module only computes the split - the entrypoint (not present in
+//! this tree) is expected to perform the `depositor_refund` transfer, set
+//! the escrow's status to `EscrowStatus::Terminated`, and reject
+//! `try_release_schedule_automatic`/`try_refund` once an escrow is in that
+//! status, the same way it already rejects them for `Released`/`Refunded`.
+
+use soroban_sdk::Env;
+
+use crate::vesting::{self, VestingSchedule};
+
+/// The atomic outcome of a [`terminate_escrow`] call.
+pub struct TerminationSplit {
+    pub schedule: VestingSchedule,
+    /// Already vested as of `now` but not yet claimed; still claimable by
+    /// the contributor via [`crate::vesting::claim_vested`].
+    pub contributor_claimable: i128,
+    /// Never vested now that the schedule is frozen; owed back to the
+    /// depositor immediately.
+    pub depositor_refund: i128,
+}
+
+/// Admin-authorized foundation clawback for `schedule_id` on `bounty_id`.
+/// Delegates the freeze to [`crate::vesting::terminate_vesting`] - which
+/// already rejects a schedule that's terminated twice - then reads off the
+/// vested-but-unclaimed portion so the entrypoint can pay out both halves
+/// of the split in the same transaction.
+pub fn terminate_escrow(
+    env: &Env,
+    bounty_id: u64,
+    schedule_id: u64,
+    now: u64,
+) -> Result<TerminationSplit, crate::Error> {
+    let (schedule, depositor_refund) = vesting::terminate_vesting(env, bounty_id, schedule_id, now)?;
+    let contributor_claimable = vesting::claimable_amount(&schedule, now);
+
+    Ok(TerminationSplit {
+        schedule,
+        contributor_claimable,
+        depositor_refund,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::Address;
+
+    fn schedule(env: &Env, recipient: Address) -> VestingSchedule {
+        vesting::create_vesting_schedule(env, 1, recipient, 1_000, 100, 50, 400).unwrap()
+    }
+
+    #[test]
+    fn test_terminate_escrow_splits_vested_and_unvested_portions() {
+        let env = Env::default();
+        let recipient = Address::generate(&env);
+        schedule(&env, recipient);
+
+        // start=100, cliff=50, duration=400 -> fully vested at 500.
+        // At now=300, 500/1000 has vested.
+        let split = terminate_escrow(&env, 1, 1, 300).unwrap();
+        assert_eq!(split.contributor_claimable, 500);
+        assert_eq!(split.depositor_refund, 500);
+        assert_eq!(split.schedule.terminated_at, Some(300));
+    }
+
+    #[test]
+    fn test_terminate_escrow_after_partial_claim_only_refunds_unvested() {
+        let env = Env::default();
+        let recipient = Address::generate(&env);
+        schedule(&env, recipient);
+
+        vesting::claim_vested(&env, 1, 1, 300).unwrap();
+
+        let split = terminate_escrow(&env, 1, 1, 300).unwrap();
+        assert_eq!(split.contributor_claimable, 0);
+        assert_eq!(split.depositor_refund, 500);
+    }
+
+    #[test]
+    fn test_terminate_escrow_rejects_double_termination() {
+        let env = Env::default();
+        let recipient = Address::generate(&env);
+        schedule(&env, recipient);
+
+        terminate_escrow(&env, 1, 1, 300).unwrap();
+        assert_eq!(
+            terminate_escrow(&env, 1, 1, 400),
+            Err(crate::Error::VestingAlreadyTerminated)
+        );
+    }
+
+    #[test]
+    fn test_terminate_escrow_missing_schedule_returns_not_found() {
+        let env = Env::default();
+        assert_eq!(
+            terminate_escrow(&env, 1, 1, 300),
+            Err(crate::Error::VestingScheduleNotFound)
+        );
+    }
+}
@@ -0,0 +1,273 @@
+//! # Deprecation / Kill-Switch Migration
+//!
+//! `set_deprecated` lets an admin flip the contract into a deprecated state
+//! and record a `migration_target`, but nothing has ever acted on that
+//! target - `test_deprecation.rs`'s tests only read it back.
+//! [`migrate_escrow`]/[`batch_migrate`] turn that metadata into a working
+//! migration path: once deprecated, a `Capability::Migrate` holder can drain
+//! a `Locked` escrow's `remaining_amount` to `migration_target`, hand that
+//! contract enough context via a `receive_migrated_escrow` cross-contract
+//! call to recreate it on the other side, and mark the local copy
+//! `EscrowStatus::Migrated` so it can never be released or refunded twice.
+//!
+//! `Escrow` doesn't store a `contributor` - it's only ever a call parameter
+//! to `release_funds`/`partial_release` - so [`migrate_escrow`] takes it the
+//! same way; the operator draining a sunset contract is expected to supply
+//! it from their own off-chain bounty records.
+
+use soroban_sdk::{contracttype, token, Address, Env, IntoVal, Symbol, Val, Vec};
+
+use crate::upgrade_safety::{require_capability, Capability};
+use crate::{DataKey, Error, Escrow, EscrowStatus};
+
+/// Entrypoint `receive_migrated_escrow` is expected to expose on
+/// `migration_target`.
+const RECEIVE_MIGRATED_ESCROW: &str = "receive_migrated_escrow";
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeprecationStatus {
+    pub deprecated: bool,
+    pub migration_target: Option<Address>,
+}
+
+/// `Capability::Admin`-gated: flip the kill switch and (optionally) record
+/// where [`migrate_escrow`] should drain locked funds to. Passing
+/// `migration_target: None` while `deprecated` stays `true` is allowed - it
+/// just leaves `migrate_escrow` unusable until a target is configured.
+pub fn set_deprecated(
+    env: &Env,
+    admin: &Address,
+    deprecated: bool,
+    migration_target: Option<Address>,
+) -> Result<(), Error> {
+    admin.require_auth();
+    require_capability(env, Capability::Admin, admin)?;
+
+    env.storage().instance().set(
+        &DataKey::DeprecationStatus,
+        &DeprecationStatus {
+            deprecated,
+            migration_target: migration_target.clone(),
+        },
+    );
+
+    crate::events::emit_deprecation_state_changed(
+        env,
+        crate::events::DeprecationStateChanged {
+            deprecated,
+            migration_target,
+            admin: admin.clone(),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(())
+}
+
+/// Current kill-switch state - `deprecated: false, migration_target: None`
+/// for a contract that has never called `set_deprecated`.
+pub fn get_deprecation_status(env: &Env) -> DeprecationStatus {
+    env.storage()
+        .instance()
+        .get(&DataKey::DeprecationStatus)
+        .unwrap_or(DeprecationStatus {
+            deprecated: false,
+            migration_target: None,
+        })
+}
+
+/// `Capability::Migrate`-gated (falls back to the plain admin check, same
+/// as every other capability here): drains `bounty_id`'s `remaining_amount`
+/// to the configured `migration_target`, tells that contract about the
+/// escrow it just received via `receive_migrated_escrow(bounty_id,
+/// depositor, contributor, amount, deadline, status)`, and marks the local
+/// copy `EscrowStatus::Migrated`.
+///
+/// # Errors
+/// * `Error::NotPaused` (reused here as "kill switch not engaged", or "no
+///   migration target configured")
+/// * `Error::EscrowNotFound` - no such escrow
+/// * `Error::FundsNotLocked` - the escrow isn't `Locked` (already settled,
+///   disputed, or already migrated)
+pub fn migrate_escrow(
+    env: &Env,
+    caller: &Address,
+    bounty_id: u64,
+    contributor: &Address,
+) -> Result<Escrow, Error> {
+    caller.require_auth();
+    require_capability(env, Capability::Migrate, caller)?;
+
+    let status = get_deprecation_status(env);
+    if !status.deprecated {
+        return Err(Error::NotPaused);
+    }
+    let target = status.migration_target.ok_or(Error::NotPaused)?;
+
+    let mut escrow = crate::errors::load_escrow(env, bounty_id)?;
+    if escrow.status != EscrowStatus::Locked {
+        return Err(Error::FundsNotLocked);
+    }
+
+    let token_address: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Token)
+        .ok_or(Error::NotInitialized)?;
+    let amount = escrow.remaining_amount;
+    if amount > 0 {
+        token::Client::new(env, &token_address).transfer(
+            &env.current_contract_address(),
+            &target,
+            &amount,
+        );
+    }
+
+    let func = Symbol::new(env, RECEIVE_MIGRATED_ESCROW);
+    let args: Vec<Val> = Vec::from_array(
+        env,
+        [
+            bounty_id.into_val(env),
+            escrow.depositor.clone().into_val(env),
+            contributor.clone().into_val(env),
+            amount.into_val(env),
+            escrow.deadline.into_val(env),
+            escrow.status.clone().into_val(env),
+        ],
+    );
+    env.invoke_contract::<()>(&target, &func, args);
+
+    escrow.status = EscrowStatus::Migrated;
+    escrow.remaining_amount = 0;
+    env.storage()
+        .persistent()
+        .set(&DataKey::Escrow(bounty_id), &escrow);
+
+    crate::events::emit_escrow_migrated(
+        env,
+        crate::events::EscrowMigrated {
+            bounty_id,
+            target: target.clone(),
+            amount,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(escrow)
+}
+
+/// Best-effort [`migrate_escrow`] over many ids at once, for an operator
+/// draining a deprecated contract. `items` pairs each `bounty_id` with the
+/// contributor `migrate_escrow` needs (see the module doc for why that can't
+/// be read back from storage). An id that fails - already migrated, not
+/// `Locked`, whatever - is skipped rather than aborting the whole batch, the
+/// same "don't let one bad id block the rest" stance `solvency_audit`'s
+/// `missing_count` takes; the returned `Vec<u64>` lists only the ids that
+/// actually migrated.
+pub fn batch_migrate(
+    env: &Env,
+    caller: &Address,
+    items: Vec<(u64, Address)>,
+) -> Vec<u64> {
+    let mut migrated: Vec<u64> = Vec::new(env);
+    for (bounty_id, contributor) in items.iter() {
+        if migrate_escrow(env, caller, bounty_id, &contributor).is_ok() {
+            migrated.push_back(bounty_id);
+        }
+    }
+    migrated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::testutils::Address as _;
+
+    fn setup(env: &Env) -> (Address, Address) {
+        let admin = Address::generate(env);
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        let token_admin = Address::generate(env);
+        let token_id = env.register_stellar_asset_contract_v2(token_admin).address();
+        env.storage().instance().set(&DataKey::Token, &token_id);
+        (admin, token_id)
+    }
+
+    fn store_escrow(env: &Env, bounty_id: u64, depositor: &Address, remaining_amount: i128) {
+        env.storage().persistent().set(
+            &DataKey::Escrow(bounty_id),
+            &Escrow {
+                depositor: depositor.clone(),
+                amount: remaining_amount,
+                status: EscrowStatus::Locked,
+                deadline: 1_000,
+                refund_history: soroban_sdk::vec![env],
+                remaining_amount,
+            },
+        );
+    }
+
+    #[test]
+    fn test_set_deprecated_persists_status_and_target() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (admin, _) = setup(&env);
+        let target = Address::generate(&env);
+
+        set_deprecated(&env, &admin, true, Some(target.clone())).unwrap();
+
+        let status = get_deprecation_status(&env);
+        assert!(status.deprecated);
+        assert_eq!(status.migration_target, Some(target));
+    }
+
+    #[test]
+    fn test_get_deprecation_status_defaults_to_not_deprecated() {
+        let env = Env::default();
+        let status = get_deprecation_status(&env);
+        assert!(!status.deprecated);
+        assert_eq!(status.migration_target, None);
+    }
+
+    #[test]
+    fn test_migrate_escrow_rejects_when_not_deprecated() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (admin, _) = setup(&env);
+        let depositor = Address::generate(&env);
+        let contributor = Address::generate(&env);
+        store_escrow(&env, 1, &depositor, 1_000);
+
+        assert_eq!(
+            migrate_escrow(&env, &admin, 1, &contributor),
+            Err(Error::NotPaused)
+        );
+    }
+
+    #[test]
+    fn test_migrate_escrow_rejects_non_locked_escrow() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (admin, _) = setup(&env);
+        let depositor = Address::generate(&env);
+        let contributor = Address::generate(&env);
+        let target = Address::generate(&env);
+        set_deprecated(&env, &admin, true, Some(target)).unwrap();
+        env.storage().persistent().set(
+            &DataKey::Escrow(1),
+            &Escrow {
+                depositor: depositor.clone(),
+                amount: 1_000,
+                status: EscrowStatus::Released,
+                deadline: 1_000,
+                refund_history: soroban_sdk::vec![&env],
+                remaining_amount: 0,
+            },
+        );
+
+        assert_eq!(
+            migrate_escrow(&env, &admin, 1, &contributor),
+            Err(Error::FundsNotLocked)
+        );
+    }
+}
@@ -0,0 +1,324 @@
+//! # On-Chain Dispute Resolution (Arbitrated Split Settlement)
+//!
+//! `test_dispute_resolution_flows` used to fake a dispute by publishing a
+//! raw `dispute`/`open` event and then calling `release_funds` directly -
+//! there was no actual dispute state anywhere in the contract, which is why
+//! `test_open_dispute_blocks_refund_before_resolution` passed without ever
+//! actually blocking anything. [`open_dispute`] gives that real state: it
+//! moves the escrow to `EscrowStatus::Disputed` and records who raised it,
+//! and [`require_not_disputed`] is the guard `release_funds`,
+//! `partial_release`, and `refund` (not present in this tree) are expected
+//! to call first, so funds stay frozen until [`resolve_dispute`] runs.
+//!
+//! Resolution is gated by `Capability::Arbitrate` - the same delegated-role
+//! mechanism `crate::pausable::pause` uses for `Capability::Pause` - and
+//! falls back to the plain admin until an operator delegates it to a
+//! dedicated arbitrator. The arbitrator picks an arbitrary split of
+//! `remaining_amount` between the contributor and the escrow's depositor
+//! (the two amounts must sum to exactly `remaining_amount`), both legs
+//! transfer in the same call, and the escrow's final status reflects which
+//! side actually received funds: `Refunded` if the depositor got
+//! everything, `Released` otherwise.
+
+use soroban_sdk::{contracttype, token, Address, Env};
+
+use crate::upgrade_safety::{require_capability, Capability};
+use crate::{DataKey, Error, Escrow, EscrowStatus};
+
+/// Records who raised a dispute and when. Kept separate from `Escrow`
+/// rather than folded into it. The key exists only while a dispute is open
+/// (`resolve_dispute` removes it), so its presence tells an open dispute
+/// apart from none; "never disputed" and "disputed and already resolved"
+/// both leave no key behind.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Dispute {
+    pub bounty_id: u64,
+    pub raiser: Address,
+    pub opened_at: u64,
+}
+
+/// Moves `bounty_id` into `EscrowStatus::Disputed`, freezing
+/// `release_funds`/`partial_release`/`refund` until `resolve_dispute` runs.
+/// `raiser` must be the escrow's depositor or `contributor` - letting any
+/// authenticated third party freeze an unrelated bounty would be a
+/// straightforward DoS against a funds-holding contract. `Escrow` doesn't
+/// store a `contributor` (see the module doc on `crate::deprecation` for
+/// why), so - same as `migrate_escrow` - the caller supplies it here from
+/// their own off-chain bounty records.
+///
+/// # Errors
+/// * `Error::EscrowNotFound` - no such escrow
+/// * `Error::FundsNotLocked` - the escrow isn't currently `Locked` (already
+///   settled, or a dispute is already open on it)
+/// * `Error::Unauthorized` - `raiser` is neither the depositor nor `contributor`
+pub fn open_dispute(
+    env: &Env,
+    bounty_id: u64,
+    raiser: &Address,
+    contributor: &Address,
+) -> Result<(), Error> {
+    raiser.require_auth();
+
+    let mut escrow = crate::errors::load_escrow(env, bounty_id)?;
+    if escrow.status != EscrowStatus::Locked {
+        return Err(Error::FundsNotLocked);
+    }
+    if raiser != &escrow.depositor && raiser != contributor {
+        return Err(Error::Unauthorized);
+    }
+
+    escrow.status = EscrowStatus::Disputed;
+    env.storage()
+        .persistent()
+        .set(&DataKey::Escrow(bounty_id), &escrow);
+
+    let opened_at = env.ledger().timestamp();
+    env.storage().instance().set(
+        &DataKey::Dispute(bounty_id),
+        &Dispute {
+            bounty_id,
+            raiser: raiser.clone(),
+            opened_at,
+        },
+    );
+
+    let (state_hash, _) = crate::hashchain::record_operation(
+        env,
+        crate::hashchain::op_tags::OPEN_DISPUTE,
+        &(bounty_id, raiser.clone()),
+    );
+
+    crate::events::emit_dispute_opened(
+        env,
+        crate::events::DisputeOpened {
+            bounty_id,
+            raiser: raiser.clone(),
+            timestamp: opened_at,
+            state_hash,
+        },
+    );
+
+    Ok(())
+}
+
+/// The check `release_funds`, `partial_release`, and `refund` should run
+/// first: `Error::DisputeOpen` while `bounty_id` has an unresolved dispute,
+/// `Ok(())` otherwise.
+pub fn require_not_disputed(env: &Env, bounty_id: u64) -> Result<(), Error> {
+    if env.storage().instance().has(&DataKey::Dispute(bounty_id)) {
+        return Err(Error::DisputeOpen);
+    }
+    Ok(())
+}
+
+/// Arbitrator-only (`Capability::Arbitrate`): settles an open dispute by
+/// splitting `remaining_amount` between `contributor` and the escrow's
+/// depositor in whatever proportion the arbitrator decides, transferring
+/// both legs immediately.
+///
+/// # Errors
+/// * `Error::EscrowNotFound` - no such escrow
+/// * `Error::DisputeNotFound` - `bounty_id` has no open dispute
+/// * `Error::Unauthorized` - caller doesn't hold `Capability::Arbitrate`
+/// * `Error::InvalidAmount` - either amount is negative, or they don't sum
+///   to exactly `remaining_amount`
+pub fn resolve_dispute(
+    env: &Env,
+    arbitrator: &Address,
+    bounty_id: u64,
+    contributor: &Address,
+    to_contributor: i128,
+    to_depositor: i128,
+) -> Result<Escrow, Error> {
+    arbitrator.require_auth();
+    require_capability(env, Capability::Arbitrate, arbitrator)?;
+
+    if !env.storage().instance().has(&DataKey::Dispute(bounty_id)) {
+        return Err(Error::DisputeNotFound);
+    }
+
+    let mut escrow = crate::errors::load_escrow(env, bounty_id)?;
+
+    if to_contributor < 0 || to_depositor < 0 || to_contributor + to_depositor != escrow.remaining_amount {
+        return Err(Error::InvalidAmount);
+    }
+
+    let token_address: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Token)
+        .ok_or(Error::NotInitialized)?;
+    let token_client = token::Client::new(env, &token_address);
+    let contract_address = env.current_contract_address();
+
+    if to_contributor > 0 {
+        token_client.transfer(&contract_address, contributor, &to_contributor);
+    }
+    if to_depositor > 0 {
+        token_client.transfer(&contract_address, &escrow.depositor, &to_depositor);
+    }
+
+    escrow.status = if to_depositor == escrow.remaining_amount {
+        EscrowStatus::Refunded
+    } else {
+        EscrowStatus::Released
+    };
+    escrow.remaining_amount = 0;
+    env.storage()
+        .persistent()
+        .set(&DataKey::Escrow(bounty_id), &escrow);
+    env.storage().instance().remove(&DataKey::Dispute(bounty_id));
+
+    let (state_hash, _) = crate::hashchain::record_operation(
+        env,
+        crate::hashchain::op_tags::RESOLVE_DISPUTE,
+        &(bounty_id, escrow.status.clone(), escrow.remaining_amount),
+    );
+
+    crate::events::emit_dispute_resolved(
+        env,
+        crate::events::DisputeResolved {
+            bounty_id,
+            to_contributor,
+            to_depositor,
+            contributor: contributor.clone(),
+            depositor: escrow.depositor.clone(),
+            arbitrator: arbitrator.clone(),
+            timestamp: env.ledger().timestamp(),
+            state_hash,
+        },
+    );
+
+    Ok(escrow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::testutils::Address as _;
+
+    fn store_escrow(env: &Env, bounty_id: u64, depositor: &Address, remaining_amount: i128) {
+        env.storage().persistent().set(
+            &DataKey::Escrow(bounty_id),
+            &Escrow {
+                depositor: depositor.clone(),
+                amount: remaining_amount,
+                status: EscrowStatus::Locked,
+                deadline: 1_000,
+                refund_history: soroban_sdk::vec![env],
+                remaining_amount,
+            },
+        );
+    }
+
+    #[test]
+    fn test_open_dispute_moves_escrow_to_disputed() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let depositor = Address::generate(&env);
+        let contributor = Address::generate(&env);
+        store_escrow(&env, 1, &depositor, 1_000);
+
+        open_dispute(&env, 1, &depositor, &contributor).unwrap();
+
+        let escrow: Escrow = env.storage().persistent().get(&DataKey::Escrow(1)).unwrap();
+        assert_eq!(escrow.status, EscrowStatus::Disputed);
+        assert_eq!(require_not_disputed(&env, 1), Err(Error::DisputeOpen));
+    }
+
+    #[test]
+    fn test_open_dispute_rejects_non_locked_escrow() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let depositor = Address::generate(&env);
+        let contributor = Address::generate(&env);
+        store_escrow(&env, 1, &depositor, 1_000);
+        open_dispute(&env, 1, &depositor, &contributor).unwrap();
+
+        assert_eq!(
+            open_dispute(&env, 1, &depositor, &contributor),
+            Err(Error::FundsNotLocked)
+        );
+    }
+
+    #[test]
+    fn test_open_dispute_allows_contributor() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let depositor = Address::generate(&env);
+        let contributor = Address::generate(&env);
+        store_escrow(&env, 1, &depositor, 1_000);
+
+        open_dispute(&env, 1, &contributor, &contributor).unwrap();
+
+        let escrow: Escrow = env.storage().persistent().get(&DataKey::Escrow(1)).unwrap();
+        assert_eq!(escrow.status, EscrowStatus::Disputed);
+    }
+
+    #[test]
+    fn test_open_dispute_rejects_unrelated_raiser() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let depositor = Address::generate(&env);
+        let contributor = Address::generate(&env);
+        let outsider = Address::generate(&env);
+        store_escrow(&env, 1, &depositor, 1_000);
+
+        assert_eq!(
+            open_dispute(&env, 1, &outsider, &contributor),
+            Err(Error::Unauthorized)
+        );
+    }
+
+    #[test]
+    fn test_resolve_dispute_without_open_dispute_fails() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let depositor = Address::generate(&env);
+        let contributor = Address::generate(&env);
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        store_escrow(&env, 1, &depositor, 1_000);
+
+        assert_eq!(
+            resolve_dispute(&env, &admin, 1, &contributor, 1_000, 0),
+            Err(Error::DisputeNotFound)
+        );
+    }
+
+    #[test]
+    fn test_resolve_dispute_rejects_split_not_summing_to_remaining() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let depositor = Address::generate(&env);
+        let contributor = Address::generate(&env);
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        store_escrow(&env, 1, &depositor, 1_000);
+        open_dispute(&env, 1, &depositor, &contributor).unwrap();
+
+        assert_eq!(
+            resolve_dispute(&env, &admin, 1, &contributor, 400, 400),
+            Err(Error::InvalidAmount)
+        );
+    }
+
+    #[test]
+    fn test_resolve_dispute_rejects_non_arbitrator() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let depositor = Address::generate(&env);
+        let contributor = Address::generate(&env);
+        let outsider = Address::generate(&env);
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        store_escrow(&env, 1, &depositor, 1_000);
+        open_dispute(&env, 1, &depositor, &contributor).unwrap();
+
+        assert_eq!(
+            resolve_dispute(&env, &outsider, 1, &contributor, 1_000, 0),
+            Err(Error::Unauthorized)
+        );
+    }
+}
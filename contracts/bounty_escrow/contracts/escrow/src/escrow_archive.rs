@@ -0,0 +1,285 @@
+//! # Terminal Escrow Archival
+//!
+//! Every `Escrow` ever locked stays a full persistent entry forever, even
+//! once it's settled - `refund_history` only grows, and a contract that's
+//! processed years of bounties ends up paying rent on thousands of records
+//! nothing will ever mutate again. [`sweep_terminal`] reclaims that: for
+//! each id in `DataKey::Escrow`'s dense `1..=LastBountyId` space (the same
+//! space [`crate::upgrade_safety::migrate_step`] walks) whose status is
+//! terminal (`Released`, `Refunded`, or the clawback-proposed `Terminated`
+//! from [`crate::clawback`]), it condenses the record into a compact
+//! [`ArchivedEscrowSummary`] and removes the original `DataKey::Escrow`
+//! entry, the same rent-reclamation trade [`events.rs`] already makes by
+//! keeping only `refund_history_len` in its events instead of the full
+//! history.
+//!
+//! Like [`crate::upgrade_safety::migrate_step`], a sweep this large doesn't
+//! have to fit in one call: [`sweep_terminal`] takes a `max` cap and
+//! persists a [`SweepCursor`] so repeated calls keep making forward
+//! progress instead of re-scanning ids already checked. It's intentionally
+//! permissionless - archiving a terminal escrow can't change what anyone is
+//! owed, only where the contract stores the record of having paid it, so
+//! there's nothing here that needs an admin's signature.
+//!
+//! [`get_escrow_v2`] is the read-side counterpart: it checks the live
+//! `DataKey::Escrow` entry first and transparently falls back to
+//! [`ArchivedEscrowSummary`] if that id has since been swept, so a caller
+//! that doesn't care whether an id was archived can use it unconditionally.
+//! The entrypoint driving `get_escrows`/`get_escrow_count` (not present in
+//! this tree) is expected to gain an `include_archived: bool` flag that, combined with
+//! [`archived_escrow_count`] and [`list_archived_summaries`], folds archived
+//! ids into those results the same way.
+
+use soroban_sdk::Env;
+
+use crate::{Escrow, EscrowStatus};
+
+/// A condensed, immutable record of a settled escrow - everything
+/// [`get_escrow_v2`] needs to answer "what happened to this bounty"
+/// without keeping the full mutable `Escrow` (and its ever-growing
+/// `refund_history`) around. `settlement_ledger_len` mirrors
+/// `crate::events::FundsRefunded`-style events that already keep only a
+/// history *length* rather than the history itself.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArchivedEscrowSummary {
+    pub bounty_id: u64,
+    pub final_status: EscrowStatus,
+    pub final_amount: i128,
+    pub settlement_ledger_len: u32,
+}
+
+/// Resumable cursor for a [`sweep_terminal`] walk, mirroring
+/// [`crate::upgrade_safety::MigrationCursor`]'s shape so a sweep too large
+/// for one call can still make guaranteed forward progress.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SweepCursor {
+    pub last_scanned_id: u64,
+}
+
+fn archive_key(bounty_id: u64) -> crate::DataKey {
+    crate::DataKey::ArchivedEscrow(bounty_id)
+}
+
+fn is_terminal(status: &EscrowStatus) -> bool {
+    matches!(
+        status,
+        EscrowStatus::Released | EscrowStatus::Refunded | EscrowStatus::Terminated
+    )
+}
+
+fn get_sweep_cursor(env: &Env) -> SweepCursor {
+    env.storage()
+        .instance()
+        .get(&crate::DataKey::SweepCursor)
+        .unwrap_or(SweepCursor { last_scanned_id: 0 })
+}
+
+/// Archive up to `max` terminal escrows starting where the last call left
+/// off, wrapping back to id `1` once `DataKey::LastBountyId` is reached.
+/// Returns the number of ids actually archived this call (which may be
+/// fewer than `max` if most of the scanned range wasn't terminal, or
+/// wasn't written at all). A `max` of `0` is treated as `1`, same
+/// convention [`crate::upgrade_safety::migrate_step`] uses.
+pub fn sweep_terminal(env: &Env, max: u32) -> u32 {
+    let last_id: u64 = env.storage().instance().get(&crate::DataKey::LastBountyId).unwrap_or(0);
+    if last_id == 0 {
+        return 0;
+    }
+
+    let max = if max == 0 { 1 } else { max };
+    let mut cursor = get_sweep_cursor(env);
+    let mut archived = 0u32;
+    let mut scanned = 0u64;
+
+    let mut id = cursor.last_scanned_id % last_id + 1;
+    while scanned < last_id && archived < max {
+        let key = crate::DataKey::Escrow(id);
+        if env.storage().persistent().has(&key) {
+            let escrow: Escrow = env.storage().persistent().get(&key).unwrap();
+            if is_terminal(&escrow.status) {
+                archive_one(env, id, &escrow);
+                archived += 1;
+            }
+        }
+
+        scanned += 1;
+        id = if id == last_id { 1 } else { id + 1 };
+    }
+
+    cursor.last_scanned_id = if id == 1 { last_id } else { id - 1 };
+    env.storage().instance().set(&crate::DataKey::SweepCursor, &cursor);
+
+    archived
+}
+
+fn archive_one(env: &Env, bounty_id: u64, escrow: &Escrow) {
+    let summary = ArchivedEscrowSummary {
+        bounty_id,
+        final_status: escrow.status.clone(),
+        final_amount: escrow.amount,
+        settlement_ledger_len: escrow.refund_history.len(),
+    };
+    env.storage().persistent().set(&archive_key(bounty_id), &summary);
+    env.storage().persistent().remove(&crate::DataKey::Escrow(bounty_id));
+}
+
+/// The archived summary for `bounty_id`, if [`sweep_terminal`] has ever
+/// reclaimed it.
+pub fn get_archived_summary(env: &Env, bounty_id: u64) -> Option<ArchivedEscrowSummary> {
+    env.storage().persistent().get(&archive_key(bounty_id))
+}
+
+/// Whichever view of `bounty_id` is still available: the live `Escrow` if
+/// it hasn't been swept, otherwise its [`ArchivedEscrowSummary`].
+/// `Error::EscrowNotFound` if neither exists.
+pub enum EscrowView {
+    Live(Escrow),
+    Archived(ArchivedEscrowSummary),
+}
+
+/// Transparent fallback for `get_escrow`: tries the live record first via
+/// [`crate::errors::load_escrow`], and only consults the archive on
+/// `Error::EscrowNotFound` - any other error (e.g. `Error::StateCorrupted`)
+/// is surfaced as-is rather than masked by a fallback lookup.
+pub fn get_escrow_v2(env: &Env, bounty_id: u64) -> Result<EscrowView, crate::Error> {
+    match crate::errors::load_escrow(env, bounty_id) {
+        Ok(escrow) => Ok(EscrowView::Live(escrow)),
+        Err(crate::Error::EscrowNotFound) => get_archived_summary(env, bounty_id)
+            .map(EscrowView::Archived)
+            .ok_or(crate::Error::EscrowNotFound),
+        Err(other) => Err(other),
+    }
+}
+
+/// Total number of escrows [`sweep_terminal`] has archived so far - the
+/// counterpart `get_escrow_count`'s proposed `include_archived` flag would
+/// add to its live count.
+pub fn archived_escrow_count(env: &Env) -> u32 {
+    let last_id: u64 = env.storage().instance().get(&crate::DataKey::LastBountyId).unwrap_or(0);
+    let mut count = 0u32;
+    for id in 1..=last_id {
+        if env.storage().persistent().has(&archive_key(id)) {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Up to `max` archived summaries, in ascending bounty id order - the
+/// counterpart `get_escrows`'s proposed `include_archived` flag would fold
+/// into its results.
+pub fn list_archived_summaries(env: &Env, max: u32) -> soroban_sdk::Vec<ArchivedEscrowSummary> {
+    let last_id: u64 = env.storage().instance().get(&crate::DataKey::LastBountyId).unwrap_or(0);
+    let mut out = soroban_sdk::Vec::new(env);
+    for id in 1..=last_id {
+        if out.len() >= max {
+            break;
+        }
+        if let Some(summary) = get_archived_summary(env, id) {
+            out.push_back(summary);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::Address;
+
+    fn store_escrow(env: &Env, id: u64, depositor: &Address, amount: i128, status: EscrowStatus) {
+        env.storage().persistent().set(
+            &crate::DataKey::Escrow(id),
+            &Escrow {
+                depositor: depositor.clone(),
+                amount,
+                status,
+                deadline: 1_000,
+                refund_history: soroban_sdk::vec![env],
+                remaining_amount: amount,
+            },
+        );
+        let last_id: u64 = env.storage().instance().get(&crate::DataKey::LastBountyId).unwrap_or(0);
+        if id > last_id {
+            env.storage().instance().set(&crate::DataKey::LastBountyId, &id);
+        }
+    }
+
+    #[test]
+    fn test_sweep_terminal_archives_settled_escrows_and_leaves_locked_ones() {
+        let env = Env::default();
+        let depositor = Address::generate(&env);
+        store_escrow(&env, 1, &depositor, 100, EscrowStatus::Locked);
+        store_escrow(&env, 2, &depositor, 50, EscrowStatus::Released);
+        store_escrow(&env, 3, &depositor, 30, EscrowStatus::Refunded);
+
+        let archived = sweep_terminal(&env, 10);
+        assert_eq!(archived, 2);
+
+        assert!(env.storage().persistent().has(&crate::DataKey::Escrow(1)));
+        assert!(!env.storage().persistent().has(&crate::DataKey::Escrow(2)));
+        assert!(!env.storage().persistent().has(&crate::DataKey::Escrow(3)));
+
+        let summary = get_archived_summary(&env, 2).unwrap();
+        assert_eq!(summary.final_status, EscrowStatus::Released);
+        assert_eq!(summary.final_amount, 50);
+    }
+
+    #[test]
+    fn test_sweep_terminal_respects_max_and_resumes_via_cursor() {
+        let env = Env::default();
+        let depositor = Address::generate(&env);
+        store_escrow(&env, 1, &depositor, 10, EscrowStatus::Released);
+        store_escrow(&env, 2, &depositor, 20, EscrowStatus::Released);
+        store_escrow(&env, 3, &depositor, 30, EscrowStatus::Released);
+
+        let first = sweep_terminal(&env, 2);
+        assert_eq!(first, 2);
+
+        let second = sweep_terminal(&env, 2);
+        assert_eq!(second, 1);
+
+        assert_eq!(archived_escrow_count(&env), 3);
+    }
+
+    #[test]
+    fn test_get_escrow_v2_falls_back_to_archived_summary() {
+        let env = Env::default();
+        let depositor = Address::generate(&env);
+        store_escrow(&env, 1, &depositor, 75, EscrowStatus::Refunded);
+        sweep_terminal(&env, 10);
+
+        match get_escrow_v2(&env, 1).unwrap() {
+            EscrowView::Archived(summary) => {
+                assert_eq!(summary.bounty_id, 1);
+                assert_eq!(summary.final_amount, 75);
+            }
+            EscrowView::Live(_) => panic!("expected archived view"),
+        }
+    }
+
+    #[test]
+    fn test_get_escrow_v2_missing_id_returns_not_found() {
+        let env = Env::default();
+        assert!(matches!(
+            get_escrow_v2(&env, 1),
+            Err(crate::Error::EscrowNotFound)
+        ));
+    }
+
+    #[test]
+    fn test_list_archived_summaries_respects_max() {
+        let env = Env::default();
+        let depositor = Address::generate(&env);
+        store_escrow(&env, 1, &depositor, 10, EscrowStatus::Released);
+        store_escrow(&env, 2, &depositor, 20, EscrowStatus::Refunded);
+        sweep_terminal(&env, 10);
+
+        let page = list_archived_summaries(&env, 1);
+        assert_eq!(page.len(), 1);
+        assert_eq!(page.get(0).unwrap().bounty_id, 1);
+    }
+}
@@ -0,0 +1,408 @@
+//! # Escrow State Transition Log (Merkle Mountain Range)
+//!
+//! `get_escrows` lets an off-chain indexer read the contract's current view
+//! of every escrow, but offers no way to prove that view hasn't been
+//! quietly edited - a reorg, a bug, or a malicious RPC could all hand back
+//! a doctored snapshot with nothing to catch it. This module gives each
+//! state transition (lock, release, refund, terminate) its own append-only
+//! commitment: every call to [`record_escrow_event`] hashes an
+//! [`EscrowLogEntry`] and appends it as a leaf to a Merkle Mountain Range,
+//! the same accumulator shape [`crate::receipt_mmr`] uses for receipts.
+//!
+//! Unlike `receipt_mmr`, which only *verifies* a path the caller already
+//! holds, [`prove_escrow_event`] has to *produce* one for an arbitrary past
+//! leaf index - so this module keeps every node it has ever created, not
+//! just the current peaks. Each [`MmrNode`] records its own `parent` and
+//! `sibling` once a merge links it into a larger subtree; those links never
+//! change afterwards; a leaf that is still a lone peak simply has no parent
+//! yet. Walking from a leaf's position up through `parent` links (collecting
+//! each `sibling` hash along the way) reconstructs exactly the
+//! authentication path a verifier needs, terminating once the position
+//! itself is a peak (`parent == None`). [`get_escrow_log_root`] "bags" the
+//! current peaks right-to-left (`accum = peaks.last()`, then
+//! `accum = sha256(accum || peak)` walking left), matching `receipt_mmr`'s
+//! convention so the two accumulators are verified the same way.
+
+use soroban_sdk::{contracttype, xdr::ToXdr, BytesN, Env, Vec};
+
+const NODES: &str = "escrow_log_nodes";
+const LEAF_POS: &str = "escrow_log_leaf_pos";
+const PEAK_POS: &str = "escrow_log_peak_pos";
+const PEAK_HEIGHTS: &str = "escrow_log_peak_heights";
+const ROOT: &str = "escrow_log_root";
+
+/// The state transition an [`EscrowLogEntry`] commits to. Mirrors the
+/// handful of terminal/near-terminal calls `crate::errors` and
+/// `crate::clawback` already distinguish.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EscrowEventKind {
+    Locked,
+    Released,
+    Refunded,
+    Terminated,
+}
+
+/// One committed state transition. `to_xdr`-hashed to produce the MMR leaf
+/// - deliberately small and self-describing rather than embedding the full
+/// `Escrow` record, since the leaf only needs to be distinguishable and
+/// reproducible by an off-chain holder, not a full audit snapshot.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowLogEntry {
+    pub bounty_id: u64,
+    pub kind: EscrowEventKind,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+/// One node of the flat, never-mutated MMR node array. `parent` and
+/// `sibling_pos`/`sibling_is_left` are filled in once (at the merge that
+/// consumes this node into a parent) and never revisited - a lone peak
+/// simply carries `parent: None` until a later append happens to merge it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MmrNode {
+    pub hash: BytesN<32>,
+    pub height: u32,
+    pub parent: Option<u32>,
+    pub sibling_pos: Option<u32>,
+    /// Whether `sibling_pos` sits to the left of this node (so recombining
+    /// needs `H(sibling || self)` rather than `H(self || sibling)`).
+    pub sibling_is_left: bool,
+}
+
+/// One step of a leaf's authentication path up to its peak - the sibling
+/// hash and which side it sits on, same shape as
+/// [`crate::receipt_mmr::MerklePathItem`].
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowMerklePathItem {
+    pub hash: BytesN<32>,
+    pub is_left: bool,
+}
+
+/// A full membership proof for one leaf: its own hash, the sibling path up
+/// to whichever peak it currently rolls up into, that peak's position
+/// (`peak_index`) within the current peak list, and every *other* peak
+/// hash in left-to-right order. A verifier recomputes the peak by folding
+/// `path` into `leaf_hash`, reinserts it at `peak_index` among
+/// `other_peaks`, and bags the result the same way [`get_escrow_log_root`]
+/// does to check it against a claimed root.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowEventProof {
+    pub leaf_hash: BytesN<32>,
+    pub path: Vec<EscrowMerklePathItem>,
+    pub peak_index: u32,
+    pub other_peaks: Vec<BytesN<32>>,
+}
+
+fn zero_root(env: &Env) -> BytesN<32> {
+    BytesN::from_array(env, &[0u8; 32])
+}
+
+fn sha256_pair(env: &Env, left: &BytesN<32>, right: &BytesN<32>) -> BytesN<32> {
+    let mut preimage = soroban_sdk::Bytes::new(env);
+    preimage.append(&soroban_sdk::Bytes::from_array(env, &left.to_array()));
+    preimage.append(&soroban_sdk::Bytes::from_array(env, &right.to_array()));
+    env.crypto().sha256(&preimage).into()
+}
+
+fn bag_peaks(env: &Env, peaks: &Vec<BytesN<32>>) -> BytesN<32> {
+    if peaks.is_empty() {
+        return zero_root(env);
+    }
+
+    let mut accum = peaks.get(peaks.len() - 1).unwrap();
+    let mut i = peaks.len() - 1;
+    while i > 0 {
+        i -= 1;
+        accum = sha256_pair(env, &accum, &peaks.get(i).unwrap());
+    }
+    accum
+}
+
+/// `sha256(xdr(entry))` - exposed so a caller can recompute the exact leaf
+/// bytes a stored [`EscrowEventProof`] was issued against.
+pub fn escrow_log_leaf_hash(env: &Env, entry: &EscrowLogEntry) -> BytesN<32> {
+    env.crypto().sha256(&entry.clone().to_xdr(env)).into()
+}
+
+/// Append `entry` as a new leaf and persist the updated node array, peak
+/// stack, and bagged root. The entrypoint driving `lock_funds`/
+/// `release_funds`/`refund`/[`crate::clawback::terminate_escrow`] is
+/// expected to call this once per state transition with the matching
+/// [`EscrowEventKind`]. Returns `(root, leaf_count)` so it can be folded
+/// into that call's own event if desired.
+pub fn record_escrow_event(env: &Env, entry: EscrowLogEntry) -> (BytesN<32>, u64) {
+    append_leaf(env, escrow_log_leaf_hash(env, &entry))
+}
+
+fn append_leaf(env: &Env, leaf_hash: BytesN<32>) -> (BytesN<32>, u64) {
+    let mut nodes: Vec<MmrNode> = env.storage().instance().get(&NODES).unwrap_or_else(|| Vec::new(env));
+    let mut leaf_positions: Vec<u32> = env
+        .storage()
+        .instance()
+        .get(&LEAF_POS)
+        .unwrap_or_else(|| Vec::new(env));
+    let mut peak_pos: Vec<u32> = env
+        .storage()
+        .instance()
+        .get(&PEAK_POS)
+        .unwrap_or_else(|| Vec::new(env));
+    let mut peak_heights: Vec<u32> = env
+        .storage()
+        .instance()
+        .get(&PEAK_HEIGHTS)
+        .unwrap_or_else(|| Vec::new(env));
+
+    let leaf_pos = nodes.len();
+    nodes.push_back(MmrNode {
+        hash: leaf_hash,
+        height: 0,
+        parent: None,
+        sibling_pos: None,
+        sibling_is_left: false,
+    });
+    leaf_positions.push_back(leaf_pos);
+    peak_pos.push_back(leaf_pos);
+    peak_heights.push_back(0);
+
+    loop {
+        let len = peak_heights.len();
+        if len < 2 {
+            break;
+        }
+        let top_height = peak_heights.get(len - 1).unwrap();
+        let prev_height = peak_heights.get(len - 2).unwrap();
+        if top_height != prev_height {
+            break;
+        }
+
+        let right_pos = peak_pos.pop_back().unwrap();
+        peak_heights.pop_back();
+        let left_pos = peak_pos.pop_back().unwrap();
+        peak_heights.pop_back();
+
+        let left_hash = nodes.get(left_pos).unwrap().hash.clone();
+        let right_hash = nodes.get(right_pos).unwrap().hash.clone();
+        let parent_pos = nodes.len();
+        let parent_hash = sha256_pair(env, &left_hash, &right_hash);
+        nodes.push_back(MmrNode {
+            hash: parent_hash,
+            height: top_height + 1,
+            parent: None,
+            sibling_pos: None,
+            sibling_is_left: false,
+        });
+
+        let mut left_node = nodes.get(left_pos).unwrap();
+        left_node.parent = Some(parent_pos);
+        left_node.sibling_pos = Some(right_pos);
+        left_node.sibling_is_left = false;
+        nodes.set(left_pos, left_node);
+
+        let mut right_node = nodes.get(right_pos).unwrap();
+        right_node.parent = Some(parent_pos);
+        right_node.sibling_pos = Some(left_pos);
+        right_node.sibling_is_left = true;
+        nodes.set(right_pos, right_node);
+
+        peak_pos.push_back(parent_pos);
+        peak_heights.push_back(top_height + 1);
+    }
+
+    env.storage().instance().set(&NODES, &nodes);
+    env.storage().instance().set(&LEAF_POS, &leaf_positions);
+    env.storage().instance().set(&PEAK_POS, &peak_pos);
+    env.storage().instance().set(&PEAK_HEIGHTS, &peak_heights);
+
+    let mut peaks: Vec<BytesN<32>> = Vec::new(env);
+    for p in peak_pos.iter() {
+        peaks.push_back(nodes.get(p).unwrap().hash.clone());
+    }
+    let root = bag_peaks(env, &peaks);
+    env.storage().instance().set(&ROOT, &root);
+
+    let leaf_count = leaf_positions.len() as u64;
+    (root, leaf_count)
+}
+
+/// The current bagged root over every recorded escrow event, or all-zero
+/// if none has been recorded yet.
+pub fn get_escrow_log_root(env: &Env) -> BytesN<32> {
+    env.storage().instance().get(&ROOT).unwrap_or_else(|| zero_root(env))
+}
+
+/// Number of escrow events recorded so far.
+pub fn escrow_event_count(env: &Env) -> u64 {
+    let leaf_positions: Vec<u32> = env
+        .storage()
+        .instance()
+        .get(&LEAF_POS)
+        .unwrap_or_else(|| Vec::new(env));
+    leaf_positions.len() as u64
+}
+
+/// Build an [`EscrowEventProof`] for the `index`-th recorded event (0
+/// based, in append order). `Error::EscrowEventNotFound` if `index` is out
+/// of range.
+pub fn prove_escrow_event(env: &Env, index: u64) -> Result<EscrowEventProof, crate::Error> {
+    let nodes: Vec<MmrNode> = env.storage().instance().get(&NODES).unwrap_or_else(|| Vec::new(env));
+    let leaf_positions: Vec<u32> = env
+        .storage()
+        .instance()
+        .get(&LEAF_POS)
+        .unwrap_or_else(|| Vec::new(env));
+    let peak_pos: Vec<u32> = env
+        .storage()
+        .instance()
+        .get(&PEAK_POS)
+        .unwrap_or_else(|| Vec::new(env));
+
+    if index >= leaf_positions.len() as u64 {
+        return Err(crate::Error::EscrowEventNotFound);
+    }
+
+    let mut pos = leaf_positions.get(index as u32).unwrap();
+    let leaf_hash = nodes.get(pos).unwrap().hash.clone();
+
+    let mut path: Vec<EscrowMerklePathItem> = Vec::new(env);
+    loop {
+        let node = nodes.get(pos).unwrap();
+        match node.sibling_pos {
+            Some(sib_pos) => {
+                let sib_hash = nodes.get(sib_pos).unwrap().hash.clone();
+                path.push_back(EscrowMerklePathItem {
+                    hash: sib_hash,
+                    is_left: node.sibling_is_left,
+                });
+                pos = node.parent.unwrap();
+            }
+            None => break,
+        }
+    }
+
+    let mut peak_index: u32 = 0;
+    let mut other_peaks: Vec<BytesN<32>> = Vec::new(env);
+    for i in 0..peak_pos.len() {
+        let p = peak_pos.get(i).unwrap();
+        if p == pos {
+            peak_index = i;
+        } else {
+            other_peaks.push_back(nodes.get(p).unwrap().hash.clone());
+        }
+    }
+
+    Ok(EscrowEventProof {
+        leaf_hash,
+        path,
+        peak_index,
+        other_peaks,
+    })
+}
+
+/// Recompute `proof`'s leaf's peak by folding `path` into `leaf_hash`,
+/// reinsert it at `peak_index` among `other_peaks`, bag the reconstructed
+/// peak list, and report whether it equals `claimed_root`. Does not touch
+/// storage or compare against [`get_escrow_log_root`] - a proof is valid
+/// against whatever root it was issued for, which may predate leaves
+/// recorded since.
+pub fn verify_escrow_event_proof(
+    env: &Env,
+    proof: &EscrowEventProof,
+    claimed_root: BytesN<32>,
+) -> bool {
+    let mut h = proof.leaf_hash.clone();
+    for item in proof.path.iter() {
+        h = if item.is_left {
+            sha256_pair(env, &item.hash, &h)
+        } else {
+            sha256_pair(env, &h, &item.hash)
+        };
+    }
+
+    let mut peaks: Vec<BytesN<32>> = Vec::new(env);
+    let mut inserted = false;
+    for i in 0..proof.other_peaks.len() {
+        if i == proof.peak_index {
+            peaks.push_back(h.clone());
+            inserted = true;
+        }
+        peaks.push_back(proof.other_peaks.get(i).unwrap());
+    }
+    if !inserted {
+        peaks.push_back(h.clone());
+    }
+
+    bag_peaks(env, &peaks) == claimed_root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(bounty_id: u64, kind: EscrowEventKind) -> EscrowLogEntry {
+        EscrowLogEntry {
+            bounty_id,
+            kind,
+            amount: 1_000,
+            timestamp: 100,
+        }
+    }
+
+    #[test]
+    fn test_empty_log_root_is_zero() {
+        let env = Env::default();
+        assert_eq!(get_escrow_log_root(&env), zero_root(&env));
+        assert_eq!(escrow_event_count(&env), 0);
+    }
+
+    #[test]
+    fn test_single_event_root_is_the_leaf_hash() {
+        let env = Env::default();
+        let e = entry(1, EscrowEventKind::Locked);
+        let expected = escrow_log_leaf_hash(&env, &e);
+
+        let (root, count) = record_escrow_event(&env, e);
+        assert_eq!(root, expected);
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_prove_and_verify_each_leaf_of_a_three_event_log() {
+        let env = Env::default();
+        record_escrow_event(&env, entry(1, EscrowEventKind::Locked));
+        record_escrow_event(&env, entry(1, EscrowEventKind::Released));
+        let (root, count) = record_escrow_event(&env, entry(2, EscrowEventKind::Locked));
+        assert_eq!(count, 3);
+
+        for index in 0..3 {
+            let proof = prove_escrow_event(&env, index).unwrap();
+            assert!(verify_escrow_event_proof(&env, &proof, root.clone()));
+        }
+    }
+
+    #[test]
+    fn test_prove_escrow_event_rejects_out_of_range_index() {
+        let env = Env::default();
+        record_escrow_event(&env, entry(1, EscrowEventKind::Locked));
+
+        assert_eq!(
+            prove_escrow_event(&env, 1),
+            Err(crate::Error::EscrowEventNotFound)
+        );
+    }
+
+    #[test]
+    fn test_verify_escrow_event_proof_rejects_tampered_root() {
+        let env = Env::default();
+        record_escrow_event(&env, entry(1, EscrowEventKind::Locked));
+        record_escrow_event(&env, entry(1, EscrowEventKind::Terminated));
+
+        let proof = prove_escrow_event(&env, 0).unwrap();
+        let wrong_root = BytesN::from_array(&env, &[42u8; 32]);
+        assert!(!verify_escrow_event_proof(&env, &proof, wrong_root));
+    }
+}
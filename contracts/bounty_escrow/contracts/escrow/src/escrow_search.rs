@@ -0,0 +1,271 @@
+//! # Multi-Status and Range Filtering for `get_escrows`
+//!
+//! `EscrowSearchCriteria`'s `status_filter` is a single value (`0` meaning
+//! "any"), so a caller wanting "every expired-but-unreleased escrow above
+//! 1000 tokens" has no way to ask for it in one call - it has to fetch
+//! everything and filter client-side. This module gives `get_escrows` a
+//! richer predicate to apply per-entry, the same scan
+//! [`crate::upgrade_safety::simulate_upgrade_paged`] already runs over the
+//! dense `DataKey::Escrow` id space:
+//!
+//! - `status_mask` replaces the single `status_filter` with a bitmask (see
+//!   the `STATUS_*` constants), so `Locked | Released` is expressible as
+//!   one query instead of two.
+//! - `min_amount`/`max_amount` and `deadline_before`/`deadline_after` add
+//!   optional range predicates alongside the existing ones.
+//!
+//! [`matches`] applies every present predicate conjunctively - an absent
+//! (`None`) bound or an empty `status_mask` (`0`, preserving the old
+//! "any status" meaning) simply never excludes anything. [`search_page`]
+//! is the paginated scan itself: same `cursor`/`next_cursor` shape
+//! `simulate_upgrade_paged` already uses, capped at [`MAX_PAGE_SIZE`] per
+//! call so a caller can't force an unbounded amount of scanning work onto
+//! a single transaction.
+
+use soroban_sdk::{contracttype, Env, Vec};
+
+use crate::{Escrow, EscrowStatus};
+
+pub const STATUS_LOCKED: u32 = 1 << 0;
+pub const STATUS_RELEASED: u32 = 1 << 1;
+pub const STATUS_REFUNDED: u32 = 1 << 2;
+pub const STATUS_PENDING: u32 = 1 << 3;
+/// Bit for `EscrowStatus::Terminated`, the terminal status a
+/// [`crate::clawback`] termination leaves an escrow in.
+pub const STATUS_TERMINATED: u32 = 1 << 4;
+pub const STATUS_DISPUTED: u32 = 1 << 5;
+pub const STATUS_MIGRATED: u32 = 1 << 6;
+
+/// Upper bound on how many ids [`search_page`] scans (not necessarily
+/// matches) in one call - the same cap `get_escrows`'s existing
+/// pagination already enforces.
+pub const MAX_PAGE_SIZE: u64 = 50;
+
+fn status_bit(status: &EscrowStatus) -> u32 {
+    match status {
+        EscrowStatus::Locked => STATUS_LOCKED,
+        EscrowStatus::Released => STATUS_RELEASED,
+        EscrowStatus::Refunded => STATUS_REFUNDED,
+        EscrowStatus::Pending => STATUS_PENDING,
+        EscrowStatus::Terminated => STATUS_TERMINATED,
+        EscrowStatus::Disputed => STATUS_DISPUTED,
+        EscrowStatus::Migrated => STATUS_MIGRATED,
+    }
+}
+
+/// Extended search predicate for `get_escrows`. Every field is optional
+/// (or `0` for `status_mask`) and absent fields impose no constraint, so
+/// `EscrowSearchCriteria::default()`-equivalent (all zero/`None`) matches
+/// every escrow, same as the old single `status_filter == 0`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowSearchCriteria {
+    /// Bitwise OR of `STATUS_*` constants; `0` matches any status.
+    pub status_mask: u32,
+    pub min_amount: Option<i128>,
+    pub max_amount: Option<i128>,
+    pub deadline_before: Option<u64>,
+    pub deadline_after: Option<u64>,
+}
+
+/// Whether `escrow` satisfies every predicate `criteria` sets - the
+/// conjunctive (AND) combination `get_escrows` is expected to apply per
+/// entry.
+pub fn matches(escrow: &Escrow, criteria: &EscrowSearchCriteria) -> bool {
+    if criteria.status_mask != 0 && criteria.status_mask & status_bit(&escrow.status) == 0 {
+        return false;
+    }
+    if let Some(min_amount) = criteria.min_amount {
+        if escrow.amount < min_amount {
+            return false;
+        }
+    }
+    if let Some(max_amount) = criteria.max_amount {
+        if escrow.amount > max_amount {
+            return false;
+        }
+    }
+    if let Some(deadline_before) = criteria.deadline_before {
+        if escrow.deadline >= deadline_before {
+            return false;
+        }
+    }
+    if let Some(deadline_after) = criteria.deadline_after {
+        if escrow.deadline <= deadline_after {
+            return false;
+        }
+    }
+    true
+}
+
+/// Scan `DataKey::Escrow` ids starting at `cursor` (`1`-based, inclusive),
+/// collecting every entry that [`matches`] `criteria` until either
+/// [`MAX_PAGE_SIZE`] ids have been scanned or `DataKey::LastBountyId` is
+/// reached. Returns the matching page and the next `cursor` to resume
+/// from, or `None` once the scan has reached the end of the id space -
+/// the same cursor-pagination contract `get_escrows` already exposes,
+/// just applied against the richer [`EscrowSearchCriteria`].
+pub fn search_page(env: &Env, criteria: &EscrowSearchCriteria, cursor: u64) -> (Vec<Escrow>, Option<u64>) {
+    let last_id: u64 = env.storage().instance().get(&crate::DataKey::LastBountyId).unwrap_or(0);
+
+    let mut matched = Vec::new(env);
+    let start = cursor.max(1);
+    let end = start.saturating_add(MAX_PAGE_SIZE).min(last_id + 1);
+
+    for id in start..end {
+        let key = crate::DataKey::Escrow(id);
+        if env.storage().persistent().has(&key) {
+            let escrow: Escrow = env.storage().persistent().get(&key).unwrap();
+            if matches(&escrow, criteria) {
+                matched.push_back(escrow);
+            }
+        }
+    }
+
+    let next_cursor = if end > last_id { None } else { Some(end) };
+    (matched, next_cursor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::Address;
+
+    fn store_escrow(env: &Env, id: u64, amount: i128, deadline: u64, status: EscrowStatus) {
+        let depositor = Address::generate(env);
+        env.storage().persistent().set(
+            &crate::DataKey::Escrow(id),
+            &Escrow {
+                depositor,
+                amount,
+                status,
+                deadline,
+                refund_history: soroban_sdk::vec![env],
+                remaining_amount: amount,
+            },
+        );
+        let last_id: u64 = env.storage().instance().get(&crate::DataKey::LastBountyId).unwrap_or(0);
+        if id > last_id {
+            env.storage().instance().set(&crate::DataKey::LastBountyId, &id);
+        }
+    }
+
+    fn any_criteria() -> EscrowSearchCriteria {
+        EscrowSearchCriteria {
+            status_mask: 0,
+            min_amount: None,
+            max_amount: None,
+            deadline_before: None,
+            deadline_after: None,
+        }
+    }
+
+    #[test]
+    fn test_matches_empty_criteria_matches_everything() {
+        let env = Env::default();
+        let depositor = Address::generate(&env);
+        let escrow = Escrow {
+            depositor,
+            amount: 500,
+            status: EscrowStatus::Locked,
+            deadline: 1_000,
+            refund_history: soroban_sdk::vec![&env],
+            remaining_amount: 500,
+        };
+        assert!(matches(&escrow, &any_criteria()));
+    }
+
+    #[test]
+    fn test_matches_status_mask_combines_multiple_statuses() {
+        let env = Env::default();
+        let depositor = Address::generate(&env);
+        let escrow = Escrow {
+            depositor,
+            amount: 500,
+            status: EscrowStatus::Released,
+            deadline: 1_000,
+            refund_history: soroban_sdk::vec![&env],
+            remaining_amount: 0,
+        };
+        let criteria = EscrowSearchCriteria {
+            status_mask: STATUS_LOCKED | STATUS_RELEASED,
+            ..any_criteria()
+        };
+        assert!(matches(&escrow, &criteria));
+
+        let excluding = EscrowSearchCriteria {
+            status_mask: STATUS_LOCKED | STATUS_PENDING,
+            ..any_criteria()
+        };
+        assert!(!matches(&escrow, &excluding));
+    }
+
+    #[test]
+    fn test_matches_applies_amount_and_deadline_ranges_conjunctively() {
+        let env = Env::default();
+        let depositor = Address::generate(&env);
+        let escrow = Escrow {
+            depositor,
+            amount: 1_500,
+            status: EscrowStatus::Locked,
+            deadline: 500,
+            refund_history: soroban_sdk::vec![&env],
+            remaining_amount: 1_500,
+        };
+
+        let criteria = EscrowSearchCriteria {
+            min_amount: Some(1_000),
+            deadline_before: Some(1_000),
+            ..any_criteria()
+        };
+        assert!(matches(&escrow, &criteria));
+
+        let too_expensive = EscrowSearchCriteria {
+            min_amount: Some(2_000),
+            ..any_criteria()
+        };
+        assert!(!matches(&escrow, &too_expensive));
+
+        let not_yet_expired = EscrowSearchCriteria {
+            deadline_after: Some(500),
+            ..any_criteria()
+        };
+        assert!(!matches(&escrow, &not_yet_expired));
+    }
+
+    #[test]
+    fn test_search_page_filters_and_paginates() {
+        let env = Env::default();
+        store_escrow(&env, 1, 1_200, 50, EscrowStatus::Locked);
+        store_escrow(&env, 2, 800, 50, EscrowStatus::Locked);
+        store_escrow(&env, 3, 1_500, 200, EscrowStatus::Pending);
+        store_escrow(&env, 4, 2_000, 50, EscrowStatus::Released);
+
+        // "expired-but-unreleased above 1000 tokens": Locked|Pending above
+        // 1000, with a deadline already in the past relative to now=100.
+        let criteria = EscrowSearchCriteria {
+            status_mask: STATUS_LOCKED | STATUS_PENDING,
+            min_amount: Some(1_000),
+            deadline_before: Some(100),
+            ..any_criteria()
+        };
+
+        let (page, next_cursor) = search_page(&env, &criteria, 1);
+        assert_eq!(page.len(), 1);
+        assert_eq!(page.get(0).unwrap().amount, 1_200);
+        assert_eq!(next_cursor, None);
+    }
+
+    #[test]
+    fn test_search_page_caps_scan_at_max_page_size() {
+        let env = Env::default();
+        for id in 1..=(MAX_PAGE_SIZE + 5) {
+            store_escrow(&env, id, 100, 1_000, EscrowStatus::Locked);
+        }
+
+        let (page, next_cursor) = search_page(&env, &any_criteria(), 1);
+        assert_eq!(page.len() as u64, MAX_PAGE_SIZE);
+        assert_eq!(next_cursor, Some(MAX_PAGE_SIZE + 1));
+    }
+}
@@ -1,5 +1,22 @@
+//! Most events here only index a short symbol plus `bounty_id` in their
+//! topics, burying the economically interesting party (recipient,
+//! depositor, refund_to, fee_recipient, beneficiary) in the data payload -
+//! so an indexer that wants "every release paid to address X" has to scan
+//! every `FundsReleased` rather than filter on a topic, the way EVM escrow
+//! contracts index `payee`/`payer` in `Deposited`/`Withdrawn`. Where an
+//! event represents money moving to or from a specific `Address`, the
+//! convention going forward is: **topics are `(symbol, bounty_id, party)`**
+//! (or `(symbol, id, party)` for ticket/capability-style events keyed by
+//! something other than `bounty_id`), with the party address always last.
+//! `getEvents` callers can then filter on that trailing topic directly
+//! instead of decoding every matching event's payload. See
+//! [`emit_funds_released`], [`emit_funds_refunded`], [`emit_fee_collected`],
+//! [`emit_ticket_claimed`], and [`emit_settlement_completed`] for the
+//! entrypoints this currently covers. [`emit_fee_collected`] carries no
+//! `bounty_id`, so its topics are just `(symbol, party)`.
+
 use crate::{CapabilityAction, DisputeOutcome, DisputeReason, ReleaseType};
-use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Symbol, String, Vec};
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, String, Symbol, Vec};
 
 pub const EVENT_VERSION_V2: u32 = 2;
 
@@ -58,8 +75,12 @@ pub struct FundsReleased {
     pub timestamp: u64,
 }
 
+/// Topics are `(symbol, bounty_id, recipient)` - the recipient address is
+/// indexed alongside `bounty_id` so an off-chain indexer can filter
+/// `getEvents` for "every release paid to address X" directly, instead of
+/// scanning every `FundsReleased` and inspecting the data payload.
 pub fn emit_funds_released(env: &Env, event: FundsReleased) {
-    let topics = (symbol_short!("f_rel"), event.bounty_id);
+    let topics = (symbol_short!("f_rel"), event.bounty_id, event.recipient.clone());
     env.events().publish(topics, event.clone());
 }
 
@@ -101,6 +122,46 @@ pub fn emit_schedule_released(env: &Env, event: ScheduleReleased) {
     env.events().publish(topics, event.clone());
 }
 
+/// Emitted once when a `crate::vesting::VestingSchedule` is created, unlike
+/// `ScheduleCreated`/`ScheduleReleased`'s single fire-at-one-timestamp
+/// model - the schedule then streams out through repeated `VestingClaimed`
+/// events as the recipient claims.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct VestingScheduleCreated {
+    pub bounty_id: u64,
+    pub schedule_id: u64,
+    pub total_amount: i128,
+    pub start: u64,
+    pub cliff: u64,
+    pub duration: u64,
+    pub recipient: Address,
+}
+
+pub fn emit_vesting_schedule_created(env: &Env, event: VestingScheduleCreated) {
+    let topics = (symbol_short!("vst_cr"), event.bounty_id, event.schedule_id);
+    env.events().publish(topics, event.clone());
+}
+
+/// Emitted on every partial or final claim against a vesting schedule, so
+/// an indexer can reconstruct the release curve without replaying the
+/// schedule's own linear formula.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct VestingClaimed {
+    pub bounty_id: u64,
+    pub schedule_id: u64,
+    pub amount_released: i128,
+    pub total_vested_to_date: i128,
+    pub remaining: i128,
+    pub timestamp: u64,
+}
+
+pub fn emit_vesting_claimed(env: &Env, event: VestingClaimed) {
+    let topics = (symbol_short!("vst_clm"), event.bounty_id, event.schedule_id);
+    env.events().publish(topics, event.clone());
+}
+
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct FundsRefunded {
@@ -108,11 +169,14 @@ pub struct FundsRefunded {
     pub bounty_id: u64,
     pub amount: i128,
     pub refund_to: Address,
+    pub refund_history_len: u32,
     pub timestamp: u64,
 }
 
+/// Topics are `(symbol, bounty_id, refund_to)`, indexing the refund
+/// recipient the same way [`emit_funds_released`] indexes its recipient.
 pub fn emit_funds_refunded(env: &Env, event: FundsRefunded) {
-    let topics = (symbol_short!("f_ref"), event.bounty_id);
+    let topics = (symbol_short!("f_ref"), event.bounty_id, event.refund_to.clone());
     env.events().publish(topics, event.clone());
 }
 
@@ -130,7 +194,9 @@ pub enum CriticalOperationOutcome {
 
 /// Receipt (signed/committed proof of execution) for release or refund.
 /// Emitted for each release/refund so users can prove completion off-chain;
-/// optional on-chain verification via verify_receipt(receipt_id).
+/// `verify_receipt(receipt_id)` looks one up directly from storage, and
+/// `receipt_mmr::verify_receipt_proof` lets a holder prove inclusion via a
+/// compact Merkle path once the contract's own copy has been pruned.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct CriticalOperationReceipt {
@@ -148,9 +214,34 @@ pub struct CriticalOperationReceipt {
     pub timestamp: u64,
 }
 
+/// Published alongside every receipt once it's folded into the
+/// [`crate::receipt_mmr`] accumulator, so an indexer (or the holder itself)
+/// can recover the root a given receipt's path should be checked against
+/// without querying contract storage.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReceiptRootUpdated {
+    pub receipt_id: u64,
+    pub root: BytesN<32>,
+    pub leaf_count: u64,
+}
+
 pub fn emit_operation_receipt(env: &Env, receipt: CriticalOperationReceipt) {
     let topics = (symbol_short!("receipt"), receipt.receipt_id);
     env.events().publish(topics, receipt.clone());
+
+    let leaf = crate::receipt_mmr::receipt_leaf_hash(env, &receipt);
+    let (root, leaf_count) = crate::receipt_mmr::append_leaf(env, leaf);
+
+    let root_topics = (symbol_short!("rcpt_rt"), receipt.receipt_id);
+    env.events().publish(
+        root_topics,
+        ReceiptRootUpdated {
+            receipt_id: receipt.receipt_id,
+            root,
+            leaf_count,
+        },
+    );
 }
 
 #[contracttype]
@@ -171,8 +262,10 @@ pub struct FeeCollected {
     pub timestamp: u64,
 }
 
+/// Topics are `(symbol, fee_recipient)`, indexing the collector address so
+/// wallets/indexers can subscribe to just their own fee stream.
 pub fn emit_fee_collected(env: &Env, event: FeeCollected) {
-    let topics = (symbol_short!("fee"),);
+    let topics = (symbol_short!("fee"), event.recipient.clone());
     env.events().publish(topics, event.clone());
 }
 
@@ -297,6 +390,11 @@ pub struct ClaimCreated {
     pub reason: DisputeReason,
 }
 
+pub fn emit_claim_created(env: &Env, event: ClaimCreated) {
+    let topics = (symbol_short!("clm_new"), event.bounty_id);
+    env.events().publish(topics, event);
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ClaimExecuted {
@@ -307,6 +405,12 @@ pub struct ClaimExecuted {
     pub outcome: DisputeOutcome,
 }
 
+/// Published as `claim_settled`: the claim ticket was exercised and funds moved.
+pub fn emit_claim_settled(env: &Env, event: ClaimExecuted) {
+    let topics = (symbol_short!("clm_stl"), event.bounty_id);
+    env.events().publish(topics, event);
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ClaimCancelled {
@@ -318,6 +422,11 @@ pub struct ClaimCancelled {
     pub outcome: DisputeOutcome,
 }
 
+pub fn emit_claim_cancelled(env: &Env, event: ClaimCancelled) {
+    let topics = (symbol_short!("clm_cnc"), event.bounty_id);
+    env.events().publish(topics, event);
+}
+
 /// Event emitted when a claim ticket is issued to a bounty winner
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -346,8 +455,14 @@ pub struct TicketClaimed {
     pub claimed_at: u64,
 }
 
+/// Topics are `(symbol, ticket_id, beneficiary)`, indexing the claimant
+/// address alongside the ticket id.
 pub fn emit_ticket_claimed(env: &Env, event: TicketClaimed) {
-    let topics = (symbol_short!("tkt_clm"), event.ticket_id);
+    let topics = (
+        symbol_short!("tkt_clm"),
+        event.ticket_id,
+        event.beneficiary.clone(),
+    );
     env.events().publish(topics, event.clone());
 }
 
@@ -479,6 +594,34 @@ pub fn emit_capability_revoked(env: &Env, event: CapabilityRevoked) {
     env.events().publish(topics, event);
 }
 
+/// Emitted when a capability holder re-delegates a strictly attenuated
+/// slice of their own authority to a new holder; see
+/// `crate::capability_delegation`. `CapabilityRevoked` on
+/// `parent_capability_id` cascades to every capability delegated from it,
+/// so a chain can never outlive the grant it was attenuated from.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CapabilityDelegated {
+    pub parent_capability_id: u64,
+    pub child_capability_id: u64,
+    pub delegator: Address,
+    pub holder: Address,
+    pub action: CapabilityAction,
+    pub amount_limit: i128,
+    pub max_uses: u32,
+    pub expires_at: u64,
+    pub timestamp: u64,
+}
+
+pub fn emit_capability_delegated(env: &Env, event: CapabilityDelegated) {
+    let topics = (
+        symbol_short!("cap_dlg"),
+        event.parent_capability_id,
+        event.child_capability_id,
+    );
+    env.events().publish(topics, event);
+}
+
 /// Emitted when the contract is deprecated or un-deprecated (kill switch / migration path).
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -491,6 +634,9 @@ pub struct DeprecationStateChanged {
 
 pub fn emit_deprecation_state_changed(env: &Env, event: DeprecationStateChanged) {
     let topics = (symbol_short!("deprec"),);
+    env.events().publish(topics, event);
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct MetadataUpdated {
@@ -530,6 +676,34 @@ pub fn emit_participant_filter_mode_changed(env: &Env, event: ParticipantFilterM
     env.events().publish(topics, event);
 }
 
+/// Emitted by `crate::filter_mode_timelock::schedule_filter_mode` when a
+/// mode change is queued rather than applied immediately.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FilterModeScheduled {
+    pub new_mode: crate::ParticipantFilterMode,
+    pub effective_at: u64,
+    pub admin: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_filter_mode_scheduled(env: &Env, event: FilterModeScheduled) {
+    let topics = (symbol_short!("p_fsched"),);
+    env.events().publish(topics, event);
+}
+
+/// Emitted by `crate::filter_mode_timelock::commit_pending_mode` when a
+/// previously scheduled mode becomes active.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FilterModeCommitted {
+    pub new_mode: crate::ParticipantFilterMode,
+    pub timestamp: u64,
+}
+
+pub fn emit_filter_mode_committed(env: &Env, event: FilterModeCommitted) {
+    let topics = (symbol_short!("p_fcmt"),);
+    env.events().publish(topics, event);
 }
 
 // ==================== Event Batching (Issue #676) ====================
@@ -724,10 +898,7 @@ pub struct SettlementGracePeriodEntered {
     pub timestamp: u64,
 }
 
-pub fn emit_settlement_grace_period_entered(
-    env: &Env,
-    event: SettlementGracePeriodEntered,
-) {
+pub fn emit_settlement_grace_period_entered(env: &Env, event: SettlementGracePeriodEntered) {
     let topics = (symbol_short!("grace_in"), event.bounty_id);
     env.events().publish(topics, event.clone());
 }
@@ -743,7 +914,305 @@ pub struct SettlementCompleted {
     pub timestamp: u64,
 }
 
+/// Topics are `(symbol, bounty_id, recipient)`, indexing the settled-to
+/// address alongside the bounty id.
 pub fn emit_settlement_completed(env: &Env, event: SettlementCompleted) {
-    let topics = (Symbol::new(env, "settle_done"), event.bounty_id);
+    let topics = (
+        Symbol::new(env, "settle_done"),
+        event.bounty_id,
+        event.recipient.clone(),
+    );
+    env.events().publish(topics, event.clone());
+}
+
+// ==================== Dust Protection ====================
+
+/// Emitted when a release would have left `0 < remaining_amount <
+/// dust_threshold` and [`crate::validation::sweep_dust_on_release`] folded
+/// the leftover into the release instead.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DustSwept {
+    pub bounty_id: u64,
+    pub dust_amount: i128,
+    pub recipient: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_dust_swept(env: &Env, event: DustSwept) {
+    let topics = (symbol_short!("dustswep"), event.bounty_id);
+    env.events().publish(topics, event.clone());
+}
+
+// ============================================================================
+// Managed Treasury Spend (see `crate::treasury_spend`)
+// ============================================================================
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpendProposed {
+    pub spend_index: u64,
+    pub region: String,
+    pub asset: Address,
+    pub amount: i128,
+    pub beneficiary: Address,
+    pub valid_until: u64,
+    pub timestamp: u64,
+}
+
+pub fn emit_spend_proposed(env: &Env, event: SpendProposed) {
+    let topics = (symbol_short!("spd_prop"), event.spend_index);
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpendPaid {
+    pub spend_index: u64,
+    pub beneficiary: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+pub fn emit_spend_paid(env: &Env, event: SpendPaid) {
+    let topics = (symbol_short!("spd_paid"), event.spend_index);
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpendFailed {
+    pub spend_index: u64,
+    pub timestamp: u64,
+}
+
+pub fn emit_spend_failed(env: &Env, event: SpendFailed) {
+    let topics = (symbol_short!("spd_fail"), event.spend_index);
     env.events().publish(topics, event.clone());
 }
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpendVoided {
+    pub spend_index: u64,
+    pub timestamp: u64,
+}
+
+pub fn emit_spend_voided(env: &Env, event: SpendVoided) {
+    let topics = (symbol_short!("spd_void"), event.spend_index);
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpendExpired {
+    pub spend_index: u64,
+    pub timestamp: u64,
+}
+
+pub fn emit_spend_expired(env: &Env, event: SpendExpired) {
+    let topics = (symbol_short!("spd_expr"), event.spend_index);
+    env.events().publish(topics, event.clone());
+}
+
+// ============================================================================
+// M-of-N Filter Governance (see `crate::filter_governance`)
+// ============================================================================
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FilterProposalExecuted {
+    pub proposal_id: u64,
+    pub proposer: Address,
+    pub approvals: u32,
+    pub timestamp: u64,
+}
+
+pub fn emit_filter_proposal_executed(env: &Env, event: FilterProposalExecuted) {
+    let topics = (symbol_short!("flt_prop"), event.proposal_id);
+    env.events().publish(topics, event.clone());
+}
+
+// ============================================================================
+// Structured Batch Events (Issue #680)
+//
+// `BatchFundsLocked`/`BatchFundsReleased` above only carry a count and a
+// total - an indexer can't tell which `bounty_id`s were in a given batch, or
+// correlate one batch's lock event with its later release event, without
+// replaying every individual escrow write. [`BatchLockExecuted`]/
+// [`BatchReleaseExecuted`] add that trail: both carry the full `bounty_ids`
+// list plus a `batch_id` derived from [`compute_batch_id`] (a sha256 of the
+// ordered ids), so two batches touching the same ids in the same order
+// produce the same id regardless of which entrypoint emitted them.
+//
+// `batch_lock_funds`/`batch_release_funds` should call
+// `emit_batch_lock_executed`/`emit_batch_release_executed` once per
+// successful batch, alongside (not instead of) the existing
+// `emit_batch_funds_locked`/`emit_batch_funds_released` calls.
+// ============================================================================
+
+/// Deterministic id for a batch: sha256 of the ordered `bounty_ids`,
+/// truncated to the first 8 bytes. Two batches over the same ids in the
+/// same order always produce the same id, so an indexer can correlate a
+/// `BatchLockExecuted` with a later `BatchReleaseExecuted` over the same set
+/// without having to diff the `bounty_ids` lists themselves.
+pub fn compute_batch_id(env: &Env, bounty_ids: &Vec<u64>) -> u64 {
+    use soroban_sdk::xdr::ToXdr;
+    let hash = env.crypto().sha256(&bounty_ids.clone().to_xdr(env));
+    let bytes = hash.to_array();
+    u64::from_be_bytes(bytes[0..8].try_into().unwrap())
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchLockExecuted {
+    pub batch_id: u64,
+    /// The first item's depositor, indexed as this event's topic so an
+    /// indexer can filter "every batch locked by address X" the way
+    /// [`emit_funds_released`] indexes a single release's recipient; the full
+    /// per-item set isn't topic-indexable, so callers needing that should
+    /// read `bounty_ids` back off individual `FundsLocked` events.
+    pub depositor: Address,
+    pub item_count: u32,
+    pub total_amount: i128,
+    pub bounty_ids: Vec<u64>,
+    pub timestamp: u64,
+}
+
+pub fn emit_batch_lock_executed(env: &Env, event: BatchLockExecuted) {
+    let topics = (
+        symbol_short!("batch_lck"),
+        event.depositor.clone(),
+        event.batch_id,
+    );
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchReleaseExecuted {
+    pub batch_id: u64,
+    /// The first item's contributor, indexed the same way
+    /// [`BatchLockExecuted::depositor`] is - see that field's doc comment.
+    pub contributor: Address,
+    pub item_count: u32,
+    pub total_amount: i128,
+    pub bounty_ids: Vec<u64>,
+    pub timestamp: u64,
+}
+
+pub fn emit_batch_release_executed(env: &Env, event: BatchReleaseExecuted) {
+    let topics = (
+        symbol_short!("batch_rls"),
+        event.contributor.clone(),
+        event.batch_id,
+    );
+    env.events().publish(topics, event.clone());
+}
+
+// ============================================================================
+// Role/Capability Changes (see `crate::upgrade_safety::{grant_role,
+// revoke_role, renounce_role}`)
+// ============================================================================
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RoleAction {
+    Granted,
+    Revoked,
+    Renounced,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleChanged {
+    pub capability: crate::upgrade_safety::Capability,
+    pub action: RoleAction,
+    pub caller: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_role_changed(env: &Env, event: RoleChanged) {
+    let topics = (symbol_short!("role_chg"), event.caller.clone());
+    env.events().publish(topics, event.clone());
+}
+
+// ============================================================================
+// Global Pause Circuit-Breaker (see `crate::pausable`)
+//
+// Distinct from `PauseStateChanged` above, which covers the older
+// per-operation `lock`/`release`/`refund` pause flags - this covers the
+// single contract-wide `DataKey::Paused` switch.
+// ============================================================================
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GlobalPauseChanged {
+    pub paused: bool,
+    pub caller: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_paused_state_changed(env: &Env, event: GlobalPauseChanged) {
+    let topics = (symbol_short!("g_pause"),);
+    env.events().publish(topics, event.clone());
+}
+
+// ============================================================================
+// Dispute Resolution (see `crate::dispute`)
+// ============================================================================
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeOpened {
+    pub bounty_id: u64,
+    pub raiser: Address,
+    pub timestamp: u64,
+    /// Hashchain head (see `crate::hashchain`) immediately after this
+    /// operation folded in, so an off-chain indexer can replay the chain
+    /// purely from emitted events without a separate state query.
+    pub state_hash: BytesN<32>,
+}
+
+pub fn emit_dispute_opened(env: &Env, event: DisputeOpened) {
+    let topics = (symbol_short!("dsp_open"), event.bounty_id);
+    env.events().publish(topics, event);
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeResolved {
+    pub bounty_id: u64,
+    pub to_contributor: i128,
+    pub to_depositor: i128,
+    pub contributor: Address,
+    pub depositor: Address,
+    pub arbitrator: Address,
+    pub timestamp: u64,
+    /// Hashchain head immediately after this operation folded in - see
+    /// `DisputeOpened::state_hash`.
+    pub state_hash: BytesN<32>,
+}
+
+pub fn emit_dispute_resolved(env: &Env, event: DisputeResolved) {
+    let topics = (symbol_short!("dsp_rslv"), event.bounty_id);
+    env.events().publish(topics, event);
+}
+
+// ============================================================================
+// Deprecation Migration (see `crate::deprecation`)
+// ============================================================================
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowMigrated {
+    pub bounty_id: u64,
+    pub target: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+pub fn emit_escrow_migrated(env: &Env, event: EscrowMigrated) {
+    let topics = (symbol_short!("migrated"), event.bounty_id);
+    env.events().publish(topics, event);
+}
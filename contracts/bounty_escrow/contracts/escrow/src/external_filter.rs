@@ -0,0 +1,161 @@
+//! # Delegated Participant Filter (External Eligibility Provider)
+//!
+//! `ParticipantFilterMode` only ever checked the contract's own local
+//! blocklist/allowlist. Mirroring how authority-set configs evolved from a
+//! flat inline `authorities` list to a `validators` object that can point
+//! at another contract, this adds a delegated mode: instead of maintaining
+//! allow/block lists in every escrow deployment, an operator can point at a
+//! shared KYC/reputation/registry contract and have `lock_funds`/
+//! `batch_lock_funds` defer eligibility to it.
+//!
+//! The provider address lives under its own `DataKey::ExternalFilterProvider`
+//! key rather than inline in the `ParticipantFilterMode` enum payload, so
+//! switching `set_filter_mode` away from the external mode and back doesn't
+//! require re-supplying the address - it persists exactly like the local
+//! lists already do across mode switches. [`is_allowed_by_provider`] is the
+//! cross-contract call `lock_funds`/`batch_lock_funds` should make once
+//! `ParticipantFilterMode::External` is active: it invokes the provider's
+//! `is_participant_allowed(depositor) -> bool` and fails closed - a `false`
+//! response and a trapping/misbehaving provider are treated identically as
+//! "not allowed," since an escrow should never let everyone through just
+//! because the provider it was pointed at broke.
+
+use soroban_sdk::{Address, Env, IntoVal, Symbol, Val, Vec};
+
+const IS_PARTICIPANT_ALLOWED: &str = "is_participant_allowed";
+
+/// Admin-only: point the external filter at `provider`. Does not itself
+/// switch `ParticipantFilterMode` to `External` - that's a separate
+/// `set_filter_mode` call, same as any other mode transition.
+pub fn set_external_filter(env: &Env, admin: &Address, provider: Address) -> Result<(), crate::Error> {
+    admin.require_auth();
+    let stored_admin = crate::errors::require_admin(env)?;
+    if *admin != stored_admin {
+        return Err(crate::Error::Unauthorized);
+    }
+
+    env.storage()
+        .instance()
+        .set(&crate::DataKey::ExternalFilterProvider, &provider);
+    Ok(())
+}
+
+/// The currently configured provider, if any. `None` means no provider has
+/// ever been set - `set_filter_mode(External)` should be rejected in that
+/// case rather than deferring to an address that was never configured.
+pub fn get_external_filter(env: &Env) -> Option<Address> {
+    env.storage()
+        .instance()
+        .get(&crate::DataKey::ExternalFilterProvider)
+}
+
+/// Cross-contract call into `provider`'s `is_participant_allowed(depositor)`.
+/// Any outcome other than a clean `Ok(true)` - `Ok(false)`, a trap inside
+/// the provider, or a missing/mismatched interface - is treated as "not
+/// allowed."
+pub fn is_allowed_by_provider(env: &Env, provider: &Address, depositor: &Address) -> bool {
+    let func = Symbol::new(env, IS_PARTICIPANT_ALLOWED);
+    let args: Vec<Val> = Vec::from_array(env, [depositor.into_val(env)]);
+
+    let result: Result<
+        Result<bool, soroban_sdk::Error>,
+        Result<soroban_sdk::InvokeError, soroban_sdk::InvokeError>,
+    > = env.try_invoke_contract(provider, &func, args);
+
+    matches!(result, Ok(Ok(true)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::{contract, contractimpl, testutils::Address as _};
+
+    #[contract]
+    struct AllowAllProvider;
+
+    #[contractimpl]
+    impl AllowAllProvider {
+        pub fn is_participant_allowed(_env: Env, _depositor: Address) -> bool {
+            true
+        }
+    }
+
+    #[contract]
+    struct DenyAllProvider;
+
+    #[contractimpl]
+    impl DenyAllProvider {
+        pub fn is_participant_allowed(_env: Env, _depositor: Address) -> bool {
+            false
+        }
+    }
+
+    #[contract]
+    struct TrappingProvider;
+
+    #[contractimpl]
+    impl TrappingProvider {
+        pub fn is_participant_allowed(_env: Env, _depositor: Address) -> bool {
+            panic!("provider misbehaving")
+        }
+    }
+
+    fn setup_admin(env: &Env) -> Address {
+        let admin = Address::generate(env);
+        env.storage().instance().set(&crate::DataKey::Admin, &admin);
+        admin
+    }
+
+    #[test]
+    fn test_set_and_get_external_filter_round_trips() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = setup_admin(&env);
+        let provider = Address::generate(&env);
+
+        assert!(get_external_filter(&env).is_none());
+        set_external_filter(&env, &admin, provider.clone()).unwrap();
+        assert_eq!(get_external_filter(&env), Some(provider));
+    }
+
+    #[test]
+    fn test_set_external_filter_rejects_non_admin() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let _admin = setup_admin(&env);
+        let impostor = Address::generate(&env);
+        let provider = Address::generate(&env);
+
+        assert_eq!(
+            set_external_filter(&env, &impostor, provider),
+            Err(crate::Error::Unauthorized)
+        );
+    }
+
+    #[test]
+    fn test_is_allowed_by_provider_true() {
+        let env = Env::default();
+        let provider_id = env.register_contract(None, AllowAllProvider);
+        let depositor = Address::generate(&env);
+
+        assert!(is_allowed_by_provider(&env, &provider_id, &depositor));
+    }
+
+    #[test]
+    fn test_is_allowed_by_provider_false() {
+        let env = Env::default();
+        let provider_id = env.register_contract(None, DenyAllProvider);
+        let depositor = Address::generate(&env);
+
+        assert!(!is_allowed_by_provider(&env, &provider_id, &depositor));
+    }
+
+    #[test]
+    fn test_is_allowed_by_provider_fails_closed_on_trap() {
+        let env = Env::default();
+        let provider_id = env.register_contract(None, TrappingProvider);
+        let depositor = Address::generate(&env);
+
+        assert!(!is_allowed_by_provider(&env, &provider_id, &depositor));
+    }
+}
@@ -0,0 +1,320 @@
+//! # M-of-N Governance for Filter Mutations
+//!
+//! `set_filter_mode`, `set_blocklist_entry`, and `set_whitelist_entry` are
+//! currently unilateral: one admin key can flip the contract straight to
+//! `AllowlistOnly` with an empty allowlist and lock out every depositor.
+//! Borrowing the validator-set model from BFT/authority-round configs (a
+//! registered `list` of authorities that collectively authorize a change),
+//! this adds an optional propose/approve/execute layer in front of those
+//! three mutations.
+//!
+//! This is a dedicated proposal flow rather than routing through the
+//! `Capability::Threshold` role system `upgrade_safety` already has: that
+//! mechanism accumulates votes keyed only by the capability itself, on the
+//! assumption that every call to the gated entrypoint in a given round
+//! carries the same arguments. That assumption doesn't hold here - two
+//! admins could legitimately want to propose *different* outcomes (one
+//! wants `AllowlistOnly`, another wants to blocklist a specific address),
+//! and conflating their votes would quorum-approve neither one correctly.
+//! Each [`FilterProposal`] here carries its own id and payload, and
+//! approvals are recorded against that specific id.
+//!
+//! [`propose_filter_change`] auto-counts the proposer as the first
+//! approval. [`approve_proposal`] returns `Some(action)` the moment the
+//! `threshold`-th distinct approval lands (and marks the proposal executed
+//! so it can never fire twice); the caller - whichever entrypoint wraps
+//! this module - is responsible for actually applying that action to
+//! `ParticipantFilterMode`/the lists and emitting the resulting event,
+//! same as the existing unilateral setters already do.
+
+use crate::ParticipantFilterMode;
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+/// The registered M-of-N admin set these proposals are voted on by.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct FilterGovernanceConfig {
+    pub admins: Vec<Address>,
+    pub threshold: u32,
+}
+
+/// One of the three mutations this module can gate behind a quorum.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FilterProposalAction {
+    SetFilterMode(ParticipantFilterMode),
+    SetBlocklistEntry(Address, bool),
+    SetWhitelistEntry(Address, bool),
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct FilterProposal {
+    pub id: u64,
+    pub proposer: Address,
+    pub action: FilterProposalAction,
+    pub approvals: Vec<Address>,
+    pub executed: bool,
+}
+
+fn config(env: &Env) -> Result<FilterGovernanceConfig, crate::Error> {
+    env.storage()
+        .instance()
+        .get(&crate::DataKey::FilterGovernanceConfig)
+        .ok_or(crate::Error::NotInitialized)
+}
+
+fn next_proposal_id(env: &Env) -> u64 {
+    let id: u64 = env
+        .storage()
+        .instance()
+        .get(&crate::DataKey::NextFilterProposalId)
+        .unwrap_or(0)
+        + 1;
+    env.storage()
+        .instance()
+        .set(&crate::DataKey::NextFilterProposalId, &id);
+    id
+}
+
+fn store_proposal(env: &Env, proposal: &FilterProposal) {
+    env.storage()
+        .persistent()
+        .set(&crate::DataKey::FilterProposal(proposal.id), proposal);
+}
+
+pub fn load_proposal(env: &Env, id: u64) -> Result<FilterProposal, crate::Error> {
+    env.storage()
+        .persistent()
+        .get(&crate::DataKey::FilterProposal(id))
+        .ok_or(crate::Error::FilterProposalNotFound)
+}
+
+/// Admin-only: register the N admins and the M-of-N threshold (`1 <= m <=
+/// n`) that [`propose_filter_change`]/[`approve_proposal`] are voted
+/// against. Approvals are checked against the *current* config each time,
+/// so reconfiguring mid-vote changes what's needed to finish an open
+/// proposal. Operators reconfiguring the admin set while a vote is open
+/// should expect that.
+pub fn configure_filter_governance(
+    env: &Env,
+    admin: &Address,
+    admins: Vec<Address>,
+    threshold: u32,
+) -> Result<(), crate::Error> {
+    admin.require_auth();
+    let stored_admin = crate::errors::require_admin(env)?;
+    if *admin != stored_admin {
+        return Err(crate::Error::Unauthorized);
+    }
+
+    let n = admins.len();
+    if threshold == 0 || threshold > n {
+        return Err(crate::Error::InvalidAmount);
+    }
+
+    env.storage().instance().set(
+        &crate::DataKey::FilterGovernanceConfig,
+        &FilterGovernanceConfig { admins, threshold },
+    );
+    Ok(())
+}
+
+/// Open a new proposal for `action`, requiring `proposer`'s auth and
+/// membership in the registered admin set. The proposer's own approval is
+/// recorded immediately, so a 1-of-N config executes right away.
+pub fn propose_filter_change(
+    env: &Env,
+    proposer: &Address,
+    action: FilterProposalAction,
+) -> Result<u64, crate::Error> {
+    proposer.require_auth();
+    let cfg = config(env)?;
+    if !cfg.admins.iter().any(|a| a == *proposer) {
+        return Err(crate::Error::Unauthorized);
+    }
+
+    let id = next_proposal_id(env);
+    let mut approvals = Vec::new(env);
+    approvals.push_back(proposer.clone());
+    let executed = approvals.len() >= cfg.threshold;
+
+    let proposal = FilterProposal {
+        id,
+        proposer: proposer.clone(),
+        action,
+        approvals,
+        executed,
+    };
+    store_proposal(env, &proposal);
+
+    Ok(id)
+}
+
+/// Record `approver`'s approval of proposal `id`. Requires `approver`'s
+/// auth and admin-set membership; repeat approvals from the same address
+/// are deduplicated rather than double-counted. Returns `Some(action)` the
+/// moment the registered threshold is met, having already marked the
+/// proposal executed so a second call (or a second distinct approver
+/// arriving after quorum) can't return it - and therefore can't apply it -
+/// twice.
+pub fn approve_proposal(
+    env: &Env,
+    approver: &Address,
+    id: u64,
+) -> Result<Option<FilterProposalAction>, crate::Error> {
+    approver.require_auth();
+    let cfg = config(env)?;
+    if !cfg.admins.iter().any(|a| a == *approver) {
+        return Err(crate::Error::Unauthorized);
+    }
+
+    let mut proposal = load_proposal(env, id)?;
+    if proposal.executed {
+        return Err(crate::Error::FilterProposalAlreadyExecuted);
+    }
+
+    if !proposal.approvals.iter().any(|a| a == *approver) {
+        proposal.approvals.push_back(approver.clone());
+    }
+
+    if proposal.approvals.len() >= cfg.threshold {
+        proposal.executed = true;
+        store_proposal(env, &proposal);
+        return Ok(Some(proposal.action));
+    }
+
+    store_proposal(env, &proposal);
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::testutils::Address as _;
+
+    fn setup(env: &Env, n: u32, threshold: u32) -> (Address, Vec<Address>) {
+        let admin = Address::generate(env);
+        env.storage().instance().set(&crate::DataKey::Admin, &admin);
+
+        let mut admins = Vec::new(env);
+        for _ in 0..n {
+            admins.push_back(Address::generate(env));
+        }
+        configure_filter_governance(env, &admin, admins.clone(), threshold).unwrap();
+        (admin, admins)
+    }
+
+    #[test]
+    fn test_configure_filter_governance_rejects_bad_threshold() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        env.storage().instance().set(&crate::DataKey::Admin, &admin);
+        let admins = soroban_sdk::vec![&env, Address::generate(&env), Address::generate(&env)];
+
+        assert_eq!(
+            configure_filter_governance(&env, &admin, admins.clone(), 0),
+            Err(crate::Error::InvalidAmount)
+        );
+        assert_eq!(
+            configure_filter_governance(&env, &admin, admins, 3),
+            Err(crate::Error::InvalidAmount)
+        );
+    }
+
+    #[test]
+    fn test_proposal_below_threshold_is_not_executed() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_admin, admins) = setup(&env, 3, 2);
+
+        let id = propose_filter_change(
+            &env,
+            &admins.get(0).unwrap(),
+            FilterProposalAction::SetFilterMode(ParticipantFilterMode::AllowlistOnly),
+        )
+        .unwrap();
+
+        let proposal = load_proposal(&env, id).unwrap();
+        assert_eq!(proposal.approvals.len(), 1);
+        assert!(!proposal.executed);
+    }
+
+    #[test]
+    fn test_proposal_executes_at_threshold() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_admin, admins) = setup(&env, 3, 2);
+
+        let id = propose_filter_change(
+            &env,
+            &admins.get(0).unwrap(),
+            FilterProposalAction::SetBlocklistEntry(Address::generate(&env), true),
+        )
+        .unwrap();
+
+        let result = approve_proposal(&env, &admins.get(1).unwrap(), id).unwrap();
+        assert!(result.is_some());
+        assert!(load_proposal(&env, id).unwrap().executed);
+    }
+
+    #[test]
+    fn test_approve_proposal_dedups_repeat_approver() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_admin, admins) = setup(&env, 3, 2);
+
+        let id = propose_filter_change(
+            &env,
+            &admins.get(0).unwrap(),
+            FilterProposalAction::SetWhitelistEntry(Address::generate(&env), true),
+        )
+        .unwrap();
+
+        // Proposer approving again shouldn't count twice toward the threshold.
+        let result = approve_proposal(&env, &admins.get(0).unwrap(), id).unwrap();
+        assert!(result.is_none());
+        assert_eq!(load_proposal(&env, id).unwrap().approvals.len(), 1);
+    }
+
+    #[test]
+    fn test_approve_proposal_rejects_non_admin() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_admin, admins) = setup(&env, 3, 2);
+        let impostor = Address::generate(&env);
+
+        let id = propose_filter_change(
+            &env,
+            &admins.get(0).unwrap(),
+            FilterProposalAction::SetFilterMode(ParticipantFilterMode::Disabled),
+        )
+        .unwrap();
+
+        assert_eq!(
+            approve_proposal(&env, &impostor, id),
+            Err(crate::Error::Unauthorized)
+        );
+    }
+
+    #[test]
+    fn test_approve_proposal_rejects_already_executed() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_admin, admins) = setup(&env, 3, 1);
+
+        let id = propose_filter_change(
+            &env,
+            &admins.get(0).unwrap(),
+            FilterProposalAction::SetFilterMode(ParticipantFilterMode::BlocklistOnly),
+        )
+        .unwrap();
+        assert!(load_proposal(&env, id).unwrap().executed);
+
+        assert_eq!(
+            approve_proposal(&env, &admins.get(1).unwrap(), id),
+            Err(crate::Error::FilterProposalAlreadyExecuted)
+        );
+    }
+}
@@ -0,0 +1,237 @@
+//! # Timelocked Filter-Mode Transitions
+//!
+//! `set_filter_mode` applies instantly, so an admin flipping to
+//! `AllowlistOnly` can strand a depositor who was mid-workflow with no
+//! warning. Mirroring step/epoch-gated validator-set transitions
+//! (`startStep`/`stepDuration`), this adds an optional two-phase path:
+//! [`schedule_filter_mode`] records a pending mode and the ledger
+//! timestamp it becomes effective at, while `lock_funds`/
+//! `batch_lock_funds` keep enforcing the *current* mode until that
+//! timestamp passes. [`commit_pending_mode`] (or a lazy check at lock
+//! time via [`effective_filter_mode`]) then promotes it to current.
+//!
+//! A configurable minimum (default [`DEFAULT_MIN_FILTER_MODE_DELAY`]) - set per
+//! contract via [`set_min_filter_mode_delay`] - prevents
+//! `schedule_filter_mode` from being used to bypass the warning window
+//! entirely with an `effective_at` of "right now". [`cancel_pending_mode`]
+//! drops a scheduled change before it activates. This follows the same
+//! propose -> delay -> execute shape `upgrade_safety` uses for staged
+//! upgrades, scoped to the single `ParticipantFilterMode` field instead of
+//! a generic capability-gated action.
+
+use soroban_sdk::{Address, Env};
+
+/// Default minimum seconds between `schedule_filter_mode` and the earliest
+/// `effective_at` it will accept, until overridden by
+/// [`set_min_filter_mode_delay`].
+pub const DEFAULT_MIN_FILTER_MODE_DELAY: u64 = 0;
+
+fn min_delay(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&crate::DataKey::MinFilterModeDelay)
+        .unwrap_or(DEFAULT_MIN_FILTER_MODE_DELAY)
+}
+
+/// Admin-only: set the minimum delay `schedule_filter_mode` must respect
+/// between scheduling and its earliest allowed `effective_at`.
+pub fn set_min_filter_mode_delay(env: &Env, admin: &Address, delay: u64) -> Result<(), crate::Error> {
+    admin.require_auth();
+    let stored_admin = crate::errors::require_admin(env)?;
+    if *admin != stored_admin {
+        return Err(crate::Error::Unauthorized);
+    }
+    env.storage()
+        .instance()
+        .set(&crate::DataKey::MinFilterModeDelay, &delay);
+    Ok(())
+}
+
+/// Admin-only: queue `new_mode` to become effective at `effective_at`
+/// (a ledger timestamp). Rejects an `effective_at` less than
+/// [`min_delay`] seconds from now. Overwrites any previously pending
+/// schedule.
+pub fn schedule_filter_mode(
+    env: &Env,
+    admin: &Address,
+    new_mode: crate::ParticipantFilterMode,
+    effective_at: u64,
+) -> Result<(), crate::Error> {
+    admin.require_auth();
+    let stored_admin = crate::errors::require_admin(env)?;
+    if *admin != stored_admin {
+        return Err(crate::Error::Unauthorized);
+    }
+
+    let now = env.ledger().timestamp();
+    if effective_at < now.saturating_add(min_delay(env)) {
+        return Err(crate::Error::FilterModeDelayNotElapsed);
+    }
+
+    env.storage()
+        .instance()
+        .set(&crate::DataKey::PendingFilterMode, &new_mode);
+    env.storage()
+        .instance()
+        .set(&crate::DataKey::PendingFilterModeEffectiveAt, &effective_at);
+
+    crate::events::emit_filter_mode_scheduled(
+        env,
+        crate::events::FilterModeScheduled {
+            new_mode,
+            effective_at,
+            admin: admin.clone(),
+            timestamp: now,
+        },
+    );
+
+    Ok(())
+}
+
+/// The pending mode and its effective timestamp, if a schedule is open.
+pub fn pending_filter_mode(env: &Env) -> Option<(crate::ParticipantFilterMode, u64)> {
+    let mode: crate::ParticipantFilterMode = env.storage().instance().get(&crate::DataKey::PendingFilterMode)?;
+    let effective_at: u64 = env
+        .storage()
+        .instance()
+        .get(&crate::DataKey::PendingFilterModeEffectiveAt)?;
+    Some((mode, effective_at))
+}
+
+fn clear_pending(env: &Env) {
+    env.storage().instance().remove(&crate::DataKey::PendingFilterMode);
+    env.storage()
+        .instance()
+        .remove(&crate::DataKey::PendingFilterModeEffectiveAt);
+}
+
+/// Admin-only: drop the pending schedule before it activates. A no-op
+/// error (`Error::NoPendingFilterMode`) if nothing is scheduled.
+pub fn cancel_pending_mode(env: &Env, admin: &Address) -> Result<(), crate::Error> {
+    admin.require_auth();
+    let stored_admin = crate::errors::require_admin(env)?;
+    if *admin != stored_admin {
+        return Err(crate::Error::Unauthorized);
+    }
+    pending_filter_mode(env).ok_or(crate::Error::NoPendingFilterMode)?;
+    clear_pending(env);
+    Ok(())
+}
+
+/// If a pending schedule's `effective_at` has passed, promote it to the
+/// active mode (stored at the same `DataKey::FilterMode` key
+/// `set_filter_mode` already uses) and clear the schedule. Returns the
+/// newly active mode if a promotion happened. Callable by anyone, same as
+/// `execute_upgrade` doesn't require the original proposer - any caller
+/// can push an overdue transition through.
+pub fn commit_pending_mode(env: &Env) -> Option<crate::ParticipantFilterMode> {
+    let (mode, effective_at) = pending_filter_mode(env)?;
+    if env.ledger().timestamp() < effective_at {
+        return None;
+    }
+
+    env.storage().instance().set(&crate::DataKey::FilterMode, &mode);
+    clear_pending(env);
+
+    crate::events::emit_filter_mode_committed(
+        env,
+        crate::events::FilterModeCommitted {
+            new_mode: mode.clone(),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Some(mode)
+}
+
+/// The mode that should actually be enforced right now: the active mode,
+/// unless a pending schedule's `effective_at` has already passed, in which
+/// case that pending mode governs even if [`commit_pending_mode`] hasn't
+/// been called yet to persist the promotion. `lock_funds`/
+/// `batch_lock_funds` should check eligibility against this rather than
+/// the raw stored mode.
+pub fn effective_filter_mode(env: &Env, active_mode: crate::ParticipantFilterMode) -> crate::ParticipantFilterMode {
+    match pending_filter_mode(env) {
+        Some((mode, effective_at)) if env.ledger().timestamp() >= effective_at => mode,
+        _ => active_mode,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParticipantFilterMode;
+    use soroban_sdk::testutils::{Address as _, Ledger};
+
+    fn setup(env: &Env) -> Address {
+        let admin = Address::generate(env);
+        env.storage().instance().set(&crate::DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&crate::DataKey::FilterMode, &ParticipantFilterMode::Disabled);
+        admin
+    }
+
+    #[test]
+    fn test_schedule_rejects_delay_below_minimum() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = setup(&env);
+        set_min_filter_mode_delay(&env, &admin, 1_000).unwrap();
+
+        let now = env.ledger().timestamp();
+        assert_eq!(
+            schedule_filter_mode(&env, &admin, ParticipantFilterMode::AllowlistOnly, now + 500),
+            Err(crate::Error::FilterModeDelayNotElapsed)
+        );
+    }
+
+    #[test]
+    fn test_pending_mode_not_enforced_before_effective_at() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = setup(&env);
+        let now = env.ledger().timestamp();
+        schedule_filter_mode(&env, &admin, ParticipantFilterMode::AllowlistOnly, now + 1_000).unwrap();
+
+        assert_eq!(
+            effective_filter_mode(&env, ParticipantFilterMode::Disabled),
+            ParticipantFilterMode::Disabled
+        );
+        assert!(commit_pending_mode(&env).is_none());
+    }
+
+    #[test]
+    fn test_pending_mode_enforced_after_effective_at() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = setup(&env);
+        let now = env.ledger().timestamp();
+        schedule_filter_mode(&env, &admin, ParticipantFilterMode::AllowlistOnly, now + 1_000).unwrap();
+
+        env.ledger().set_timestamp(now + 1_000);
+
+        assert_eq!(
+            effective_filter_mode(&env, ParticipantFilterMode::Disabled),
+            ParticipantFilterMode::AllowlistOnly
+        );
+        assert_eq!(commit_pending_mode(&env), Some(ParticipantFilterMode::AllowlistOnly));
+        assert!(pending_filter_mode(&env).is_none());
+    }
+
+    #[test]
+    fn test_cancel_pending_mode_drops_schedule() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = setup(&env);
+        let now = env.ledger().timestamp();
+        schedule_filter_mode(&env, &admin, ParticipantFilterMode::BlocklistOnly, now + 1_000).unwrap();
+
+        cancel_pending_mode(&env, &admin).unwrap();
+        assert!(pending_filter_mode(&env).is_none());
+        assert_eq!(
+            cancel_pending_mode(&env, &admin),
+            Err(crate::Error::NoPendingFilterMode)
+        );
+    }
+}
@@ -0,0 +1,265 @@
+//! # Operation Hashchain
+//!
+//! A running, tamper-evident commitment over every state-mutating call the
+//! contract accepts. Balances-and-admin style assertions (as used by the
+//! upgrade/rollback tests) only check the fields they remembered to read;
+//! this chain lets an off-chain verifier replay every emitted event from
+//! `seq = 0` and confirm the recomputed head matches the stored head,
+//! catching any mutation those per-field asserts would miss.
+//!
+//! `init` seeds `H_0 = sha256(admin || token)`. Every mutating operation
+//! folds in the previous head, a one-byte operation tag, and the XDR
+//! encoding of its own arguments: `H_n = sha256(H_{n-1} || op_tag || xdr(args))`.
+//! The head and `seq` must be updated atomically with the state change they
+//! cover (same transaction), and both live in instance storage so they carry
+//! across a WASM upgrade untouched.
+
+use soroban_sdk::{xdr::ToXdr, Address, Bytes, BytesN, Env};
+
+/// One-byte tags folded into the chain alongside each operation's arguments.
+pub mod op_tags {
+    pub const LOCK: u8 = 1;
+    pub const RELEASE: u8 = 2;
+    pub const REFUND: u8 = 3;
+    pub const UPDATE_METADATA: u8 = 4;
+    pub const PAUSE_CHANGE: u8 = 5;
+    pub const EMERGENCY_WITHDRAW: u8 = 6;
+    pub const PARTIAL_RELEASE: u8 = 7;
+    pub const FREEZE: u8 = 8;
+    pub const UNFREEZE: u8 = 9;
+    pub const RISK_FLAG_CHANGE: u8 = 10;
+    pub const OPEN_DISPUTE: u8 = 11;
+    pub const RESOLVE_DISPUTE: u8 = 12;
+}
+
+/// Storage key for the running sequence counter. The head itself lives at
+/// `crate::DataKey::Hashchain`.
+const HASHCHAIN_SEQ: &str = "hc_seq";
+
+fn empty_head(env: &Env) -> BytesN<32> {
+    BytesN::from_array(env, &[0u8; 32])
+}
+
+/// Seed the hashchain at `init` time: `H_0 = sha256(admin || token)`.
+pub fn seed_hashchain(env: &Env, admin: &Address, token: &Address) {
+    let mut preimage = Bytes::new(env);
+    preimage.append(&admin.to_xdr(env));
+    preimage.append(&token.to_xdr(env));
+
+    let head: BytesN<32> = env.crypto().sha256(&preimage).into();
+
+    env.storage()
+        .instance()
+        .set(&crate::DataKey::Hashchain, &head);
+    env.storage().instance().set(&HASHCHAIN_SEQ, &0u64);
+}
+
+/// Fold one mutating operation into the chain and persist the new head/seq.
+/// Returns `(head, seq)` so the caller can include both in the operation's
+/// own event, which is what lets an off-chain verifier replay the chain.
+///
+/// Must be called in the same transaction as the state mutation it covers -
+/// the chain is only tamper-evident if it can't fall out of sync with the
+/// state it's attesting to.
+pub fn record_operation<T: ToXdr>(env: &Env, op_tag: u8, args: &T) -> (BytesN<32>, u64) {
+    let prev_head: BytesN<32> = env
+        .storage()
+        .instance()
+        .get(&crate::DataKey::Hashchain)
+        .unwrap_or_else(|| empty_head(env));
+
+    let mut preimage = Bytes::new(env);
+    preimage.append(&Bytes::from_array(env, &prev_head.to_array()));
+    preimage.push_back(op_tag);
+    preimage.append(&args.to_xdr(env));
+
+    let head: BytesN<32> = env.crypto().sha256(&preimage).into();
+    let seq: u64 = env
+        .storage()
+        .instance()
+        .get(&HASHCHAIN_SEQ)
+        .unwrap_or(0u64)
+        + 1;
+
+    env.storage()
+        .instance()
+        .set(&crate::DataKey::Hashchain, &head);
+    env.storage().instance().set(&HASHCHAIN_SEQ, &seq);
+
+    (head, seq)
+}
+
+/// Current chain head and sequence number, for on-chain callers and
+/// off-chain verifiers replaying emitted events to cross-check it.
+pub fn get_hashchain(env: &Env) -> (BytesN<32>, u64) {
+    let head: BytesN<32> = env
+        .storage()
+        .instance()
+        .get(&crate::DataKey::Hashchain)
+        .unwrap_or_else(|| empty_head(env));
+    let seq: u64 = env.storage().instance().get(&HASHCHAIN_SEQ).unwrap_or(0);
+
+    (head, seq)
+}
+
+/// `(seq, head)` - the same pair [`get_hashchain`] returns, just
+/// `seq`-first, matching the `get_hashchain_head() -> (u64, BytesN<32>)`
+/// shape integrators asked for.
+pub fn get_hashchain_head(env: &Env) -> (u64, BytesN<32>) {
+    let (head, seq) = get_hashchain(env);
+    (seq, head)
+}
+
+/// The current chain head alone, for callers that only want
+/// `state_hash` and don't need `seq` alongside it.
+pub fn get_state_hash(env: &Env) -> BytesN<32> {
+    get_hashchain(env).0
+}
+
+/// Whether the chain looks like it was actually seeded: once any escrow
+/// exists, `seq` must be nonzero and the head must differ from the
+/// pre-`init` zero placeholder. [`upgrade_safety::run_safety_checks`]'s
+/// twelfth check uses this as the on-chain-feasible half of tamper
+/// detection - see that check's doc comment for why full event-log replay
+/// has to happen off-chain instead.
+pub fn is_chain_consistent(env: &Env, escrows_exist: bool) -> bool {
+    let (head, seq) = get_hashchain(env);
+    if !escrows_exist {
+        return true;
+    }
+    seq > 0 && head != empty_head(env)
+}
+
+/// Admin-only: re-seed the chain from scratch. Guarded to only run before
+/// the first [`record_operation`] call (`seq == 0`) - once any operation has
+/// been folded in, resetting would let someone erase the history the chain
+/// exists to make tamper-evident in the first place.
+pub fn reset_hashchain(
+    env: &Env,
+    admin: &Address,
+    new_admin: &Address,
+    token: &Address,
+) -> Result<(), crate::Error> {
+    admin.require_auth();
+    let stored_admin: Address = env
+        .storage()
+        .instance()
+        .get(&crate::DataKey::Admin)
+        .ok_or(crate::Error::NotInitialized)?;
+    if *admin != stored_admin {
+        return Err(crate::Error::Unauthorized);
+    }
+
+    let (_, seq) = get_hashchain(env);
+    if seq != 0 {
+        return Err(crate::Error::HashchainAlreadyStarted);
+    }
+
+    seed_hashchain(env, new_admin, token);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::testutils::Address as _;
+
+    #[test]
+    fn test_seed_then_record_advances_seq_and_changes_head() {
+        let env = Env::default();
+        let admin = Address::generate(&env);
+        let token = Address::generate(&env);
+
+        seed_hashchain(&env, &admin, &token);
+        let (seed_head, seed_seq) = get_hashchain(&env);
+        assert_eq!(seed_seq, 0);
+
+        let (head_1, seq_1) = record_operation(&env, op_tags::LOCK, &1u64);
+        assert_eq!(seq_1, 1);
+        assert_ne!(head_1, seed_head);
+
+        let (head_2, seq_2) = record_operation(&env, op_tags::RELEASE, &1u64);
+        assert_eq!(seq_2, 2);
+        assert_ne!(head_2, head_1);
+    }
+
+    #[test]
+    fn test_same_op_tag_and_args_is_deterministic_from_same_seed() {
+        let env = Env::default();
+        let admin = Address::generate(&env);
+        let token = Address::generate(&env);
+
+        seed_hashchain(&env, &admin, &token);
+        let (head_a, _) = record_operation(&env, op_tags::LOCK, &42u64);
+
+        seed_hashchain(&env, &admin, &token);
+        let (head_b, _) = record_operation(&env, op_tags::LOCK, &42u64);
+
+        assert_eq!(head_a, head_b);
+    }
+
+    #[test]
+    fn test_get_hashchain_head_matches_get_hashchain_reordered() {
+        let env = Env::default();
+        let admin = Address::generate(&env);
+        let token = Address::generate(&env);
+
+        seed_hashchain(&env, &admin, &token);
+        record_operation(&env, op_tags::LOCK, &1u64);
+
+        let (head, seq) = get_hashchain(&env);
+        let (seq_head, head_head) = get_hashchain_head(&env);
+        assert_eq!(seq, seq_head);
+        assert_eq!(head, head_head);
+    }
+
+    #[test]
+    fn test_reset_hashchain_rejects_once_an_operation_was_recorded() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let token = Address::generate(&env);
+        env.storage().instance().set(&crate::DataKey::Admin, &admin);
+
+        seed_hashchain(&env, &admin, &token);
+        record_operation(&env, op_tags::LOCK, &1u64);
+
+        assert_eq!(
+            reset_hashchain(&env, &admin, &admin, &token),
+            Err(crate::Error::HashchainAlreadyStarted)
+        );
+    }
+
+    #[test]
+    fn test_reset_hashchain_allowed_before_first_operation() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let token = Address::generate(&env);
+        env.storage().instance().set(&crate::DataKey::Admin, &admin);
+
+        seed_hashchain(&env, &admin, &token);
+        let new_token = Address::generate(&env);
+        reset_hashchain(&env, &admin, &admin, &new_token).unwrap();
+
+        let (_, seq) = get_hashchain(&env);
+        assert_eq!(seq, 0);
+    }
+
+    #[test]
+    fn test_reset_hashchain_rejects_non_admin() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let outsider = Address::generate(&env);
+        let token = Address::generate(&env);
+        env.storage().instance().set(&crate::DataKey::Admin, &admin);
+
+        seed_hashchain(&env, &admin, &token);
+
+        assert_eq!(
+            reset_hashchain(&env, &outsider, &outsider, &token),
+            Err(crate::Error::Unauthorized)
+        );
+    }
+}
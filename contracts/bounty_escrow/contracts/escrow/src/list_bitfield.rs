@@ -0,0 +1,369 @@
+//! # Compact RLE Membership Bitfields
+//!
+//! Per-address `set_blocklist_entry`/`set_whitelist_entry` writes one
+//! storage key per participant, which gets expensive to write in bulk and
+//! offers no way to enumerate or edit a batch atomically. Drawing on
+//! Filecoin-style RLE bitfields (and the partition "move" operations built
+//! on top of them), membership here is stored as a compact structure keyed
+//! by a stable participant index: an `Address -> u64 index` map
+//! (assigned once, on first mention, and never reused) plus a
+//! run-length-encoded bitfield of set indices, so a membership check is a
+//! single bit test rather than a storage read per address.
+//!
+//! The encoding is `runs: Vec<u64>` of alternating lengths - gap, set,
+//! gap, set, ... - always starting with a gap run (length zero if index 0
+//! is itself a member). [`bit_get`] walks the runs to find which one
+//! covers a given index; [`bit_set`] splits the run straddling the target
+//! index into up to three pieces, flips the single bit, and re-merges any
+//! runs that ended up sharing a parity with a neighbour, so the encoding
+//! never accumulates redundant same-parity runs across repeated edits.
+//!
+//! [`batch_set_whitelist`]/[`batch_set_blocklist`] fold a whole batch of
+//! edits into one read-modify-write of the bitfield instead of one storage
+//! round-trip per address. [`move_list_entries`] clears each address from
+//! one list's bitfield and sets it in the other's in a single pass, so an
+//! allow/block migration can't leave an address on both lists (or
+//! neither) if it's interrupted partway. [`list_allowlist`]/
+//! [`list_blocklist`] page through the index space to recover the
+//! enumeration that per-address keys never supported.
+
+use soroban_sdk::{Address, Env, Vec};
+
+/// Which list a bitfield operation targets.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ListKind {
+    Whitelist,
+    Blocklist,
+}
+
+fn bitfield_key(kind: ListKind) -> crate::DataKey {
+    match kind {
+        ListKind::Whitelist => crate::DataKey::WhitelistBitfield,
+        ListKind::Blocklist => crate::DataKey::BlocklistBitfield,
+    }
+}
+
+fn load_runs(env: &Env, kind: ListKind) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&bitfield_key(kind))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn store_runs(env: &Env, kind: ListKind, runs: &Vec<u64>) {
+    env.storage().persistent().set(&bitfield_key(kind), runs);
+}
+
+/// Look up `address`'s stable participant index, assigning the next free
+/// one on first mention. The mapping is never reused, so an address's bit
+/// position is stable for the lifetime of the contract even if it's later
+/// cleared from both lists.
+fn participant_index(env: &Env, address: &Address) -> u64 {
+    if let Some(index) = env
+        .storage()
+        .persistent()
+        .get::<_, u64>(&crate::DataKey::ParticipantIndex(address.clone()))
+    {
+        return index;
+    }
+
+    let next: u64 = env
+        .storage()
+        .instance()
+        .get(&crate::DataKey::NextParticipantIndex)
+        .unwrap_or(0);
+    env.storage()
+        .instance()
+        .set(&crate::DataKey::NextParticipantIndex, &(next + 1));
+    env.storage()
+        .persistent()
+        .set(&crate::DataKey::ParticipantIndex(address.clone()), &next);
+    env.storage().persistent().set(
+        &crate::DataKey::ParticipantByIndex(next),
+        address,
+    );
+    next
+}
+
+/// Whether `index` falls inside a "set" run. Odd run positions are set
+/// runs by convention (the first run, position 0, is always a gap).
+fn bit_get(runs: &Vec<u64>, index: u64) -> bool {
+    let mut pos: u64 = 0;
+    for i in 0..runs.len() {
+        let len = runs.get(i).unwrap();
+        if index < pos + len {
+            return i % 2 == 1;
+        }
+        pos += len;
+    }
+    false
+}
+
+/// Return `runs` with bit `index` set to `value`, splitting and re-merging
+/// runs as needed. A no-op (returns `runs` unchanged) if the bit already
+/// has that value.
+fn bit_set(env: &Env, runs: &Vec<u64>, index: u64, value: bool) -> Vec<u64> {
+    if bit_get(runs, index) == value {
+        return runs.clone();
+    }
+
+    let mut lengths: Vec<u64> = Vec::new(env);
+    let mut parities: Vec<bool> = Vec::new(env);
+
+    let mut pos: u64 = 0;
+    let mut handled = false;
+    for i in 0..runs.len() {
+        let len = runs.get(i).unwrap();
+        let is_set = i % 2 == 1;
+        if !handled && index >= pos && index < pos + len {
+            let before = index - pos;
+            let after = len - before - 1;
+            if before > 0 {
+                lengths.push_back(before);
+                parities.push_back(is_set);
+            }
+            lengths.push_back(1);
+            parities.push_back(value);
+            if after > 0 {
+                lengths.push_back(after);
+                parities.push_back(is_set);
+            }
+            handled = true;
+        } else {
+            lengths.push_back(len);
+            parities.push_back(is_set);
+        }
+        pos += len;
+    }
+    if !handled {
+        // Only reachable for `value == true`: clearing a bit beyond every
+        // existing run is a no-op on an already-unset bit, and that was
+        // already caught above.
+        let gap = index - pos;
+        if gap > 0 {
+            lengths.push_back(gap);
+            parities.push_back(false);
+        }
+        lengths.push_back(1);
+        parities.push_back(true);
+    }
+
+    // Splitting always introduces exactly one parity flip, but the new
+    // piece can end up matching a neighbour's parity - merge those back
+    // together and drop any now-empty runs before re-encoding.
+    let mut merged_lengths: Vec<u64> = Vec::new(env);
+    let mut merged_parities: Vec<bool> = Vec::new(env);
+    for i in 0..lengths.len() {
+        let len = lengths.get(i).unwrap();
+        if len == 0 {
+            continue;
+        }
+        let is_set = parities.get(i).unwrap();
+        let last = merged_lengths.len();
+        if last > 0 && merged_parities.get(last - 1).unwrap() == is_set {
+            let combined = merged_lengths.get(last - 1).unwrap() + len;
+            merged_lengths.set(last - 1, combined);
+        } else {
+            merged_lengths.push_back(len);
+            merged_parities.push_back(is_set);
+        }
+    }
+
+    // The encoding always starts with a gap run, inserting a zero-length
+    // one if the merged runs now begin with a set run.
+    let mut out: Vec<u64> = Vec::new(env);
+    if merged_parities.len() > 0 && merged_parities.get(0).unwrap() {
+        out.push_back(0);
+    }
+    for i in 0..merged_lengths.len() {
+        out.push_back(merged_lengths.get(i).unwrap());
+    }
+    out
+}
+
+/// Apply every `(address, member)` edit to `kind`'s bitfield in one
+/// read-modify-write. Setting an address already at that value is a no-op
+/// for that entry.
+pub fn batch_set(env: &Env, kind: ListKind, edits: Vec<(Address, bool)>) {
+    let mut runs = load_runs(env, kind);
+    for i in 0..edits.len() {
+        let (address, member) = edits.get(i).unwrap();
+        let index = participant_index(env, &address);
+        runs = bit_set(env, &runs, index, member);
+    }
+    store_runs(env, kind, &runs);
+}
+
+pub fn batch_set_whitelist(env: &Env, edits: Vec<(Address, bool)>) {
+    batch_set(env, ListKind::Whitelist, edits);
+}
+
+pub fn batch_set_blocklist(env: &Env, edits: Vec<(Address, bool)>) {
+    batch_set(env, ListKind::Blocklist, edits);
+}
+
+/// Clear each of `addresses` from `from`'s bitfield and set it in `to`'s,
+/// so a migration never leaves an address on both lists or neither if it
+/// were instead done as two separate batch calls.
+pub fn move_list_entries(env: &Env, from: ListKind, to: ListKind, addresses: Vec<Address>) {
+    let mut from_runs = load_runs(env, from);
+    let mut to_runs = load_runs(env, to);
+    for i in 0..addresses.len() {
+        let address = addresses.get(i).unwrap();
+        let index = participant_index(env, &address);
+        from_runs = bit_set(env, &from_runs, index, false);
+        to_runs = bit_set(env, &to_runs, index, true);
+    }
+    store_runs(env, from, &from_runs);
+    store_runs(env, to, &to_runs);
+}
+
+pub fn is_member(env: &Env, kind: ListKind, address: &Address) -> bool {
+    let index = participant_index(env, address);
+    bit_get(&load_runs(env, kind), index)
+}
+
+/// Page through assigned participant indices in `[start, start + limit)`,
+/// returning the addresses whose bit is set in `kind`'s bitfield. Indices
+/// with no address on record (never assigned) are skipped.
+fn list_members(env: &Env, kind: ListKind, start: u64, limit: u32) -> Vec<Address> {
+    let runs = load_runs(env, kind);
+    let mut out: Vec<Address> = Vec::new(env);
+    let mut index = start;
+    let mut found: u32 = 0;
+    while found < limit {
+        if bit_get(&runs, index) {
+            if let Some(address) = env
+                .storage()
+                .persistent()
+                .get::<_, Address>(&crate::DataKey::ParticipantByIndex(index))
+            {
+                out.push_back(address);
+            }
+        }
+        index += 1;
+        found += 1;
+    }
+    out
+}
+
+pub fn list_allowlist(env: &Env, start: u64, limit: u32) -> Vec<Address> {
+    list_members(env, ListKind::Whitelist, start, limit)
+}
+
+pub fn list_blocklist(env: &Env, start: u64, limit: u32) -> Vec<Address> {
+    list_members(env, ListKind::Blocklist, start, limit)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use soroban_sdk::{testutils::Address as _, vec};
+
+    #[test]
+    fn test_batch_set_whitelist_large_batch_round_trips() {
+        let env = Env::default();
+        let addresses: std::vec::Vec<Address> =
+            (0..50).map(|_| Address::generate(&env)).collect();
+
+        let mut edits: Vec<(Address, bool)> = Vec::new(&env);
+        for address in addresses.iter() {
+            edits.push_back((address.clone(), true));
+        }
+        batch_set_whitelist(&env, edits);
+
+        for address in addresses.iter() {
+            assert!(is_member(&env, ListKind::Whitelist, address));
+        }
+        assert_eq!(list_allowlist(&env, 0, 50).len(), 50);
+    }
+
+    #[test]
+    fn test_batch_set_is_idempotent_on_repeat_sets() {
+        let env = Env::default();
+        let address = Address::generate(&env);
+
+        batch_set_whitelist(&env, vec![&env, (address.clone(), true)]);
+        let runs_after_first = load_runs(&env, ListKind::Whitelist);
+        batch_set_whitelist(&env, vec![&env, (address.clone(), true)]);
+        let runs_after_second = load_runs(&env, ListKind::Whitelist);
+
+        assert_eq!(runs_after_first, runs_after_second);
+        assert!(is_member(&env, ListKind::Whitelist, &address));
+    }
+
+    #[test]
+    fn test_bit_set_clear_then_set_merges_runs() {
+        let env = Env::default();
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        let c = Address::generate(&env);
+
+        batch_set_whitelist(
+            &env,
+            vec![
+                &env,
+                (a.clone(), true),
+                (b.clone(), true),
+                (c.clone(), true),
+            ],
+        );
+        assert!(is_member(&env, ListKind::Whitelist, &b));
+
+        batch_set_whitelist(&env, vec![&env, (b.clone(), false)]);
+        assert!(!is_member(&env, ListKind::Whitelist, &b));
+        assert!(is_member(&env, ListKind::Whitelist, &a));
+        assert!(is_member(&env, ListKind::Whitelist, &c));
+
+        batch_set_whitelist(&env, vec![&env, (b.clone(), true)]);
+        assert!(is_member(&env, ListKind::Whitelist, &a));
+        assert!(is_member(&env, ListKind::Whitelist, &b));
+        assert!(is_member(&env, ListKind::Whitelist, &c));
+    }
+
+    #[test]
+    fn test_move_list_entries_migrates_atomically() {
+        let env = Env::default();
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+
+        batch_set_blocklist(
+            &env,
+            vec![&env, (a.clone(), true), (b.clone(), true)],
+        );
+
+        move_list_entries(
+            &env,
+            ListKind::Blocklist,
+            ListKind::Whitelist,
+            vec![&env, a.clone()],
+        );
+
+        assert!(!is_member(&env, ListKind::Blocklist, &a));
+        assert!(is_member(&env, ListKind::Whitelist, &a));
+        assert!(is_member(&env, ListKind::Blocklist, &b));
+        assert!(!is_member(&env, ListKind::Whitelist, &b));
+    }
+
+    #[test]
+    fn test_list_allowlist_paginates_and_enumerates() {
+        let env = Env::default();
+        let addresses: std::vec::Vec<Address> =
+            (0..10).map(|_| Address::generate(&env)).collect();
+        let mut edits: Vec<(Address, bool)> = Vec::new(&env);
+        for address in addresses.iter() {
+            edits.push_back((address.clone(), true));
+        }
+        batch_set_whitelist(&env, edits);
+
+        let page1 = list_allowlist(&env, 0, 4);
+        let page2 = list_allowlist(&env, 4, 4);
+        let page3 = list_allowlist(&env, 8, 4);
+
+        assert_eq!(page1.len(), 4);
+        assert_eq!(page2.len(), 4);
+        assert_eq!(page3.len(), 2);
+    }
+}
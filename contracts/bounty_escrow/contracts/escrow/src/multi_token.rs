@@ -0,0 +1,119 @@
+//! # Multi-Token Support
+//!
+//! `init` wires the contract to a single token, so every bounty is forced
+//! onto the same asset. This module adds an admin-managed allow-list of
+//! tokens (`add_token` / `remove_token`, backed by `DataKey::AllowedTokens`)
+//! so a single deployment can host bounties denominated in different
+//! Stellar assets - mirroring the silo-mode idea of supporting multiple
+//! asset contracts from one main contract instead of deploying one contract
+//! per asset.
+//!
+//! Per-bounty token selection itself (storing `token: Address` on `Escrow`
+//! and having `lock_funds`/`release_funds`/refund/`emergency_withdraw`
+//! transfer through the bounty's own token instead of the single configured
+//! one) is wired in the contract entrypoints; this module only owns the
+//! allow-list and the balance/withdrawal helpers that need to iterate it.
+
+use soroban_sdk::{token, Address, Env, Vec};
+
+/// Register `token` as an asset bounties may lock funds in. Idempotent.
+pub fn add_token(env: &Env, token: &Address) {
+    let mut tokens = allowed_tokens(env);
+    if !tokens.contains(token) {
+        tokens.push_back(token.clone());
+        env.storage()
+            .instance()
+            .set(&crate::DataKey::AllowedTokens, &tokens);
+    }
+}
+
+/// Deregister `token`. Escrows already denominated in it are unaffected;
+/// only new `lock_funds` calls for that asset are rejected afterward.
+pub fn remove_token(env: &Env, token: &Address) {
+    let tokens = allowed_tokens(env);
+    let mut remaining = Vec::new(env);
+    for t in tokens.iter() {
+        if &t != token {
+            remaining.push_back(t);
+        }
+    }
+    env.storage()
+        .instance()
+        .set(&crate::DataKey::AllowedTokens, &remaining);
+}
+
+/// The current set of tokens bounties may be locked in.
+pub fn allowed_tokens(env: &Env) -> Vec<Address> {
+    env.storage()
+        .instance()
+        .get(&crate::DataKey::AllowedTokens)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Whether `lock_funds` should accept this asset.
+pub fn is_token_allowed(env: &Env, token: &Address) -> bool {
+    allowed_tokens(env).contains(token)
+}
+
+/// This contract's balance of every registered token, for a multi-asset
+/// `get_balance`.
+pub fn get_balances(env: &Env, contract_address: &Address) -> Vec<(Address, i128)> {
+    let mut balances = Vec::new(env);
+    for token_address in allowed_tokens(env).iter() {
+        let balance = token::Client::new(env, &token_address).balance(contract_address);
+        balances.push_back((token_address, balance));
+    }
+    balances
+}
+
+/// Transfer the full balance of every registered token to `target` - the
+/// multi-asset analogue of the single-token `emergency_withdraw`. Returns
+/// the amount actually moved per token, for the caller's event payload.
+pub fn emergency_withdraw_all(
+    env: &Env,
+    contract_address: &Address,
+    target: &Address,
+) -> Vec<(Address, i128)> {
+    let mut withdrawn = Vec::new(env);
+    for token_address in allowed_tokens(env).iter() {
+        let client = token::Client::new(env, &token_address);
+        let balance = client.balance(contract_address);
+        if balance > 0 {
+            client.transfer(contract_address, target, &balance);
+        }
+        withdrawn.push_back((token_address, balance));
+    }
+    withdrawn
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::testutils::Address as _;
+
+    #[test]
+    fn test_add_token_is_idempotent() {
+        let env = Env::default();
+        let token = Address::generate(&env);
+
+        add_token(&env, &token);
+        add_token(&env, &token);
+
+        assert_eq!(allowed_tokens(&env).len(), 1);
+        assert!(is_token_allowed(&env, &token));
+    }
+
+    #[test]
+    fn test_remove_token_drops_only_that_token() {
+        let env = Env::default();
+        let token_a = Address::generate(&env);
+        let token_b = Address::generate(&env);
+
+        add_token(&env, &token_a);
+        add_token(&env, &token_b);
+        remove_token(&env, &token_a);
+
+        assert!(!is_token_allowed(&env, &token_a));
+        assert!(is_token_allowed(&env, &token_b));
+    }
+}
@@ -0,0 +1,143 @@
+//! # Global Pause Circuit-Breaker
+//!
+//! `freeze_escrow`/`freeze_address` (and `crate::upgrade_safety`'s
+//! maintenance mode) are both granular - one escrow, one address, or one
+//! lock/release/refund operation at a time. Neither gives an operator a
+//! single switch to halt every fund-moving entrypoint during an incident.
+//! [`pause`] sets a single `DataKey::Paused` flag that [`require_not_paused`]
+//! checks; mutating entrypoints (`lock_funds`, `release_funds`,
+//! `partial_release`, `batch_release_funds`, `refund`) should call it first
+//! and propagate `Error::ContractPaused`, while read-only calls like
+//! `get_escrow_info` are untouched - matching the existing read-while-frozen
+//! invariant `test_freeze_escrow_allows_read_access` already covers for the
+//! per-escrow freeze.
+//!
+//! Gated by `crate::upgrade_safety::Capability::Pause`, which - like every
+//! other capability in that module - falls back to the plain admin check
+//! until an operator explicitly delegates it to a `PAUSER` policy.
+
+use soroban_sdk::{Address, Env};
+
+use crate::upgrade_safety::{require_capability, Capability};
+
+/// Admin- (or `Capability::Pause`-) gated: halt every entrypoint that calls
+/// [`require_not_paused`]. A no-op (but still `Ok`) if already paused.
+pub fn pause(env: &Env, caller: &Address) -> Result<(), crate::Error> {
+    caller.require_auth();
+    require_capability(env, Capability::Pause, caller)?;
+
+    env.storage().instance().set(&crate::DataKey::Paused, &true);
+
+    crate::events::emit_paused_state_changed(
+        env,
+        crate::events::GlobalPauseChanged {
+            paused: true,
+            caller: caller.clone(),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(())
+}
+
+/// Admin- (or `Capability::Pause`-) gated: lift the halt. A no-op (but still
+/// `Ok`) if already unpaused.
+pub fn unpause(env: &Env, caller: &Address) -> Result<(), crate::Error> {
+    caller.require_auth();
+    require_capability(env, Capability::Pause, caller)?;
+
+    env.storage().instance().set(&crate::DataKey::Paused, &false);
+
+    crate::events::emit_paused_state_changed(
+        env,
+        crate::events::GlobalPauseChanged {
+            paused: false,
+            caller: caller.clone(),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(())
+}
+
+/// Whether the circuit-breaker is currently engaged. `false` (not paused)
+/// until [`pause`] has ever been called, so existing deployments keep
+/// working unmodified.
+pub fn paused(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&crate::DataKey::Paused)
+        .unwrap_or(false)
+}
+
+/// The check every mutating entrypoint should run first:
+/// `Error::ContractPaused` while [`paused`], `Ok(())` otherwise.
+pub fn require_not_paused(env: &Env) -> Result<(), crate::Error> {
+    if paused(env) {
+        return Err(crate::Error::ContractPaused);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::testutils::Address as _;
+
+    fn setup(env: &Env) -> Address {
+        let admin = Address::generate(env);
+        env.storage().instance().set(&crate::DataKey::Admin, &admin);
+        admin
+    }
+
+    #[test]
+    fn test_paused_defaults_to_false() {
+        let env = Env::default();
+        assert!(!paused(&env));
+        assert_eq!(require_not_paused(&env), Ok(()));
+    }
+
+    #[test]
+    fn test_pause_blocks_require_not_paused() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = setup(&env);
+
+        pause(&env, &admin).unwrap();
+        assert!(paused(&env));
+        assert_eq!(require_not_paused(&env), Err(crate::Error::ContractPaused));
+
+        unpause(&env, &admin).unwrap();
+        assert!(!paused(&env));
+        assert_eq!(require_not_paused(&env), Ok(()));
+    }
+
+    #[test]
+    fn test_pause_rejects_non_admin_without_pause_capability() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let _admin = setup(&env);
+        let outsider = Address::generate(&env);
+
+        assert_eq!(pause(&env, &outsider), Err(crate::Error::Unauthorized));
+    }
+
+    #[test]
+    fn test_pause_honours_delegated_pause_capability() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = setup(&env);
+        let pauser = Address::generate(&env);
+
+        crate::upgrade_safety::grant_role(
+            &env,
+            &admin,
+            Capability::Pause,
+            crate::upgrade_safety::RolePolicy::Address(pauser.clone()),
+        )
+        .unwrap();
+
+        pause(&env, &pauser).unwrap();
+        assert!(paused(&env));
+    }
+}
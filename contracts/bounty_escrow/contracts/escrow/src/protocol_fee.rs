@@ -0,0 +1,675 @@
+//! # Protocol Fee on Release
+//!
+//! Borrows the silo idea of charging a fixed, admin-configured cost per
+//! operation: an optional protocol fee skimmed from `release_funds`.
+//! `DataKey::FeeBps` and `DataKey::FeeCollector` are admin-managed via
+//! [`set_fee`], capped at [`MAX_FEE_BPS`] so the admin can't tax a release
+//! into nothing. The fee is computed from the escrow's own recorded
+//! `amount` at release time - never from anything that could drift after
+//! lock - so it can't be changed retroactively. A zero-fee default keeps
+//! existing `token_client.balance(&contributor) == amount` assertions
+//! passing for deployments that never call `set_fee`.
+
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+/// Basis-point denominator; `fee_bps` is parts out of this.
+const TOTAL_BASIS_POINTS: i128 = 10_000;
+
+/// Admin can never set a fee above 10% of a release.
+pub const MAX_FEE_BPS: u32 = 1_000;
+
+/// The protocol fee rate and collector, or `None` if `set_fee` was never
+/// called (equivalent to a zero fee).
+pub fn get_fee(env: &Env) -> Option<(u32, Address)> {
+    let bps: Option<u32> = env.storage().instance().get(&crate::DataKey::FeeBps);
+    let collector: Option<Address> = env.storage().instance().get(&crate::DataKey::FeeCollector);
+    match (bps, collector) {
+        (Some(bps), Some(collector)) if bps > 0 => Some((bps, collector)),
+        _ => None,
+    }
+}
+
+/// Admin-only: set the protocol fee rate and collector. Rejects `bps` above
+/// [`MAX_FEE_BPS`] to cap how much of a release the protocol can take.
+pub fn set_fee(
+    env: &Env,
+    admin: &Address,
+    bps: u32,
+    collector: Address,
+) -> Result<(), crate::Error> {
+    admin.require_auth();
+
+    if bps > MAX_FEE_BPS {
+        return Err(crate::Error::FeeExceedsCap);
+    }
+
+    env.storage().instance().set(&crate::DataKey::FeeBps, &bps);
+    env.storage()
+        .instance()
+        .set(&crate::DataKey::FeeCollector, &collector);
+
+    Ok(())
+}
+
+/// Split `amount` (the escrow's own recorded amount, not anything that
+/// could have drifted since lock) into `(fee, remainder)` per the
+/// currently configured rate. `(0, amount)` when no fee is configured.
+pub fn split(env: &Env, amount: i128) -> (i128, i128) {
+    match get_fee(env) {
+        Some((bps, _)) => {
+            let fee = (amount * bps as i128) / TOTAL_BASIS_POINTS;
+            (fee, amount - fee)
+        }
+        None => (0, amount),
+    }
+}
+
+/// Which fee calculation `FeeConfig`-driven entrypoints (`lock_funds`,
+/// `release_funds` via `update_fee_config`/`get_fee_config`) apply. Wiring
+/// a `fee_mode: FeeMode` field plus `fixed_lock_fee`/`fixed_release_fee`
+/// amounts onto `FeeConfig` itself - and charging the result instead of the
+/// old bps-only rate - happens at those contract entrypoints; this module
+/// only owns the calculation in [`compute_fee`].
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FeeMode {
+    /// `amount * rate_bps / 10_000` - the original, and default, behavior.
+    Percentage,
+    /// A flat fee in token units, independent of `amount`.
+    Fixed,
+    /// The larger of the percentage and fixed amounts.
+    MaxOfBoth,
+    /// The smaller of the percentage and fixed amounts.
+    MinOfBoth,
+}
+
+/// Compute the fee to charge on `amount` under `mode`, given a basis-point
+/// `rate_bps` and a flat `fixed_fee` (token units, only meaningful for the
+/// `Fixed`/`MaxOfBoth`/`MinOfBoth` modes). Rejects with
+/// `Error::InvalidAmount` rather than letting `lock_funds` undercharge or
+/// strand a contributor with nothing - a fixed fee sized for an average
+/// deposit can otherwise exceed a much smaller one outright.
+pub fn compute_fee(
+    amount: i128,
+    rate_bps: u32,
+    fixed_fee: i128,
+    mode: FeeMode,
+) -> Result<i128, crate::Error> {
+    let percentage_fee = (amount * rate_bps as i128) / TOTAL_BASIS_POINTS;
+    let fee = match mode {
+        FeeMode::Percentage => percentage_fee,
+        FeeMode::Fixed => fixed_fee,
+        FeeMode::MaxOfBoth => percentage_fee.max(fixed_fee),
+        FeeMode::MinOfBoth => percentage_fee.min(fixed_fee),
+    };
+
+    if fee > amount {
+        return Err(crate::Error::InvalidAmount);
+    }
+
+    Ok(fee)
+}
+
+/// Split `total` across `destinations` using the largest-remainder
+/// (Hamilton) method: every destination first gets
+/// `floor(total * weight / total_weight)`, then the
+/// `total - sum(floors)` units left over - always fewer than
+/// `destinations.len()`, since each floor loses less than one unit - go one
+/// at a time to whichever destination has the largest un-rounded remainder,
+/// breaking ties by larger weight and then by earlier position. This keeps
+/// the per-destination amounts summing to exactly `total` for any weight
+/// vector, instead of letting plain integer division strand dust in the
+/// contract the way [`split`] does for a single collector.
+///
+/// Returns amounts in the same order as `destinations`. A zero total weight
+/// (empty `destinations`, or every weight zero) returns all zeros -
+/// `set_treasury_distributions` is expected to reject that configuration
+/// before it ever reaches here.
+pub fn split_weighted(
+    env: &Env,
+    total: i128,
+    destinations: &Vec<crate::TreasuryDestination>,
+) -> Vec<i128> {
+    let total_weight: i128 = destinations.iter().map(|d| d.weight as i128).sum();
+
+    let mut amounts = Vec::new(env);
+    if total_weight <= 0 {
+        for _ in destinations.iter() {
+            amounts.push_back(0);
+        }
+        return amounts;
+    }
+
+    let mut remainders = Vec::new(env);
+    let mut allocated = 0i128;
+    for destination in destinations.iter() {
+        let share = total * destination.weight as i128;
+        let floor = share / total_weight;
+        amounts.push_back(floor);
+        remainders.push_back(share % total_weight);
+        allocated += floor;
+    }
+
+    let mut leftover = total - allocated;
+    while leftover > 0 {
+        let mut best_index = 0u32;
+        let mut best_remainder = -1i128;
+        let mut best_weight = -1i128;
+        for i in 0..remainders.len() {
+            let remainder = remainders.get(i).unwrap();
+            if remainder < 0 {
+                continue;
+            }
+            let weight = destinations.get(i).unwrap().weight as i128;
+            if remainder > best_remainder || (remainder == best_remainder && weight > best_weight) {
+                best_remainder = remainder;
+                best_weight = weight;
+                best_index = i;
+            }
+        }
+        amounts.set(best_index, amounts.get(best_index).unwrap() + 1);
+        remainders.set(best_index, -1);
+        leftover -= 1;
+    }
+
+    amounts
+}
+
+/// [`split_weighted`], then convert each destination's native-token share
+/// into its own `payout_asset` via [`crate::asset_rates::convert`] - the
+/// escrow token itself when a destination has none configured. Distribution
+/// computes the split in the escrow's own token first and only converts
+/// afterwards, so a destination's rounding share is still derived from the
+/// same largest-remainder split as single-asset distributions, never from a
+/// share that was already converted and re-rounded.
+///
+/// Fails with whatever [`crate::asset_rates::convert`] returns (notably
+/// `Error::AssetRateNotSet`) if any destination requests an asset with no
+/// configured rate from `native_asset` - distribution is all-or-nothing,
+/// never a partial payout with some destinations silently skipped.
+pub fn split_weighted_multi_asset(
+    env: &Env,
+    total: i128,
+    native_asset: &Address,
+    destinations: &Vec<crate::TreasuryDestination>,
+    payout_assets: &Vec<Option<Address>>,
+) -> Result<Vec<i128>, crate::Error> {
+    if payout_assets.len() != destinations.len() {
+        return Err(crate::Error::InvalidAmount);
+    }
+
+    let native_amounts = split_weighted(env, total, destinations);
+
+    let mut converted = Vec::new(env);
+    for i in 0..native_amounts.len() {
+        let native_amount = native_amounts.get(i).unwrap();
+        let payout_asset = payout_assets.get(i).unwrap();
+        let amount = match payout_asset {
+            Some(asset) => crate::asset_rates::convert(env, native_amount, native_asset, &asset)?,
+            None => native_amount,
+        };
+        converted.push_back(amount);
+    }
+
+    Ok(converted)
+}
+
+/// Upper bound on [`FeeConfig::basis_points`] - the same 10% cap
+/// [`MAX_FEE_BPS`] already enforces for the older bps-only [`set_fee`]
+/// path, kept as its own constant since the two configs are set
+/// independently.
+pub const MAX_SETTLEMENT_FEE_BPS: u32 = 1_000;
+
+/// Admin-configurable settlement fee: `basis_points` and `flat_fee`
+/// combine additively (`flat_fee + amount * basis_points / 10_000`, capped
+/// at `amount` by [`compute_settlement_fee`] so a settlement can never be
+/// charged more than it's actually worth), routed to `recipient` while
+/// `enabled`. Unlike the older [`set_fee`]/[`split`] pair of instance
+/// entries, this is a single atomic record - getter/setter guarded the
+/// same way `test_settlement_grace_periods.rs`'s
+/// `set_settlement_grace_period_config` is - so toggling `enabled` off
+/// doesn't require separately clearing a rate and a collector.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeConfig {
+    pub basis_points: u32,
+    pub flat_fee: i128,
+    pub recipient: Address,
+    pub enabled: bool,
+}
+
+/// The current settlement fee config, or a disabled all-zero default if
+/// [`set_fee_config`] was never called - so a deployment that never
+/// touches this keeps charging nothing, the same zero-fee-by-default
+/// invariant [`split`] upholds for the bps-only path.
+pub fn get_fee_config(env: &Env) -> FeeConfig {
+    env.storage()
+        .instance()
+        .get(&crate::DataKey::FeeConfig)
+        .unwrap_or(FeeConfig {
+            basis_points: 0,
+            flat_fee: 0,
+            recipient: env.current_contract_address(),
+            enabled: false,
+        })
+}
+
+/// Admin-only: replace the settlement fee config wholesale. Rejects
+/// `basis_points` above [`MAX_SETTLEMENT_FEE_BPS`] and a negative
+/// `flat_fee`, the same way [`set_fee`] rejects an out-of-range bps - a
+/// large `flat_fee` still can't swallow more than a settlement is worth,
+/// since [`compute_settlement_fee`] caps the combined result at `amount`.
+pub fn set_fee_config(
+    env: &Env,
+    admin: &Address,
+    basis_points: u32,
+    flat_fee: i128,
+    recipient: Address,
+    enabled: bool,
+) -> Result<(), crate::Error> {
+    admin.require_auth();
+    let stored_admin = crate::errors::require_admin(env)?;
+    if *admin != stored_admin {
+        return Err(crate::Error::Unauthorized);
+    }
+    if basis_points > MAX_SETTLEMENT_FEE_BPS {
+        return Err(crate::Error::FeeExceedsCap);
+    }
+    if flat_fee < 0 {
+        return Err(crate::Error::InvalidAmount);
+    }
+
+    env.storage().instance().set(
+        &crate::DataKey::FeeConfig,
+        &FeeConfig {
+            basis_points,
+            flat_fee,
+            recipient,
+            enabled,
+        },
+    );
+    Ok(())
+}
+
+/// `min(amount, flat_fee + amount * basis_points / 10_000)` per the
+/// currently configured [`FeeConfig`], or `0` if none is configured or it's
+/// disabled - the default every existing `release_funds`/`refund` test
+/// keeps passing under.
+pub fn compute_settlement_fee(env: &Env, amount: i128) -> i128 {
+    let config = get_fee_config(env);
+    if !config.enabled {
+        return 0;
+    }
+
+    let percentage = (amount * config.basis_points as i128) / TOTAL_BASIS_POINTS;
+    (config.flat_fee + percentage).clamp(0, amount)
+}
+
+/// Split `amount` into `(fee, remainder, recipient)` per
+/// [`compute_settlement_fee`] - what `release_funds` (and optionally
+/// `refund`) are expected to call before their payout transfer, recording
+/// the returned fee as the escrow's `fee_paid` the same way
+/// [`crate::clawback::terminate_escrow`] hands its split back to its
+/// caller to act on rather than transferring anything itself.
+pub fn split_settlement_fee(env: &Env, amount: i128) -> (i128, i128, Address) {
+    let config = get_fee_config(env);
+    let fee = compute_settlement_fee(env, amount);
+    (fee, amount - fee, config.recipient)
+}
+
+/// Running total of every fee [`charge_settlement_fee`] has collected,
+/// `0` if it's never been called. `refund` is expected to stay fee-exempt
+/// by never calling [`charge_settlement_fee`] in the first place, so
+/// refunded escrows never add to this total.
+pub fn get_accrued_fees(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&crate::DataKey::AccruedFees)
+        .unwrap_or(0)
+}
+
+/// [`split_settlement_fee`], plus the bookkeeping `release_funds`/
+/// `partial_release` (not present in this tree) are expected to use instead
+/// of calling `split_settlement_fee` directly: adds `fee` to
+/// [`get_accrued_fees`]'s running total and emits `FeeCollected` (the same
+/// event the older bps-only path publishes for `FeeOperationType::Release`)
+/// so indexers see one fee stream regardless of which config produced it.
+/// A `0` fee - no config set, disabled, or a zero rate - updates nothing and
+/// emits nothing.
+pub fn charge_settlement_fee(env: &Env, amount: i128) -> (i128, i128, Address) {
+    let (fee, remainder, recipient) = split_settlement_fee(env, amount);
+
+    if fee > 0 {
+        let accrued = get_accrued_fees(env) + fee;
+        env.storage()
+            .instance()
+            .set(&crate::DataKey::AccruedFees, &accrued);
+
+        crate::events::emit_fee_collected(
+            env,
+            crate::events::FeeCollected {
+                version: 1,
+                operation_type: crate::events::FeeOperationType::Release,
+                amount,
+                fee_rate: fee,
+                recipient: recipient.clone(),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    (fee, remainder, recipient)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::testutils::Address as _;
+
+    #[test]
+    fn test_no_fee_configured_keeps_full_amount() {
+        let env = Env::default();
+        assert_eq!(split(&env, 10_000), (0, 10_000));
+    }
+
+    #[test]
+    fn test_split_applies_configured_rate() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let collector = Address::generate(&env);
+
+        set_fee(&env, &admin, 250, collector.clone()).unwrap();
+
+        let (fee, remainder) = split(&env, 10_000);
+        assert_eq!(fee, 250);
+        assert_eq!(remainder, 9_750);
+        assert_eq!(get_fee(&env).unwrap().1, collector);
+    }
+
+    #[test]
+    fn test_set_fee_rejects_above_cap() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let collector = Address::generate(&env);
+
+        let result = set_fee(&env, &admin, MAX_FEE_BPS + 1, collector);
+        assert_eq!(result, Err(crate::Error::FeeExceedsCap));
+    }
+
+    #[test]
+    fn test_compute_fee_percentage_mode_matches_bps_math() {
+        assert_eq!(compute_fee(10_000, 250, 999, FeeMode::Percentage), Ok(250));
+    }
+
+    #[test]
+    fn test_compute_fee_fixed_mode_ignores_rate() {
+        assert_eq!(compute_fee(10_000, 250, 75, FeeMode::Fixed), Ok(75));
+    }
+
+    #[test]
+    fn test_compute_fee_fixed_mode_rejects_fee_above_amount() {
+        assert_eq!(
+            compute_fee(10, 0, 75, FeeMode::Fixed),
+            Err(crate::Error::InvalidAmount)
+        );
+    }
+
+    #[test]
+    fn test_compute_fee_max_and_min_of_both() {
+        // percentage fee = 10_000 * 250 / 10_000 = 250
+        assert_eq!(compute_fee(10_000, 250, 75, FeeMode::MaxOfBoth), Ok(250));
+        assert_eq!(compute_fee(10_000, 250, 75, FeeMode::MinOfBoth), Ok(75));
+    }
+
+    fn destination(env: &Env, weight: u32, region: &str) -> crate::TreasuryDestination {
+        crate::TreasuryDestination {
+            address: Address::generate(env),
+            weight,
+            region: soroban_sdk::String::from_str(env, region),
+        }
+    }
+
+    #[test]
+    fn test_split_weighted_exact_division_has_no_remainder() {
+        let env = Env::default();
+        let destinations = soroban_sdk::vec![
+            &env,
+            destination(&env, 6_000, "na"),
+            destination(&env, 4_000, "eu"),
+        ];
+
+        let amounts = split_weighted(&env, 100, &destinations);
+        assert_eq!(amounts.get(0).unwrap(), 60);
+        assert_eq!(amounts.get(1).unwrap(), 40);
+    }
+
+    #[test]
+    fn test_split_weighted_dust_goes_to_largest_remainder() {
+        let env = Env::default();
+        let destinations = soroban_sdk::vec![
+            &env,
+            destination(&env, 6_000, "na"),
+            destination(&env, 4_000, "eu"),
+        ];
+
+        // fee=101 split 60/40: floors are 60 and 40, leaving 1 unit of dust
+        // that the largest-remainder method must still hand out.
+        let amounts = split_weighted(&env, 101, &destinations);
+        let sum: i128 = amounts.iter().sum();
+        assert_eq!(sum, 101);
+        assert_eq!(amounts.get(0).unwrap(), 61);
+        assert_eq!(amounts.get(1).unwrap(), 40);
+    }
+
+    #[test]
+    fn test_split_weighted_ties_broken_by_weight_then_index() {
+        let env = Env::default();
+        // Equal weights -> equal remainders for every leftover unit, so ties
+        // must fall back to earlier index.
+        let destinations = soroban_sdk::vec![
+            &env,
+            destination(&env, 1, "a"),
+            destination(&env, 1, "b"),
+            destination(&env, 1, "c"),
+        ];
+
+        let amounts = split_weighted(&env, 10, &destinations);
+        let sum: i128 = amounts.iter().sum();
+        assert_eq!(sum, 10);
+        assert_eq!(amounts.get(0).unwrap(), 4);
+        assert_eq!(amounts.get(1).unwrap(), 3);
+        assert_eq!(amounts.get(2).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_split_weighted_multi_asset_defaults_to_native_without_payout_asset() {
+        let env = Env::default();
+        let destinations = soroban_sdk::vec![
+            &env,
+            destination(&env, 6_000, "na"),
+            destination(&env, 4_000, "eu"),
+        ];
+        let payout_assets: Vec<Option<Address>> = soroban_sdk::vec![&env, None, None];
+        let native_asset = Address::generate(&env);
+
+        let amounts =
+            split_weighted_multi_asset(&env, 100, &native_asset, &destinations, &payout_assets)
+                .unwrap();
+        assert_eq!(amounts.get(0).unwrap(), 60);
+        assert_eq!(amounts.get(1).unwrap(), 40);
+    }
+
+    #[test]
+    fn test_split_weighted_multi_asset_converts_using_stored_rate() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let native_asset = Address::generate(&env);
+        let eu_payout_asset = Address::generate(&env);
+        crate::asset_rates::set_asset_rate(
+            &env,
+            &admin,
+            native_asset.clone(),
+            eu_payout_asset.clone(),
+            3,
+            2,
+        )
+        .unwrap();
+
+        let destinations = soroban_sdk::vec![
+            &env,
+            destination(&env, 6_000, "na"),
+            destination(&env, 4_000, "eu"),
+        ];
+        let payout_assets: Vec<Option<Address>> =
+            soroban_sdk::vec![&env, None, Some(eu_payout_asset)];
+
+        let amounts =
+            split_weighted_multi_asset(&env, 100, &native_asset, &destinations, &payout_assets)
+                .unwrap();
+        assert_eq!(amounts.get(0).unwrap(), 60);
+        // 40 native units converted at a 3:2 rate -> 60
+        assert_eq!(amounts.get(1).unwrap(), 60);
+    }
+
+    #[test]
+    fn test_split_weighted_multi_asset_rejects_missing_rate() {
+        let env = Env::default();
+        let native_asset = Address::generate(&env);
+        let eu_payout_asset = Address::generate(&env);
+
+        let destinations = soroban_sdk::vec![&env, destination(&env, 10_000, "eu")];
+        let payout_assets: Vec<Option<Address>> = soroban_sdk::vec![&env, Some(eu_payout_asset)];
+
+        let result =
+            split_weighted_multi_asset(&env, 100, &native_asset, &destinations, &payout_assets);
+        assert_eq!(result, Err(crate::Error::AssetRateNotSet));
+    }
+
+    #[test]
+    fn test_compute_settlement_fee_is_zero_by_default() {
+        let env = Env::default();
+        assert_eq!(compute_settlement_fee(&env, 10_000), 0);
+    }
+
+    #[test]
+    fn test_set_fee_config_rejects_bps_above_cap() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        env.storage().instance().set(&crate::DataKey::Admin, &admin);
+        let recipient = Address::generate(&env);
+
+        assert_eq!(
+            set_fee_config(
+                &env,
+                &admin,
+                MAX_SETTLEMENT_FEE_BPS + 1,
+                0,
+                recipient,
+                true
+            ),
+            Err(crate::Error::FeeExceedsCap)
+        );
+    }
+
+    #[test]
+    fn test_compute_settlement_fee_combines_flat_and_percentage_additively() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        env.storage().instance().set(&crate::DataKey::Admin, &admin);
+        let recipient = Address::generate(&env);
+
+        set_fee_config(&env, &admin, 250, 10, recipient.clone(), true).unwrap();
+
+        // flat_fee(10) + 10_000 * 250 / 10_000 (250) = 260
+        let (fee, remainder, fee_recipient) = split_settlement_fee(&env, 10_000);
+        assert_eq!(fee, 260);
+        assert_eq!(remainder, 9_740);
+        assert_eq!(fee_recipient, recipient);
+    }
+
+    #[test]
+    fn test_compute_settlement_fee_caps_at_amount() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        env.storage().instance().set(&crate::DataKey::Admin, &admin);
+        let recipient = Address::generate(&env);
+
+        set_fee_config(&env, &admin, 0, 500, recipient, true).unwrap();
+
+        let (fee, remainder, _) = split_settlement_fee(&env, 100);
+        assert_eq!(fee, 100);
+        assert_eq!(remainder, 0);
+    }
+
+    #[test]
+    fn test_compute_settlement_fee_ignores_config_when_disabled() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        env.storage().instance().set(&crate::DataKey::Admin, &admin);
+        let recipient = Address::generate(&env);
+
+        set_fee_config(&env, &admin, 250, 10, recipient, false).unwrap();
+
+        assert_eq!(compute_settlement_fee(&env, 10_000), 0);
+    }
+
+    #[test]
+    fn test_get_accrued_fees_starts_at_zero() {
+        let env = Env::default();
+        assert_eq!(get_accrued_fees(&env), 0);
+    }
+
+    #[test]
+    fn test_charge_settlement_fee_accumulates_across_calls() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        env.storage().instance().set(&crate::DataKey::Admin, &admin);
+        let recipient = Address::generate(&env);
+        set_fee_config(&env, &admin, 250, 10, recipient.clone(), true).unwrap();
+
+        let (fee1, remainder1, fee_recipient) = charge_settlement_fee(&env, 10_000);
+        assert_eq!(fee1, 260);
+        assert_eq!(remainder1, 9_740);
+        assert_eq!(fee_recipient, recipient);
+        assert_eq!(get_accrued_fees(&env), 260);
+
+        let (fee2, ..) = charge_settlement_fee(&env, 1_000);
+        assert_eq!(fee2, 35);
+        assert_eq!(get_accrued_fees(&env), 295);
+    }
+
+    #[test]
+    fn test_charge_settlement_fee_no_op_when_disabled() {
+        let env = Env::default();
+        assert_eq!(charge_settlement_fee(&env, 10_000), (0, 10_000, env.current_contract_address()));
+        assert_eq!(get_accrued_fees(&env), 0);
+    }
+
+    #[test]
+    fn test_split_weighted_invariant_holds_across_fee_and_weight_combinations() {
+        let env = Env::default();
+        let weight_sets: [[u32; 3]; 3] = [[6_000, 3_000, 1_000], [1, 1, 1], [9_999, 1, 0]];
+
+        for weights in weight_sets.iter() {
+            let destinations = soroban_sdk::vec![
+                &env,
+                destination(&env, weights[0], "a"),
+                destination(&env, weights[1], "b"),
+                destination(&env, weights[2], "c"),
+            ];
+            for fee in [0i128, 1, 7, 100, 101, 9_999].iter() {
+                let amounts = split_weighted(&env, *fee, &destinations);
+                let sum: i128 = amounts.iter().sum();
+                assert_eq!(sum, *fee);
+            }
+        }
+    }
+}
@@ -0,0 +1,259 @@
+//! # Receipt Merkle Mountain Range
+//!
+//! `emit_operation_receipt`'s doc comment has long promised "optional
+//! on-chain verification via `verify_receipt(receipt_id)`" without any
+//! cryptographic backing for it - the only thing actually checkable was
+//! whatever the event indexer happened to still have on hand. This module
+//! gives receipts a real accumulator: a persistent append-only Merkle
+//! Mountain Range (MMR) over `sha256(xdr(CriticalOperationReceipt))`
+//! leaves, so a holder can prove a specific release/refund receipt was
+//! committed with a compact path even after the contract (or an indexer)
+//! has pruned the underlying event.
+//!
+//! Storage holds the current peak stack as two parallel vectors - hashes
+//! and heights - plus the running leaf count; there is no history of
+//! intermediate roots beyond the current one, since a path proof is
+//! verified against whatever root the caller supplies, not one the
+//! contract looks up. Appending follows the standard MMR merge rule: push
+//! the new leaf as a height-0 peak, then while the two topmost peaks share
+//! a height, pop both and replace them with `sha256(left || right)` at the
+//! next height up. The committed root "bags" the resulting peaks
+//! right-to-left: `accum = peaks.last()`, then for each peak moving left,
+//! `accum = sha256(accum || peak)`.
+//!
+//! [`verify_receipt_proof`] is the read-only counterpart: given the raw
+//! leaf bytes, a [`MerklePathItem`] path, and a claimed root, it recomputes
+//! `h = sha256(leaf)` and folds in each path item (`sha256(item.hash || h)`
+//! when `item.is_left`, else `sha256(h || item.hash)`), returning whether
+//! the result equals the claimed root. It is named distinctly from the
+//! existing `verify_receipt(receipt_id)` lookup entrypoint - that call
+//! returns the stored receipt itself and serves callers who trust the
+//! contract's own storage; this one lets a caller who only kept a leaf and
+//! a path prove inclusion against a root they already have, which is the
+//! scenario that matters once receipts age out of storage or indexing.
+
+use soroban_sdk::{contracttype, xdr::ToXdr, Bytes, BytesN, Env, Vec};
+
+const MMR_PEAKS: &str = "mmr_peaks";
+const MMR_HEIGHTS: &str = "mmr_heights";
+const MMR_LEAF_COUNT: &str = "mmr_leaves";
+const MMR_ROOT: &str = "mmr_root";
+
+/// One step of a Merkle inclusion path: the sibling hash and whether it
+/// sits to the left of the accumulated hash at this step (mirrors the NEAR
+/// Merkle path convention).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MerklePathItem {
+    pub hash: BytesN<32>,
+    pub is_left: bool,
+}
+
+fn zero_root(env: &Env) -> BytesN<32> {
+    BytesN::from_array(env, &[0u8; 32])
+}
+
+fn sha256_pair(env: &Env, left: &BytesN<32>, right: &BytesN<32>) -> BytesN<32> {
+    let mut preimage = Bytes::new(env);
+    preimage.append(&Bytes::from_array(env, &left.to_array()));
+    preimage.append(&Bytes::from_array(env, &right.to_array()));
+    env.crypto().sha256(&preimage).into()
+}
+
+fn bag_peaks(env: &Env, peaks: &Vec<BytesN<32>>) -> BytesN<32> {
+    if peaks.is_empty() {
+        return zero_root(env);
+    }
+
+    let mut accum = peaks.get(peaks.len() - 1).unwrap();
+    let mut i = peaks.len() - 1;
+    while i > 0 {
+        i -= 1;
+        accum = sha256_pair(env, &accum, &peaks.get(i).unwrap());
+    }
+    accum
+}
+
+/// `sha256(xdr(receipt))` - the leaf the MMR actually accumulates. Exposed
+/// so an off-chain holder can recompute the exact leaf bytes to pair with a
+/// stored [`MerklePathItem`] path.
+pub fn receipt_leaf_hash(env: &Env, receipt: &crate::events::CriticalOperationReceipt) -> BytesN<32> {
+    env.crypto().sha256(&receipt.clone().to_xdr(env)).into()
+}
+
+/// Append `leaf` to the accumulator and persist the new peak stack, leaf
+/// count, and bagged root. Returns `(root, leaf_count)` so the caller can
+/// fold both into the receipt's own event.
+pub fn append_leaf(env: &Env, leaf: BytesN<32>) -> (BytesN<32>, u64) {
+    let mut peaks: Vec<BytesN<32>> = env
+        .storage()
+        .instance()
+        .get(&MMR_PEAKS)
+        .unwrap_or_else(|| Vec::new(env));
+    let mut heights: Vec<u32> = env
+        .storage()
+        .instance()
+        .get(&MMR_HEIGHTS)
+        .unwrap_or_else(|| Vec::new(env));
+
+    peaks.push_back(leaf);
+    heights.push_back(0);
+
+    loop {
+        let len = heights.len();
+        if len < 2 {
+            break;
+        }
+        let top_height = heights.get(len - 1).unwrap();
+        let prev_height = heights.get(len - 2).unwrap();
+        if top_height != prev_height {
+            break;
+        }
+
+        let right = peaks.pop_back().unwrap();
+        heights.pop_back();
+        let left = peaks.pop_back().unwrap();
+        heights.pop_back();
+
+        peaks.push_back(sha256_pair(env, &left, &right));
+        heights.push_back(top_height + 1);
+    }
+
+    env.storage().instance().set(&MMR_PEAKS, &peaks);
+    env.storage().instance().set(&MMR_HEIGHTS, &heights);
+
+    let leaf_count: u64 = env.storage().instance().get(&MMR_LEAF_COUNT).unwrap_or(0) + 1;
+    env.storage().instance().set(&MMR_LEAF_COUNT, &leaf_count);
+
+    let root = bag_peaks(env, &peaks);
+    env.storage().instance().set(&MMR_ROOT, &root);
+
+    (root, leaf_count)
+}
+
+/// The current bagged root, or all-zero if no leaf has ever been appended.
+pub fn current_root(env: &Env) -> BytesN<32> {
+    env.storage()
+        .instance()
+        .get(&MMR_ROOT)
+        .unwrap_or_else(|| zero_root(env))
+}
+
+/// Number of leaves appended so far.
+pub fn leaf_count(env: &Env) -> u64 {
+    env.storage().instance().get(&MMR_LEAF_COUNT).unwrap_or(0)
+}
+
+/// Recompute `h = sha256(receipt_leaf_bytes)`, fold in each `path` item in
+/// order, and report whether the result equals `claimed_root`. Does not
+/// touch storage or compare against [`current_root`] - a path is valid
+/// against whatever root it was issued for, which may be older than the
+/// current one if leaves were appended since.
+pub fn verify_receipt_proof(
+    env: &Env,
+    receipt_leaf_bytes: Bytes,
+    path: Vec<MerklePathItem>,
+    claimed_root: BytesN<32>,
+) -> bool {
+    let mut h: BytesN<32> = env.crypto().sha256(&receipt_leaf_bytes).into();
+
+    for item in path.iter() {
+        h = if item.is_left {
+            sha256_pair(env, &item.hash, &h)
+        } else {
+            sha256_pair(env, &h, &item.hash)
+        };
+    }
+
+    h == claimed_root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf(env: &Env, byte: u8) -> BytesN<32> {
+        BytesN::from_array(env, &[byte; 32])
+    }
+
+    #[test]
+    fn test_empty_tree_root_is_zero() {
+        let env = Env::default();
+        assert_eq!(current_root(&env), zero_root(&env));
+        assert_eq!(leaf_count(&env), 0);
+    }
+
+    #[test]
+    fn test_single_leaf_root_is_the_leaf_hash() {
+        let env = Env::default();
+        let l0 = leaf(&env, 1);
+
+        let (root, count) = append_leaf(&env, l0.clone());
+        assert_eq!(root, l0);
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_two_leaves_merge_into_one_peak() {
+        let env = Env::default();
+        let l0 = leaf(&env, 1);
+        let l1 = leaf(&env, 2);
+
+        append_leaf(&env, l0.clone());
+        let (root, count) = append_leaf(&env, l1.clone());
+
+        assert_eq!(count, 2);
+        assert_eq!(root, sha256_pair(&env, &l0, &l1));
+    }
+
+    #[test]
+    fn test_verify_receipt_proof_for_each_leaf_of_a_two_leaf_tree() {
+        let env = Env::default();
+        let leaf0_bytes = Bytes::from_array(&env, &[10u8; 5]);
+        let leaf1_bytes = Bytes::from_array(&env, &[20u8; 5]);
+
+        let l0: BytesN<32> = env.crypto().sha256(&leaf0_bytes).into();
+        let l1: BytesN<32> = env.crypto().sha256(&leaf1_bytes).into();
+
+        append_leaf(&env, l0.clone());
+        let (root, _) = append_leaf(&env, l1.clone());
+
+        let path0 = soroban_sdk::vec![
+            &env,
+            MerklePathItem {
+                hash: l1.clone(),
+                is_left: false,
+            },
+        ];
+        assert!(verify_receipt_proof(
+            &env,
+            leaf0_bytes.clone(),
+            path0,
+            root.clone()
+        ));
+
+        let path1 = soroban_sdk::vec![
+            &env,
+            MerklePathItem {
+                hash: l0.clone(),
+                is_left: true,
+            },
+        ];
+        assert!(verify_receipt_proof(&env, leaf1_bytes, path1, root));
+    }
+
+    #[test]
+    fn test_verify_receipt_proof_rejects_wrong_root() {
+        let env = Env::default();
+        let leaf_bytes = Bytes::from_array(&env, &[7u8; 5]);
+        let (_, _) = append_leaf(&env, env.crypto().sha256(&leaf_bytes).into());
+
+        let wrong_root = leaf(&env, 99);
+        assert!(!verify_receipt_proof(
+            &env,
+            leaf_bytes,
+            Vec::new(&env),
+            wrong_root
+        ));
+    }
+}
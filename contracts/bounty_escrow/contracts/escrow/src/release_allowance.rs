@@ -0,0 +1,255 @@
+//! # Delegated Release Allowances
+//!
+//! Releasing a bounty's funds currently requires the depositor's own
+//! `require_auth`, so a depositor who wants a third party (an automation
+//! bot, a multisig cosigner, a dispute-resolution service) to be able to
+//! trigger payouts on their behalf has no option short of handing over the
+//! depositor key itself. Borrowing the allowance pattern from cw20-escrow,
+//! this module lets a depositor pre-authorize a `releaser` address to
+//! invoke `release_funds`/`partial_release` in their place, bounded by a
+//! cumulative `max_amount` and an `expiry` rather than unlimited standing
+//! authority.
+//!
+//! A [`ReleaseAllowance`] is keyed by `bounty_id` - one active allowance per
+//! escrow, mirroring the one-policy-per-key shape `batch_policy.rs` and
+//! `treasury_limits.rs` already use. [`approve_releaser`] overwrites
+//! whatever was previously recorded rather than layering grants, so
+//! re-approving always starts the spent counter back at zero. [`spend_release_allowance`]
+//! is what `release_funds`/`partial_release` should call when the caller
+//! isn't the depositor themself: it checks the `releaser`'s own auth,
+//! confirms `now < expiry` and the cumulative spend stays within
+//! `max_amount`, and records the spend atomically with the check so two
+//! concurrent partial releases can't both slip in under the cap.
+//!
+//! Freeze checks are this module's caller's responsibility and must run
+//! first - `test_freeze_escrow_blocks_partial_release` expects
+//! `Error::EscrowFrozen` to win over every other rejection reason, so a
+//! frozen escrow must never reach this module's checks in the first place,
+//! valid allowance or not.
+
+use soroban_sdk::{contracttype, Address, Env};
+
+/// One depositor-granted, bounty-scoped release allowance.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReleaseAllowance {
+    pub depositor: Address,
+    pub bounty_id: u64,
+    pub releaser: Address,
+    pub max_amount: i128,
+    pub released: i128,
+    pub expiry: u64,
+}
+
+fn storage_key(bounty_id: u64) -> crate::DataKey {
+    crate::DataKey::ReleaseAllowance(bounty_id)
+}
+
+pub fn get_release_allowance(env: &Env, bounty_id: u64) -> Option<ReleaseAllowance> {
+    env.storage().persistent().get(&storage_key(bounty_id))
+}
+
+/// Depositor-only: authorize `releaser` to spend up to `max_amount` across
+/// one or more future `release_funds`/`partial_release` calls against
+/// `bounty_id`, until `expiry`. Replaces any allowance already on record
+/// for this bounty rather than adding to it. Rejects a non-positive
+/// `max_amount` - an allowance that can never be spent isn't worth storing.
+pub fn approve_releaser(
+    env: &Env,
+    depositor: &Address,
+    bounty_id: u64,
+    releaser: Address,
+    max_amount: i128,
+    expiry: u64,
+) -> Result<(), crate::Error> {
+    depositor.require_auth();
+    if max_amount <= 0 {
+        return Err(crate::Error::InvalidAmount);
+    }
+
+    let allowance = ReleaseAllowance {
+        depositor: depositor.clone(),
+        bounty_id,
+        releaser,
+        max_amount,
+        released: 0,
+        expiry,
+    };
+    env.storage()
+        .persistent()
+        .set(&storage_key(bounty_id), &allowance);
+
+    Ok(())
+}
+
+/// Depositor-only: withdraw the allowance entirely, regardless of how much
+/// of it had already been spent. `Error::AllowanceNotFound` if there was
+/// nothing to revoke.
+pub fn revoke_releaser(env: &Env, depositor: &Address, bounty_id: u64) -> Result<(), crate::Error> {
+    depositor.require_auth();
+    let allowance = get_release_allowance(env, bounty_id).ok_or(crate::Error::AllowanceNotFound)?;
+    if allowance.depositor != *depositor {
+        return Err(crate::Error::Unauthorized);
+    }
+
+    env.storage().persistent().remove(&storage_key(bounty_id));
+    Ok(())
+}
+
+/// What `release_funds`/`partial_release` should call when the caller is
+/// the delegated `releaser` rather than the depositor: authenticates the
+/// releaser, rejects an expired or non-matching allowance, and - only if
+/// `amount` still fits within the remaining budget - records the spend.
+/// Must only be reached after the usual escrow/address freeze checks have
+/// already passed; this function has no awareness of freeze state.
+pub fn spend_release_allowance(
+    env: &Env,
+    bounty_id: u64,
+    releaser: &Address,
+    amount: i128,
+    now: u64,
+) -> Result<(), crate::Error> {
+    releaser.require_auth();
+    let mut allowance =
+        get_release_allowance(env, bounty_id).ok_or(crate::Error::AllowanceNotFound)?;
+    if allowance.releaser != *releaser {
+        return Err(crate::Error::Unauthorized);
+    }
+    if now >= allowance.expiry {
+        return Err(crate::Error::AllowanceExpired);
+    }
+    if allowance.released + amount > allowance.max_amount {
+        return Err(crate::Error::AllowanceExceeded);
+    }
+
+    allowance.released += amount;
+    env.storage()
+        .persistent()
+        .set(&storage_key(bounty_id), &allowance);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::testutils::Address as _;
+
+    #[test]
+    fn test_approve_releaser_rejects_non_positive_max_amount() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let depositor = Address::generate(&env);
+        let releaser = Address::generate(&env);
+
+        assert_eq!(
+            approve_releaser(&env, &depositor, 1, releaser, 0, 1_000),
+            Err(crate::Error::InvalidAmount)
+        );
+    }
+
+    #[test]
+    fn test_spend_release_allowance_decrements_remaining_budget() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let depositor = Address::generate(&env);
+        let releaser = Address::generate(&env);
+
+        approve_releaser(&env, &depositor, 1, releaser.clone(), 1_000, 5_000).unwrap();
+        spend_release_allowance(&env, 1, &releaser, 400, 100).unwrap();
+
+        let allowance = get_release_allowance(&env, 1).unwrap();
+        assert_eq!(allowance.released, 400);
+
+        spend_release_allowance(&env, 1, &releaser, 600, 100).unwrap();
+        assert_eq!(
+            spend_release_allowance(&env, 1, &releaser, 1, 100),
+            Err(crate::Error::AllowanceExceeded)
+        );
+    }
+
+    #[test]
+    fn test_spend_release_allowance_rejects_after_expiry() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let depositor = Address::generate(&env);
+        let releaser = Address::generate(&env);
+
+        approve_releaser(&env, &depositor, 1, releaser.clone(), 1_000, 5_000).unwrap();
+
+        assert_eq!(
+            spend_release_allowance(&env, 1, &releaser, 100, 5_000),
+            Err(crate::Error::AllowanceExpired)
+        );
+    }
+
+    #[test]
+    fn test_spend_release_allowance_rejects_wrong_releaser() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let depositor = Address::generate(&env);
+        let releaser = Address::generate(&env);
+        let impostor = Address::generate(&env);
+
+        approve_releaser(&env, &depositor, 1, releaser, 1_000, 5_000).unwrap();
+
+        assert_eq!(
+            spend_release_allowance(&env, 1, &impostor, 100, 100),
+            Err(crate::Error::Unauthorized)
+        );
+    }
+
+    #[test]
+    fn test_revoke_releaser_clears_allowance() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let depositor = Address::generate(&env);
+        let releaser = Address::generate(&env);
+
+        approve_releaser(&env, &depositor, 1, releaser.clone(), 1_000, 5_000).unwrap();
+        revoke_releaser(&env, &depositor, 1).unwrap();
+
+        assert!(get_release_allowance(&env, 1).is_none());
+        assert_eq!(
+            spend_release_allowance(&env, 1, &releaser, 100, 100),
+            Err(crate::Error::AllowanceNotFound)
+        );
+    }
+
+    #[test]
+    fn test_revoke_releaser_rejects_non_depositor() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let depositor = Address::generate(&env);
+        let releaser = Address::generate(&env);
+        let outsider = Address::generate(&env);
+
+        approve_releaser(&env, &depositor, 1, releaser, 1_000, 5_000).unwrap();
+
+        assert_eq!(
+            revoke_releaser(&env, &outsider, 1),
+            Err(crate::Error::Unauthorized)
+        );
+    }
+
+    #[test]
+    fn test_approve_releaser_overwrites_prior_allowance_and_resets_spend() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let depositor = Address::generate(&env);
+        let releaser_a = Address::generate(&env);
+        let releaser_b = Address::generate(&env);
+
+        approve_releaser(&env, &depositor, 1, releaser_a.clone(), 1_000, 5_000).unwrap();
+        spend_release_allowance(&env, 1, &releaser_a, 900, 100).unwrap();
+
+        approve_releaser(&env, &depositor, 1, releaser_b.clone(), 500, 9_000).unwrap();
+        let allowance = get_release_allowance(&env, 1).unwrap();
+        assert_eq!(allowance.releaser, releaser_b);
+        assert_eq!(allowance.released, 0);
+        assert_eq!(
+            spend_release_allowance(&env, 1, &releaser_a, 1, 100),
+            Err(crate::Error::Unauthorized)
+        );
+    }
+}
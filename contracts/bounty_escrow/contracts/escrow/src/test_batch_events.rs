@@ -0,0 +1,102 @@
+// ============================================================
+// FILE: contracts/bounty_escrow/contracts/escrow/src/test_batch_events.rs
+//
+// Coverage for `events::compute_batch_id`/`emit_batch_lock_executed`/
+// `emit_batch_release_executed` (Issue #680).
+// ============================================================
+
+#![cfg(test)]
+
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, IntoVal, Symbol, TryIntoVal};
+
+use crate::events::{
+    compute_batch_id, emit_batch_lock_executed, emit_batch_release_executed, BatchLockExecuted,
+    BatchReleaseExecuted,
+};
+
+#[test]
+fn test_compute_batch_id_is_deterministic_for_same_ordered_ids() {
+    let env = Env::default();
+    let ids = vec![&env, 1u64, 2u64, 3u64];
+    assert_eq!(compute_batch_id(&env, &ids), compute_batch_id(&env, &ids));
+}
+
+#[test]
+fn test_compute_batch_id_differs_for_different_order() {
+    let env = Env::default();
+    let forward = vec![&env, 1u64, 2u64, 3u64];
+    let reversed = vec![&env, 3u64, 2u64, 1u64];
+    assert_ne!(
+        compute_batch_id(&env, &forward),
+        compute_batch_id(&env, &reversed)
+    );
+}
+
+#[test]
+fn test_emit_batch_lock_executed_publishes_depositor_and_batch_id_topics() {
+    let env = Env::default();
+    let depositor = Address::generate(&env);
+    let bounty_ids = vec![&env, 1u64, 2u64];
+    let batch_id = compute_batch_id(&env, &bounty_ids);
+
+    emit_batch_lock_executed(
+        &env,
+        BatchLockExecuted {
+            batch_id,
+            depositor: depositor.clone(),
+            item_count: 2,
+            total_amount: 2_000,
+            bounty_ids: bounty_ids.clone(),
+            timestamp: 1_234,
+        },
+    );
+
+    let events = env.events().all();
+    let emitted = events.iter().last().unwrap();
+    let topics = emitted.1;
+    let topic_0: Symbol = topics.get(0).unwrap().into_val(&env);
+    assert_eq!(topic_0, Symbol::new(&env, "batch_lck"));
+    let topic_1: Address = topics.get(1).unwrap().into_val(&env);
+    assert_eq!(topic_1, depositor);
+    let topic_2: u64 = topics.get(2).unwrap().into_val(&env);
+    assert_eq!(topic_2, batch_id);
+
+    let data: BatchLockExecuted = emitted.2.try_into_val(&env).unwrap();
+    assert_eq!(data.item_count, 2);
+    assert_eq!(data.total_amount, 2_000);
+    assert_eq!(data.bounty_ids, bounty_ids);
+}
+
+#[test]
+fn test_emit_batch_release_executed_publishes_contributor_and_batch_id_topics() {
+    let env = Env::default();
+    let contributor = Address::generate(&env);
+    let bounty_ids = vec![&env, 5u64];
+    let batch_id = compute_batch_id(&env, &bounty_ids);
+
+    emit_batch_release_executed(
+        &env,
+        BatchReleaseExecuted {
+            batch_id,
+            contributor: contributor.clone(),
+            item_count: 1,
+            total_amount: 500,
+            bounty_ids: bounty_ids.clone(),
+            timestamp: 5_678,
+        },
+    );
+
+    let events = env.events().all();
+    let emitted = events.iter().last().unwrap();
+    let topics = emitted.1;
+    let topic_0: Symbol = topics.get(0).unwrap().into_val(&env);
+    assert_eq!(topic_0, Symbol::new(&env, "batch_rls"));
+    let topic_1: Address = topics.get(1).unwrap().into_val(&env);
+    assert_eq!(topic_1, contributor);
+    let topic_2: u64 = topics.get(2).unwrap().into_val(&env);
+    assert_eq!(topic_2, batch_id);
+
+    let data: BatchReleaseExecuted = emitted.2.try_into_val(&env).unwrap();
+    assert_eq!(data.total_amount, 500);
+    assert_eq!(data.bounty_ids, bounty_ids);
+}
@@ -0,0 +1,136 @@
+// ============================================================
+// FILE: contracts/bounty_escrow/contracts/escrow/src/test_solvency_audit.rs
+//
+// Integration coverage for `solvency_audit::check_invariants`: locks a
+// MAX-size batch, asserts the global "sum of locked == held funds"
+// invariant holds, releases part of the batch, and re-asserts.
+// ============================================================
+
+#![cfg(test)]
+
+use soroban_sdk::{testutils::Address as _, token, vec, Address, Env, Vec};
+
+use crate::{
+    solvency_audit::check_invariants, BountyEscrowContract, BountyEscrowContractClient,
+    LockFundsItem, ReleaseFundsItem,
+};
+
+const MAX_BATCH: u32 = 20; // Must match MAX_BATCH_SIZE in lib.rs
+const AMOUNT: i128 = 1_000;
+const DEADLINE_OFFSET: u64 = 3_600;
+
+struct TestCtx<'a> {
+    env: Env,
+    client: BountyEscrowContractClient<'a>,
+    token_id: Address,
+    depositor: Address,
+    contributor: Address,
+}
+
+impl<'a> TestCtx<'a> {
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let depositor = Address::generate(&env);
+        let contributor = Address::generate(&env);
+
+        let token_id = env.register_stellar_asset_contract(admin.clone());
+        let token_sac = token::StellarAssetClient::new(&env, &token_id);
+
+        let contract_id = env.register_contract(None, BountyEscrowContract);
+        let client = BountyEscrowContractClient::new(&env, &contract_id);
+        client.init(&admin, &token_id);
+
+        token_sac.mint(&depositor, &1_000_000i128);
+
+        Self {
+            env,
+            client,
+            token_id,
+            depositor,
+            contributor,
+        }
+    }
+
+    fn deadline(&self) -> u64 {
+        self.env.ledger().timestamp() + DEADLINE_OFFSET
+    }
+
+    fn build_lock_batch(&self, n: u32) -> Vec<LockFundsItem> {
+        let mut items = Vec::new(&self.env);
+        for i in 1..=(n as u64) {
+            items.push_back(LockFundsItem {
+                bounty_id: i,
+                depositor: self.depositor.clone(),
+                amount: AMOUNT,
+                deadline: self.deadline(),
+            });
+        }
+        items
+    }
+
+    fn build_release_batch(&self, n: u32) -> Vec<ReleaseFundsItem> {
+        let mut items = Vec::new(&self.env);
+        for i in 1..=(n as u64) {
+            items.push_back(ReleaseFundsItem {
+                bounty_id: i,
+                contributor: self.contributor.clone(),
+            });
+        }
+        items
+    }
+
+    fn ids(&self, n: u32) -> Vec<u64> {
+        let mut ids = Vec::new(&self.env);
+        for i in 1..=(n as u64) {
+            ids.push_back(i);
+        }
+        ids
+    }
+}
+
+#[test]
+fn test_check_invariants_holds_after_max_batch_lock_and_partial_release() {
+    let ctx = TestCtx::new();
+
+    let batch = ctx.build_lock_batch(MAX_BATCH);
+    let locked_count = ctx.client.batch_lock_funds(&batch);
+    assert_eq!(locked_count, MAX_BATCH);
+
+    let ids = ctx.ids(MAX_BATCH);
+    let report = ctx.env.as_contract(&ctx.client.address, || {
+        check_invariants(&ctx.env, &ctx.token_id, &ids)
+    });
+    assert_eq!(report.locked_count, MAX_BATCH);
+    assert_eq!(report.total_locked, AMOUNT * MAX_BATCH as i128);
+    assert_eq!(report.token_balance, AMOUNT * MAX_BATCH as i128);
+    assert!(report.solvent);
+
+    // Release half the batch.
+    let half = MAX_BATCH / 2;
+    let release_batch = ctx.build_release_batch(half);
+    let released_count = ctx.client.batch_release_funds(&release_batch);
+    assert_eq!(released_count, half);
+
+    let report = ctx.env.as_contract(&ctx.client.address, || {
+        check_invariants(&ctx.env, &ctx.token_id, &ids)
+    });
+    assert_eq!(report.locked_count, MAX_BATCH - half);
+    assert_eq!(report.released_count, half);
+    assert_eq!(report.total_locked, AMOUNT * (MAX_BATCH - half) as i128);
+    assert_eq!(report.token_balance, AMOUNT * (MAX_BATCH - half) as i128);
+    assert!(report.solvent);
+}
+
+#[test]
+fn test_check_invariants_counts_missing_ids_without_failing() {
+    let ctx = TestCtx::new();
+    let report = ctx.env.as_contract(&ctx.client.address, || {
+        check_invariants(&ctx.env, &ctx.token_id, &vec![&ctx.env, 1u64, 2u64])
+    });
+    assert_eq!(report.missing_count, 2);
+    assert_eq!(report.locked_count, 0);
+    assert!(report.solvent);
+}
@@ -0,0 +1,295 @@
+//! # Treasury Destination Count and Per-Destination Fee Caps
+//!
+//! `set_treasury_distributions` only ever rejected an empty destination
+//! list or a zero total weight, leaving both the number of destinations
+//! (unbounded storage and per-`lock_funds`/`release_funds` iteration cost)
+//! and the amount any single region can receive (no per-destination ceiling)
+//! uncapped. This module adds both limits as a config-time check
+//! ([`validate_destination_count`]) and a capped variant of
+//! [`crate::protocol_fee::split_weighted`] ([`split_weighted_capped`]) that
+//! an entrypoint can use in place of the uncapped split once any
+//! destination has a `max_fee_per_tx`.
+//!
+//! When a destination's computed share would exceed its cap,
+//! [`split_weighted_capped`] clips it to the cap and redistributes the
+//! excess proportionally (by weight, via another largest-remainder split)
+//! among the destinations that aren't already capped - repeating until no
+//! further destination is pushed over its own cap. If every destination
+//! ends up capped before the excess is fully redistributed, the remainder
+//! is left for the caller to [`accrue_undistributed`] into the contract's
+//! own undistributed balance instead of forcing it onto a capped
+//! destination; [`undistributed_treasury_balance`] is the getter that
+//! balance is queryable through.
+
+use soroban_sdk::{Address, Env, Vec};
+
+/// `set_treasury_distributions` falls back to this cap when
+/// [`set_max_treasury_destinations`] has never been called.
+pub const DEFAULT_MAX_TREASURY_DESTINATIONS: u32 = 20;
+
+/// Admin-only: set the upper bound on how many destinations
+/// `set_treasury_distributions` will accept. Rejects zero - a contract that
+/// can never configure a single destination isn't a cap, it's a lockout.
+pub fn set_max_treasury_destinations(
+    env: &Env,
+    admin: &Address,
+    max: u32,
+) -> Result<(), crate::Error> {
+    admin.require_auth();
+    let stored_admin: Address = env
+        .storage()
+        .instance()
+        .get(&crate::DataKey::Admin)
+        .ok_or(crate::Error::NotInitialized)?;
+    if *admin != stored_admin {
+        return Err(crate::Error::Unauthorized);
+    }
+    if max == 0 {
+        return Err(crate::Error::InvalidAmount);
+    }
+
+    env.storage()
+        .instance()
+        .set(&crate::DataKey::MaxTreasuryDestinations, &max);
+
+    Ok(())
+}
+
+/// The currently configured destination-count cap, or
+/// [`DEFAULT_MAX_TREASURY_DESTINATIONS`] if none was ever set.
+pub fn max_treasury_destinations(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&crate::DataKey::MaxTreasuryDestinations)
+        .unwrap_or(DEFAULT_MAX_TREASURY_DESTINATIONS)
+}
+
+/// `set_treasury_distributions`'s config-time check that `destinations`
+/// doesn't exceed [`max_treasury_destinations`].
+pub fn validate_destination_count(
+    env: &Env,
+    destinations: &Vec<crate::TreasuryDestination>,
+) -> Result<(), crate::Error> {
+    if destinations.len() > max_treasury_destinations(env) {
+        return Err(crate::Error::TooManyTreasuryDestinations);
+    }
+    Ok(())
+}
+
+/// [`crate::protocol_fee::split_weighted`], then clip any destination whose
+/// share exceeds its entry in `max_fee_per_tx` (same order as
+/// `destinations`; `None` means uncapped) down to that cap, redistributing
+/// the excess by weight among the destinations not already capped. Repeats
+/// until nothing is newly capped, since redistributing excess can itself
+/// push another destination over its own cap.
+///
+/// Returns `(amounts, undistributed)` - `undistributed` is only nonzero
+/// when every destination ends up capped before the excess is fully handed
+/// out, and is left for the caller to [`accrue_undistributed`] rather than
+/// forced onto an already-capped destination.
+pub fn split_weighted_capped(
+    env: &Env,
+    total: i128,
+    destinations: &Vec<crate::TreasuryDestination>,
+    max_fee_per_tx: &Vec<Option<i128>>,
+) -> Result<(Vec<i128>, i128), crate::Error> {
+    if max_fee_per_tx.len() != destinations.len() {
+        return Err(crate::Error::InvalidAmount);
+    }
+
+    let mut amounts = crate::protocol_fee::split_weighted(env, total, destinations);
+    let mut capped = Vec::new(env);
+    for _ in destinations.iter() {
+        capped.push_back(false);
+    }
+    let mut undistributed = 0i128;
+
+    loop {
+        let mut excess = 0i128;
+        for i in 0..amounts.len() {
+            if capped.get(i).unwrap() {
+                continue;
+            }
+            if let Some(cap) = max_fee_per_tx.get(i).unwrap() {
+                let amount = amounts.get(i).unwrap();
+                if amount > cap {
+                    excess += amount - cap;
+                    amounts.set(i, cap);
+                    capped.set(i, true);
+                }
+            }
+        }
+
+        if excess == 0 {
+            break;
+        }
+
+        let mut remaining_destinations = Vec::new(env);
+        let mut remaining_indices = Vec::new(env);
+        for i in 0..destinations.len() {
+            if !capped.get(i).unwrap() {
+                remaining_destinations.push_back(destinations.get(i).unwrap());
+                remaining_indices.push_back(i);
+            }
+        }
+
+        if remaining_destinations.is_empty() {
+            undistributed += excess;
+            break;
+        }
+
+        let sub_amounts = crate::protocol_fee::split_weighted(env, excess, &remaining_destinations);
+        for j in 0..remaining_indices.len() {
+            let i = remaining_indices.get(j).unwrap();
+            amounts.set(i, amounts.get(i).unwrap() + sub_amounts.get(j).unwrap());
+        }
+    }
+
+    Ok((amounts, undistributed))
+}
+
+/// Credit `amount` to the contract's own undistributed-treasury balance.
+/// Called by a distribution entrypoint with whatever
+/// [`split_weighted_capped`] couldn't hand out.
+pub fn accrue_undistributed(env: &Env, amount: i128) {
+    if amount == 0 {
+        return;
+    }
+    let key = crate::DataKey::UndistributedTreasuryBalance;
+    let balance: i128 = env.storage().instance().get(&key).unwrap_or(0);
+    env.storage().instance().set(&key, &(balance + amount));
+}
+
+/// The contract's accumulated undistributed-treasury balance - fee amounts
+/// that [`split_weighted_capped`] couldn't hand out because every
+/// destination was already at its cap.
+pub fn undistributed_treasury_balance(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&crate::DataKey::UndistributedTreasuryBalance)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::testutils::Address as _;
+
+    fn setup_admin(env: &Env) -> Address {
+        let admin = Address::generate(env);
+        env.storage().instance().set(&crate::DataKey::Admin, &admin);
+        admin
+    }
+
+    fn destination(env: &Env, weight: u32, region: &str) -> crate::TreasuryDestination {
+        crate::TreasuryDestination {
+            address: Address::generate(env),
+            weight,
+            region: soroban_sdk::String::from_str(env, region),
+        }
+    }
+
+    #[test]
+    fn test_max_treasury_destinations_defaults_without_config() {
+        let env = Env::default();
+        assert_eq!(
+            max_treasury_destinations(&env),
+            DEFAULT_MAX_TREASURY_DESTINATIONS
+        );
+    }
+
+    #[test]
+    fn test_set_max_treasury_destinations_rejects_zero() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = setup_admin(&env);
+        assert_eq!(
+            set_max_treasury_destinations(&env, &admin, 0),
+            Err(crate::Error::InvalidAmount)
+        );
+    }
+
+    #[test]
+    fn test_validate_destination_count_fails_above_configured_max() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = setup_admin(&env);
+        set_max_treasury_destinations(&env, &admin, 2).unwrap();
+
+        let destinations = soroban_sdk::vec![
+            &env,
+            destination(&env, 1, "a"),
+            destination(&env, 1, "b"),
+            destination(&env, 1, "c"),
+        ];
+
+        assert_eq!(
+            validate_destination_count(&env, &destinations),
+            Err(crate::Error::TooManyTreasuryDestinations)
+        );
+    }
+
+    #[test]
+    fn test_validate_destination_count_passes_at_the_boundary() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = setup_admin(&env);
+        set_max_treasury_destinations(&env, &admin, 2).unwrap();
+
+        let destinations =
+            soroban_sdk::vec![&env, destination(&env, 1, "a"), destination(&env, 1, "b"),];
+
+        assert_eq!(validate_destination_count(&env, &destinations), Ok(()));
+    }
+
+    #[test]
+    fn test_split_weighted_capped_redistributes_excess_to_uncapped_destination() {
+        let env = Env::default();
+        // na=60, eu=40 on a 100 split. Cap na at 55; the 5 excess should
+        // flow entirely to eu, the only uncapped destination.
+        let destinations = soroban_sdk::vec![
+            &env,
+            destination(&env, 6_000, "na"),
+            destination(&env, 4_000, "eu"),
+        ];
+        let caps: Vec<Option<i128>> = soroban_sdk::vec![&env, Some(55), None];
+
+        let (amounts, undistributed) =
+            split_weighted_capped(&env, 100, &destinations, &caps).unwrap();
+        assert_eq!(amounts.get(0).unwrap(), 55);
+        assert_eq!(amounts.get(1).unwrap(), 45);
+        assert_eq!(undistributed, 0);
+    }
+
+    #[test]
+    fn test_split_weighted_capped_all_capped_leaves_contract_balance() {
+        let env = Env::default();
+        let destinations = soroban_sdk::vec![
+            &env,
+            destination(&env, 6_000, "na"),
+            destination(&env, 4_000, "eu"),
+        ];
+        let caps: Vec<Option<i128>> = soroban_sdk::vec![&env, Some(10), Some(10)];
+
+        let (amounts, undistributed) =
+            split_weighted_capped(&env, 100, &destinations, &caps).unwrap();
+        assert_eq!(amounts.get(0).unwrap(), 10);
+        assert_eq!(amounts.get(1).unwrap(), 10);
+        assert_eq!(undistributed, 80);
+
+        accrue_undistributed(&env, undistributed);
+        assert_eq!(undistributed_treasury_balance(&env), 80);
+    }
+
+    #[test]
+    fn test_split_weighted_capped_rejects_mismatched_caps_length() {
+        let env = Env::default();
+        let destinations = soroban_sdk::vec![&env, destination(&env, 1, "a")];
+        let caps: Vec<Option<i128>> = Vec::new(&env);
+
+        assert_eq!(
+            split_weighted_capped(&env, 100, &destinations, &caps),
+            Err(crate::Error::InvalidAmount)
+        );
+    }
+}
@@ -0,0 +1,389 @@
+//! # Managed Treasury Spend Workflow
+//!
+//! `lock_funds`/`release_funds` forward collected fees straight to each
+//! configured `TreasuryDestination` when distribution is enabled, which
+//! gives no governance over the outflow. This module adds an opt-in
+//! "managed spend" mode: instead of an immediate push, fees accrue into a
+//! balance keyed by `(region, asset)` via [`accrue`], and moving money out
+//! of that balance requires an explicit `propose_spend` -> `payout`
+//! lifecycle an admin drives - mirroring the propose/approve staging
+//! [`crate::upgrade_safety`] already uses for upgrades and admin rotation,
+//! rather than a single-step transfer.
+//!
+//! `propose_spend` debits the regional balance up front so two pending
+//! proposals can never be honored against the same accrued fees twice;
+//! `void_spend` and an expired [`payout`]/[`check_spend`] credit it back.
+
+use soroban_sdk::{contracttype, token, Address, Env, String};
+
+pub type SpendIndex = u64;
+
+/// Lifecycle state of a single [`SpendRecord`].
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SpendStatus {
+    /// Proposed, not yet (successfully) paid out.
+    Pending,
+    /// Transferred to `beneficiary`.
+    Paid,
+    /// A `payout` attempt's transfer failed; another `payout` call retries it.
+    Failed,
+    /// Cancelled via `void_spend` before payout.
+    Voided,
+    /// `valid_until` elapsed before payout; no further action is possible.
+    Expired,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpendRecord {
+    pub region: String,
+    pub asset: Address,
+    pub amount: i128,
+    pub beneficiary: Address,
+    pub valid_until: u64,
+    pub status: SpendStatus,
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), crate::Error> {
+    admin.require_auth();
+    let stored_admin: Address = env
+        .storage()
+        .instance()
+        .get(&crate::DataKey::Admin)
+        .ok_or(crate::Error::NotInitialized)?;
+    if *admin != stored_admin {
+        return Err(crate::Error::Unauthorized);
+    }
+    Ok(())
+}
+
+fn balance_key(region: &String, asset: &Address) -> crate::DataKey {
+    crate::DataKey::RegionBalance(region.clone(), asset.clone())
+}
+
+/// Credit `amount` of `asset` to `region`'s spendable balance. Called by
+/// `lock_funds`/`release_funds` instead of transferring straight to a
+/// `TreasuryDestination` once managed-spend mode is active for that region.
+pub fn accrue(env: &Env, region: &String, asset: &Address, amount: i128) {
+    let key = balance_key(region, asset);
+    let balance: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+    env.storage().persistent().set(&key, &(balance + amount));
+}
+
+/// Current spendable balance for `(region, asset)`.
+pub fn region_balance(env: &Env, region: &String, asset: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&balance_key(region, asset))
+        .unwrap_or(0)
+}
+
+fn next_spend_index(env: &Env) -> SpendIndex {
+    let next: SpendIndex = env
+        .storage()
+        .instance()
+        .get(&crate::DataKey::NextSpendIndex)
+        .unwrap_or(0);
+    env.storage()
+        .instance()
+        .set(&crate::DataKey::NextSpendIndex, &(next + 1));
+    next
+}
+
+/// Admin-only: record a pending spend of `amount` of `asset` out of
+/// `region`'s accrued balance, payable to `beneficiary` any time up to
+/// (and including) `valid_until`. Debits the regional balance immediately.
+pub fn propose_spend(
+    env: &Env,
+    admin: &Address,
+    region: String,
+    asset: Address,
+    amount: i128,
+    beneficiary: Address,
+    valid_until: u64,
+) -> Result<SpendIndex, crate::Error> {
+    require_admin(env, admin)?;
+
+    if amount <= 0 {
+        return Err(crate::Error::InvalidAmount);
+    }
+
+    let key = balance_key(&region, &asset);
+    let balance: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+    if amount > balance {
+        return Err(crate::Error::InsufficientBalance);
+    }
+    env.storage().persistent().set(&key, &(balance - amount));
+
+    let index = next_spend_index(env);
+    let record = SpendRecord {
+        region: region.clone(),
+        asset: asset.clone(),
+        amount,
+        beneficiary: beneficiary.clone(),
+        valid_until,
+        status: SpendStatus::Pending,
+    };
+    env.storage()
+        .persistent()
+        .set(&crate::DataKey::Spend(index), &record);
+
+    crate::events::emit_spend_proposed(
+        env,
+        crate::events::SpendProposed {
+            spend_index: index,
+            region,
+            asset,
+            amount,
+            beneficiary,
+            valid_until,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(index)
+}
+
+fn load_spend(env: &Env, index: SpendIndex) -> Result<SpendRecord, crate::Error> {
+    env.storage()
+        .persistent()
+        .get(&crate::DataKey::Spend(index))
+        .ok_or(crate::Error::SpendNotFound)
+}
+
+fn expire_if_due(env: &Env, index: SpendIndex, mut record: SpendRecord) -> SpendRecord {
+    if record.status == SpendStatus::Pending && env.ledger().timestamp() > record.valid_until {
+        record.status = SpendStatus::Expired;
+        env.storage()
+            .persistent()
+            .set(&crate::DataKey::Spend(index), &record);
+        accrue(env, &record.region, &record.asset, record.amount);
+        crate::events::emit_spend_expired(
+            env,
+            crate::events::SpendExpired {
+                spend_index: index,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+    record
+}
+
+/// Admin-only: transfer a pending (or previously failed) spend to its
+/// beneficiary. Expires it in place instead - crediting the amount back to
+/// the regional balance - if `valid_until` has already passed.
+pub fn payout(env: &Env, admin: &Address, index: SpendIndex) -> Result<(), crate::Error> {
+    require_admin(env, admin)?;
+
+    let mut record = expire_if_due(env, index, load_spend(env, index)?);
+
+    match record.status {
+        SpendStatus::Pending | SpendStatus::Failed => {}
+        SpendStatus::Expired => return Err(crate::Error::SpendExpired),
+        SpendStatus::Paid | SpendStatus::Voided => return Err(crate::Error::SpendNotPending),
+    }
+
+    let token_client = token::Client::new(env, &record.asset);
+    let transfer_result = token_client.try_transfer(
+        &env.current_contract_address(),
+        &record.beneficiary,
+        &record.amount,
+    );
+
+    match transfer_result {
+        Ok(Ok(())) => {
+            record.status = SpendStatus::Paid;
+            env.storage()
+                .persistent()
+                .set(&crate::DataKey::Spend(index), &record);
+            crate::events::emit_spend_paid(
+                env,
+                crate::events::SpendPaid {
+                    spend_index: index,
+                    beneficiary: record.beneficiary,
+                    amount: record.amount,
+                    timestamp: env.ledger().timestamp(),
+                },
+            );
+            Ok(())
+        }
+        _ => {
+            record.status = SpendStatus::Failed;
+            env.storage()
+                .persistent()
+                .set(&crate::DataKey::Spend(index), &record);
+            crate::events::emit_spend_failed(
+                env,
+                crate::events::SpendFailed {
+                    spend_index: index,
+                    timestamp: env.ledger().timestamp(),
+                },
+            );
+            Err(crate::Error::SpendPayoutFailed)
+        }
+    }
+}
+
+/// Admin-only: cancel a pending (or previously failed) spend before it's
+/// paid, crediting its amount back to the regional balance.
+pub fn void_spend(env: &Env, admin: &Address, index: SpendIndex) -> Result<(), crate::Error> {
+    require_admin(env, admin)?;
+
+    let mut record = expire_if_due(env, index, load_spend(env, index)?);
+
+    match record.status {
+        SpendStatus::Pending | SpendStatus::Failed => {}
+        SpendStatus::Expired => return Err(crate::Error::SpendExpired),
+        SpendStatus::Paid | SpendStatus::Voided => return Err(crate::Error::SpendNotPending),
+    }
+
+    record.status = SpendStatus::Voided;
+    env.storage()
+        .persistent()
+        .set(&crate::DataKey::Spend(index), &record);
+    accrue(env, &record.region, &record.asset, record.amount);
+
+    crate::events::emit_spend_voided(
+        env,
+        crate::events::SpendVoided {
+            spend_index: index,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(())
+}
+
+/// Current status of a spend, applying expiry (and its balance refund) if
+/// `valid_until` has passed since the last state change.
+pub fn check_spend(env: &Env, index: SpendIndex) -> Result<SpendStatus, crate::Error> {
+    let record = expire_if_due(env, index, load_spend(env, index)?);
+    Ok(record.status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::testutils::{Address as _, Ledger};
+
+    fn setup(env: &Env) -> (Address, Address, String) {
+        let admin = Address::generate(env);
+        env.storage().instance().set(&crate::DataKey::Admin, &admin);
+        let asset = Address::generate(env);
+        let region = String::from_str(env, "north_america");
+        (admin, asset, region)
+    }
+
+    #[test]
+    fn test_propose_spend_requires_sufficient_region_balance() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (admin, asset, region) = setup(&env);
+        let beneficiary = Address::generate(&env);
+
+        let result = propose_spend(
+            &env,
+            &admin,
+            region.clone(),
+            asset.clone(),
+            100,
+            beneficiary,
+            env.ledger().timestamp() + 1000,
+        );
+        assert_eq!(result, Err(crate::Error::InsufficientBalance));
+
+        accrue(&env, &region, &asset, 100);
+        assert_eq!(region_balance(&env, &region, &asset), 100);
+    }
+
+    #[test]
+    fn test_propose_spend_debits_region_balance() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (admin, asset, region) = setup(&env);
+        let beneficiary = Address::generate(&env);
+        accrue(&env, &region, &asset, 100);
+
+        let index = propose_spend(
+            &env,
+            &admin,
+            region.clone(),
+            asset.clone(),
+            60,
+            beneficiary,
+            env.ledger().timestamp() + 1000,
+        )
+        .unwrap();
+
+        assert_eq!(region_balance(&env, &region, &asset), 40);
+        assert_eq!(check_spend(&env, index).unwrap(), SpendStatus::Pending);
+    }
+
+    #[test]
+    fn test_void_spend_credits_balance_back() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (admin, asset, region) = setup(&env);
+        let beneficiary = Address::generate(&env);
+        accrue(&env, &region, &asset, 100);
+
+        let index = propose_spend(
+            &env,
+            &admin,
+            region.clone(),
+            asset.clone(),
+            60,
+            beneficiary,
+            env.ledger().timestamp() + 1000,
+        )
+        .unwrap();
+
+        void_spend(&env, &admin, index).unwrap();
+        assert_eq!(check_spend(&env, index).unwrap(), SpendStatus::Voided);
+        assert_eq!(region_balance(&env, &region, &asset), 100);
+
+        // A voided spend can't be voided again or paid out.
+        assert_eq!(
+            void_spend(&env, &admin, index),
+            Err(crate::Error::SpendNotPending)
+        );
+        assert_eq!(
+            payout(&env, &admin, index),
+            Err(crate::Error::SpendNotPending)
+        );
+    }
+
+    #[test]
+    fn test_check_spend_expires_past_valid_until_and_refunds() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (admin, asset, region) = setup(&env);
+        let beneficiary = Address::generate(&env);
+        accrue(&env, &region, &asset, 100);
+
+        let valid_until = env.ledger().timestamp() + 100;
+        let index = propose_spend(
+            &env,
+            &admin,
+            region.clone(),
+            asset.clone(),
+            60,
+            beneficiary,
+            valid_until,
+        )
+        .unwrap();
+
+        env.ledger().set_timestamp(valid_until + 1);
+
+        assert_eq!(check_spend(&env, index).unwrap(), SpendStatus::Expired);
+        assert_eq!(region_balance(&env, &region, &asset), 100);
+        assert_eq!(payout(&env, &admin, index), Err(crate::Error::SpendExpired));
+    }
+
+    #[test]
+    fn test_check_spend_missing_index_returns_not_found() {
+        let env = Env::default();
+        assert_eq!(check_spend(&env, 999), Err(crate::Error::SpendNotFound));
+    }
+}
@@ -0,0 +1,653 @@
+//! # Declarative Validation Precedence
+//!
+//! `lock_funds`/`release_funds` used to enforce their error precedence with
+//! ordered `if` checks repeated at each call site, so the only place the
+//! hierarchy was documented was in the tests that observed it empirically.
+//! [`ValidationCheck`] makes the hierarchy data instead: each variant carries
+//! its own priority and reads the one `DataKey` it cares about. [`validate`]
+//! walks the variants in ascending priority and returns the first failing
+//! `Error`, so the entrypoints and the read-only `simulate_*` functions below
+//! run the exact same pipeline and can never drift apart.
+
+use crate::{DataKey, Error, Escrow, EscrowStatus, PauseFlags};
+use soroban_sdk::{contracttype, Address, Env};
+
+/// The mutating entrypoint a validation pass is standing in for.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Operation {
+    LockFunds,
+    ReleaseFunds,
+}
+
+/// Configured bounds for `lock_funds` amounts, set via `set_amount_policy`.
+///
+/// `dust_threshold` borrows the EIP-168/169 notion of dust: any release that
+/// would leave `0 < remaining_amount < dust_threshold` gets auto-swept
+/// instead of leaving an un-claimable residue locked forever (see
+/// [`sweep_dust_on_release`]), and `lock_funds` rejects new escrows whose
+/// `amount` would already be below it.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AmountPolicy {
+    pub min: i128,
+    pub max: i128,
+    pub dust_threshold: i128,
+}
+
+/// Set the bounds `lock_funds`/releases are validated against. Admin-gated,
+/// same pattern as the other single-admin setters in this crate.
+pub fn set_amount_policy(
+    env: &Env,
+    admin: &Address,
+    min: i128,
+    max: i128,
+    dust_threshold: i128,
+) -> Result<(), Error> {
+    admin.require_auth();
+    let stored_admin = crate::errors::require_admin(env)?;
+    if *admin != stored_admin {
+        return Err(Error::Unauthorized);
+    }
+    env.storage().instance().set(
+        &DataKey::AmountPolicy,
+        &AmountPolicy {
+            min,
+            max,
+            dust_threshold,
+        },
+    );
+    Ok(())
+}
+
+fn amount_policy(env: &Env) -> Option<AmountPolicy> {
+    env.storage().instance().get(&DataKey::AmountPolicy)
+}
+
+fn load_escrow(env: &Env, bounty_id: u64) -> Option<Escrow> {
+    env.storage().persistent().get(&DataKey::Escrow(bounty_id))
+}
+
+/// Everything a check needs to decide whether it applies and whether it passes.
+#[derive(Clone, Copy, Debug)]
+pub struct ValidationContext {
+    pub op: Operation,
+    pub bounty_id: u64,
+    pub amount: i128,
+    /// Present only for `release_with_capability` callers; carries the
+    /// replay-detection key the [`ValidationCheck::DuplicateOperation`]
+    /// check reads. `None` for plain `lock_funds`/`release_funds`, which
+    /// have no capability or nonce to dedup against.
+    pub capability_release: Option<CapabilityReleaseContext>,
+}
+
+/// The `(capability_id, nonce)` pair a `release_with_capability` call is
+/// replay-checked against. See [`record_processed_op`].
+#[derive(Clone, Copy, Debug)]
+pub struct CapabilityReleaseContext {
+    pub capability_id: u64,
+    pub nonce: u64,
+}
+
+/// One precedence tier in the shared validation pipeline. Declared in the
+/// order they're defined below; [`ValidationCheck::ALL`] is the canonical
+/// iteration order and must stay sorted by [`ValidationCheck::priority`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ValidationCheck {
+    Paused,
+    Initialized,
+    ResourceExists,
+    ResourceState,
+    FundsLocked,
+    DuplicateOperation,
+    AmountPolicy,
+    Solvency,
+}
+
+impl ValidationCheck {
+    /// All checks, already in ascending-priority order.
+    pub const ALL: [ValidationCheck; 8] = [
+        ValidationCheck::Paused,
+        ValidationCheck::Initialized,
+        ValidationCheck::ResourceExists,
+        ValidationCheck::ResourceState,
+        ValidationCheck::FundsLocked,
+        ValidationCheck::DuplicateOperation,
+        ValidationCheck::AmountPolicy,
+        ValidationCheck::Solvency,
+    ];
+
+    /// Lower runs first. Gaps are intentional headroom for future checks
+    /// without having to renumber the existing ones. `DuplicateOperation`
+    /// took the gap at 7, right above amount validation, as requested.
+    pub fn priority(&self) -> u32 {
+        match self {
+            ValidationCheck::Paused => 1,
+            ValidationCheck::Initialized => 2,
+            ValidationCheck::ResourceExists => 4,
+            ValidationCheck::ResourceState => 5,
+            ValidationCheck::FundsLocked => 6,
+            ValidationCheck::DuplicateOperation => 7,
+            ValidationCheck::AmountPolicy => 8,
+            ValidationCheck::Solvency => 10,
+        }
+    }
+
+    fn evaluate(&self, env: &Env, ctx: &ValidationContext) -> Result<(), Error> {
+        match self {
+            ValidationCheck::Paused => {
+                let flags: PauseFlags = env
+                    .storage()
+                    .instance()
+                    .get(&DataKey::PauseFlags)
+                    .unwrap_or(PauseFlags {
+                        lock: false,
+                        release: false,
+                        refund: false,
+                    });
+                let paused = match ctx.op {
+                    Operation::LockFunds => flags.lock,
+                    Operation::ReleaseFunds => flags.release,
+                };
+                if paused {
+                    Err(Error::FundsPaused)
+                } else {
+                    Ok(())
+                }
+            }
+            ValidationCheck::Initialized => {
+                if env.storage().instance().has(&DataKey::Admin) {
+                    Ok(())
+                } else {
+                    Err(Error::NotInitialized)
+                }
+            }
+            ValidationCheck::ResourceExists => {
+                // Only release_funds needs the bounty to already exist;
+                // lock_funds is what brings a bounty into existence. Goes
+                // through `errors::load_escrow` rather than the plain
+                // `Option` probe below so a present-but-undecodable entry
+                // surfaces as `Error::StateCorrupted` instead of being
+                // folded into the ordinary not-found case.
+                match ctx.op {
+                    Operation::LockFunds => Ok(()),
+                    Operation::ReleaseFunds => match crate::errors::load_escrow(env, ctx.bounty_id)
+                    {
+                        Ok(_) => Ok(()),
+                        Err(Error::EscrowNotFound) => Err(Error::BountyNotFound),
+                        Err(e) => Err(e),
+                    },
+                }
+            }
+            ValidationCheck::ResourceState => match ctx.op {
+                Operation::LockFunds => {
+                    if load_escrow(env, ctx.bounty_id).is_some() {
+                        Err(Error::BountyExists)
+                    } else {
+                        Ok(())
+                    }
+                }
+                Operation::ReleaseFunds => {
+                    if env.storage().persistent().has(&DataKey::Claim(ctx.bounty_id)) {
+                        Err(Error::ClaimPending)
+                    } else {
+                        Ok(())
+                    }
+                }
+            },
+            ValidationCheck::FundsLocked => match ctx.op {
+                Operation::LockFunds => Ok(()),
+                Operation::ReleaseFunds => match load_escrow(env, ctx.bounty_id) {
+                    Some(escrow) if escrow.status == EscrowStatus::Locked => Ok(()),
+                    _ => Err(Error::FundsNotLocked),
+                },
+            },
+            ValidationCheck::DuplicateOperation => match ctx.capability_release {
+                None => Ok(()),
+                Some(cap) => {
+                    if env
+                        .storage()
+                        .persistent()
+                        .has(&DataKey::ProcessedOp(cap.capability_id, cap.nonce))
+                    {
+                        Err(Error::DuplicateOperation)
+                    } else {
+                        Ok(())
+                    }
+                }
+            },
+            ValidationCheck::AmountPolicy => {
+                if ctx.amount <= 0 {
+                    return Err(Error::InvalidAmount);
+                }
+                if let Some(policy) = amount_policy(env) {
+                    if ctx.op == Operation::LockFunds && ctx.amount < policy.dust_threshold {
+                        return Err(Error::AmountIsDust);
+                    }
+                    if ctx.amount < policy.min {
+                        return Err(Error::AmountBelowMinimum);
+                    }
+                    if ctx.amount > policy.max {
+                        return Err(Error::AmountAboveMaximum);
+                    }
+                }
+                Ok(())
+            }
+            ValidationCheck::Solvency => match ctx.op {
+                Operation::LockFunds => Ok(()),
+                Operation::ReleaseFunds => match load_escrow(env, ctx.bounty_id) {
+                    Some(escrow) if ctx.amount > escrow.remaining_amount => {
+                        Err(Error::InsufficientFunds)
+                    }
+                    _ => Ok(()),
+                },
+            },
+        }
+    }
+}
+
+/// Run every applicable check in ascending priority and return the first
+/// failure - the exact `Error` a caller would hit by submitting `op`.
+pub fn validate(env: &Env, ctx: ValidationContext) -> Result<(), Error> {
+    for check in ValidationCheck::ALL {
+        check.evaluate(env, &ctx)?;
+    }
+    Ok(())
+}
+
+/// Read-only pre-flight for `lock_funds`: runs the same pipeline `lock_funds`
+/// would, against current storage, without mutating anything.
+pub fn simulate_lock_funds(env: &Env, bounty_id: u64, amount: i128) -> Result<(), Error> {
+    validate(
+        env,
+        ValidationContext {
+            op: Operation::LockFunds,
+            bounty_id,
+            amount,
+            capability_release: None,
+        },
+    )
+}
+
+/// Read-only pre-flight for `release_funds`: runs the same pipeline
+/// `release_funds` would, against current storage, without mutating anything.
+pub fn simulate_release(env: &Env, bounty_id: u64, amount: i128) -> Result<(), Error> {
+    validate(
+        env,
+        ValidationContext {
+            op: Operation::ReleaseFunds,
+            bounty_id,
+            amount,
+            capability_release: None,
+        },
+    )
+}
+
+/// Read-only pre-flight for `release_with_capability`: same pipeline as
+/// [`simulate_release`], plus the `(capability_id, nonce)` replay check that
+/// only capability-authorized releases need.
+pub fn simulate_release_with_capability(
+    env: &Env,
+    bounty_id: u64,
+    amount: i128,
+    capability_id: u64,
+    nonce: u64,
+) -> Result<(), Error> {
+    validate(
+        env,
+        ValidationContext {
+            op: Operation::ReleaseFunds,
+            bounty_id,
+            amount,
+            capability_release: Some(CapabilityReleaseContext {
+                capability_id,
+                nonce,
+            }),
+        },
+    )
+}
+
+/// Record `(capability_id, nonce)` as processed so a replayed
+/// `release_with_capability` call with the same pair is rejected by
+/// [`ValidationCheck::DuplicateOperation`] on its next attempt.
+///
+/// Call this only after the release it guards has actually gone through -
+/// recording ahead of a failed release would lock out the legitimate retry.
+pub fn record_processed_op(env: &Env, capability_id: u64, nonce: u64) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::ProcessedOp(capability_id, nonce), &true);
+}
+
+/// Bounded pruning for a capability that's now fully spent or expired:
+/// drop every `ProcessedOp` entry it could have written, from nonce `1`
+/// through `highest_nonce` (the last nonce the caller actually recorded),
+/// so the replay cache doesn't grow unbounded for capabilities nobody will
+/// ever use again.
+pub fn prune_processed_ops(env: &Env, capability_id: u64, highest_nonce: u64) {
+    for nonce in 1..=highest_nonce {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ProcessedOp(capability_id, nonce));
+    }
+}
+
+/// What actually happened to an escrow after [`sweep_dust_on_release`] ran.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DustSweepOutcome {
+    /// No dust threshold configured, or the remainder stayed above it - a
+    /// plain partial (or exact) release, no sweep needed.
+    NoSweep,
+    /// The leftover dropped below `dust_threshold`, so it was folded into
+    /// this release and the escrow is now fully `Released`.
+    Swept,
+}
+
+/// Apply `requested_amount` to `escrow`'s `remaining_amount`, auto-settling
+/// any dust the release would otherwise leave behind.
+///
+/// Does not touch storage or move tokens - it only mutates `escrow` in place
+/// and returns the amount that should actually be transferred to the
+/// recipient, so `release_funds` can persist the escrow and emit
+/// `funds_released` (and, when a sweep occurred, `dust_swept`) itself.
+pub fn sweep_dust_on_release(
+    env: &Env,
+    escrow: &mut Escrow,
+    requested_amount: i128,
+) -> (i128, DustSweepOutcome) {
+    let leftover = escrow.remaining_amount - requested_amount;
+    let dust_threshold = amount_policy(env).map(|p| p.dust_threshold).unwrap_or(0);
+
+    if leftover <= 0 {
+        escrow.remaining_amount = 0;
+        escrow.status = EscrowStatus::Released;
+        return (requested_amount, DustSweepOutcome::NoSweep);
+    }
+
+    if leftover < dust_threshold {
+        // Folding the dust into this release keeps it in the recipient's
+        // hands rather than stranding it back with the depositor.
+        escrow.remaining_amount = 0;
+        escrow.status = EscrowStatus::Released;
+        (requested_amount + leftover, DustSweepOutcome::Swept)
+    } else {
+        escrow.remaining_amount = leftover;
+        (requested_amount, DustSweepOutcome::NoSweep)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BountyEscrowContract, BountyEscrowContractClient};
+    use soroban_sdk::{testutils::Address as _, vec, Address};
+
+    fn create_test_env() -> (Env, BountyEscrowContractClient<'static>, Address) {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, BountyEscrowContract);
+        let client = BountyEscrowContractClient::new(&env, &contract_id);
+        (env, client, contract_id)
+    }
+
+    #[test]
+    fn test_simulate_lock_funds_before_init_is_not_initialized() {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.register_contract(None, BountyEscrowContract);
+
+        assert_eq!(
+            simulate_lock_funds(&env, 1, 100),
+            Err(Error::NotInitialized)
+        );
+    }
+
+    #[test]
+    fn test_simulate_lock_funds_prefers_paused_over_not_initialized() {
+        let (env, _client, contract_id) = create_test_env();
+        env.as_contract(&contract_id, || {
+            env.storage().instance().set(
+                &DataKey::PauseFlags,
+                &PauseFlags {
+                    lock: true,
+                    release: false,
+                    refund: false,
+                },
+            );
+        });
+
+        env.as_contract(&contract_id, || {
+            assert_eq!(simulate_lock_funds(&env, 1, 100), Err(Error::FundsPaused));
+        });
+    }
+
+    #[test]
+    fn test_simulate_lock_funds_rejects_existing_bounty() {
+        let (env, client, contract_id) = create_test_env();
+        let admin = Address::generate(&env);
+        let token = Address::generate(&env);
+        client.init(&admin, &token);
+
+        env.as_contract(&contract_id, || {
+            env.storage().persistent().set(
+                &DataKey::Escrow(1),
+                &Escrow {
+                    depositor: admin.clone(),
+                    amount: 100,
+                    status: EscrowStatus::Locked,
+                    deadline: 2000,
+                    refund_history: vec![&env],
+                    remaining_amount: 100,
+                },
+            );
+            assert_eq!(simulate_lock_funds(&env, 1, 100), Err(Error::BountyExists));
+        });
+    }
+
+    #[test]
+    fn test_simulate_lock_funds_enforces_amount_policy() {
+        let (env, client, contract_id) = create_test_env();
+        let admin = Address::generate(&env);
+        let token = Address::generate(&env);
+        client.init(&admin, &token);
+
+        env.as_contract(&contract_id, || {
+            env.storage().instance().set(
+                &DataKey::AmountPolicy,
+                &AmountPolicy {
+                    min: 50,
+                    max: 500,
+                    dust_threshold: 0,
+                },
+            );
+
+            assert_eq!(simulate_lock_funds(&env, 1, 0), Err(Error::InvalidAmount));
+            assert_eq!(
+                simulate_lock_funds(&env, 1, 10),
+                Err(Error::AmountBelowMinimum)
+            );
+            assert_eq!(
+                simulate_lock_funds(&env, 1, 1000),
+                Err(Error::AmountAboveMaximum)
+            );
+            assert_eq!(simulate_lock_funds(&env, 1, 100), Ok(()));
+        });
+    }
+
+    #[test]
+    fn test_simulate_release_requires_existing_bounty_before_state_checks() {
+        let (env, client, contract_id) = create_test_env();
+        let admin = Address::generate(&env);
+        let token = Address::generate(&env);
+        client.init(&admin, &token);
+
+        env.as_contract(&contract_id, || {
+            assert_eq!(
+                simulate_release(&env, 99, 10),
+                Err(Error::BountyNotFound)
+            );
+        });
+    }
+
+    #[test]
+    fn test_load_escrow_not_found_maps_to_bounty_not_found_in_pipeline() {
+        // `errors::load_escrow` distinguishes `EscrowNotFound` from
+        // `StateCorrupted`; the `ResourceExists` check must still surface
+        // the former as the pipeline's own `BountyNotFound`.
+        let (env, client, contract_id) = create_test_env();
+        let admin = Address::generate(&env);
+        let token = Address::generate(&env);
+        client.init(&admin, &token);
+
+        env.as_contract(&contract_id, || {
+            assert_eq!(
+                crate::errors::load_escrow(&env, 1),
+                Err(Error::EscrowNotFound)
+            );
+            assert_eq!(simulate_release(&env, 1, 10), Err(Error::BountyNotFound));
+        });
+    }
+
+    #[test]
+    fn test_simulate_release_prefers_claim_pending_over_funds_not_locked() {
+        let (env, client, contract_id) = create_test_env();
+        let admin = Address::generate(&env);
+        let token = Address::generate(&env);
+        client.init(&admin, &token);
+
+        env.as_contract(&contract_id, || {
+            env.storage().persistent().set(
+                &DataKey::Escrow(1),
+                &Escrow {
+                    depositor: admin.clone(),
+                    amount: 100,
+                    status: EscrowStatus::Released,
+                    deadline: 2000,
+                    refund_history: vec![&env],
+                    remaining_amount: 0,
+                },
+            );
+            env.storage().persistent().set(&DataKey::Claim(1), &true);
+
+            assert_eq!(simulate_release(&env, 1, 10), Err(Error::ClaimPending));
+        });
+    }
+
+    #[test]
+    fn test_simulate_release_rejects_amount_above_remaining() {
+        let (env, client, contract_id) = create_test_env();
+        let admin = Address::generate(&env);
+        let token = Address::generate(&env);
+        client.init(&admin, &token);
+
+        env.as_contract(&contract_id, || {
+            env.storage().persistent().set(
+                &DataKey::Escrow(1),
+                &Escrow {
+                    depositor: admin.clone(),
+                    amount: 100,
+                    status: EscrowStatus::Locked,
+                    deadline: 2000,
+                    refund_history: vec![&env],
+                    remaining_amount: 40,
+                },
+            );
+
+            assert_eq!(
+                simulate_release(&env, 1, 50),
+                Err(Error::InsufficientFunds)
+            );
+            assert_eq!(simulate_release(&env, 1, 40), Ok(()));
+        });
+    }
+
+    #[test]
+    fn test_simulate_release_with_capability_rejects_replayed_nonce() {
+        let (env, client, contract_id) = create_test_env();
+        let admin = Address::generate(&env);
+        let token = Address::generate(&env);
+        client.init(&admin, &token);
+
+        env.as_contract(&contract_id, || {
+            env.storage().persistent().set(
+                &DataKey::Escrow(1),
+                &Escrow {
+                    depositor: admin.clone(),
+                    amount: 100,
+                    status: EscrowStatus::Locked,
+                    deadline: 2000,
+                    refund_history: vec![&env],
+                    remaining_amount: 100,
+                },
+            );
+
+            assert_eq!(
+                simulate_release_with_capability(&env, 1, 40, 7, 1),
+                Ok(())
+            );
+            record_processed_op(&env, 7, 1);
+
+            assert_eq!(
+                simulate_release_with_capability(&env, 1, 40, 7, 1),
+                Err(Error::DuplicateOperation)
+            );
+            // A fresh nonce for the same capability is unaffected.
+            assert_eq!(
+                simulate_release_with_capability(&env, 1, 40, 7, 2),
+                Ok(())
+            );
+        });
+    }
+
+    #[test]
+    fn test_simulate_release_with_capability_prefers_duplicate_over_amount_policy() {
+        let (env, client, contract_id) = create_test_env();
+        let admin = Address::generate(&env);
+        let token = Address::generate(&env);
+        client.init(&admin, &token);
+
+        env.as_contract(&contract_id, || {
+            env.storage().persistent().set(
+                &DataKey::Escrow(1),
+                &Escrow {
+                    depositor: admin.clone(),
+                    amount: 100,
+                    status: EscrowStatus::Locked,
+                    deadline: 2000,
+                    refund_history: vec![&env],
+                    remaining_amount: 100,
+                },
+            );
+            record_processed_op(&env, 7, 1);
+
+            // amount 0 would also fail AmountPolicy (priority 8), but the
+            // replay check at priority 7 must win.
+            assert_eq!(
+                simulate_release_with_capability(&env, 1, 0, 7, 1),
+                Err(Error::DuplicateOperation)
+            );
+        });
+    }
+
+    #[test]
+    fn test_prune_processed_ops_clears_replay_cache() {
+        let (env, client, contract_id) = create_test_env();
+        let admin = Address::generate(&env);
+        let token = Address::generate(&env);
+        client.init(&admin, &token);
+
+        env.as_contract(&contract_id, || {
+            record_processed_op(&env, 7, 1);
+            record_processed_op(&env, 7, 2);
+            prune_processed_ops(&env, 7, 2);
+
+            assert!(!env
+                .storage()
+                .persistent()
+                .has(&DataKey::ProcessedOp(7, 1)));
+            assert!(!env
+                .storage()
+                .persistent()
+                .has(&DataKey::ProcessedOp(7, 2)));
+        });
+    }
+}
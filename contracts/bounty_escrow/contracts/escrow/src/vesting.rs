@@ -0,0 +1,406 @@
+//! # Vesting Schedules
+//!
+//! The existing scheduled-release events (`ScheduleCreated`/`ScheduleReleased`)
+//! only model a discrete lump sum unlocked at one fixed timestamp, so a
+//! long-running bounty that wants milestone-style streaming payouts has to
+//! be carved into N separate schedules up front. Borrowing the
+//! funds-unlock-gradually-over-a-window model from Filecoin miner vesting,
+//! this module adds a genuine linear-with-cliff schedule: nothing is
+//! claimable before `start + cliff`, the full amount is claimable once
+//! `start + duration` has passed, and in between it unlocks linearly.
+//!
+//! A [`VestingSchedule`] is keyed by `(bounty_id, schedule_id)`, mirroring
+//! how the lump-sum schedules are addressed, and tracks only `claimed` as
+//! mutable state - [`claimable_amount`] is always recomputed from
+//! `total_amount`, `start`, `cliff`, `duration`, and the ledger's current
+//! timestamp rather than cached, so it can never drift out of sync with the
+//! schedule's own parameters. [`claim_vested`] takes whatever is
+//! currently claimable, bumps `claimed`, returns the amount for the caller
+//! to transfer, and rejects a call that would claim zero - repeated claims in the same ledger are no-ops that fail
+//! loudly rather than emitting an empty transfer.
+
+use soroban_sdk::{contracttype, Address, Env};
+
+/// One bounty's linear-with-cliff vesting schedule. `total_amount` is fixed
+/// at creation; `claimed` and `terminated_at` are the only fields
+/// [`claim_vested`]/[`terminate_vesting`] mutate.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingSchedule {
+    pub bounty_id: u64,
+    pub schedule_id: u64,
+    pub recipient: Address,
+    pub total_amount: i128,
+    pub start: u64,
+    pub cliff: u64,
+    pub duration: u64,
+    pub claimed: i128,
+    /// Set by [`terminate_vesting`] to freeze vesting as of that timestamp;
+    /// `None` means still live. Mirrors the NEAR lockup contract's
+    /// `terminate_vesting`, which stops further unlock while leaving
+    /// whatever had already vested claimable.
+    pub terminated_at: Option<u64>,
+}
+
+fn next_schedule_id(env: &Env) -> u64 {
+    let id: u64 = env
+        .storage()
+        .instance()
+        .get(&crate::DataKey::NextVestingScheduleId)
+        .unwrap_or(0)
+        + 1;
+    env.storage()
+        .instance()
+        .set(&crate::DataKey::NextVestingScheduleId, &id);
+    id
+}
+
+fn storage_key(bounty_id: u64, schedule_id: u64) -> crate::DataKey {
+    crate::DataKey::VestingSchedule(bounty_id, schedule_id)
+}
+
+/// Create a vesting schedule for `bounty_id`, locking `total_amount` to
+/// unlock linearly between `start + cliff` and `start + duration`. Rejects
+/// `duration == 0` (a schedule that can never fully vest) and a
+/// non-positive `total_amount`.
+pub fn create_vesting_schedule(
+    env: &Env,
+    bounty_id: u64,
+    recipient: Address,
+    total_amount: i128,
+    start: u64,
+    cliff: u64,
+    duration: u64,
+) -> Result<VestingSchedule, crate::Error> {
+    if total_amount <= 0 {
+        return Err(crate::Error::InvalidAmount);
+    }
+    if duration == 0 {
+        return Err(crate::Error::InvalidAmount);
+    }
+
+    let schedule_id = next_schedule_id(env);
+    let schedule = VestingSchedule {
+        bounty_id,
+        schedule_id,
+        recipient,
+        total_amount,
+        start,
+        cliff,
+        duration,
+        claimed: 0,
+        terminated_at: None,
+    };
+    env.storage()
+        .persistent()
+        .set(&storage_key(bounty_id, schedule_id), &schedule);
+
+    Ok(schedule)
+}
+
+pub fn load_vesting_schedule(
+    env: &Env,
+    bounty_id: u64,
+    schedule_id: u64,
+) -> Result<VestingSchedule, crate::Error> {
+    env.storage()
+        .persistent()
+        .get(&storage_key(bounty_id, schedule_id))
+        .ok_or(crate::Error::VestingScheduleNotFound)
+}
+
+/// Total amount vested as of `now`, ignoring anything already claimed:
+/// zero before the cliff, the full `total_amount` once `duration` has
+/// elapsed, otherwise `total_amount * (now - start) / duration` using i128
+/// arithmetic throughout so a large `total_amount` can't overflow before
+/// the division brings it back down. If [`terminate_vesting`] already froze
+/// the schedule, `now` is clamped to `terminated_at` so no further amount
+/// vests past the termination point.
+pub fn vested_amount(schedule: &VestingSchedule, now: u64) -> i128 {
+    let now = match schedule.terminated_at {
+        Some(terminated_at) if terminated_at < now => terminated_at,
+        _ => now,
+    };
+
+    let unlock_at = schedule.start.saturating_add(schedule.cliff);
+    if now < unlock_at {
+        return 0;
+    }
+
+    let fully_vested_at = schedule.start.saturating_add(schedule.duration);
+    if now >= fully_vested_at {
+        return schedule.total_amount;
+    }
+
+    let elapsed = (now - schedule.start) as i128;
+    (schedule.total_amount * elapsed) / schedule.duration as i128
+}
+
+/// [`vested_amount`] minus whatever has already been claimed.
+pub fn claimable_amount(schedule: &VestingSchedule, now: u64) -> i128 {
+    vested_amount(schedule, now) - schedule.claimed
+}
+
+/// Claim whatever is currently claimable: bumps `claimed` by that amount
+/// and persists the schedule. Returns `(schedule, amount_released)` so the
+/// caller can perform the token transfer and emit `VestingClaimed` with
+/// both the amount released and the schedule's new totals. Rejects with
+/// `Error::NothingToClaim` before the cliff, or on a repeat call in the
+/// same ledger once everything currently vested has already been claimed.
+pub fn claim_vested(
+    env: &Env,
+    bounty_id: u64,
+    schedule_id: u64,
+    now: u64,
+) -> Result<(VestingSchedule, i128), crate::Error> {
+    let mut schedule = load_vesting_schedule(env, bounty_id, schedule_id)?;
+    let claimable = claimable_amount(&schedule, now);
+    if claimable <= 0 {
+        return Err(crate::Error::NothingToClaim);
+    }
+
+    schedule.claimed += claimable;
+    env.storage()
+        .persistent()
+        .set(&storage_key(bounty_id, schedule_id), &schedule);
+
+    Ok((schedule, claimable))
+}
+
+/// Freeze `schedule` as of `now`: no further amount vests past this point,
+/// and whatever was vested up to `now` stays claimable via [`claim_vested`].
+/// Returns `(schedule, unvested_remainder)` - the `total_amount` minus what
+/// had vested by `now` - which the caller (the entrypoint, which holds the
+/// admin auth check and the token client) should refund to the depositor.
+/// Rejects a second call against an already-terminated schedule rather than
+/// silently moving the freeze point or double-refunding the remainder.
+pub fn terminate_vesting(
+    env: &Env,
+    bounty_id: u64,
+    schedule_id: u64,
+    now: u64,
+) -> Result<(VestingSchedule, i128), crate::Error> {
+    let mut schedule = load_vesting_schedule(env, bounty_id, schedule_id)?;
+    if schedule.terminated_at.is_some() {
+        return Err(crate::Error::VestingAlreadyTerminated);
+    }
+
+    let unvested_remainder = schedule.total_amount - vested_amount(&schedule, now);
+    schedule.terminated_at = Some(now);
+    env.storage()
+        .persistent()
+        .set(&storage_key(bounty_id, schedule_id), &schedule);
+
+    Ok((schedule, unvested_remainder))
+}
+
+/// Whether `schedule` has released every unit of `total_amount`. The
+/// entrypoint driving [`claim_vested`] should only report the associated
+/// escrow's final status as `Released` once this is true - a partial claim
+/// leaves the schedule (and the escrow) open for further claims.
+pub fn is_fully_claimed(schedule: &VestingSchedule) -> bool {
+    schedule.claimed >= schedule.total_amount
+}
+
+/// [`claim_vested`], but gated on a settlement grace period the same way
+/// the lump-sum path gates `try_release_schedule_automatic` (see
+/// `test_schedule_release_blocks_during_grace`). This module has no access
+/// to `SettlementGracePeriodConfig` itself, so the entrypoint resolves
+/// `grace_deadline` (`deadline + grace_period_seconds` when a grace period
+/// is configured and enabled, `None` otherwise) and passes it straight
+/// through rather than this fn re-deriving it.
+pub fn claim_vested_respecting_grace_period(
+    env: &Env,
+    bounty_id: u64,
+    schedule_id: u64,
+    now: u64,
+    grace_deadline: Option<u64>,
+) -> Result<(VestingSchedule, i128), crate::Error> {
+    if let Some(deadline) = grace_deadline {
+        if now < deadline {
+            return Err(crate::Error::SettlementGracePeriodActive);
+        }
+    }
+    claim_vested(env, bounty_id, schedule_id, now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::testutils::Address as _;
+
+    fn schedule(env: &Env, recipient: Address) -> VestingSchedule {
+        create_vesting_schedule(env, 1, recipient, 1_000, 100, 50, 400).unwrap()
+    }
+
+    #[test]
+    fn test_create_vesting_schedule_rejects_zero_duration_and_amount() {
+        let env = Env::default();
+        let recipient = Address::generate(&env);
+
+        assert_eq!(
+            create_vesting_schedule(&env, 1, recipient.clone(), 1_000, 0, 0, 0),
+            Err(crate::Error::InvalidAmount)
+        );
+        assert_eq!(
+            create_vesting_schedule(&env, 1, recipient, 0, 0, 0, 100),
+            Err(crate::Error::InvalidAmount)
+        );
+    }
+
+    #[test]
+    fn test_vested_amount_is_zero_before_cliff() {
+        let env = Env::default();
+        let recipient = Address::generate(&env);
+        let schedule = schedule(&env, recipient);
+
+        assert_eq!(vested_amount(&schedule, 100), 0);
+        assert_eq!(vested_amount(&schedule, 149), 0);
+    }
+
+    #[test]
+    fn test_vested_amount_is_linear_between_cliff_and_end() {
+        let env = Env::default();
+        let recipient = Address::generate(&env);
+        let schedule = schedule(&env, recipient);
+
+        // start=100, duration=400 -> fully vested at 500.
+        assert_eq!(vested_amount(&schedule, 300), (1_000 * 200) / 400);
+        assert_eq!(vested_amount(&schedule, 500), 1_000);
+        assert_eq!(vested_amount(&schedule, 600), 1_000);
+    }
+
+    #[test]
+    fn test_claim_vested_rejects_zero_claimable_before_cliff() {
+        let env = Env::default();
+        let recipient = Address::generate(&env);
+        schedule(&env, recipient);
+
+        assert_eq!(
+            claim_vested(&env, 1, 1, 100),
+            Err(crate::Error::NothingToClaim)
+        );
+    }
+
+    #[test]
+    fn test_claim_vested_tracks_partial_claims_monotonically() {
+        let env = Env::default();
+        let recipient = Address::generate(&env);
+        schedule(&env, recipient);
+
+        let (after_first, released_first) = claim_vested(&env, 1, 1, 300).unwrap();
+        assert_eq!(released_first, 500);
+        assert_eq!(after_first.claimed, 500);
+
+        // Same ledger timestamp again: nothing new has vested.
+        assert_eq!(
+            claim_vested(&env, 1, 1, 300),
+            Err(crate::Error::NothingToClaim)
+        );
+
+        let (after_second, released_second) = claim_vested(&env, 1, 1, 500).unwrap();
+        assert_eq!(released_second, 500);
+        assert_eq!(after_second.claimed, 1_000);
+    }
+
+    #[test]
+    fn test_load_vesting_schedule_missing_returns_not_found() {
+        let env = Env::default();
+        assert_eq!(
+            load_vesting_schedule(&env, 1, 1),
+            Err(crate::Error::VestingScheduleNotFound)
+        );
+    }
+
+    #[test]
+    fn test_terminate_vesting_freezes_further_vesting_and_returns_remainder() {
+        let env = Env::default();
+        let recipient = Address::generate(&env);
+        schedule(&env, recipient);
+
+        // start=100, cliff=50, duration=400 -> fully vested at 500.
+        // At now=300, 500/1000 has vested; terminating there should freeze
+        // the remaining 500 as never-to-vest.
+        let (terminated, unvested_remainder) = terminate_vesting(&env, 1, 1, 300).unwrap();
+        assert_eq!(terminated.terminated_at, Some(300));
+        assert_eq!(unvested_remainder, 500);
+
+        // Past the termination point, nothing new vests even though the
+        // schedule's own end (500) hasn't passed yet.
+        assert_eq!(vested_amount(&terminated, 500), 500);
+    }
+
+    #[test]
+    fn test_terminate_vesting_rejects_double_termination() {
+        let env = Env::default();
+        let recipient = Address::generate(&env);
+        schedule(&env, recipient);
+
+        terminate_vesting(&env, 1, 1, 300).unwrap();
+        assert_eq!(
+            terminate_vesting(&env, 1, 1, 400),
+            Err(crate::Error::VestingAlreadyTerminated)
+        );
+    }
+
+    #[test]
+    fn test_claim_vested_still_claimable_after_termination() {
+        let env = Env::default();
+        let recipient = Address::generate(&env);
+        schedule(&env, recipient);
+
+        terminate_vesting(&env, 1, 1, 300).unwrap();
+        let (after_claim, released) = claim_vested(&env, 1, 1, 300).unwrap();
+        assert_eq!(released, 500);
+        assert_eq!(after_claim.claimed, 500);
+    }
+
+    #[test]
+    fn test_is_fully_claimed() {
+        let env = Env::default();
+        let recipient = Address::generate(&env);
+        schedule(&env, recipient);
+
+        let (partial, _) = claim_vested(&env, 1, 1, 300).unwrap();
+        assert!(!is_fully_claimed(&partial));
+
+        let (full, _) = claim_vested(&env, 1, 1, 500).unwrap();
+        assert!(is_fully_claimed(&full));
+    }
+
+    #[test]
+    fn test_claim_vested_respecting_grace_period_blocks_before_deadline() {
+        let env = Env::default();
+        let recipient = Address::generate(&env);
+        schedule(&env, recipient);
+
+        assert_eq!(
+            claim_vested_respecting_grace_period(&env, 1, 1, 300, Some(400)),
+            Err(crate::Error::SettlementGracePeriodActive)
+        );
+    }
+
+    #[test]
+    fn test_claim_vested_respecting_grace_period_allows_after_deadline() {
+        let env = Env::default();
+        let recipient = Address::generate(&env);
+        schedule(&env, recipient);
+
+        let (after, released) =
+            claim_vested_respecting_grace_period(&env, 1, 1, 300, Some(300)).unwrap();
+        assert_eq!(released, 500);
+        assert_eq!(after.claimed, 500);
+    }
+
+    #[test]
+    fn test_claim_vested_respecting_grace_period_ignores_none_deadline() {
+        let env = Env::default();
+        let recipient = Address::generate(&env);
+        schedule(&env, recipient);
+
+        let (after, released) =
+            claim_vested_respecting_grace_period(&env, 1, 1, 300, None).unwrap();
+        assert_eq!(released, 500);
+        assert_eq!(after.claimed, 500);
+    }
+}
@@ -0,0 +1,126 @@
+//! # Staged Feature Activation (Pending / Active Gates)
+//!
+//! Mirrors the staged feature-gate pattern from Solana's `feature.rs`
+//! (`CliFeatureStatus::{Inactive, Pending, Active(slot)}`): instead of an
+//! admin flipping a plain boolean the instant a transaction lands, a
+//! feature is staged with a future `activation_ts`, sits `Pending` until
+//! that ledger time arrives, then flips to `Active` on its own - no
+//! second transaction required, and every observer can predict exactly
+//! when the switch happens.
+//!
+//! [`crate::DataKey::FeatureGate`] holds one [`FeatureGate`] per `id`.
+//! [`is_feature_active`] is the hook sensitive call sites consult
+//! (see `update_fee_config` and `release_program_schedule_manual` in
+//! `lib.rs`) before applying a rule that should only take effect once its
+//! gate activates.
+
+use soroban_sdk::{contracttype, Address, Env, Symbol};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeatureGate {
+    pub id: Symbol,
+    pub activation_ts: Option<u64>,
+}
+
+/// Mirrors `CliFeatureStatus`: `Active` carries the `activation_ts` the
+/// gate flipped at, so a caller doesn't need a second lookup to learn it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FeatureStatus {
+    Inactive,
+    Pending,
+    Active(u64),
+}
+
+/// Stages `id` to activate at `activation_ts`. Re-staging an existing gate
+/// (already staged or already active) simply overwrites its activation
+/// time - there's no append-only history to preserve here.
+pub fn stage_feature(env: &Env, admin: &Address, id: Symbol, activation_ts: u64) {
+    admin.require_auth();
+    let key = crate::DataKey::FeatureGate(id.clone());
+    env.storage().instance().set(
+        &key,
+        &FeatureGate {
+            id,
+            activation_ts: Some(activation_ts),
+        },
+    );
+}
+
+/// Clears a staged or active gate back to `Inactive`.
+pub fn cancel_feature(env: &Env, admin: &Address, id: Symbol) {
+    admin.require_auth();
+    let key = crate::DataKey::FeatureGate(id);
+    env.storage().instance().remove(&key);
+}
+
+/// The current status of feature `id`: `Inactive` if it was never staged
+/// (or has been cancelled), `Pending` before its `activation_ts`, and
+/// `Active` from that ledger timestamp onward.
+pub fn feature_status(env: &Env, id: Symbol) -> FeatureStatus {
+    let key = crate::DataKey::FeatureGate(id);
+    let gate: Option<FeatureGate> = env.storage().instance().get(&key);
+    match gate.and_then(|g| g.activation_ts) {
+        None => FeatureStatus::Inactive,
+        Some(activation_ts) => {
+            if env.ledger().timestamp() >= activation_ts {
+                FeatureStatus::Active(activation_ts)
+            } else {
+                FeatureStatus::Pending
+            }
+        }
+    }
+}
+
+/// Whether `id` is currently `Active` - the predicate sensitive call sites
+/// consult before applying a rule gated behind a staged rollout.
+pub fn is_feature_active(env: &Env, id: Symbol) -> bool {
+    matches!(feature_status(env, id), FeatureStatus::Active(_))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::testutils::{Address as _, Ledger};
+    use soroban_sdk::{symbol_short, Address};
+
+    #[test]
+    fn test_feature_status_unset_is_inactive() {
+        let env = Env::default();
+        assert_eq!(
+            feature_status(&env, symbol_short!("rollout")),
+            FeatureStatus::Inactive
+        );
+        assert!(!is_feature_active(&env, symbol_short!("rollout")));
+    }
+
+    #[test]
+    fn test_staged_feature_is_pending_then_active() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let id = symbol_short!("rollout");
+
+        env.ledger().set_timestamp(100);
+        stage_feature(&env, &admin, id.clone(), 200);
+        assert_eq!(feature_status(&env, id.clone()), FeatureStatus::Pending);
+        assert!(!is_feature_active(&env, id.clone()));
+
+        env.ledger().set_timestamp(200);
+        assert_eq!(feature_status(&env, id.clone()), FeatureStatus::Active(200));
+        assert!(is_feature_active(&env, id));
+    }
+
+    #[test]
+    fn test_cancel_feature_reverts_to_inactive() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let id = symbol_short!("rollout");
+
+        stage_feature(&env, &admin, id.clone(), 50);
+        cancel_feature(&env, &admin, id.clone());
+        assert_eq!(feature_status(&env, id), FeatureStatus::Inactive);
+    }
+}
@@ -0,0 +1,138 @@
+//! # Payout Idempotency / Replay-Protection Cache
+//!
+//! A client that submits `single_payout`/`batch_payout` and never sees the
+//! response (a dropped connection, a timed-out horizon request) can't tell
+//! whether the transfer actually landed, so a naive retry risks paying the
+//! recipient twice. Callers can instead pass an opaque `request_id`: the
+//! first call executes normally and caches its resulting `ProgramData`
+//! against that key; any call repeating the same `request_id` within the
+//! idempotency window returns the cached result directly without moving
+//! any tokens.
+//!
+//! Storage is a fixed-size ring of [`IDEMPOTENCY_BUCKET_COUNT`] buckets per
+//! program, each covering one `window / IDEMPOTENCY_BUCKET_COUNT` slice of
+//! time - the same fixed-slot-count approach `record_fee_ledger_entry`
+//! uses to keep a ledger bounded, but keyed by time slot instead of by
+//! entry count. A bucket whose slot has rolled over to a new time slice is
+//! discarded wholesale on next use rather than trimmed entry-by-entry, so
+//! a replayed key older than the window is simply absent - its slot has
+//! already been recycled for a newer one.
+
+use soroban_sdk::{contracttype, Address, Env, String, Vec};
+
+use crate::ProgramData;
+
+/// Number of time-sliced buckets kept per program. Fixed (not part of the
+/// configurable window) so storage per program is bounded regardless of
+/// how wide the window is configured.
+const IDEMPOTENCY_BUCKET_COUNT: u32 = 8;
+
+/// Window used when a program has never called `set_idempotency_window`.
+const DEFAULT_IDEMPOTENCY_WINDOW_SECS: u64 = 3600;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct IdempotencyEntry {
+    request_id: String,
+    result: ProgramData,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct IdempotencyBucket {
+    /// Start timestamp of the time slice currently occupying this slot.
+    /// `entries` belongs to this slice only - once the ring wraps around
+    /// (`IDEMPOTENCY_BUCKET_COUNT` spans later), the slot is reused for a new
+    /// slice and these entries are discarded rather than carried forward.
+    slice_start: u64,
+    entries: Vec<IdempotencyEntry>,
+}
+
+fn window_secs(env: &Env, program_id: &String) -> u64 {
+    env.storage()
+        .instance()
+        .get(&crate::DataKey::IdempotencyWindow(program_id.clone()))
+        .unwrap_or(DEFAULT_IDEMPOTENCY_WINDOW_SECS)
+}
+
+fn span_secs(window: u64) -> u64 {
+    (window / IDEMPOTENCY_BUCKET_COUNT as u64).max(1)
+}
+
+fn slice_start(now: u64, span: u64) -> u64 {
+    (now / span) * span
+}
+
+fn bucket_index(now: u64, span: u64) -> u32 {
+    ((now / span) % IDEMPOTENCY_BUCKET_COUNT as u64) as u32
+}
+
+/// Sets how long a `request_id` is remembered for `program_id` (admin only).
+/// Only changes the width of each of the `IDEMPOTENCY_BUCKET_COUNT` slices
+/// going forward; it does not retroactively extend or shrink entries
+/// already cached under the previous window.
+pub fn set_idempotency_window(env: &Env, admin: &Address, program_id: &String, window_secs: u64) {
+    admin.require_auth();
+    if window_secs == 0 {
+        panic!("window_secs must be positive");
+    }
+    env.storage().instance().set(
+        &crate::DataKey::IdempotencyWindow(program_id.clone()),
+        &window_secs,
+    );
+}
+
+/// The `ProgramData` cached for `request_id` if it was recorded within the
+/// current idempotency window for `program_id` (any of the last
+/// `IDEMPOTENCY_BUCKET_COUNT` slices, the current one included), `None` if
+/// the key has never been seen or its slice has since fallen out of the ring.
+pub fn lookup(env: &Env, program_id: &String, request_id: &String) -> Option<ProgramData> {
+    let window = window_secs(env, program_id);
+    let span = span_secs(window);
+    let now = env.ledger().timestamp();
+    let current_slice = slice_start(now, span);
+    let oldest_slice = current_slice.saturating_sub(span * (IDEMPOTENCY_BUCKET_COUNT as u64 - 1));
+
+    for index in 0..IDEMPOTENCY_BUCKET_COUNT {
+        let bucket: Option<IdempotencyBucket> = env
+            .storage()
+            .persistent()
+            .get(&crate::DataKey::IdempotencyBucket(program_id.clone(), index));
+        let Some(bucket) = bucket else { continue };
+        if bucket.slice_start < oldest_slice || bucket.slice_start > current_slice {
+            continue;
+        }
+        for entry in bucket.entries.iter() {
+            if entry.request_id == *request_id {
+                return Some(entry.result.clone());
+            }
+        }
+    }
+    None
+}
+
+/// Records `result` under `request_id` for `program_id`, reclaiming the
+/// slot's previous contents first if its time slice has rolled over.
+pub fn record(env: &Env, program_id: &String, request_id: &String, result: &ProgramData) {
+    let window = window_secs(env, program_id);
+    let span = span_secs(window);
+    let now = env.ledger().timestamp();
+    let current_slice = slice_start(now, span);
+    let index = bucket_index(now, span);
+    let key = crate::DataKey::IdempotencyBucket(program_id.clone(), index);
+
+    let existing: Option<IdempotencyBucket> = env.storage().persistent().get(&key);
+    let mut bucket = match existing {
+        Some(b) if b.slice_start == current_slice => b,
+        _ => IdempotencyBucket {
+            slice_start: current_slice,
+            entries: Vec::new(env),
+        },
+    };
+
+    bucket.entries.push_back(IdempotencyEntry {
+        request_id: request_id.clone(),
+        result: result.clone(),
+    });
+    env.storage().persistent().set(&key, &bucket);
+}
@@ -141,7 +141,7 @@
 #![no_std]
 use soroban_sdk::{
     contract, contracterror, contractimpl, contracttype, symbol_short, token, vec, Address, Env,
-    String, Symbol, Vec,
+    IntoVal, String, Symbol, Val, Vec,
 };
 
 // Event symbols
@@ -152,6 +152,15 @@ const PAYOUT: Symbol = symbol_short!("Payout");
 const DEPENDENCY_CREATED: Symbol = symbol_short!("dep_add");
 const DEPENDENCY_CLEARED: Symbol = symbol_short!("dep_clr");
 const DEPENDENCY_STATUS_UPDATED: Symbol = symbol_short!("dep_sts");
+const PROGRAM_FROZEN: Symbol = symbol_short!("ProgFrzn");
+const PROGRAM_SETTLED: Symbol = symbol_short!("ProgSttl");
+const FEE_COLLECTED: Symbol = symbol_short!("FeeColl");
+const CONDITIONAL_PAYOUT_EXECUTED: Symbol = symbol_short!("CondPay");
+const MULTISIG_PAYOUT_EXECUTED: Symbol = symbol_short!("MSigPay");
+const PROGRAM_CLOSED: Symbol = symbol_short!("ProgCls");
+const PROGRAM_RECLAIMED: Symbol = symbol_short!("ProgRclm");
+const REALIZOR_BLOCKED: Symbol = symbol_short!("RlzBlkd");
+const SCHEDULE_REALIZOR_BLOCKED: Symbol = symbol_short!("SchRlzBk");
 
 // Storage keys
 const PROGRAM_DATA: Symbol = symbol_short!("ProgData");
@@ -162,6 +171,12 @@ const FEE_CONFIG: Symbol = symbol_short!("FeeCfg");
 const BASIS_POINTS: i128 = 10_000;
 const MAX_FEE_RATE: i128 = 1_000; // Maximum 10% fee
 
+// Feature gates consulted by sensitive paths below (see `feature_gate.rs`).
+// Staging either of these lets the admin schedule a stricter rule for a
+// future ledger timestamp instead of flipping it on immediately.
+const FEATURE_STRICT_FEE_CAP: Symbol = symbol_short!("strctfee"); // halves MAX_FEE_RATE in `update_fee_config` once active
+const FEATURE_MANUAL_RELEASE_PAUSE: Symbol = symbol_short!("pausegate"); // makes `release_program_schedule_manual` respect PAUSE_RELEASE once active
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct FeeConfig {
@@ -171,6 +186,70 @@ pub struct FeeConfig {
     pub fee_enabled: bool,         // Global fee enable/disable flag
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeCollectedEvent {
+    pub version: u32,
+    pub program_id: String,
+    pub fee_type: Symbol,
+    pub amount: i128,
+    pub recipient: Address,
+    pub receipt_id: u64,
+}
+
+/// Which operation charged a given `FeeLedgerEntry`. `ScheduledRelease`
+/// is the global payout fee charged by `release_prog_schedule_automatic`
+/// and `release_due_schedules`; `ScheduleMaintenance` is the fee charged
+/// by `extend_schedule_ttl` - see `get_fee_report`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FeeKind {
+    Lock,
+    Payout,
+    BatchPayout,
+    ScheduledRelease,
+    ScheduleMaintenance,
+}
+
+/// One itemized fee collection, appended to `DataKey::FeeLedger` every time
+/// a fee-charging path actually collects a non-zero fee.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeLedgerEntry {
+    pub kind: FeeKind,
+    pub gross_amount: i128,
+    pub fee_amount: i128,
+    pub fee_rate_bps: i128,
+    pub recipient: Address,
+    pub timestamp: u64,
+    pub receipt_id: u64,
+}
+
+/// Rolled-up view over a program's `FeeLedger`, returned by
+/// `get_fee_report`. `effective_rate_bps` is the grand total collected as a
+/// fraction of the grand total gross amount fees were charged against, `0`
+/// when no fees have been collected yet.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeReport {
+    pub entries: Vec<FeeLedgerEntry>,
+    pub total_lock_fees: i128,
+    pub total_payout_fees: i128,
+    pub total_batch_payout_fees: i128,
+    pub total_scheduled_release_fees: i128,
+    pub grand_total: i128,
+    pub effective_rate_bps: i128,
+}
+
+/// Combined view `get_program_accounting` returns so an organizer can
+/// reconcile locked-vs-paid-vs-fees without cross-referencing two calls.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProgramAccountingReport {
+    pub stats: ProgramAggregateStats,
+    pub fee_report: FeeReport,
+}
+
 extern crate grainlify_core;
 
 // Event types
@@ -226,6 +305,36 @@ pub struct BatchPayoutEvent {
     pub receipt_id: u64,
 }
 
+/// Result of a `release_due_schedules` crank: how many schedules it
+/// actually released, the total net amount transferred, and the last
+/// `schedule_id` it looked at - pass that back in as `start_after` to
+/// resume the scan on the next call.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DueScheduleReleaseResult {
+    pub released_count: u32,
+    pub total_amount: i128,
+    pub last_processed_schedule_id: Option<u64>,
+}
+
+/// Per-schedule outcome of a `release_pending_batch` call - `Skipped`
+/// carries a short human-readable reason (e.g. "already released", "not
+/// yet due") rather than aborting the whole batch the way a single-
+/// schedule release would panic.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BatchReleaseOutcome {
+    Released,
+    Skipped(String),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchReleaseResult {
+    pub schedule_id: u64,
+    pub outcome: BatchReleaseOutcome,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PayoutEvent {
@@ -248,17 +357,52 @@ pub struct ScheduleCreatedEvent {
     pub receipt_id: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProgramFrozenEvent {
+    pub program_id: String,
+    pub total_funds: i128,
+    pub total_paid_out: i128,
+    pub remaining_balance: i128,
+    pub unreleased_schedule_count: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProgramSettledEvent {
+    pub program_id: String,
+    pub refund_address: Address,
+    pub refunded_amount: i128,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ProgramData {
     pub program_id: String,
     pub total_funds: i128,
     pub remaining_balance: i128,
+    /// Sum of unreleased, uncancelled release-schedule amounts, tracked
+    /// incrementally so `create_program_release_schedule` can reject an
+    /// over-commitment in O(1) instead of re-scanning every schedule.
+    /// Grows when a schedule is created, shrinks when one is released
+    /// (by however much was actually paid out) or cancelled (by however
+    /// much was still outstanding).
+    pub reserved_balance: i128,
     pub authorized_payout_key: Address,
     pub payout_history: Vec<PayoutRecord>,
     pub token_address: Address,
     pub initial_liquidity: i128,
     pub reference_hash: Option<soroban_sdk::Bytes>,
+    /// Disputes ever opened via `open_dispute`, win or lose - feeds the
+    /// penalty `get_program_reputation` applies to `overall_score_bps`.
+    pub dispute_count: u32,
+    /// Schedules ever sent back through `refund_program_schedule`.
+    pub refund_count: u32,
+    /// Who `refund_program_schedule` returns disputed funds to. Set from
+    /// `initialize_program`'s `creator`; programs created via
+    /// `init_program_from_parent`/`batch_initialize_programs` (which take
+    /// no creator) fall back to `authorized_payout_key`.
+    pub funder: Address,
 }
 
 #[contracttype]
@@ -279,18 +423,70 @@ pub enum DataKey {
     ProgramRegistry,                 // Global registry of all program IDs
     ProgramDependencies(String),     // program_id -> Vec<dependency_id>
     DependencyStatus(String),        // dependency_id -> DependencyStatus
+    SplitConfig(String),              // program_id -> payout_splits::SplitConfig
+    SplitClaimBalance(String, Address), // program_id, beneficiary -> i128 claimable under PayoutMode::Pull
+    SplitClosed(String),             // program_id -> bool, set once a split payout has drained it
+    SplitPayoutState(String),        // program_id -> in-progress SplitPayoutState cursor
+    ProgramPhase(String),            // program_id -> ProgramPhase
+    ProgramSettlement(String),       // program_id -> ProgramSettlement (set once Frozen)
+    FeeLedger(String),               // program_id -> Vec<FeeLedgerEntry>, capped at FEE_LEDGER_LIMIT
+    ProgramParent(String),           // program_id -> parent_program_id, set by init_program_from_parent
+    NextPlanId(String),              // program_id -> next conditional payout plan_id
+    ConditionalPlan(String, u64),    // program_id, plan_id -> ConditionalPlan
+    PlanWitnesses(String, u64),      // program_id, plan_id -> Vec<Address> of witnessed Signature conditions
+    ApprovalNonce(String),           // program_id -> next approval_nonce, advanced only once a multisig payout executes
+    PendingPayoutApproval(String, u64), // program_id, approval_nonce -> PendingPayoutApproval
+    Tombstone(String),               // program_id -> ProgramTombstone, set once close_program retires it
+    NextConditionalPayoutId(String), // program_id -> next ConditionalPayout payout_id
+    ConditionalPayout(String, u64),  // program_id, payout_id -> ConditionalPayout
+    Realizor(String),                // program_id -> Address of the external eligibility contract, if set
+    FeatureGate(Symbol),             // feature id -> feature_gate::FeatureGate, if staged
+    ProgramSigners(String),          // program_id -> ProgramSigners, gating release_program_schedule_manual
+    ReleaseApprovals(String, u64),   // program_id, schedule_id -> Vec<Address> of signers who have approved
+    ReleaseAllowance(String, Address), // program_id, spender -> ReleaseAllowance
+    ProgramFee(String),              // program_id -> ProgramFeeConfig, if a per-program platform fee is set
+    AccumulatedProgramFees(String),  // program_id -> i128, total platform fee collected so far
+    ProgramFeatures(String),         // program_id -> u64 bitmap of feature_id bits enabled via enable_feature
+    SettlementP50Estimator,          // contract-wide percentile::P2Estimator tracking settlement-time p50
+    SettlementP95Estimator,          // contract-wide percentile::P2Estimator tracking settlement-time p95
+    IdempotencyWindow(String),       // program_id -> replay-protection window in seconds, defaults if unset
+    IdempotencyBucket(String, u32),  // program_id, bucket index -> idempotency::IdempotencyBucket ring slot
 }
 
+/// Bit flags composing a `PauseFlags::mask`. New operations that need their
+/// own pause gate can claim the next free bit without touching existing
+/// callers, unlike the one-field-per-operation layout this replaced.
+pub const PAUSE_LOCK: u32 = 1 << 0;
+pub const PAUSE_RELEASE: u32 = 1 << 1;
+pub const PAUSE_REFUND: u32 = 1 << 2;
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PauseFlags {
-    pub lock_paused: bool,
-    pub release_paused: bool,
-    pub refund_paused: bool,
+    /// Bitwise-OR of `PAUSE_*` flags currently in effect.
+    pub mask: u32,
     pub pause_reason: Option<String>,
     pub paused_at: u64,
 }
 
+impl PauseFlags {
+    pub fn is_set(&self, flag: u32) -> bool {
+        self.mask & flag != 0
+    }
+
+    pub fn set_flag(&mut self, flag: u32, paused: bool) {
+        if paused {
+            self.mask |= flag;
+        } else {
+            self.mask &= !flag;
+        }
+    }
+
+    pub fn any_paused(&self) -> bool {
+        self.mask != 0
+    }
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PauseStateChanged {
@@ -330,6 +526,211 @@ pub struct ProgramReleaseSchedule {
     pub released: bool,
     pub released_at: Option<u64>,
     pub released_by: Option<Address>,
+    /// `Some` turns this into a continuously-vesting schedule: instead of
+    /// `amount` unlocking in one shot at `release_timestamp`, cranking the
+    /// schedule pays out whatever has vested since the last crank. `None`
+    /// preserves the original single-shot behavior.
+    pub vesting: Option<VestingTerms>,
+    /// Set by `cancel_program_release_schedule`. A tombstone distinct from
+    /// `released`: once cancelled, this schedule_id can never be released
+    /// or re-cancelled, even though it (unlike a released schedule) never
+    /// paid anything out.
+    pub cancelled: bool,
+    /// `Some` gates this specific schedule behind an external condition
+    /// contract, the way [`crate::realizor`] gates a whole program - but
+    /// scoped to one schedule instead of every payout in the program, and
+    /// checked via `is_realized(program_id, schedule_id, recipient)`
+    /// rather than the program-level `is_realized(program_id, recipient,
+    /// amount)`. Attached/cleared via `attach_schedule_realizor` /
+    /// `clear_schedule_realizor`.
+    pub realizor: Option<Address>,
+    /// Set by `open_dispute`, cleared by `resolve_dispute`. While `true`,
+    /// neither `release_program_schedule_manual` nor the automatic/crank
+    /// release paths may pay this schedule out.
+    pub disputed: bool,
+    /// The reason the recipient gave `open_dispute`, kept for the
+    /// duration of the dispute; cleared alongside `disputed` on resolution.
+    pub dispute_reason: Option<String>,
+    /// `Some` while this is a conditional release plan (see
+    /// [`ReleasePlan`]) created via `create_program_conditional_schedule`
+    /// and still awaiting a witness through `submit_witness`. Reduced in
+    /// place as witnesses arrive; cleared back to `None` once the plan
+    /// resolves to `Pay` and the payout executes.
+    pub plan: Option<ReleasePlan>,
+}
+
+/// A fact `submit_witness` can attest to, and what a [`ReleasePlan`] branch
+/// waits on. Modeled on the "composing contracts" payment-plan primitives:
+/// `Timestamp` is satisfied once the ledger clock reaches it regardless of
+/// what's submitted as the witness; `Signature` is satisfied when the
+/// witnessing caller authenticates as the named address.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Condition {
+    Timestamp(u64),
+    Signature(Address),
+}
+
+/// A small payment-plan interpreter for releases that depend on more than
+/// a single timestamp. `Pay` releases immediately; `After` waits for one
+/// condition to be witnessed; `Race` waits for either of two conditions,
+/// whichever lands first, and discards the other branch entirely - there
+/// is no recursion here (a branch pays a flat amount, not a nested plan),
+/// which keeps every variant a fixed, storable shape.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ReleasePlan {
+    Pay(i128),
+    After(Condition, i128),
+    Race((Condition, i128), (Condition, i128)),
+}
+
+/// The amount a [`ReleasePlan`] could still pay out - used to size how
+/// much of the program's balance `create_program_conditional_schedule`
+/// must reserve. For `Race`, only one branch ever pays, but the reservation
+/// must cover whichever branch turns out to win.
+fn release_plan_reserve_amount(plan: &ReleasePlan) -> i128 {
+    match plan {
+        ReleasePlan::Pay(amount) => *amount,
+        ReleasePlan::After(_, amount) => *amount,
+        ReleasePlan::Race((_, amount_a), (_, amount_b)) => {
+            if *amount_a > *amount_b {
+                *amount_a
+            } else {
+                *amount_b
+            }
+        }
+    }
+}
+
+/// Whether witnessing `condition` with `witness` satisfies it. A mismatched
+/// witness kind (e.g. a `Signature` offered against a `Timestamp`
+/// condition) never satisfies anything.
+fn condition_satisfied(env: &Env, condition: &Condition, witness: &Condition) -> bool {
+    match (condition, witness) {
+        (Condition::Timestamp(target), Condition::Timestamp(_)) => {
+            env.ledger().timestamp() >= *target
+        }
+        (Condition::Signature(addr), Condition::Signature(witness_addr)) => {
+            witness_addr.require_auth();
+            witness_addr == addr
+        }
+        _ => false,
+    }
+}
+
+/// Cliff + linear vesting terms for a [`ProgramReleaseSchedule`]. Nothing
+/// vests before `cliff_ts`; everything has vested by `end_ts`; in between,
+/// the vested amount grows linearly from `start_ts`. `released_amount`
+/// tracks how much of `total_amount` has already been paid out across
+/// prior cranks, so `release_prog_schedule_automatic` only ever transfers
+/// the newly-vested remainder.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingTerms {
+    pub start_ts: u64,
+    pub cliff_ts: u64,
+    pub end_ts: u64,
+    pub total_amount: i128,
+    pub released_amount: i128,
+    /// `Some(n)` discretizes vesting into `n`-second steps (the amount
+    /// vested only increases at step boundaries) instead of accruing
+    /// continuously - see `create_program_vesting_schedule`. `None`
+    /// preserves the original continuous straight-line behavior from
+    /// `create_vesting_release_schedule`.
+    pub step_seconds: Option<u64>,
+}
+
+/// Computes the cumulative amount that should have vested by `now` under
+/// `terms`: `0` before the cliff, `total_amount` at/after `end_ts`, and
+/// either a straight-line interpolation or a stepwise one in between,
+/// depending on `terms.step_seconds`. Multiplies before dividing (with
+/// checked arithmetic) so the division doesn't truncate intermediate terms.
+fn vested_amount(terms: &VestingTerms, now: u64) -> i128 {
+    if now < terms.cliff_ts {
+        return 0;
+    }
+    if now >= terms.end_ts {
+        return terms.total_amount;
+    }
+    match terms.step_seconds {
+        None => {
+            let elapsed = (now - terms.start_ts) as i128;
+            let duration = (terms.end_ts - terms.start_ts) as i128;
+            terms
+                .total_amount
+                .checked_mul(elapsed)
+                .unwrap_or_else(|| panic!("Vesting amount overflow"))
+                .checked_div(duration)
+                .unwrap_or_else(|| panic!("Vesting duration invalid"))
+        }
+        Some(step_seconds) => {
+            let step_seconds = step_seconds.max(1);
+            let total_duration = terms.end_ts - terms.start_ts;
+            let total_steps = (total_duration + step_seconds - 1) / step_seconds;
+            let elapsed_steps = (now.min(terms.end_ts) - terms.start_ts) / step_seconds;
+            terms
+                .total_amount
+                .checked_mul(elapsed_steps as i128)
+                .unwrap_or_else(|| panic!("Vesting amount overflow"))
+                .checked_div(total_steps as i128)
+                .unwrap_or_else(|| panic!("Vesting duration invalid"))
+        }
+    }
+}
+
+/// Grace window added on top of a schedule's due date before computing how
+/// many ledgers its persistent entries need to survive - keepers have this
+/// much slack after `release_timestamp` to actually crank the release
+/// before the entry is at risk of eviction.
+const SCHEDULE_TTL_GRACE_SECONDS: u64 = 30 * 24 * 60 * 60;
+
+/// Rough seconds-per-ledger used only to translate a schedule's due date
+/// into a ledger count for `extend_ttl` - Soroban's TTL accounting is
+/// ledger-based, not timestamp-based, and there's no contract-visible way
+/// to read the network's actual average close time.
+const ASSUMED_LEDGER_SECONDS: u64 = 5;
+
+/// How many ledgers a schedule due at `release_timestamp` needs its
+/// persistent entries extended by to comfortably survive until
+/// `release_timestamp` plus [`SCHEDULE_TTL_GRACE_SECONDS`].
+fn schedule_ttl_ledgers(env: &Env, release_timestamp: u64) -> u32 {
+    let now = env.ledger().timestamp();
+    let target = release_timestamp.saturating_add(SCHEDULE_TTL_GRACE_SECONDS);
+    let seconds_until = target.saturating_sub(now);
+    (seconds_until / ASSUMED_LEDGER_SECONDS).min(u32::MAX as u64) as u32
+}
+
+/// Extends `schedule_id`'s persistent entry (plus the program's shared
+/// `NextScheduleId`/`ReleaseHistory` entries, when already present) so none
+/// of them expire for at least `ledgers` more ledgers, then emits
+/// [`ScheduleTtlExtendedEvent`] recording the new expiry so integrators can
+/// track entries that are approaching eviction.
+fn extend_schedule_storage_ttl(env: &Env, program_id: &String, schedule_id: u64, ledgers: u32) -> u64 {
+    let schedule_key = DataKey::ReleaseSchedule(program_id.clone(), schedule_id);
+    env.storage().persistent().extend_ttl(&schedule_key, ledgers, ledgers);
+
+    let next_id_key = DataKey::NextScheduleId(program_id.clone());
+    if env.storage().persistent().has(&next_id_key) {
+        env.storage().persistent().extend_ttl(&next_id_key, ledgers, ledgers);
+    }
+
+    let history_key = DataKey::ReleaseHistory(program_id.clone());
+    if env.storage().persistent().has(&history_key) {
+        env.storage().persistent().extend_ttl(&history_key, ledgers, ledgers);
+    }
+
+    let new_expiry_ledger = env.ledger().sequence() as u64 + ledgers as u64;
+    env.events().publish(
+        (SCHEDULE_TTL_EXTENDED,),
+        ScheduleTtlExtendedEvent {
+            program_id: program_id.clone(),
+            schedule_id,
+            extended_by_ledgers: ledgers,
+            new_expiry_ledger,
+        },
+    );
+    new_expiry_ledger
 }
 
 #[contracttype]
@@ -338,6 +739,16 @@ pub enum ReleaseType {
     Manual,
     Automatic,
     Oracle,
+    /// A recipient-initiated pull via `claim_vested`, as opposed to a
+    /// keeper/authorized-key push through `release_prog_schedule_automatic`
+    /// or `release_program_schedule_manual`.
+    Vesting,
+    /// `refund_program_schedule` returning a disputed/unreleased
+    /// schedule's amount to the program funder instead of the recipient.
+    Refund,
+    /// A `ReleasePlan` resolving to `Pay` and executing through
+    /// `create_program_conditional_schedule`/`submit_witness`.
+    Conditional,
 }
 
 #[contracttype]
@@ -348,6 +759,106 @@ pub struct ProgramReleaseHistory {
     pub amount: i128,
     pub released_at: u64,
     pub release_type: ReleaseType,
+    /// The platform fee (see `set_program_fee`/`ProgramFeeConfig`) withheld
+    /// from `amount` and sent to the program's treasury instead of
+    /// `recipient`. `0` when no per-program fee is configured.
+    pub fee_amount: i128,
+}
+
+/// Whether a `ProgramFeeConfig` charges a proportional basis-point rate or
+/// a flat token amount per payout. `Fixed` exists for programs whose
+/// bounties vary widely in size, where a percentage either rounds away to
+/// nothing on small payouts or takes an outsized cut of large ones.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FeeMode {
+    Percentage,
+    Fixed,
+}
+
+/// An optional platform fee the program's authorized payout key can
+/// configure via `set_program_fee`/`set_program_fee_fixed`, withheld from
+/// the manual, automatic, vesting, and conditional release paths and
+/// sent to `treasury` instead of the recipient - distinct from the
+/// contract-wide `FeeConfig`, which charges a single global fee recipient
+/// rather than a per-program treasury. The `release_due_schedules` and
+/// `release_due_program_schedules` cranks do not withhold it. `fee_mode` picks which of `fee_bps`
+/// (basis points) or `fixed_fee_amount` (a flat token amount) applies; the
+/// other field is left at its zero value and ignored.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProgramFeeConfig {
+    pub fee_mode: FeeMode,
+    pub fee_bps: u32,
+    pub fixed_fee_amount: i128,
+    pub treasury: Address,
+}
+
+/// Streaming p50/p95 of settlement time - the gap between a schedule's
+/// `release_timestamp` and the ledger time it actually paid out - across
+/// every schedule released so far, contract-wide. Backed by two
+/// independent [`percentile::P2Estimator`]s (see that module for why this
+/// doesn't need to store every sample), updated by
+/// [`ProgramEscrowContract::record_settlement_time`] from each release
+/// path and exposed via `get_settlement_time_percentiles`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementTimePercentiles {
+    pub p50_settlement_secs: i128,
+    pub p95_settlement_secs: i128,
+}
+
+/// Emitted by `enable_feature`/`disable_feature`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeatureActivatedEvent {
+    pub program_id: String,
+    pub feature_id: u32,
+    pub enabled: bool,
+    pub admin: Address,
+    pub activated_at: u64,
+}
+
+/// On-chain reputation snapshot returned by `get_program_reputation`, in
+/// basis points (10000 = 100%) for every `*_bps` field.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProgramReputationScore {
+    pub total_payouts: u32,
+    pub total_scheduled: u32,
+    pub completed_releases: u32,
+    pub pending_releases: u32,
+    pub overdue_releases: u32,
+    pub dispute_count: u32,
+    pub refund_count: u32,
+    pub total_funds_locked: i128,
+    pub total_funds_distributed: i128,
+    pub completion_rate_bps: u32,
+    pub payout_fulfillment_rate_bps: u32,
+    pub overall_score_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProgramScheduleCancelled {
+    pub program_id: String,
+    pub schedule_id: u64,
+    pub amount_returned: i128,
+    pub cancelled_by: Address,
+    pub cancelled_at: u64,
+}
+
+/// Emitted whenever a schedule's persistent-storage TTL is topped up -
+/// either automatically at creation time, or via `extend_schedule_ttl` -
+/// so off-chain keepers can watch for entries drifting back towards
+/// eviction instead of discovering an unreleasable schedule after the fact.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduleTtlExtendedEvent {
+    pub program_id: String,
+    pub schedule_id: u64,
+    pub extended_by_ledgers: u32,
+    pub new_expiry_ledger: u64,
 }
 
 #[contracttype]
@@ -381,6 +892,160 @@ pub struct ProgramInitItem {
     pub reference_hash: Option<soroban_sdk::Bytes>,
 }
 
+/// Per-field overrides for `init_program_from_parent`. A `None`
+/// `authorized_payout_key`, `token_address` or `multisig_config` is
+/// inherited from the parent program instead of requiring the caller to
+/// re-enter it - the whole point of versioning a recurring program.
+/// `reference_hash` is not inherited: it is taken as given, so `None`
+/// leaves the new program without one. Note
+/// `FeeConfig` is not one of these fields: in this contract it's a single
+/// contract-wide value (`FEE_CONFIG`), not a per-program setting, so there
+/// is nothing program-specific to inherit or override.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProgramInitOverrides {
+    pub authorized_payout_key: Option<Address>,
+    pub token_address: Option<Address>,
+    pub multisig_config: Option<MultisigConfig>,
+    pub reference_hash: Option<soroban_sdk::Bytes>,
+}
+
+/// Aggregate prize-distribution totals across a program's whole lineage
+/// (the program itself plus every ancestor `get_program_ancestry` finds),
+/// returned by `get_lineage_stats`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LineageStats {
+    pub lineage: Vec<String>,
+    pub total_paid_out: i128,
+    pub payout_count: u32,
+}
+
+/// A condition gating a `BudgetNode`: satisfied once the ledger clock
+/// passes `Timestamp`, or once the named `Signature` address has called
+/// `apply_witness` for the plan.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Condition {
+    Timestamp(u64),
+    Signature(Address),
+}
+
+/// The leaf of a `Budget` plan: pay `amount` to `to`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Payment {
+    pub amount: i128,
+    pub to: Address,
+}
+
+/// One node of a `Budget` plan tree, stored flattened in
+/// `ConditionalPlan::nodes` with children referenced by index rather than
+/// `Box` - Soroban contract types need a fixed XDR shape, which rules out a
+/// literal Rust-level recursive `Budget` enum. `nodes[0]` is always the
+/// plan's root. Conceptually this is exactly the `Pay` / `After(Condition,
+/// Box<Budget>)` / `And(Condition, Condition, Box<Budget>)` /
+/// `Or((Condition, Box<Budget>), (Condition, Box<Budget>))` DSL, with the
+/// `Box<Budget>` links replaced by `u32` indices into the same arena.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BudgetNode {
+    Pay(Payment),
+    After(Condition, u32),
+    And(Condition, Condition, u32),
+    Or(Condition, u32, Condition, u32),
+}
+
+/// A conditional payout plan created by `create_conditional_payout`.
+/// `nodes` never mutates after creation - the only mutable state is the set
+/// of witnessed `Signature` addresses (`DataKey::PlanWitnesses`); whether
+/// the tree currently resolves to a firm `Payment` is recomputed from
+/// `nodes` + witnesses on every `apply_witness` call.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConditionalPlan {
+    pub plan_id: u64,
+    pub program_id: String,
+    pub nodes: Vec<BudgetNode>,
+    pub total_reserved: i128,
+    pub executed: bool,
+    pub cancelled: bool,
+}
+
+/// A leaf predicate in a `ConditionalPayout`'s condition tree (see
+/// `ConditionNode`). Distinct from `Condition` above: that one gates a
+/// `Budget` plan's `And`/`Or` nodes via an already-resolved `Signature`
+/// witness list, while `Witness` here is checked by calling `require_auth()`
+/// directly at claim time.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PayoutCondition {
+    After(u64),
+    Witness(Address),
+}
+
+/// One node of a `ConditionalPayout`'s condition tree, stored flattened in
+/// `ConditionalPayout::nodes` with children referenced by `u32` index -
+/// same arena workaround `BudgetNode` uses, since Soroban `#[contracttype]`
+/// can't express `And(Vec<Condition>)` / `Or(Vec<Condition>)` as a literally
+/// recursive enum. `nodes[0]` is always the tree's root.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ConditionNode {
+    Leaf(PayoutCondition),
+    And(Vec<u32>),
+    Or(Vec<u32>),
+}
+
+/// A payout armed by `arm_conditional_payout`, claimable once its
+/// condition tree is fully satisfied. `amount` is reserved against the
+/// program's `remaining_balance` at creation time so it can't be
+/// double-spent by a concurrent `single_payout`/`batch_payout` while the
+/// conditions are still pending.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConditionalPayout {
+    pub payout_id: u64,
+    pub program_id: String,
+    pub recipient: Address,
+    pub amount: i128,
+    pub nodes: Vec<ConditionNode>,
+    pub claimed: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConditionalPayoutExecutedEvent {
+    pub program_id: String,
+    pub plan_id: u64,
+    pub recipient: Address,
+    pub amount: i128,
+    pub receipt_id: u64,
+}
+
+/// Emitted once `approve_payout` collects `required_signatures` and
+/// executes the transfer. `approval_nonce` is the slot that just fired, so
+/// off-chain signers watching this event always know the next nonce to
+/// bind their signatures to.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MultisigPayoutExecutedEvent {
+    pub program_id: String,
+    pub approval_nonce: u64,
+    pub recipient: Address,
+    pub amount: i128,
+    pub receipt_id: u64,
+}
+
+/// Emitted once `close_program` tombstones a drained program.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProgramClosedEvent {
+    pub program_id: String,
+    pub final_receipt_id: u64,
+    pub closed_at: u64,
+}
+
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
@@ -388,56 +1053,523 @@ pub enum BatchError {
     InvalidBatchSize = 1,
     ProgramAlreadyExists = 2,
     DuplicateProgramId = 3,
+    ProgramFrozen = 4,
 }
 
+/// Lifecycle phase of a `ProgramData`, mirroring a bank account's
+/// open -> frozen -> rooted progression so a closed program has an
+/// auditable terminal state instead of staying open to payouts forever.
+/// Stored under `DataKey::ProgramPhase`, alongside `ProgramData` rather
+/// than embedded in it, the same way `MultisigConfig`/`SplitClosed` are
+/// kept as separate per-program keys.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct MultisigConfig {
-    pub threshold_amount: i128,
-    pub signers: Vec<Address>,
-    pub required_signatures: u32,
+pub enum ProgramPhase {
+    /// Normal operation: locking, payouts, and new schedules are allowed.
+    Open,
+    /// `freeze_program` has been called. Only releases of already-created
+    /// schedules are permitted so in-flight obligations can still drain;
+    /// locking, payouts, and new schedule creation panic.
+    Frozen,
+    /// `settle_program` has been called: every schedule has released, the
+    /// residual balance has been refunded, and the program is terminal.
+    Settled,
 }
 
+/// Immutable accounting snapshot captured by `freeze_program`, returned by
+/// `get_settlement`. Numbers are frozen at freeze time; later releases via
+/// `release_program_schedule_manual` change the live `ProgramData` but not
+/// this record, by design, so an auditor can always see what the program
+/// looked like the moment it was closed for new business.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct PayoutApproval {
+pub struct ProgramSettlement {
     pub program_id: String,
-    pub recipient: Address,
-    pub amount: i128,
-    pub approvals: Vec<Address>,
+    pub total_funds: i128,
+    pub total_paid_out: i128,
+    pub remaining_balance: i128,
+    pub unreleased_schedule_count: u32,
+    pub settled_at: u64,
 }
 
-pub const MAX_BATCH_SIZE: u32 = 100;
+/// Immutable marker left by `close_program` once a drained program is
+/// retired. Replaces the `DataKey::Program` entry so the id can never be
+/// re-initialized, while keeping enough of a record (`final_receipt_id`,
+/// `reference_hash`) that `program_status` can still answer "this hackathon
+/// escrow existed and was settled" long after the live state is gone.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProgramTombstone {
+    pub program_id: String,
+    pub closed_at: u64,
+    pub final_receipt_id: u64,
+    pub reference_hash: Option<soroban_sdk::Bytes>,
+}
 
-fn vec_contains(values: &Vec<String>, target: &String) -> bool {
-    for value in values.iter() {
-        if value == *target {
-            return true;
-        }
-    }
-    false
+/// Tri-state lifecycle view over a `program_id`, for callers that just need
+/// to know whether an id is safe to reuse rather than the full
+/// `ProgramTombstone`/`ProgramData`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ProgramStatus {
+    Active,
+    Closed,
+    NotFound,
 }
 
-fn get_program_dependencies_internal(env: &Env, program_id: &String) -> Vec<String> {
-    env.storage()
-        .instance()
-        .get(&DataKey::ProgramDependencies(program_id.clone()))
-        .unwrap_or(vec![env])
+/// Result of comparing a token's live contract balance against the sum of
+/// `remaining_balance` recorded across every program that shares it.
+/// `drift` is `on_chain - recorded`: positive means the contract holds more
+/// than any program accounts for (e.g. an un-recorded direct transfer),
+/// negative means storage claims funds the contract no longer has (e.g. a
+/// transfer that silently failed to debit `remaining_balance`).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReconcileReport {
+    pub token_address: Address,
+    pub recorded: i128,
+    pub on_chain: i128,
+    pub drift: i128,
 }
 
-fn dependency_status_internal(env: &Env, dependency_id: &String) -> DependencyStatus {
-    env.storage()
-        .instance()
-        .get(&DataKey::DependencyStatus(dependency_id.clone()))
-        .unwrap_or(DependencyStatus::Pending)
+/// Emitted once `sweep_empty_programs` deletes a drained program's
+/// per-program storage entries and removes it from `PROGRAM_REGISTRY`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProgramReclaimedEvent {
+    pub program_id: String,
+    pub receipt_id: u64,
 }
 
-fn path_exists_to_target(
-    env: &Env,
-    from_program: &String,
-    target_program: &String,
-    visited: &mut Vec<String>,
-) -> bool {
+/// Emitted whenever `single_payout`, `batch_payout`, or
+/// `release_prog_schedule_automatic` is blocked because the program's
+/// `realizor` (see [`crate::realizor`]) has not yet reported `recipient`
+/// as realized for `amount`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RealizorBlockedEvent {
+    pub program_id: String,
+    pub recipient: Address,
+    pub amount: i128,
+}
+
+/// Emitted when `release_program_schedule_manual` is blocked because the
+/// schedule's own `realizor` (distinct from the program-level one above)
+/// has not yet reported this schedule as realized.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduleRealizorBlockedEvent {
+    pub program_id: String,
+    pub schedule_id: u64,
+    pub recipient: Address,
+}
+
+/// Emitted by `open_dispute`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeOpenedEvent {
+    pub program_id: String,
+    pub schedule_id: u64,
+    pub opened_by: Address,
+    pub reason: String,
+}
+
+/// Emitted by `resolve_dispute`. `upheld` mirrors the `uphold` argument:
+/// `true` means the dispute stands and the schedule stays blocked (pending
+/// a `refund_program_schedule` call), `false` clears it and restores the
+/// schedule to its normal releasable state.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeResolvedEvent {
+    pub program_id: String,
+    pub schedule_id: u64,
+    pub upheld: bool,
+    pub resolved_by: Address,
+}
+
+/// Emitted by `refund_program_schedule`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduleRefundedEvent {
+    pub program_id: String,
+    pub schedule_id: u64,
+    pub amount: i128,
+    pub refunded_to: Address,
+}
+
+/// Emitted by `terminate_vesting`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingTerminatedEvent {
+    pub program_id: String,
+    pub schedule_id: u64,
+    pub vested_amount: i128,
+    pub unvested_amount: i128,
+    pub recipient: Address,
+    pub terminated_by: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MultisigConfig {
+    pub threshold_amount: i128,
+    pub signers: Vec<Address>,
+    pub required_signatures: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayoutApproval {
+    pub program_id: String,
+    pub recipient: Address,
+    pub amount: i128,
+    pub approvals: Vec<Address>,
+}
+
+/// A multisig payout awaiting `required_signatures` approvals, bound to a
+/// specific `(program_id, approval_nonce)` slot. `recipient`/`amount` are
+/// fixed by whichever signer calls `approve_payout` first for this nonce;
+/// every later approval for the same nonce must match them exactly or it's
+/// rejected, so a collected signature can't be redirected to a different
+/// payout. `approval_nonce` only advances once the threshold executes, so
+/// there is at most one pending approval per program at a time.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingPayoutApproval {
+    pub program_id: String,
+    pub approval_nonce: u64,
+    pub recipient: Address,
+    pub amount: i128,
+    pub approvals: Vec<Address>,
+}
+
+/// M-of-N governance over a single program's `release_program_schedule_manual`
+/// calls, set via `set_program_signers`. Distinct from `MultisigConfig`
+/// (which gates any single/batch payout above `threshold_amount`): this
+/// gates every manual schedule release for the program, regardless of
+/// amount, once configured.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProgramSigners {
+    pub signers: Vec<Address>,
+    pub threshold: u32,
+}
+
+/// A capped, expiring delegation of release authority, modeled on the
+/// standard token allowance pattern (`increase_allowance`/`allowance` with
+/// a `live_until_ledger`). `amount` decrements as `release_with_allowance`
+/// spends it; once `env.ledger().sequence()` passes `expiration_ledger`,
+/// the allowance reads as exhausted regardless of `amount`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReleaseAllowance {
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
+pub const MAX_BATCH_SIZE: u32 = 100;
+
+fn vec_contains(values: &Vec<String>, target: &String) -> bool {
+    for value in values.iter() {
+        if value == *target {
+            return true;
+        }
+    }
+    false
+}
+
+fn get_program_phase_internal(env: &Env, program_id: &String) -> ProgramPhase {
+    env.storage()
+        .instance()
+        .get(&DataKey::ProgramPhase(program_id.clone()))
+        .unwrap_or(ProgramPhase::Open)
+}
+
+/// Panics if `program_id` is `Frozen` or `Settled`. Locking, payouts, and new
+/// schedule creation all call this so a frozen program can't grow new
+/// obligations while its already-created schedules keep draining.
+fn assert_program_open(env: &Env, program_id: &String) {
+    if get_program_phase_internal(env, program_id) != ProgramPhase::Open {
+        panic!("Program is frozen or settled");
+    }
+}
+
+/// Appends a `FeeLedgerEntry` to `program_id`'s ledger (trimming the oldest
+/// entry once `FEE_LEDGER_LIMIT` is exceeded, the same ring-buffer trim
+/// `create_config_snapshot` uses for `ConfigSnapshotKey::SnapshotIndex`) and
+/// emits a `FeeCollected` event for off-chain indexers. Only called when
+/// `fee_amount > 0` - a zero fee leaves no ledger trace, same as it leaves
+/// no event today.
+fn record_fee_ledger_entry(
+    env: &Env,
+    program_id: &String,
+    kind: FeeKind,
+    gross_amount: i128,
+    fee_amount: i128,
+    fee_rate_bps: i128,
+    recipient: &Address,
+    receipt_id: u64,
+) {
+    let entry = FeeLedgerEntry {
+        kind: kind.clone(),
+        gross_amount,
+        fee_amount,
+        fee_rate_bps,
+        recipient: recipient.clone(),
+        timestamp: env.ledger().timestamp(),
+        receipt_id,
+    };
+
+    let ledger_key = DataKey::FeeLedger(program_id.clone());
+    let mut ledger: Vec<FeeLedgerEntry> = env.storage().instance().get(&ledger_key).unwrap_or(vec![env]);
+    ledger.push_back(entry);
+    if ledger.len() > FEE_LEDGER_LIMIT {
+        let mut trimmed = Vec::new(env);
+        for i in 1..ledger.len() {
+            trimmed.push_back(ledger.get(i).unwrap());
+        }
+        ledger = trimmed;
+    }
+    env.storage().instance().set(&ledger_key, &ledger);
+
+    let fee_type = match kind {
+        FeeKind::Lock => symbol_short!("lock"),
+        FeeKind::Payout => symbol_short!("payout"),
+        FeeKind::BatchPayout => symbol_short!("batch"),
+        FeeKind::ScheduledRelease => symbol_short!("schedrel"),
+        FeeKind::ScheduleMaintenance => symbol_short!("schedmnt"),
+    };
+
+    env.events().publish(
+        (FEE_COLLECTED,),
+        FeeCollectedEvent {
+            version: EVENT_VERSION_V2,
+            program_id: program_id.clone(),
+            fee_type,
+            amount: fee_amount,
+            recipient: recipient.clone(),
+            receipt_id,
+        },
+    );
+}
+
+/// Splits `amount` between `recipient` and the program's configured
+/// `ProgramFeeConfig` treasury (if any), transferring both legs in this
+/// call and accumulating the fee leg under `DataKey::AccumulatedProgramFees`.
+/// Returns `(net_amount, fee_amount)` so callers can record both in their
+/// `ProgramReleaseHistory` entry. A program with no fee configured (or
+/// `fee_bps == 0`) pays `amount` straight through with `fee_amount` `0`.
+fn transfer_with_program_fee(
+    env: &Env,
+    program_id: &String,
+    token_client: &token::Client,
+    contract_address: &Address,
+    recipient: &Address,
+    amount: i128,
+) -> (i128, i128) {
+    let fee_config: Option<ProgramFeeConfig> = env
+        .storage()
+        .instance()
+        .get(&DataKey::ProgramFee(program_id.clone()));
+
+    let fee_amount = match &fee_config {
+        Some(cfg) => match cfg.fee_mode {
+            FeeMode::Percentage if cfg.fee_bps > 0 => amount * cfg.fee_bps as i128 / 10_000,
+            FeeMode::Percentage => 0,
+            FeeMode::Fixed if cfg.fixed_fee_amount > 0 => {
+                if cfg.fixed_fee_amount > amount {
+                    panic!("fixed_fee_amount exceeds payout amount");
+                }
+                cfg.fixed_fee_amount
+            }
+            FeeMode::Fixed => 0,
+        },
+        None => 0,
+    };
+    let net_amount = amount - fee_amount;
+
+    token_client.transfer(contract_address, recipient, &net_amount);
+    if fee_amount > 0 {
+        let treasury = &fee_config.unwrap().treasury;
+        token_client.transfer(contract_address, treasury, &fee_amount);
+
+        let accrued_key = DataKey::AccumulatedProgramFees(program_id.clone());
+        let accrued: i128 = env.storage().instance().get(&accrued_key).unwrap_or(0);
+        env.storage().instance().set(&accrued_key, &(accrued + fee_amount));
+    }
+
+    (net_amount, fee_amount)
+}
+
+fn vec_contains_address(values: &Vec<Address>, target: &Address) -> bool {
+    for value in values.iter() {
+        if value == *target {
+            return true;
+        }
+    }
+    false
+}
+
+fn condition_satisfied(env: &Env, condition: &Condition, witnesses: &Vec<Address>) -> bool {
+    match condition {
+        Condition::Timestamp(t) => env.ledger().timestamp() >= *t,
+        Condition::Signature(addr) => vec_contains_address(witnesses, addr),
+    }
+}
+
+/// Total amount a `Budget` plan could ever pay out, reserved up front by
+/// `create_conditional_payout`. For `Or`, only one branch ever actually
+/// executes, so the reservation is the larger of the two branch totals
+/// (enough to cover whichever one fires) rather than their sum.
+fn budget_total(nodes: &Vec<BudgetNode>, idx: u32) -> i128 {
+    match nodes.get(idx).unwrap() {
+        BudgetNode::Pay(payment) => payment.amount,
+        BudgetNode::After(_, next) => budget_total(nodes, next),
+        BudgetNode::And(_, _, next) => budget_total(nodes, next),
+        BudgetNode::Or(_, n1, _, n2) => {
+            let t1 = budget_total(nodes, n1);
+            let t2 = budget_total(nodes, n2);
+            if t1 > t2 {
+                t1
+            } else {
+                t2
+            }
+        }
+    }
+}
+
+/// Resolves node `idx` of a `Budget` plan against the currently satisfied
+/// `witnesses`, returning the firm `Payment` if the subtree collapses all
+/// the way down, or `None` if it's still waiting on a condition. `Or`
+/// checks its first branch's condition before its second, so the first one
+/// to fire wins and the other is discarded, per the DSL's semantics.
+fn evaluate_budget(env: &Env, nodes: &Vec<BudgetNode>, idx: u32, witnesses: &Vec<Address>) -> Option<Payment> {
+    match nodes.get(idx).unwrap() {
+        BudgetNode::Pay(payment) => Some(payment),
+        BudgetNode::After(condition, next) => {
+            if condition_satisfied(env, &condition, witnesses) {
+                evaluate_budget(env, nodes, next, witnesses)
+            } else {
+                None
+            }
+        }
+        BudgetNode::And(c1, c2, next) => {
+            if condition_satisfied(env, &c1, witnesses) && condition_satisfied(env, &c2, witnesses) {
+                evaluate_budget(env, nodes, next, witnesses)
+            } else {
+                None
+            }
+        }
+        BudgetNode::Or(c1, n1, c2, n2) => {
+            if condition_satisfied(env, &c1, witnesses) {
+                evaluate_budget(env, nodes, n1, witnesses)
+            } else if condition_satisfied(env, &c2, witnesses) {
+                evaluate_budget(env, nodes, n2, witnesses)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// Recursively evaluates a `ConditionalPayout`'s condition tree starting at
+/// `idx`. `PayoutCondition::Witness` is checked by calling `require_auth()`
+/// directly, so an `Or` branch that tries an unauthorized witness first
+/// traps the whole invocation rather than falling through to the next
+/// branch - callers should order `Or` children with the witness they
+/// actually authorized first, or use only `After` alongside untried
+/// witnesses.
+fn evaluate_condition_node(env: &Env, nodes: &Vec<ConditionNode>, idx: u32) -> bool {
+    match nodes.get(idx).unwrap() {
+        ConditionNode::Leaf(PayoutCondition::After(t)) => env.ledger().timestamp() >= t,
+        ConditionNode::Leaf(PayoutCondition::Witness(addr)) => {
+            addr.require_auth();
+            true
+        }
+        ConditionNode::And(children) => children
+            .iter()
+            .all(|child| evaluate_condition_node(env, nodes, child)),
+        ConditionNode::Or(children) => children
+            .iter()
+            .any(|child| evaluate_condition_node(env, nodes, child)),
+    }
+}
+
+fn get_approval_nonce_internal(env: &Env, program_id: &String) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::ApprovalNonce(program_id.clone()))
+        .unwrap_or(0)
+}
+
+/// Whether `program_id` has any conditional payout plan that hasn't yet
+/// fired or been cancelled - such a plan still has `remaining_balance`
+/// reserved against it, so the program can't be reclaimed underneath it.
+fn has_active_conditional_plans(env: &Env, program_id: &String) -> bool {
+    let next_plan_id: u64 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::NextPlanId(program_id.clone()))
+        .unwrap_or(1);
+
+    for plan_id in 1..next_plan_id {
+        let plan: Option<ConditionalPlan> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ConditionalPlan(program_id.clone(), plan_id));
+        if let Some(plan) = plan {
+            if !plan.executed && !plan.cancelled {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// Whether `program_id`'s current `approval_nonce` slot has a multisig
+/// approval collecting signatures.
+fn has_pending_multisig_approval(env: &Env, program_id: &String) -> bool {
+    let nonce = get_approval_nonce_internal(env, program_id);
+    env.storage()
+        .persistent()
+        .has(&DataKey::PendingPayoutApproval(program_id.clone(), nonce))
+}
+
+fn get_program_dependencies_internal(env: &Env, program_id: &String) -> Vec<String> {
+    env.storage()
+        .instance()
+        .get(&DataKey::ProgramDependencies(program_id.clone()))
+        .unwrap_or(vec![env])
+}
+
+fn dependency_status_internal(env: &Env, dependency_id: &String) -> DependencyStatus {
+    env.storage()
+        .instance()
+        .get(&DataKey::DependencyStatus(dependency_id.clone()))
+        .unwrap_or(DependencyStatus::Pending)
+}
+
+fn path_exists_to_target(
+    env: &Env,
+    from_program: &String,
+    target_program: &String,
+    visited: &mut Vec<String>,
+) -> bool {
+    path_exists_via(env, from_program, target_program, visited, get_program_dependencies_internal)
+}
+
+/// Shared graph walk behind `path_exists_to_target` (dependency edges) and
+/// `init_program_from_parent`'s cycle check (parent edges): does a path from
+/// `from_program` to `target_program` exist, following whatever `edges`
+/// considers the outgoing links of a program? `edges` is a plain fn pointer
+/// rather than a closure since both call sites need no captured state.
+fn path_exists_via(
+    env: &Env,
+    from_program: &String,
+    target_program: &String,
+    visited: &mut Vec<String>,
+    edges: fn(&Env, &String) -> Vec<String>,
+) -> bool {
     if *from_program == *target_program {
         return true;
     }
@@ -446,10 +1578,10 @@ fn path_exists_to_target(
     }
 
     visited.push_back(from_program.clone());
-    let deps = get_program_dependencies_internal(env, from_program);
-    for dep in deps.iter() {
+    let next = edges(env, from_program);
+    for dep in next.iter() {
         if env.storage().instance().has(&DataKey::Program(dep.clone()))
-            && path_exists_to_target(env, &dep, target_program, visited)
+            && path_exists_via(env, &dep, target_program, visited, edges)
         {
             return true;
         }
@@ -457,6 +1589,22 @@ fn path_exists_to_target(
 
     false
 }
+
+/// Outgoing edge for the parent-ancestry graph: `program_id`'s declared
+/// parent, if any, as a single-element `Vec` so it fits `path_exists_via`'s
+/// multi-edge `edges` signature the same way dependency edges do.
+fn get_program_parent_edges(env: &Env, program_id: &String) -> Vec<String> {
+    match get_program_parent_internal(env, program_id) {
+        Some(parent_id) => vec![env, parent_id],
+        None => vec![env],
+    }
+}
+
+fn get_program_parent_internal(env: &Env, program_id: &String) -> Option<String> {
+    env.storage()
+        .instance()
+        .get(&DataKey::ProgramParent(program_id.clone()))
+}
 mod anti_abuse {
     use soroban_sdk::{symbol_short, Address, Env, Symbol};
 
@@ -589,16 +1737,26 @@ pub mod token_math;
 pub use claim_period::{ClaimRecord, ClaimStatus};
 mod error_recovery;
 mod reentrancy_guard;
+pub mod payout_splits;
+pub use payout_splits::EscrowError;
+pub mod realizor;
+pub mod feature_gate;
+pub mod percentile;
+pub mod idempotency;
 #[cfg(test)]
 mod test_claim_period_expiry_cancellation;
 
 #[cfg(test)]
 mod test_token_math;
 
+#[cfg(test)]
+mod test_payout_splits;
+
 // Storage keys
 const PROGRAM_DATA: Symbol = symbol_short!("ProgData");
 const FEE_CONFIG: Symbol = symbol_short!("FeeCfg");
 const CONFIG_SNAPSHOT_LIMIT: u32 = 20;
+const FEE_LEDGER_LIMIT: u32 = 50;
 
 // Fee rate is stored in basis points (1 basis point = 0.01%)
 // Example: 100 basis points = 1%, 1000 basis points = 10%
@@ -623,6 +1781,15 @@ pub struct ConfigSnapshot {
     pub anti_abuse_config: anti_abuse::AntiAbuseConfig,
     pub anti_abuse_admin: Option<Address>,
     pub is_paused: bool,
+    /// The snapshot id most recently taken before this one, `None` for the
+    /// very first snapshot - mirrors a bank chain pointing back to its
+    /// parent instead of `list_config_snapshots`'s flat ring buffer.
+    pub parent_id: Option<u64>,
+    /// Set by `freeze_config_snapshot`. Once `true`, the snapshot is
+    /// "rooted": `restore_config_snapshot` will only accept frozen
+    /// snapshots, so a verified-good config can't be silently restored
+    /// from an in-flux one still being compared via `diff_config_snapshots`.
+    pub frozen: bool,
 }
 
 #[contracttype]
@@ -632,6 +1799,88 @@ pub enum ConfigSnapshotKey {
     SnapshotIndex,
     SnapshotCounter,
 }
+
+/// Field-by-field comparison between two snapshots, returned by
+/// `diff_config_snapshots`. Each `Option` is `Some(to_value)` only if that
+/// field actually differs between `from_id` and `to_id`; `None` means it
+/// was unchanged.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigSnapshotDiff {
+    pub from_id: u64,
+    pub to_id: u64,
+    pub lock_fee_rate: Option<i128>,
+    pub payout_fee_rate: Option<i128>,
+    pub fee_recipient: Option<Address>,
+    pub fee_enabled: Option<bool>,
+    pub anti_abuse_window_size: Option<u64>,
+    pub anti_abuse_max_operations: Option<u32>,
+    pub anti_abuse_cooldown_period: Option<u64>,
+    /// Whether the anti-abuse admin changed between the two snapshots -
+    /// kept separate from `new_admin` since the new value may itself be
+    /// `None` (admin cleared), which would otherwise be indistinguishable
+    /// from "unchanged".
+    pub admin_changed: bool,
+    pub new_admin: Option<Address>,
+    pub is_paused: Option<bool>,
+}
+
+/// Current full-state snapshot schema. Bumped whenever a field is added
+/// to [`ProgramSnapshotEntry`]/[`FullStateSnapshot`] in a way existing
+/// exported snapshots won't carry - `import_snapshot` accepts this version
+/// or [`MIN_READABLE_SNAPSHOT_VERSION`] so a schema bump doesn't force
+/// every snapshot taken under the old shape to be re-exported immediately.
+pub const SNAPSHOT_VERSION: u32 = 2;
+/// Oldest `snapshot_version` `import_snapshot` will still rehydrate.
+/// `1` predates `ProgramFeeConfig` (chunk23-5); a v1 snapshot simply never
+/// populates `ProgramSnapshotEntry::fee_config`, so no separate wire shape
+/// is needed to read it.
+pub const MIN_READABLE_SNAPSHOT_VERSION: u32 = 1;
+
+/// One program's exportable state: everything `export_snapshot` needs to
+/// fully reconstruct it on another instance - its `ProgramData`, every
+/// release schedule (single-shot, vesting, and conditional alike, since
+/// they're all just variants of `ProgramReleaseSchedule`), and its
+/// per-program platform fee config if one was set.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProgramSnapshotEntry {
+    pub program_id: String,
+    pub program_data: ProgramData,
+    pub schedules: Vec<ProgramReleaseSchedule>,
+    pub fee_config: Option<ProgramFeeConfig>,
+}
+
+/// Full migration snapshot of contract-wide state, produced by
+/// `export_snapshot` and consumed by `import_snapshot` on a
+/// freshly-initialized instance - e.g. moving from one deployed WASM
+/// instance to another, or across a storage-schema upgrade. Covers every
+/// registered program (metadata, locked/remaining/reserved balances,
+/// pending vesting and payout schedules, per-program fee config) plus the
+/// contract-wide fee config and pause state. Doesn't cover per-schedule
+/// realizor/multisig/allowance configuration or release history - those
+/// are re-derivable or re-configurable on the new instance and would
+/// otherwise make every snapshot proportional to a program's entire
+/// lifetime rather than its current state.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FullStateSnapshot {
+    pub snapshot_version: u32,
+    pub exported_at: u64,
+    pub fee_config: FeeConfig,
+    pub pause_flags: PauseFlags,
+    pub programs: Vec<ProgramSnapshotEntry>,
+}
+
+/// Emitted by `import_snapshot`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SnapshotImportedEvent {
+    pub source_version: u32,
+    pub program_count: u32,
+    pub imported_at: u64,
+}
+
 // ==================== MONITORING MODULE ====================
 mod monitoring {
     use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Symbol};
@@ -667,6 +1916,14 @@ pub struct ProgramEscrowContract;
 // Event symbols for program release schedules
 const PROG_SCHEDULE_CREATED: soroban_sdk::Symbol = soroban_sdk::symbol_short!("prg_sch_c");
 const PROG_SCHEDULE_RELEASED: soroban_sdk::Symbol = soroban_sdk::symbol_short!("prg_sch_r");
+const PROG_SCHEDULE_CANCELLED: soroban_sdk::Symbol = soroban_sdk::symbol_short!("prg_sch_x");
+const SCHEDULE_TTL_EXTENDED: soroban_sdk::Symbol = soroban_sdk::symbol_short!("sch_ttl_x");
+const DISPUTE_OPENED: soroban_sdk::Symbol = soroban_sdk::symbol_short!("disp_opn");
+const DISPUTE_RESOLVED: soroban_sdk::Symbol = soroban_sdk::symbol_short!("disp_res");
+const SCHEDULE_REFUNDED: soroban_sdk::Symbol = soroban_sdk::symbol_short!("sch_rfnd");
+const VESTING_TERMINATED: soroban_sdk::Symbol = soroban_sdk::symbol_short!("vst_term");
+const FEATURE_ACTIVATED: soroban_sdk::Symbol = soroban_sdk::symbol_short!("feat_act");
+const SNAPSHOT_IMPORTED: soroban_sdk::Symbol = soroban_sdk::symbol_short!("snap_in");
 
 #[contractimpl]
 impl ProgramEscrowContract {
@@ -767,6 +2024,83 @@ impl ProgramEscrowContract {
         Self::is_paused_internal(&env)
     }
 
+    /// Load the granular `PauseFlags` mask, defaulting to "nothing paused".
+    fn get_pause_flags(env: &Env) -> PauseFlags {
+        env.storage()
+            .instance()
+            .get(&DataKey::PauseFlags)
+            .unwrap_or(PauseFlags {
+                mask: 0,
+                pause_reason: None,
+                paused_at: 0,
+            })
+    }
+
+    /// Whether new fund locking is currently paused.
+    pub fn is_lock_paused(env: Env) -> bool {
+        Self::get_pause_flags(&env).is_set(PAUSE_LOCK)
+    }
+
+    /// Whether payout releases are currently paused.
+    pub fn is_release_paused(env: Env) -> bool {
+        Self::get_pause_flags(&env).is_set(PAUSE_RELEASE)
+    }
+
+    /// Whether refunds are currently paused.
+    pub fn is_refund_paused(env: Env) -> bool {
+        Self::get_pause_flags(&env).is_set(PAUSE_REFUND)
+    }
+
+    /// Returns true if `operation` ("lock" | "release" | "refund") is currently
+    /// blocked. When `caller` is the stored admin, the admin may bypass an
+    /// active pause by authenticating as itself, mirroring the
+    /// `is_paused(flag) = (mask & flag) != 0 && !is_owner()` rule: this lets the
+    /// admin fix up or re-key individual escrows mid-upgrade without lifting the
+    /// pause for everyone else. Pass `None` when the entrypoint has no
+    /// authenticated caller to bypass with (e.g. permissionless callers).
+    fn check_paused(env: &Env, operation: Symbol, caller: Option<&Address>) -> bool {
+        let flag = if operation == symbol_short!("lock") {
+            PAUSE_LOCK
+        } else if operation == symbol_short!("release") {
+            PAUSE_RELEASE
+        } else {
+            PAUSE_REFUND
+        };
+
+        if !Self::get_pause_flags(env).is_set(flag) {
+            return false;
+        }
+
+        let admin: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+        match (caller, admin) {
+            (Some(caller), Some(admin)) if *caller == admin => {
+                caller.require_auth();
+                false
+            }
+            _ => true,
+        }
+    }
+
+    /// If `program_id` has a realizor configured, cross-calls its
+    /// `is_realized(program_id, recipient, amount)` and panics (after
+    /// emitting `RealizorBlockedEvent`) if it doesn't return `true`. A
+    /// no-op when no realizor is set, so existing programs are unaffected.
+    fn enforce_realizor(env: &Env, program_id: &String, recipient: &Address, amount: i128) {
+        if let Some(realizor_addr) = realizor::get_program_realizor(env, program_id) {
+            if !realizor::is_realized(env, &realizor_addr, program_id, recipient, amount) {
+                env.events().publish(
+                    (REALIZOR_BLOCKED,),
+                    RealizorBlockedEvent {
+                        program_id: program_id.clone(),
+                        recipient: recipient.clone(),
+                        amount,
+                    },
+                );
+                panic!("Realizor has not yet confirmed this payout");
+            }
+        }
+    }
+
     /// Pause the contract (authorized payout key only)
     /// Prevents new fund locking, payouts, and schedule releases
     pub fn pause(env: Env) -> () {
@@ -797,27 +2131,25 @@ impl ProgramEscrowContract {
     }
 
     /// Emergency withdrawal for all contract funds (authorized payout key only, only when paused)
-    pub fn emergency_withdraw(env: Env, program_id: String, recipient: Address) -> i128 {
+    pub fn emergency_withdraw(env: Env, program_id: String, recipient: Address) -> Result<i128, EscrowError> {
         // Only allow emergency withdrawal when contract is paused
         if !Self::is_paused_internal(&env) {
-            panic!("Contract must be paused for emergency withdrawal");
+            return Err(EscrowError::ContractNotPaused);
         }
 
         // Get program data to access token address
         let program_key = DataKey::Program(program_id.clone());
-        let program_data: ProgramData =
-            env.storage()
-                .instance()
-                .get(&program_key)
-                .unwrap_or_else(|| {
-                    panic!("Program not found");
-                });
+        let program_data: ProgramData = env
+            .storage()
+            .instance()
+            .get(&program_key)
+            .ok_or(EscrowError::ProgramNotFound)?;
 
         let client = token::Client::new(&env, &program_data.token_address);
         let balance = client.balance(&env.current_contract_address());
 
         if balance <= 0 {
-            return 0; // No funds to withdraw
+            return Ok(0); // No funds to withdraw
         }
 
         // Transfer all funds to recipient
@@ -828,7 +2160,7 @@ impl ProgramEscrowContract {
             (balance, env.ledger().timestamp()),
         );
 
-        balance
+        Ok(balance)
     }
 
     /// Initialize a new program escrow
@@ -840,7 +2172,7 @@ impl ProgramEscrowContract {
         creator: Address,
         initial_liquidity: Option<i128>,
         reference_hash: Option<soroban_sdk::Bytes>,
-    ) -> ProgramData {
+    ) -> Result<ProgramData, EscrowError> {
         Self::initialize_program(
             env,
             program_id,
@@ -860,13 +2192,16 @@ impl ProgramEscrowContract {
         creator: Address,
         initial_liquidity: Option<i128>,
         reference_hash: Option<soroban_sdk::Bytes>,
-    ) -> ProgramData {
+    ) -> Result<ProgramData, EscrowError> {
         let receipt_id = Self::increment_receipt_id(&env);
         let program_key = DataKey::Program(program_id.clone());
 
         // Check if program already exists
         if env.storage().instance().has(&program_key) {
-            panic!("Program already initialized");
+            return Err(EscrowError::ProgramAlreadyInitialized);
+        }
+        if env.storage().persistent().has(&DataKey::Tombstone(program_id.clone())) {
+            return Err(EscrowError::ProgramClosed);
         }
 
         let mut total_funds = 0i128;
@@ -891,11 +2226,15 @@ impl ProgramEscrowContract {
             program_id: program_id.clone(),
             total_funds: 0,
             remaining_balance: 0,
+            reserved_balance: 0,
             authorized_payout_key: authorized_payout_key.clone(),
             payout_history: vec![&env],
             token_address: token_address.clone(),
             initial_liquidity: init_liquidity,
             reference_hash: reference_hash.clone(),
+            dispute_count: 0,
+            refund_count: 0,
+            funder: creator.clone(),
         };
 
         // Initialize fee config with zero fees (disabled by default)
@@ -931,9 +2270,153 @@ impl ProgramEscrowContract {
             },
         );
 
+        Ok(program_data)
+    }
+
+    /// Initializes `program_id` as a new version of `parent_id` - the
+    /// "each bank points back to a parent" relationship, so a recurring
+    /// hackathon can be re-run without re-entering every setting. Inherits
+    /// `authorized_payout_key`, `token_address`, and `MultisigConfig` from
+    /// the parent (see `ProgramInitOverrides` for the per-field override /
+    /// inherit choice and why `FeeConfig` isn't one of these fields), and
+    /// records the parent link so `get_program_ancestry`/`get_lineage_stats`
+    /// can walk it. Rejects cycles the same way `path_exists_to_target`
+    /// rejects a dependency cycle, generalized via `path_exists_via` to walk
+    /// parent edges instead of dependency edges.
+    pub fn init_program_from_parent(
+        env: Env,
+        program_id: String,
+        parent_id: String,
+        overrides: ProgramInitOverrides,
+    ) -> ProgramData {
+        let program_key = DataKey::Program(program_id.clone());
+        if env.storage().instance().has(&program_key) {
+            panic!("Program already initialized");
+        }
+
+        let parent_key = DataKey::Program(parent_id.clone());
+        let parent_data: ProgramData = env
+            .storage()
+            .instance()
+            .get(&parent_key)
+            .unwrap_or_else(|| panic!("Parent program not found"));
+
+        let mut visited = Vec::new(&env);
+        if path_exists_via(&env, &parent_id, &program_id, &mut visited, get_program_parent_edges) {
+            panic!("Cyclic parent ancestry");
+        }
+
+        let authorized_payout_key = overrides
+            .authorized_payout_key
+            .unwrap_or_else(|| parent_data.authorized_payout_key.clone());
+        let token_address = overrides
+            .token_address
+            .unwrap_or_else(|| parent_data.token_address.clone());
+        let reference_hash = overrides.reference_hash.clone();
+
+        let program_data = ProgramData {
+            program_id: program_id.clone(),
+            total_funds: 0,
+            remaining_balance: 0,
+            reserved_balance: 0,
+            authorized_payout_key: authorized_payout_key.clone(),
+            payout_history: vec![&env],
+            token_address: token_address.clone(),
+            initial_liquidity: 0,
+            reference_hash: reference_hash.clone(),
+            dispute_count: 0,
+            refund_count: 0,
+            funder: authorized_payout_key.clone(),
+        };
+        env.storage().instance().set(&program_key, &program_data);
+        env.storage()
+            .instance()
+            .set(&DataKey::ProgramParent(program_id.clone()), &parent_id);
+
+        let multisig_config = overrides.multisig_config.unwrap_or_else(|| {
+            env.storage()
+                .persistent()
+                .get(&DataKey::MultisigConfig(parent_id.clone()))
+                .unwrap_or(MultisigConfig {
+                    threshold_amount: i128::MAX,
+                    signers: Vec::new(&env),
+                    required_signatures: 0,
+                })
+        });
+        env.storage().persistent().set(
+            &DataKey::MultisigConfig(program_id.clone()),
+            &multisig_config,
+        );
+
+        let receipt_id = Self::increment_receipt_id(&env);
+        env.events().publish(
+            (PROGRAM_INITIALIZED,),
+            ProgramInitializedEvent {
+                version: EVENT_VERSION_V2,
+                program_id,
+                authorized_payout_key,
+                token_address,
+                total_funds: 0,
+                reference_hash,
+                receipt_id,
+            },
+        );
+
         program_data
     }
 
+    /// Walks `program_id`'s parent links up to the root, returning the
+    /// ordered chain starting with `program_id` itself. A program with no
+    /// declared parent returns a single-element chain.
+    pub fn get_program_ancestry(env: Env, program_id: String) -> Vec<String> {
+        let mut chain = Vec::new(&env);
+        let mut current = program_id;
+        let mut visited = Vec::new(&env);
+
+        loop {
+            chain.push_back(current.clone());
+            if vec_contains(&visited, &current) {
+                break;
+            }
+            visited.push_back(current.clone());
+
+            match get_program_parent_internal(&env, &current) {
+                Some(parent_id) if env.storage().instance().has(&DataKey::Program(parent_id.clone())) => {
+                    current = parent_id;
+                }
+                _ => break,
+            }
+        }
+
+        chain
+    }
+
+    /// Aggregates `total_paid_out` and `payout_count` across `program_id`'s
+    /// whole lineage (itself plus every ancestor `get_program_ancestry`
+    /// finds) - cumulative prize distribution across every edition of a
+    /// recurring program.
+    pub fn get_lineage_stats(env: Env, program_id: String) -> LineageStats {
+        let lineage = Self::get_program_ancestry(env.clone(), program_id);
+
+        let mut total_paid_out: i128 = 0;
+        let mut payout_count: u32 = 0;
+        for id in lineage.iter() {
+            let data: Option<ProgramData> = env.storage().instance().get(&DataKey::Program(id.clone()));
+            if let Some(data) = data {
+                for record in data.payout_history.iter() {
+                    total_paid_out += record.amount;
+                }
+                payout_count += data.payout_history.len();
+            }
+        }
+
+        LineageStats {
+            lineage,
+            total_paid_out,
+            payout_count,
+        }
+    }
+
     /// Batch-initialize multiple programs in one transaction (all-or-nothing).
     pub fn batch_initialize_programs(
         env: Env,
@@ -951,10 +2434,14 @@ impl ProgramEscrowContract {
             }
         }
         for i in 0..batch_size {
-            let program_key = DataKey::Program(items.get(i).unwrap().program_id.clone());
+            let program_id = items.get(i).unwrap().program_id.clone();
+            let program_key = DataKey::Program(program_id.clone());
             if env.storage().instance().has(&program_key) {
                 return Err(BatchError::ProgramAlreadyExists);
             }
+            if env.storage().persistent().has(&DataKey::Tombstone(program_id)) {
+                return Err(BatchError::ProgramAlreadyExists);
+            }
         }
 
         let mut registry: Vec<String> = env
@@ -978,11 +2465,15 @@ impl ProgramEscrowContract {
                 program_id: program_id.clone(),
                 total_funds: 0,
                 remaining_balance: 0,
+                reserved_balance: 0,
                 authorized_payout_key: authorized_payout_key.clone(),
                 payout_history: Vec::new(&env),
                 token_address: token_address.clone(),
                 initial_liquidity: 0,
                 reference_hash: item.reference_hash.clone(),
+                dispute_count: 0,
+                refund_count: 0,
+                funder: authorized_payout_key.clone(),
             };
             let program_key = DataKey::Program(program_id.clone());
             env.storage().instance().set(&program_key, &program_data);
@@ -1095,8 +2586,22 @@ impl ProgramEscrowContract {
     /// # Returns
     /// * `bool` - True if program exists, false otherwise
     pub fn program_exists(env: Env, program_id: String) -> bool {
-        let program_key = DataKey::Program(program_id);
+        let program_key = DataKey::Program(program_id.clone());
         env.storage().instance().has(&program_key)
+            || env.storage().persistent().has(&DataKey::Tombstone(program_id))
+    }
+
+    /// Tri-state lifecycle check: `Active` while `DataKey::Program` is live,
+    /// `Closed` once `close_program` has tombstoned it, `NotFound` if the id
+    /// was never initialized at all.
+    pub fn program_status(env: Env, program_id: String) -> ProgramStatus {
+        if env.storage().instance().has(&DataKey::Program(program_id.clone())) {
+            return ProgramStatus::Active;
+        }
+        if env.storage().persistent().has(&DataKey::Tombstone(program_id)) {
+            return ProgramStatus::Closed;
+        }
+        ProgramStatus::NotFound
     }
 
     // ========================================================================
@@ -1182,20 +2687,13 @@ impl ProgramEscrowContract {
     /// -  Locking amount that exceeds actual contract balance
     /// -  Not verifying contract received the tokens
 
-    pub fn lock_program_funds(env: Env, program_id: String, amount: i128) -> ProgramData {
+    pub fn lock_program_funds(env: Env, program_id: String, amount: i128) -> Result<ProgramData, EscrowError> {
         // Apply rate limiting
         anti_abuse::check_rate_limit(&env, env.current_contract_address());
 
-        if Self::check_paused(&env, symbol_short!("lock")) {
-            panic!("Funds Paused");
-        }
-
         // Validate amount
         if amount <= 0 {
-            // `caller` is not defined here, assuming it should be the authorized_payout_key or similar
-            // For now, removing the monitoring call as it would cause a compile error.
-            // monitoring::track_operation(&env, symbol_short!("lock"), caller.clone(), false);
-            panic!("Amount must be greater than zero");
+            return Err(EscrowError::NonPositiveAmount);
         }
 
         let program_key = DataKey::Program(program_id.clone());
@@ -1203,7 +2701,17 @@ impl ProgramEscrowContract {
             .storage()
             .instance()
             .get(&program_key)
-            .unwrap_or_else(|| panic!("Program not initialized"));
+            .ok_or(EscrowError::ProgramNotFound)?;
+
+        if Self::check_paused(
+            &env,
+            symbol_short!("lock"),
+            Some(&program_data.authorized_payout_key),
+        ) {
+            return Err(EscrowError::OperationPaused);
+        }
+
+        assert_program_open(&env, &program_id);
 
         // Require the authorized payout key or creator
         program_data.authorized_payout_key.require_auth();
@@ -1226,18 +2734,17 @@ impl ProgramEscrowContract {
 
         let receipt_id = Self::increment_receipt_id(&env);
 
-        // Emit fee collected event if applicable
+        // Record the itemized fee ledger entry and emit FeeCollected, if applicable
         if fee_amount > 0 {
-            env.events().publish(
-                (FEE_COLLECTED,),
-                FeeCollectedEvent {
-                    version: 2, // Changed from EVENT_VERSION_V2
-                    program_id: program_data.program_id.clone(),
-                    fee_type: symbol_short!("lock"),
-                    amount: fee_amount,
-                    recipient: fee_config.fee_recipient.clone(),
-                    receipt_id,
-                },
+            record_fee_ledger_entry(
+                &env,
+                &program_id,
+                FeeKind::Lock,
+                amount,
+                fee_amount,
+                fee_config.lock_fee_rate,
+                &fee_config.fee_recipient,
+                receipt_id,
             );
         }
 
@@ -1253,7 +2760,7 @@ impl ProgramEscrowContract {
             },
         );
 
-        program_data
+        Ok(program_data)
     }
 
     // ========================================================================
@@ -1313,7 +2820,7 @@ impl ProgramEscrowContract {
         }
 
         if let Some(paused) = lock {
-            flags.lock_paused = paused;
+            flags.set_flag(PAUSE_LOCK, paused);
             let receipt_id = Self::increment_receipt_id(&env);
             env.events().publish(
                 (PAUSE_STATE_CHANGED,),
@@ -1329,7 +2836,7 @@ impl ProgramEscrowContract {
         }
 
         if let Some(paused) = release {
-            flags.release_paused = paused;
+            flags.set_flag(PAUSE_RELEASE, paused);
             let receipt_id = Self::increment_receipt_id(&env);
             env.events().publish(
                 (PAUSE_STATE_CHANGED,),
@@ -1345,7 +2852,7 @@ impl ProgramEscrowContract {
         }
 
         if let Some(paused) = refund {
-            flags.refund_paused = paused;
+            flags.set_flag(PAUSE_REFUND, paused);
             let receipt_id = Self::increment_receipt_id(&env);
             env.events().publish(
                 (PAUSE_STATE_CHANGED,),
@@ -1360,15 +2867,17 @@ impl ProgramEscrowContract {
             );
         }
 
-        let any_paused = flags.lock_paused || flags.release_paused || flags.refund_paused;
+        let any_paused = flags.any_paused();
 
         if any_paused {
             if flags.paused_at == 0 {
                 flags.paused_at = timestamp;
             }
         } else {
-            0
-        };
+            flags.paused_at = 0;
+        }
+
+        env.storage().instance().set(&DataKey::PauseFlags, &flags);
         let net_amount = amount - fee_amount;
 
         // Update balances with net amount
@@ -1442,16 +2951,20 @@ impl ProgramEscrowContract {
         program_id: String,
         recipients: Vec<Address>,
         amounts: Vec<i128>,
+        request_id: Option<String>,
     ) -> ProgramData {
+        // Replay protection: a request_id seen within the idempotency
+        // window returns the original outcome instead of paying again.
+        if let Some(ref id) = request_id {
+            if let Some(cached) = idempotency::lookup(&env, &program_id, id) {
+                return cached;
+            }
+        }
+
         // Reentrancy guard: Check and set
         reentrancy_guard::check_not_entered(&env);
         reentrancy_guard::set_entered(&env);
 
-        if Self::check_paused(&env, symbol_short!("release")) {
-            reentrancy_guard::clear_entered(&env);
-            panic!("Funds Paused");
-        }
-
         // Verify authorization
         let program_key = DataKey::Program(program_id.clone());
         let program_data: ProgramData =
@@ -1463,6 +2976,17 @@ impl ProgramEscrowContract {
                     panic!("Program not found")
                 });
 
+        if Self::check_paused(
+            &env,
+            symbol_short!("release"),
+            Some(&program_data.authorized_payout_key),
+        ) {
+            reentrancy_guard::clear_entered(&env);
+            panic!("Funds Paused");
+        }
+
+        assert_program_open(&env, &program_id);
+
         Self::assert_dependencies_satisfied(&env, &program_data.program_id);
 
         // Apply rate limiting to the authorized payout key
@@ -1513,6 +3037,9 @@ impl ProgramEscrowContract {
             let recipient = recipients.get(i).unwrap();
             let amount = amounts.get(i).unwrap();
 
+            // Block on the program's realizor, if one is configured
+            Self::enforce_realizor(&env, &program_id, &recipient, amount);
+
             // Calculate fee for this payout
             let fee_amount = if fee_config.fee_enabled && fee_config.payout_fee_rate > 0 {
                 Self::calculate_fee(amount, fee_config.payout_fee_rate)
@@ -1528,6 +3055,17 @@ impl ProgramEscrowContract {
             // Transfer fee to fee recipient if applicable
             if fee_amount > 0 {
                 token_client.transfer(&contract_address, &fee_config.fee_recipient, &fee_amount);
+                let fee_receipt_id = Self::increment_receipt_id(&env);
+                record_fee_ledger_entry(
+                    &env,
+                    &program_id,
+                    FeeKind::BatchPayout,
+                    amount,
+                    fee_amount,
+                    fee_config.payout_fee_rate,
+                    &fee_config.fee_recipient,
+                    fee_receipt_id,
+                );
             }
 
             // Record payout (with net amount)
@@ -1578,6 +3116,10 @@ impl ProgramEscrowContract {
             },
         );
 
+        if let Some(ref id) = request_id {
+            idempotency::record(&env, &program_id, id, &updated_data);
+        }
+
         updated_data
     }
 
@@ -1587,6 +3129,9 @@ impl ProgramEscrowContract {
     /// * `env` - The contract environment
     /// * `recipient` - Address of the prize recipient
     /// * `amount` - Amount to transfer (in token's smallest denomination)
+    /// * `request_id` - Optional idempotency key; replaying the same key
+    ///   within the program's idempotency window returns the original
+    ///   `ProgramData` instead of transferring again - see `idempotency`.
     ///
     /// # Returns
     /// * `ProgramData` - Updated program data after payout
@@ -1639,7 +3184,16 @@ impl ProgramEscrowContract {
         program_id: String,
         recipient: Address,
         amount: i128,
+        request_id: Option<String>,
     ) -> ProgramData {
+        // Replay protection: a request_id seen within the idempotency
+        // window returns the original outcome instead of paying again.
+        if let Some(ref id) = request_id {
+            if let Some(cached) = idempotency::lookup(&env, &program_id, id) {
+                return cached;
+            }
+        }
+
         // Check if contract is paused
         if Self::is_paused_internal(&env) {
             panic!("Contract is paused");
@@ -1656,11 +3210,17 @@ impl ProgramEscrowContract {
         reentrancy_guard::check_not_entered(&env);
         reentrancy_guard::set_entered(&env);
 
-        if Self::check_paused(&env, symbol_short!("release")) {
+        if Self::check_paused(
+            &env,
+            symbol_short!("release"),
+            Some(&program_data.authorized_payout_key),
+        ) {
             reentrancy_guard::clear_entered(&env);
             panic!("Funds Paused");
         }
 
+        assert_program_open(&env, &program_id);
+
         Self::assert_dependencies_satisfied(&env, &program_id);
 
         program_data.authorized_payout_key.require_auth();
@@ -1692,6 +3252,9 @@ impl ProgramEscrowContract {
             );
         }
 
+        // Block on the program's realizor, if one is configured
+        Self::enforce_realizor(&env, &program_id, &recipient, amount);
+
         // Calculate and collect fee if enabled
         let fee_config = Self::get_fee_config_internal(&env);
         let fee_amount = if fee_config.fee_enabled && fee_config.payout_fee_rate > 0 {
@@ -1719,6 +3282,17 @@ impl ProgramEscrowContract {
                     fee_config.fee_recipient.clone(),
                 ),
             );
+            let fee_receipt_id = Self::increment_receipt_id(&env);
+            record_fee_ledger_entry(
+                &env,
+                &program_id,
+                FeeKind::Payout,
+                amount,
+                fee_amount,
+                fee_config.payout_fee_rate,
+                &fee_config.fee_recipient,
+                fee_receipt_id,
+            );
         }
 
         // Record payout (with net amount after fee)
@@ -1742,6 +3316,10 @@ impl ProgramEscrowContract {
 
         let receipt_id = Self::increment_receipt_id(&env);
 
+        if let Some(ref id) = request_id {
+            idempotency::record(&env, &program_id, id, &updated_data);
+        }
+
         // Emit Payout event (with net amount after fee)
         // Emit event
             env.events().publish(
@@ -1760,389 +3338,3627 @@ impl ProgramEscrowContract {
     }
 
     // ========================================================================
-    // Release Schedule Functions
+    // Conditional Payout Plans
     // ========================================================================
 
-    /// Creates a time-based release schedule for a program.
-    ///
-    /// # Arguments
-    /// * `env` - The contract environment
-    /// * `program_id` - The program to create schedule for
-    /// * `amount` - Amount to release (in token's smallest denomination)
-    /// * `release_timestamp` - Unix timestamp when funds become available
-    /// * `recipient` - Address that will receive the funds
-    ///
-    /// # Returns
-    /// * `ProgramData` - Updated program data
-    ///
-    /// # Panics
-    /// * If program is not initialized
-    /// * If caller is not authorized payout key
-    /// * If amount is invalid
-    /// * If timestamp is in the past
-    /// * If amount exceeds remaining balance
-    ///
-    /// # State Changes
-    /// - Creates ProgramReleaseSchedule record
-    /// - Updates next schedule ID
-    /// - Emits ScheduleCreated event
-    ///
-    /// # Authorization
-    /// - Only authorized payout key can call this function
-    ///
-    /// # Example
-    /// ```rust
-    /// let now = env.ledger().timestamp();
-    /// let release_time = now + (30 * 24 * 60 * 60); // 30 days from now
-    /// escrow_client.create_program_release_schedule(
-    ///     &"Hackathon2024",
-    ///     &500_0000000, // 500 tokens
-    ///     &release_time,
-    ///     &winner_address
-    /// );
-    /// ```
-    pub fn create_program_release_schedule(
-        env: Env,
-        program_id: String,
-        amount: i128,
-        release_timestamp: u64,
-        recipient: Address,
-    ) -> ProgramData {
-        let start = env.ledger().timestamp();
-
-        // Check if contract is paused
-        if Self::is_paused_internal(&env) {
-            panic!("Contract is paused");
+    /// Reserves a conditional `Budget` plan against `program_id`'s
+    /// `remaining_balance` and stores it for `apply_witness` to progress.
+    /// `nodes` is the plan's arena (see `BudgetNode`); `nodes[0]` is the
+    /// root. Reserves `budget_total(&nodes, 0)` up front so the funds can't
+    /// be double-committed to a manual payout while the plan is pending.
+    /// Gated like `lock_program_funds`: the authorized payout key, and only
+    /// while the program is `Open`.
+    pub fn create_conditional_payout(env: Env, program_id: String, nodes: Vec<BudgetNode>) -> u64 {
+        if nodes.is_empty() {
+            panic!("Budget plan must have at least one node");
         }
 
-        // Get program data
         let program_key = DataKey::Program(program_id.clone());
-        let program_data: ProgramData = env
+        let mut program_data: ProgramData = env
             .storage()
             .instance()
             .get(&program_key)
             .unwrap_or_else(|| panic!("Program not found"));
 
-        // Apply rate limiting to the authorized payout key
-        anti_abuse::check_rate_limit(&env, program_data.authorized_payout_key.clone());
+        assert_program_open(&env, &program_id);
 
-        // Verify authorization
         program_data.authorized_payout_key.require_auth();
 
-        // Validate amount
-        if amount <= 0 {
-            panic!("Amount must be greater than zero");
+        let total = budget_total(&nodes, 0);
+        if total <= 0 {
+            panic!("Budget plan must reserve a positive amount");
         }
-
-        // Validate timestamp
-        if release_timestamp <= env.ledger().timestamp() {
-            panic!("Release timestamp must be in the future");
+        if total > program_data.remaining_balance {
+            panic!("Insufficient balance to reserve conditional payout plan");
         }
 
-        // Check sufficient remaining balance
-        let scheduled_total = get_program_total_scheduled_amount(&env, &program_id);
-        if scheduled_total + amount > program_data.remaining_balance {
-            panic!("Insufficient balance for scheduled amount");
-        }
+        program_data.remaining_balance -= total;
+        env.storage().instance().set(&program_key, &program_data);
 
-        // Get next schedule ID
-        let schedule_id: u64 = env
+        let plan_id: u64 = env
             .storage()
             .persistent()
-            .get(&DataKey::NextScheduleId(program_id.clone()))
+            .get(&DataKey::NextPlanId(program_id.clone()))
             .unwrap_or(1);
+        env.storage()
+            .persistent()
+            .set(&DataKey::NextPlanId(program_id.clone()), &(plan_id + 1));
 
-        // Create release schedule
-        let schedule = ProgramReleaseSchedule {
-            schedule_id,
-            amount,
-            release_timestamp,
-            recipient: recipient.clone(),
-            released: false,
-            released_at: None,
-            released_by: None,
+        let plan = ConditionalPlan {
+            plan_id,
+            program_id: program_id.clone(),
+            nodes,
+            total_reserved: total,
+            executed: false,
+            cancelled: false,
         };
-    /// Create a release schedule entry that can be triggered at/after `release_timestamp`.
-    pub fn create_program_release_schedule(
-        env: Env,
-        recipient: Address,
-        amount: i128,
-        release_timestamp: u64,
-    ) -> ProgramReleaseSchedule {
-        let program_data: ProgramData = env
-            .storage()
-            .instance()
-            .get(&PROGRAM_DATA)
-            .unwrap_or_else(|| panic!("Program not initialized"));
+        env.storage()
+            .persistent()
+            .set(&DataKey::ConditionalPlan(program_id, plan_id), &plan);
 
-        program_data.authorized_payout_key.require_auth();
+        plan_id
+    }
 
-        if amount <= 0 {
-            panic!("Amount must be greater than zero");
+    /// Records that `witness` satisfies any `Condition::Signature(witness)`
+    /// in `plan_id`'s tree (idempotent - witnessing twice is a no-op), then
+    /// re-resolves the plan against every condition satisfied so far
+    /// (`Condition::Timestamp` is checked live against the ledger clock, so
+    /// a purely time-gated plan can be progressed by any caller). If the
+    /// plan now collapses all the way to a `Pay` leaf, executes the
+    /// transfer, marks the plan executed (a plan can only ever fire once),
+    /// and emits `ConditionalPayoutExecuted`.
+    pub fn apply_witness(env: Env, program_id: String, plan_id: u64, witness: Address) -> ConditionalPlan {
+        witness.require_auth();
+
+        let plan_key = DataKey::ConditionalPlan(program_id.clone(), plan_id);
+        let mut plan: ConditionalPlan = env
+            .storage()
+            .persistent()
+            .get(&plan_key)
+            .unwrap_or_else(|| panic!("Conditional payout plan not found"));
+
+        if plan.executed {
+            panic!("Conditional payout plan already executed");
+        }
+        if plan.cancelled {
+            panic!("Conditional payout plan was cancelled");
         }
 
-        let mut schedules: Vec<ProgramReleaseSchedule> = env
-            .storage()
-            .instance()
-            .get(&SCHEDULES)
-            .unwrap_or_else(|| Vec::new(&env));
-        let schedule_id: u64 = env
+        let witnesses_key = DataKey::PlanWitnesses(program_id.clone(), plan_id);
+        let mut witnesses: Vec<Address> = env
             .storage()
-            .instance()
-            .get(&NEXT_SCHEDULE_ID)
-            .unwrap_or(1_u64);
-
-        let schedule = ProgramReleaseSchedule {
-            schedule_id,
-            recipient,
-            amount,
-            release_timestamp,
-            released: false,
-            released_at: None,
-            released_by: None,
-        };
-        schedules.push_back(schedule.clone());
+            .persistent()
+            .get(&witnesses_key)
+            .unwrap_or(vec![&env]);
+        if !vec_contains_address(&witnesses, &witness) {
+            witnesses.push_back(witness.clone());
+            env.storage().persistent().set(&witnesses_key, &witnesses);
+        }
 
-        env.storage().instance().set(&SCHEDULES, &schedules);
+        if let Some(payment) = evaluate_budget(&env, &plan.nodes, 0, &witnesses) {
+            let program_key = DataKey::Program(program_id.clone());
+            let program_data: ProgramData = env
+                .storage()
+                .instance()
+                .get(&program_key)
+                .unwrap_or_else(|| panic!("Program not found"));
+
+            let contract_address = env.current_contract_address();
+            let token_client = token::Client::new(&env, &program_data.token_address);
+            token_client.transfer(&contract_address, &payment.to, &payment.amount);
+
+            plan.executed = true;
+            env.storage().persistent().set(&plan_key, &plan);
+
+            let receipt_id = Self::increment_receipt_id(&env);
+            env.events().publish(
+                (CONDITIONAL_PAYOUT_EXECUTED,),
+                ConditionalPayoutExecutedEvent {
+                    program_id,
+                    plan_id,
+                    recipient: payment.to,
+                    amount: payment.amount,
+                    receipt_id,
+                },
+            );
+        }
+
+        plan
+    }
+
+    /// Cancels a not-yet-executed plan and returns its reserved funds to
+    /// `remaining_balance`. Like `emergency_withdraw`, only callable while
+    /// the contract is paused, and only by the program's authorized payout
+    /// key.
+    pub fn cancel_conditional_payout(env: Env, program_id: String, plan_id: u64) {
+        if !Self::is_paused_internal(&env) {
+            panic!("Contract must be paused to cancel a conditional payout plan");
+        }
+
+        let program_key = DataKey::Program(program_id.clone());
+        let mut program_data: ProgramData = env
+            .storage()
+            .instance()
+            .get(&program_key)
+            .unwrap_or_else(|| panic!("Program not found"));
+
+        program_data.authorized_payout_key.require_auth();
+
+        let plan_key = DataKey::ConditionalPlan(program_id.clone(), plan_id);
+        let mut plan: ConditionalPlan = env
+            .storage()
+            .persistent()
+            .get(&plan_key)
+            .unwrap_or_else(|| panic!("Conditional payout plan not found"));
+
+        if plan.executed {
+            panic!("Conditional payout plan already executed");
+        }
+        if plan.cancelled {
+            panic!("Conditional payout plan already cancelled");
+        }
+
+        plan.cancelled = true;
+        env.storage().persistent().set(&plan_key, &plan);
+
+        program_data.remaining_balance += plan.total_reserved;
+        env.storage().instance().set(&program_key, &program_data);
+    }
+
+    /// Read-only lookup of a conditional payout plan's current state.
+    pub fn get_conditional_plan(env: Env, program_id: String, plan_id: u64) -> ConditionalPlan {
         env.storage()
+            .persistent()
+            .get(&DataKey::ConditionalPlan(program_id, plan_id))
+            .unwrap_or_else(|| panic!("Conditional payout plan not found"))
+    }
+
+    // ========================================================================
+    // Conditional Payouts (boolean condition trees)
+    // ========================================================================
+
+    /// Arms a conditional payout: `amount` to `recipient` once `nodes`'s
+    /// root (index `0`) evaluates to satisfied under `claim_conditional_payout`.
+    /// Reserves `amount` against `remaining_balance` immediately, the same
+    /// way `create_conditional_payout` reserves a `Budget` plan's total, so
+    /// the funds can't be double-committed to a manual payout while the
+    /// conditions are still pending. Gated like `single_payout`: authorized
+    /// payout key, program must be `Open`.
+    pub fn arm_conditional_payout(
+        env: Env,
+        program_id: String,
+        recipient: Address,
+        amount: i128,
+        nodes: Vec<ConditionNode>,
+    ) -> u64 {
+        if nodes.is_empty() {
+            panic!("Condition tree must have at least one node");
+        }
+        if amount <= 0 {
+            panic!("Amount must be greater than zero");
+        }
+
+        let program_key = DataKey::Program(program_id.clone());
+        let mut program_data: ProgramData = env
+            .storage()
             .instance()
-            .set(&NEXT_SCHEDULE_ID, &(schedule_id + 1));
+            .get(&program_key)
+            .unwrap_or_else(|| panic!("Program not found"));
+
+        assert_program_open(&env, &program_id);
+
+        anti_abuse::check_rate_limit(&env, program_data.authorized_payout_key.clone());
+        program_data.authorized_payout_key.require_auth();
+
+        if amount > program_data.remaining_balance {
+            panic!("Insufficient balance for conditional payout");
+        }
+
+        let payout_id: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::NextConditionalPayoutId(program_id.clone()))
+            .unwrap_or(1);
+
+        let payout = ConditionalPayout {
+            payout_id,
+            program_id: program_id.clone(),
+            recipient,
+            amount,
+            nodes,
+            claimed: false,
+        };
+
+        program_data.remaining_balance -= amount;
+        env.storage().instance().set(&program_key, &program_data);
+
+        env.storage().persistent().set(
+            &DataKey::ConditionalPayout(program_id.clone(), payout_id),
+            &payout,
+        );
+        env.storage().persistent().set(
+            &DataKey::NextConditionalPayoutId(program_id.clone()),
+            &(payout_id + 1),
+        );
+
+        payout_id
+    }
+
+    /// Evaluates `payout_id`'s condition tree and, if fully satisfied,
+    /// performs the fee-adjusted transfer used by `single_payout`. The
+    /// amount was already reserved out of `remaining_balance` by
+    /// `arm_conditional_payout`, so no further balance deduction happens
+    /// here.
+    pub fn claim_conditional_payout(env: Env, program_id: String, payout_id: u64) -> ProgramData {
+        if Self::is_paused_internal(&env) {
+            panic!("Contract is paused");
+        }
+
+        let payout_key = DataKey::ConditionalPayout(program_id.clone(), payout_id);
+        let mut payout: ConditionalPayout = env
+            .storage()
+            .persistent()
+            .get(&payout_key)
+            .unwrap_or_else(|| panic!("Conditional payout not found"));
+
+        if payout.claimed {
+            panic!("Conditional payout already claimed");
+        }
+
+        if !evaluate_condition_node(&env, &payout.nodes, 0) {
+            panic!("Conditions not yet satisfied");
+        }
+
+        let program_key = DataKey::Program(program_id.clone());
+        let program_data: ProgramData = env
+            .storage()
+            .instance()
+            .get(&program_key)
+            .unwrap_or_else(|| panic!("Program not found"));
+
+        // Calculate and collect fee if enabled, same as single_payout
+        let fee_config = Self::get_fee_config_internal(&env);
+        let fee_amount = if fee_config.fee_enabled && fee_config.payout_fee_rate > 0 {
+            Self::calculate_fee(payout.amount, fee_config.payout_fee_rate)
+        } else {
+            0
+        };
+        let net_amount = payout.amount - fee_amount;
+
+        let contract_address = env.current_contract_address();
+        let token_client = token::Client::new(&env, &program_data.token_address);
+        token_client.transfer(&contract_address, &payout.recipient, &net_amount);
+
+        if fee_amount > 0 {
+            token_client.transfer(&contract_address, &fee_config.fee_recipient, &fee_amount);
+            let fee_receipt_id = Self::increment_receipt_id(&env);
+            record_fee_ledger_entry(
+                &env,
+                &program_id,
+                FeeKind::Payout,
+                payout.amount,
+                fee_amount,
+                fee_config.payout_fee_rate,
+                &fee_config.fee_recipient,
+                fee_receipt_id,
+            );
+        }
+
+        payout.claimed = true;
+        env.storage().persistent().set(&payout_key, &payout);
 
         let receipt_id = Self::increment_receipt_id(&env);
         env.events().publish(
-            (symbol_short!("sch_cred"),),
-            ScheduleCreatedEvent {
-                program_id: program_data.program_id.clone(),
-                schedule_id,
-                recipient: schedule.recipient.clone(),
-                amount,
-                release_timestamp,
+            (PAYOUT,),
+            PayoutEvent {
+                version: EVENT_VERSION_V2,
+                program_id: program_id.clone(),
+                recipient: payout.recipient.clone(),
+                amount: net_amount,
+                remaining_balance: program_data.remaining_balance,
                 receipt_id,
             },
         );
-        schedule
+
+        program_data
     }
 
-        // Store schedule
-        env.storage().persistent().set(
-            &DataKey::ReleaseSchedule(program_id.clone(), schedule_id),
-            &schedule,
-        );
+    /// Read-only lookup of a conditional payout by id.
+    pub fn get_conditional_payout(env: Env, program_id: String, payout_id: u64) -> ConditionalPayout {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ConditionalPayout(program_id, payout_id))
+            .unwrap_or_else(|| panic!("Conditional payout not found"))
+    }
 
-        // Update next schedule ID
-        env.storage().persistent().set(
-            &DataKey::NextScheduleId(program_id.clone()),
-            &(schedule_id + 1),
-        );
+    // ========================================================================
+    // Multisig Payout Approvals
+    // ========================================================================
 
-        // Emit program schedule created event
-        env.events().publish(
-            (PROG_SCHEDULE_CREATED,),
-            ProgramScheduleCreated {
+    /// Records `signer`'s approval of a `(recipient, amount)` payout bound
+    /// to the program's current `approval_nonce`, executing the transfer
+    /// once `required_signatures` is reached.
+    ///
+    /// `signer` must authorize `require_auth_for_args` over exactly
+    /// `(program_id, approval_nonce, recipient, amount)` - Soroban's
+    /// authorization envelope already binds the invoking contract address
+    /// and `env.ledger().network_id()` underneath that, so pinning these
+    /// four fields on top is what stops a collected signature from being
+    /// replayed against a different payout or resubmitted once the nonce
+    /// has moved on. The first approval for a nonce fixes its
+    /// `recipient`/`amount`; every later approval for that same nonce must
+    /// match exactly or it's rejected. `approval_nonce` only advances once
+    /// the threshold executes, so there is at most one pending approval
+    /// per program at a time.
+    pub fn approve_payout(
+        env: Env,
+        program_id: String,
+        recipient: Address,
+        amount: i128,
+        signer: Address,
+    ) -> PendingPayoutApproval {
+        let config: MultisigConfig = env
+            .storage()
+            .instance()
+            .get(&DataKey::MultisigConfig(program_id.clone()))
+            .unwrap_or_else(|| panic!("Multisig not configured for program"));
+
+        if !vec_contains_address(&config.signers, &signer) {
+            panic!("Not an authorized multisig signer");
+        }
+
+        let nonce = get_approval_nonce_internal(&env, &program_id);
+
+        let bound_args: Vec<Val> = vec![
+            &env,
+            program_id.clone().into_val(&env),
+            nonce.into_val(&env),
+            recipient.clone().into_val(&env),
+            amount.into_val(&env),
+        ];
+        signer.require_auth_for_args(bound_args);
+
+        let approval_key = DataKey::PendingPayoutApproval(program_id.clone(), nonce);
+        let mut approval: PendingPayoutApproval =
+            env.storage().persistent().get(&approval_key).unwrap_or(PendingPayoutApproval {
                 program_id: program_id.clone(),
-                schedule_id,
-                amount,
-                release_timestamp,
+                approval_nonce: nonce,
                 recipient: recipient.clone(),
-                created_by: program_data.authorized_payout_key.clone(),
+                amount,
+                approvals: Vec::new(&env),
+            });
+
+        if approval.recipient != recipient || approval.amount != amount {
+            panic!("Approval does not match this nonce's bound recipient/amount");
+        }
+
+        if !vec_contains_address(&approval.approvals, &signer) {
+            approval.approvals.push_back(signer);
+        }
+
+        if config.required_signatures == 0 || approval.approvals.len() < config.required_signatures {
+            env.storage().persistent().set(&approval_key, &approval);
+            return approval;
+        }
+
+        let program_key = DataKey::Program(program_id.clone());
+        let mut program_data: ProgramData = env
+            .storage()
+            .instance()
+            .get(&program_key)
+            .unwrap_or_else(|| panic!("Program not found"));
+
+        assert_program_open(&env, &program_id);
+
+        if amount > program_data.remaining_balance {
+            panic!("Insufficient remaining balance for multisig payout");
+        }
+
+        program_data.remaining_balance -= amount;
+        env.storage().instance().set(&program_key, &program_data);
+
+        let token_client = token::Client::new(&env, &program_data.token_address);
+        token_client.transfer(&env.current_contract_address(), &recipient, &amount);
+
+        env.storage().persistent().remove(&approval_key);
+        env.storage()
+            .instance()
+            .set(&DataKey::ApprovalNonce(program_id.clone()), &(nonce + 1));
+
+        let receipt_id = Self::increment_receipt_id(&env);
+        env.events().publish(
+            (MULTISIG_PAYOUT_EXECUTED,),
+            MultisigPayoutExecutedEvent {
+                program_id,
+                approval_nonce: nonce,
+                recipient,
+                amount,
+                receipt_id,
             },
         );
 
-        // Track successful operation
-        monitoring::track_operation(
+        approval
+    }
+
+    /// The `approval_nonce` the next `approve_payout` call must bind to -
+    /// off-chain signers should watch `MultisigPayoutExecutedEvent` and
+    /// call this to know what to sign next.
+    pub fn get_approval_nonce(env: Env, program_id: String) -> u64 {
+        get_approval_nonce_internal(&env, &program_id)
+    }
+
+    // ========================================================================
+    // Realizor Gating
+    // ========================================================================
+
+    /// Sets (or clears, with `None`) the realizor gating `program_id`'s
+    /// `single_payout`/`batch_payout`/`release_prog_schedule_automatic`
+    /// calls. See [`realizor`] for the cross-contract interface a realizor
+    /// must implement. Authorized payout key only.
+    pub fn set_program_realizor(env: Env, program_id: String, realizor_addr: Option<Address>) {
+        let program_key = DataKey::Program(program_id.clone());
+        let program_data: ProgramData = env
+            .storage()
+            .instance()
+            .get(&program_key)
+            .unwrap_or_else(|| panic!("Program not found"));
+
+        realizor::set_program_realizor(
             &env,
-            symbol_short!("create_p"),
-            program_data.authorized_payout_key,
-            true,
+            &program_id,
+            &program_data.authorized_payout_key,
+            realizor_addr,
         );
+    }
 
-        // Track performance
-        let duration = env.ledger().timestamp().saturating_sub(start);
-        monitoring::emit_performance(&env, symbol_short!("create_p"), duration);
-
-        // Return updated program data
-        let updated_data: ProgramData = env.storage().instance().get(&program_key).unwrap();
-        updated_data
+    /// The realizor currently gating `program_id`'s payouts, if any.
+    pub fn get_program_realizor(env: Env, program_id: String) -> Option<Address> {
+        realizor::get_program_realizor(&env, &program_id)
     }
 
-    /// Automatically releases funds for program schedules that are due.
-    /// Can be called by anyone after the release timestamp has passed.
+    // ========================================================================
+    // Release Schedule Functions
+    // ========================================================================
+
+    /// Creates a time-based release schedule for a program.
     ///
     /// # Arguments
     /// * `env` - The contract environment
-    /// * `program_id` - The program to check for due schedules
-    /// * `schedule_id` - The specific schedule to release
+    /// * `program_id` - The program to create schedule for
+    /// * `amount` - Amount to release (in token's smallest denomination)
+    /// * `release_timestamp` - Unix timestamp when funds become available
+    /// * `recipient` - Address that will receive the funds
+    ///
+    /// # Returns
+    /// * `ProgramData` - Updated program data
     ///
     /// # Panics
-    /// * If program doesn't exist
-    /// * If schedule doesn't exist
-    /// * If schedule is already released
-    /// * If schedule is not yet due
+    /// * If program is not initialized
+    /// * If caller is not authorized payout key
+    /// * If amount is invalid
+    /// * If timestamp is in the past
+    /// * If amount exceeds remaining balance
     ///
     /// # State Changes
-    /// - Transfers tokens to recipient
-    /// - Updates schedule status to released
-    /// - Adds to release history
-    /// - Updates program remaining balance
-    /// - Emits ScheduleReleased event
+    /// - Creates ProgramReleaseSchedule record
+    /// - Updates next schedule ID
+    /// - Emits ScheduleCreated event
+    ///
+    /// # Authorization
+    /// - Only authorized payout key can call this function
     ///
     /// # Example
     /// ```rust
-    /// // Anyone can call this after the timestamp
-    /// escrow_client.release_program_schedule_automatic(&"Hackathon2024", &1);
+    /// let now = env.ledger().timestamp();
+    /// let release_time = now + (30 * 24 * 60 * 60); // 30 days from now
+    /// escrow_client.create_program_release_schedule(
+    ///     &"Hackathon2024",
+    ///     &500_0000000, // 500 tokens
+    ///     &release_time,
+    ///     &winner_address
+    /// );
     /// ```
-    pub fn release_prog_schedule_automatic(env: Env, program_id: String, schedule_id: u64) {
+    pub fn create_program_release_schedule(
+        env: Env,
+        program_id: String,
+        amount: i128,
+        release_timestamp: u64,
+        recipient: Address,
+    ) -> ProgramData {
         let start = env.ledger().timestamp();
 
-        // Check if contract is paused
-        if Self::check_paused(&env, symbol_short!("release")) {
-            panic!("Funds Paused");
-        }
+        // Check if contract is paused
+        if Self::is_paused_internal(&env) {
+            panic!("Contract is paused");
+        }
+
+        // Get program data
+        let program_key = DataKey::Program(program_id.clone());
+        let mut program_data: ProgramData = env
+            .storage()
+            .instance()
+            .get(&program_key)
+            .unwrap_or_else(|| panic!("Program not found"));
+
+        assert_program_open(&env, &program_id);
+
+        // Apply rate limiting to the authorized payout key
+        anti_abuse::check_rate_limit(&env, program_data.authorized_payout_key.clone());
+
+        // Verify authorization
+        program_data.authorized_payout_key.require_auth();
+
+        // Validate amount
+        if amount <= 0 {
+            panic!("Amount must be greater than zero");
+        }
+
+        // Validate timestamp
+        if release_timestamp <= env.ledger().timestamp() {
+            panic!("Release timestamp must be in the future");
+        }
+
+        // Check sufficient unreserved balance, then reserve this schedule's
+        // amount so a concurrently created schedule (or conditional payout,
+        // which already reserves by debiting `remaining_balance` directly)
+        // can't collectively overdraw the escrow.
+        if amount > program_data.remaining_balance - program_data.reserved_balance {
+            panic!("Insufficient balance for scheduled amount");
+        }
+        program_data.reserved_balance += amount;
+        env.storage().instance().set(&program_key, &program_data);
+
+        // Get next schedule ID
+        let schedule_id: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::NextScheduleId(program_id.clone()))
+            .unwrap_or(1);
+
+        // Create release schedule
+        let schedule = ProgramReleaseSchedule {
+            schedule_id,
+            amount,
+            release_timestamp,
+            recipient: recipient.clone(),
+            released: false,
+            released_at: None,
+            released_by: None,
+            vesting: None,
+            cancelled: false,
+            realizor: None,
+            disputed: false,
+            dispute_reason: None,
+            plan: None,
+        };
+    /// Create a release schedule entry that can be triggered at/after `release_timestamp`.
+    pub fn create_program_release_schedule(
+        env: Env,
+        recipient: Address,
+        amount: i128,
+        release_timestamp: u64,
+    ) -> ProgramReleaseSchedule {
+        let program_data: ProgramData = env
+            .storage()
+            .instance()
+            .get(&PROGRAM_DATA)
+            .unwrap_or_else(|| panic!("Program not initialized"));
+
+        program_data.authorized_payout_key.require_auth();
+
+        if amount <= 0 {
+            panic!("Amount must be greater than zero");
+        }
+
+        let mut schedules: Vec<ProgramReleaseSchedule> = env
+            .storage()
+            .instance()
+            .get(&SCHEDULES)
+            .unwrap_or_else(|| Vec::new(&env));
+        let schedule_id: u64 = env
+            .storage()
+            .instance()
+            .get(&NEXT_SCHEDULE_ID)
+            .unwrap_or(1_u64);
+
+        let schedule = ProgramReleaseSchedule {
+            schedule_id,
+            recipient,
+            amount,
+            release_timestamp,
+            released: false,
+            released_at: None,
+            released_by: None,
+            vesting: None,
+            cancelled: false,
+            realizor: None,
+            disputed: false,
+            dispute_reason: None,
+            plan: None,
+        };
+        schedules.push_back(schedule.clone());
+
+        env.storage().instance().set(&SCHEDULES, &schedules);
+        env.storage()
+            .instance()
+            .set(&NEXT_SCHEDULE_ID, &(schedule_id + 1));
+
+        let receipt_id = Self::increment_receipt_id(&env);
+        env.events().publish(
+            (symbol_short!("sch_cred"),),
+            ScheduleCreatedEvent {
+                program_id: program_data.program_id.clone(),
+                schedule_id,
+                recipient: schedule.recipient.clone(),
+                amount,
+                release_timestamp,
+                receipt_id,
+            },
+        );
+        schedule
+    }
+
+        // Store schedule
+        env.storage().persistent().set(
+            &DataKey::ReleaseSchedule(program_id.clone(), schedule_id),
+            &schedule,
+        );
+
+        // Update next schedule ID
+        env.storage().persistent().set(
+            &DataKey::NextScheduleId(program_id.clone()),
+            &(schedule_id + 1),
+        );
+
+        // Extend storage TTL so the schedule survives until it's due, plus
+        // a grace window for a keeper to actually crank the release
+        extend_schedule_storage_ttl(
+            &env,
+            &program_id,
+            schedule_id,
+            schedule_ttl_ledgers(&env, release_timestamp),
+        );
+
+        // Emit program schedule created event
+        env.events().publish(
+            (PROG_SCHEDULE_CREATED,),
+            ProgramScheduleCreated {
+                program_id: program_id.clone(),
+                schedule_id,
+                amount,
+                release_timestamp,
+                recipient: recipient.clone(),
+                created_by: program_data.authorized_payout_key.clone(),
+            },
+        );
+
+        // Track successful operation
+        monitoring::track_operation(
+            &env,
+            symbol_short!("create_p"),
+            program_data.authorized_payout_key,
+            true,
+        );
+
+        // Track performance
+        let duration = env.ledger().timestamp().saturating_sub(start);
+        monitoring::emit_performance(&env, symbol_short!("create_p"), duration);
+
+        // Return updated program data
+        let updated_data: ProgramData = env.storage().instance().get(&program_key).unwrap();
+        updated_data
+    }
+
+    /// Creates a cliff + linear vesting release schedule for a program.
+    /// Unlike `create_program_release_schedule`, `total_amount` does not
+    /// unlock all at once - cranking `release_prog_schedule_automatic`
+    /// pays out whatever has vested since the last crank, per
+    /// [`vested_amount`].
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `program_id` - The program to create the schedule for
+    /// * `recipient` - Address that will receive vested funds
+    /// * `total_amount` - Total amount to vest over the schedule
+    /// * `start_ts` - When vesting begins accruing
+    /// * `cliff_ts` - No funds vest before this timestamp
+    /// * `end_ts` - Everything has vested by this timestamp
+    ///
+    /// # Panics
+    /// * If program is not initialized or not open
+    /// * If caller is not authorized payout key
+    /// * If `total_amount` is not positive
+    /// * If `start_ts <= cliff_ts <= end_ts` does not hold
+    /// * If `total_amount` exceeds the program's remaining balance
+    ///
+    /// # State Changes
+    /// - Creates ProgramReleaseSchedule record with `vesting` populated
+    /// - Updates next schedule ID
+    /// - Emits ScheduleCreated event
+    ///
+    /// # Authorization
+    /// - Only authorized payout key can call this function
+    pub fn create_vesting_release_schedule(
+        env: Env,
+        program_id: String,
+        recipient: Address,
+        total_amount: i128,
+        start_ts: u64,
+        cliff_ts: u64,
+        end_ts: u64,
+    ) -> ProgramData {
+        // Check if contract is paused
+        if Self::is_paused_internal(&env) {
+            panic!("Contract is paused");
+        }
+
+        // Get program data
+        let program_key = DataKey::Program(program_id.clone());
+        let mut program_data: ProgramData = env
+            .storage()
+            .instance()
+            .get(&program_key)
+            .unwrap_or_else(|| panic!("Program not found"));
+
+        assert_program_open(&env, &program_id);
+
+        // Apply rate limiting to the authorized payout key
+        anti_abuse::check_rate_limit(&env, program_data.authorized_payout_key.clone());
+
+        // Verify authorization
+        program_data.authorized_payout_key.require_auth();
+
+        // Validate amount
+        if total_amount <= 0 {
+            panic!("Amount must be greater than zero");
+        }
+
+        // Validate the vesting timeline
+        if !(start_ts <= cliff_ts && cliff_ts <= end_ts && start_ts < end_ts) {
+            panic!("Vesting timeline must satisfy start_ts <= cliff_ts <= end_ts with start_ts < end_ts");
+        }
+
+        // Check sufficient unreserved balance, then reserve the full
+        // vesting amount up front, same as a single-shot schedule.
+        if total_amount > program_data.remaining_balance - program_data.reserved_balance {
+            panic!("Insufficient balance for scheduled amount");
+        }
+        program_data.reserved_balance += total_amount;
+        env.storage().instance().set(&program_key, &program_data);
+
+        // Get next schedule ID
+        let schedule_id: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::NextScheduleId(program_id.clone()))
+            .unwrap_or(1);
+
+        // Create vesting release schedule; `release_timestamp` mirrors
+        // `end_ts` so existing due/pending schedule queries keep working
+        // without needing to special-case vesting.
+        let schedule = ProgramReleaseSchedule {
+            schedule_id,
+            amount: total_amount,
+            release_timestamp: end_ts,
+            recipient: recipient.clone(),
+            released: false,
+            released_at: None,
+            released_by: None,
+            vesting: Some(VestingTerms {
+                start_ts,
+                cliff_ts,
+                end_ts,
+                total_amount,
+                released_amount: 0,
+                step_seconds: None,
+            }),
+            cancelled: false,
+            realizor: None,
+            disputed: false,
+            dispute_reason: None,
+            plan: None,
+        };
+
+        // Store schedule
+        env.storage().persistent().set(
+            &DataKey::ReleaseSchedule(program_id.clone(), schedule_id),
+            &schedule,
+        );
+
+        // Update next schedule ID
+        env.storage().persistent().set(
+            &DataKey::NextScheduleId(program_id.clone()),
+            &(schedule_id + 1),
+        );
+
+        // Extend storage TTL so the schedule survives until it's fully
+        // vested, plus a grace window for a keeper to crank releases
+        extend_schedule_storage_ttl(&env, &program_id, schedule_id, schedule_ttl_ledgers(&env, end_ts));
+
+        // Emit program schedule created event
+        env.events().publish(
+            (PROG_SCHEDULE_CREATED,),
+            ProgramScheduleCreated {
+                program_id: program_id.clone(),
+                schedule_id,
+                amount: total_amount,
+                release_timestamp: end_ts,
+                recipient: recipient.clone(),
+                created_by: program_data.authorized_payout_key.clone(),
+            },
+        );
+
+        // Track successful operation
+        monitoring::track_operation(
+            &env,
+            symbol_short!("create_v"),
+            program_data.authorized_payout_key,
+            true,
+        );
+
+        // Return updated program data
+        let updated_data: ProgramData = env.storage().instance().get(&program_key).unwrap();
+        updated_data
+    }
+
+    /// Creates a stepwise cliff + linear vesting release schedule, as in a
+    /// standard token-vesting escrow. Unlike `create_vesting_release_schedule`
+    /// (which vests continuously), the vested amount here only increases at
+    /// `step_seconds`-aligned boundaries - see `vested_amount`. Claimed the
+    /// same way: through the existing `claim_vested`.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `program_id` - The program to create the schedule for
+    /// * `total_amount` - Total amount to vest over the schedule
+    /// * `start_time` - When vesting begins accruing
+    /// * `end_time` - Everything has vested by this timestamp
+    /// * `step_seconds` - Size of each discrete vesting step, in seconds
+    /// * `cliff_time` - No funds vest before this timestamp
+    /// * `recipient` - Address that will receive vested funds
+    ///
+    /// # Panics
+    /// * If program is not initialized or not open
+    /// * If caller is not authorized payout key
+    /// * If `total_amount` is not positive
+    /// * If `step_seconds` is zero
+    /// * If `start_time <= cliff_time <= end_time` does not hold
+    /// * If `total_amount` exceeds the program's remaining balance
+    ///
+    /// # State Changes
+    /// - Creates ProgramReleaseSchedule record with stepwise `vesting` populated
+    /// - Updates next schedule ID
+    /// - Emits ScheduleCreated event
+    ///
+    /// # Authorization
+    /// - Only authorized payout key can call this function
+    pub fn create_program_vesting_schedule(
+        env: Env,
+        program_id: String,
+        total_amount: i128,
+        start_time: u64,
+        end_time: u64,
+        step_seconds: u64,
+        cliff_time: u64,
+        recipient: Address,
+    ) -> ProgramData {
+        // Check if contract is paused
+        if Self::is_paused_internal(&env) {
+            panic!("Contract is paused");
+        }
+
+        // Get program data
+        let program_key = DataKey::Program(program_id.clone());
+        let mut program_data: ProgramData = env
+            .storage()
+            .instance()
+            .get(&program_key)
+            .unwrap_or_else(|| panic!("Program not found"));
+
+        assert_program_open(&env, &program_id);
+
+        // Apply rate limiting to the authorized payout key
+        anti_abuse::check_rate_limit(&env, program_data.authorized_payout_key.clone());
+
+        // Verify authorization
+        program_data.authorized_payout_key.require_auth();
+
+        // Validate amount
+        if total_amount <= 0 {
+            panic!("Amount must be greater than zero");
+        }
+
+        if step_seconds == 0 {
+            panic!("step_seconds must be greater than zero");
+        }
+
+        // Validate the vesting timeline
+        if !(start_time <= cliff_time && cliff_time <= end_time && start_time < end_time) {
+            panic!("Vesting timeline must satisfy start_time <= cliff_time <= end_time with start_time < end_time");
+        }
+
+        // Check sufficient unreserved balance, then reserve the full
+        // vesting amount up front, same as a single-shot schedule.
+        if total_amount > program_data.remaining_balance - program_data.reserved_balance {
+            panic!("Insufficient balance for scheduled amount");
+        }
+        program_data.reserved_balance += total_amount;
+        env.storage().instance().set(&program_key, &program_data);
+
+        // Get next schedule ID
+        let schedule_id: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::NextScheduleId(program_id.clone()))
+            .unwrap_or(1);
+
+        // Create stepwise vesting release schedule; `release_timestamp`
+        // mirrors `end_time` so existing due/pending schedule queries keep
+        // working without needing to special-case vesting.
+        let schedule = ProgramReleaseSchedule {
+            schedule_id,
+            amount: total_amount,
+            release_timestamp: end_time,
+            recipient: recipient.clone(),
+            released: false,
+            released_at: None,
+            released_by: None,
+            vesting: Some(VestingTerms {
+                start_ts: start_time,
+                cliff_ts: cliff_time,
+                end_ts: end_time,
+                total_amount,
+                released_amount: 0,
+                step_seconds: Some(step_seconds),
+            }),
+            cancelled: false,
+            realizor: None,
+            disputed: false,
+            dispute_reason: None,
+            plan: None,
+        };
+
+        // Store schedule
+        env.storage().persistent().set(
+            &DataKey::ReleaseSchedule(program_id.clone(), schedule_id),
+            &schedule,
+        );
+
+        // Update next schedule ID
+        env.storage().persistent().set(
+            &DataKey::NextScheduleId(program_id.clone()),
+            &(schedule_id + 1),
+        );
+
+        // Extend storage TTL so the schedule survives until it's fully
+        // vested, plus a grace window for a keeper to crank releases
+        extend_schedule_storage_ttl(
+            &env,
+            &program_id,
+            schedule_id,
+            schedule_ttl_ledgers(&env, end_time),
+        );
+
+        // Emit program schedule created event
+        env.events().publish(
+            (PROG_SCHEDULE_CREATED,),
+            ProgramScheduleCreated {
+                program_id: program_id.clone(),
+                schedule_id,
+                amount: total_amount,
+                release_timestamp: end_time,
+                recipient: recipient.clone(),
+                created_by: program_data.authorized_payout_key.clone(),
+            },
+        );
+
+        // Track successful operation
+        monitoring::track_operation(
+            &env,
+            symbol_short!("create_s"),
+            program_data.authorized_payout_key,
+            true,
+        );
+
+        // Return updated program data
+        let updated_data: ProgramData = env.storage().instance().get(&program_key).unwrap();
+        updated_data
+    }
+
+    /// Pays `amount` out of `program_id` to `schedule`'s recipient and
+    /// records the release, shared by `create_program_conditional_schedule`
+    /// (when a plan is `Pay` from the start) and `submit_witness` (when a
+    /// plan reduces to `Pay` after a witness). Does not apply the standard
+    /// payout fee - like `claim_vested`, a conditional release is a
+    /// recipient/witness-driven pull, not a fee-bearing crank.
+    fn execute_conditional_payment(
+        env: &Env,
+        program_id: &String,
+        schedule: &mut ProgramReleaseSchedule,
+        program_data: &mut ProgramData,
+        amount: i128,
+    ) {
+        let now = env.ledger().timestamp();
+        let contract_address = env.current_contract_address();
+        let token_client = token::Client::new(env, &program_data.token_address);
+        let (_, fee_amount) = transfer_with_program_fee(
+            env,
+            program_id,
+            &token_client,
+            &contract_address,
+            &schedule.recipient,
+            amount,
+        );
+
+        schedule.plan = None;
+        schedule.released = true;
+        schedule.released_at = Some(now);
+        schedule.released_by = Some(schedule.recipient.clone());
+        env.storage().persistent().set(
+            &DataKey::ReleaseSchedule(program_id.clone(), schedule.schedule_id),
+            &schedule,
+        );
+
+        program_data.remaining_balance -= amount;
+        program_data.reserved_balance -= amount;
+        env.storage()
+            .instance()
+            .set(&DataKey::Program(program_id.clone()), &program_data);
+
+        let mut history: Vec<ProgramReleaseHistory> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ReleaseHistory(program_id.clone()))
+            .unwrap_or(Vec::new(env));
+        history.push_back(ProgramReleaseHistory {
+            schedule_id: schedule.schedule_id,
+            program_id: program_id.clone(),
+            amount,
+            recipient: schedule.recipient.clone(),
+            released_at: now,
+            released_by: schedule.recipient.clone(),
+            release_type: ReleaseType::Conditional,
+            fee_amount,
+        });
+        env.storage()
+            .persistent()
+            .set(&DataKey::ReleaseHistory(program_id.clone()), &history);
+
+        env.events().publish(
+            (PROG_SCHEDULE_RELEASED,),
+            ProgramScheduleReleased {
+                program_id: program_id.clone(),
+                schedule_id: schedule.schedule_id,
+                amount,
+                recipient: schedule.recipient.clone(),
+                released_at: now,
+                released_by: schedule.recipient.clone(),
+                release_type: ReleaseType::Conditional,
+            },
+        );
+    }
+
+    /// Creates a release schedule driven by a [`ReleasePlan`] instead of a
+    /// single timestamp - e.g. "release to the winner once the judge signs
+    /// OR after the deadline passes" as a `Race`. A `Pay` plan executes
+    /// immediately; `After`/`Race` plans are stored and wait for a witness
+    /// via `submit_witness`.
+    ///
+    /// # Panics
+    /// * If program is not initialized or not open
+    /// * If caller is not authorized payout key
+    /// * If the plan's payable amount is not positive
+    /// * If the plan's reserve amount exceeds the program's remaining balance
+    pub fn create_program_conditional_schedule(
+        env: Env,
+        program_id: String,
+        plan: ReleasePlan,
+        recipient: Address,
+    ) -> ProgramReleaseSchedule {
+        if Self::is_paused_internal(&env) {
+            panic!("Contract is paused");
+        }
+
+        let program_key = DataKey::Program(program_id.clone());
+        let mut program_data: ProgramData = env
+            .storage()
+            .instance()
+            .get(&program_key)
+            .unwrap_or_else(|| panic!("Program not found"));
+
+        assert_program_open(&env, &program_id);
+        anti_abuse::check_rate_limit(&env, program_data.authorized_payout_key.clone());
+        program_data.authorized_payout_key.require_auth();
+
+        let reserve_amount = release_plan_reserve_amount(&plan);
+        if reserve_amount <= 0 {
+            panic!("Amount must be greater than zero");
+        }
+        if reserve_amount > program_data.remaining_balance - program_data.reserved_balance {
+            panic!("Insufficient balance for scheduled amount");
+        }
+        program_data.reserved_balance += reserve_amount;
+        env.storage().instance().set(&program_key, &program_data);
+
+        let schedule_id: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::NextScheduleId(program_id.clone()))
+            .unwrap_or(1);
+
+        let mut schedule = ProgramReleaseSchedule {
+            schedule_id,
+            amount: reserve_amount,
+            release_timestamp: env.ledger().timestamp(),
+            recipient: recipient.clone(),
+            released: false,
+            released_at: None,
+            released_by: None,
+            vesting: None,
+            cancelled: false,
+            realizor: None,
+            disputed: false,
+            dispute_reason: None,
+            plan: Some(plan.clone()),
+        };
+
+        env.storage().persistent().set(
+            &DataKey::ReleaseSchedule(program_id.clone(), schedule_id),
+            &schedule,
+        );
+        env.storage().persistent().set(
+            &DataKey::NextScheduleId(program_id.clone()),
+            &(schedule_id + 1),
+        );
+
+        env.events().publish(
+            (PROG_SCHEDULE_CREATED,),
+            ProgramScheduleCreated {
+                program_id: program_id.clone(),
+                schedule_id,
+                amount: reserve_amount,
+                release_timestamp: schedule.release_timestamp,
+                recipient: recipient.clone(),
+                created_by: program_data.authorized_payout_key.clone(),
+            },
+        );
+
+        // A `Pay` plan has no condition to wait on - release right away.
+        if let ReleasePlan::Pay(amount) = plan {
+            let mut program_data: ProgramData =
+                env.storage().instance().get(&program_key).unwrap();
+            Self::execute_conditional_payment(
+                &env,
+                &program_id,
+                &mut schedule,
+                &mut program_data,
+                amount,
+            );
+        }
+
+        schedule
+    }
+
+    /// Submits `witness` toward `schedule_id`'s [`ReleasePlan`]. Reduces the
+    /// plan: an `After` branch pays out once its condition is satisfied; a
+    /// `Race` pays out whichever of its two branches is satisfied first and
+    /// discards the other. Once reduced to `Pay`, the payout executes
+    /// immediately and the schedule is released.
+    ///
+    /// # Panics
+    /// * If the schedule doesn't exist, isn't conditional, or is already
+    ///   released/cancelled/disputed
+    /// * If `witness` satisfies neither branch's condition
+    pub fn submit_witness(
+        env: Env,
+        program_id: String,
+        schedule_id: u64,
+        witness: Condition,
+    ) -> ProgramReleaseSchedule {
+        if Self::is_paused_internal(&env) {
+            panic!("Contract is paused");
+        }
+
+        let program_key = DataKey::Program(program_id.clone());
+        let mut program_data: ProgramData = env
+            .storage()
+            .instance()
+            .get(&program_key)
+            .unwrap_or_else(|| panic!("Program not found"));
+
+        let schedule_key = DataKey::ReleaseSchedule(program_id.clone(), schedule_id);
+        let mut schedule: ProgramReleaseSchedule = env
+            .storage()
+            .persistent()
+            .get(&schedule_key)
+            .unwrap_or_else(|| panic!("Schedule not found"));
+
+        if schedule.released {
+            panic!("Schedule already released");
+        }
+        if schedule.cancelled {
+            panic!("Schedule was cancelled");
+        }
+        if schedule.disputed {
+            panic!("Schedule is under dispute");
+        }
+
+        let plan = schedule
+            .plan
+            .clone()
+            .unwrap_or_else(|| panic!("Schedule is not a conditional schedule"));
+
+        match plan {
+            ReleasePlan::Pay(amount) => {
+                Self::execute_conditional_payment(
+                    &env,
+                    &program_id,
+                    &mut schedule,
+                    &mut program_data,
+                    amount,
+                );
+            }
+            ReleasePlan::After(condition, amount) => {
+                if !condition_satisfied(&env, &condition, &witness) {
+                    panic!("Witness does not satisfy condition");
+                }
+                Self::execute_conditional_payment(
+                    &env,
+                    &program_id,
+                    &mut schedule,
+                    &mut program_data,
+                    amount,
+                );
+            }
+            ReleasePlan::Race((condition_a, amount_a), (condition_b, amount_b)) => {
+                if condition_satisfied(&env, &condition_a, &witness) {
+                    Self::execute_conditional_payment(
+                        &env,
+                        &program_id,
+                        &mut schedule,
+                        &mut program_data,
+                        amount_a,
+                    );
+                } else if condition_satisfied(&env, &condition_b, &witness) {
+                    Self::execute_conditional_payment(
+                        &env,
+                        &program_id,
+                        &mut schedule,
+                        &mut program_data,
+                        amount_b,
+                    );
+                } else {
+                    panic!("Witness does not satisfy either condition");
+                }
+            }
+        }
+
+        schedule
+    }
+
+    /// Automatically releases funds for program schedules that are due.
+    /// Can be called by anyone after the release timestamp has passed.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `program_id` - The program to check for due schedules
+    /// * `schedule_id` - The specific schedule to release
+    ///
+    /// # Panics
+    /// * If program doesn't exist
+    /// * If schedule doesn't exist
+    /// * If schedule is already released
+    /// * If schedule is not yet due
+    ///
+    /// # State Changes
+    /// - Transfers tokens to recipient
+    /// - Updates schedule status to released
+    /// - Adds to release history
+    /// - Updates program remaining balance
+    /// - Emits ScheduleReleased event
+    ///
+    /// # Example
+    /// ```rust
+    /// // Anyone can call this after the timestamp
+    /// escrow_client.release_program_schedule_automatic(&"Hackathon2024", &1);
+    /// ```
+    pub fn release_prog_schedule_automatic(env: Env, program_id: String, schedule_id: u64) {
+        let start = env.ledger().timestamp();
+
+        // Check if contract is paused (no authenticated caller to bypass with here;
+        // this entrypoint is callable by anyone once the schedule is due)
+        if Self::check_paused(&env, symbol_short!("release"), None) {
+            panic!("Funds Paused");
+        }
+
+        // Get program data
+        let program_key = DataKey::Program(program_id.clone());
+        let mut program_data: ProgramData = env
+            .storage()
+            .instance()
+            .get(&program_key)
+            .unwrap_or_else(|| panic!("Program not found"));
+
+        Self::assert_dependencies_satisfied(&env, &program_id);
+
+        // Get schedule
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::ReleaseSchedule(program_id.clone(), schedule_id))
+        {
+            panic!("Schedule not found");
+        }
+
+        let mut schedule: ProgramReleaseSchedule = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ReleaseSchedule(program_id.clone(), schedule_id))
+            .unwrap();
+
+        // Check if already released
+        if schedule.released {
+            panic!("Schedule already released");
+        }
+
+        // Check if cancelled
+        if schedule.cancelled {
+            panic!("Schedule was cancelled");
+        }
+
+        // Check if disputed
+        if schedule.disputed {
+            panic!("Schedule is under dispute");
+        }
+
+        // Block on the program's realizor, if one is configured
+        Self::enforce_realizor(&env, &program_id, &schedule.recipient, schedule.amount);
+
+        let now = env.ledger().timestamp();
+
+        // For a vesting schedule, only the newly-vested remainder is due on
+        // this crank; for a single-shot schedule, the whole amount becomes
+        // due at `release_timestamp`.
+        let claim_amount = if let Some(terms) = schedule.vesting.clone() {
+            if now < terms.cliff_ts {
+                panic!("Schedule not yet due for release");
+            }
+            let vested = vested_amount(&terms, now);
+            let claimable = vested - terms.released_amount;
+            if claimable <= 0 {
+                panic!("Nothing has vested since the last release");
+            }
+            claimable
+        } else {
+            if now < schedule.release_timestamp {
+                panic!("Schedule not yet due for release");
+            }
+            schedule.amount
+        };
+
+        // Get token client
+        let contract_address = env.current_contract_address();
+        let token_client = token::Client::new(&env, &program_data.token_address);
+
+        // Deduct the standard payout fee, same as other payout paths
+        let fee_config = Self::get_fee_config_internal(&env);
+        let fee_amount = if fee_config.fee_enabled && fee_config.payout_fee_rate > 0 {
+            Self::calculate_fee(claim_amount, fee_config.payout_fee_rate)
+        } else {
+            0
+        };
+        let net_amount = claim_amount - fee_amount;
+
+        // Transfer net funds to recipient (less any per-program platform
+        // fee on top of the global payout fee already deducted above), fee
+        // to fee recipient
+        let (_, program_fee_amount) = transfer_with_program_fee(
+            &env,
+            &program_id,
+            &token_client,
+            &contract_address,
+            &schedule.recipient,
+            net_amount,
+        );
+        if fee_amount > 0 {
+            token_client.transfer(&contract_address, &fee_config.fee_recipient, &fee_amount);
+            let fee_receipt_id = Self::increment_receipt_id(&env);
+            record_fee_ledger_entry(
+                &env,
+                &program_id,
+                FeeKind::ScheduledRelease,
+                claim_amount,
+                fee_amount,
+                fee_config.payout_fee_rate,
+                &fee_config.fee_recipient,
+                fee_receipt_id,
+            );
+        }
+
+        // Update schedule
+        if let Some(terms) = schedule.vesting.as_mut() {
+            terms.released_amount += claim_amount;
+            schedule.released = terms.released_amount == terms.total_amount;
+        } else {
+            schedule.released = true;
+            Self::record_settlement_time(&env, schedule.release_timestamp, now);
+        }
+        schedule.released_at = Some(now);
+        schedule.released_by = Some(env.current_contract_address());
+
+        // Update program data
+        program_data.remaining_balance -= claim_amount;
+        program_data.reserved_balance -= claim_amount;
+
+        // Add to release history
+        let history_entry = ProgramReleaseHistory {
+            schedule_id,
+            program_id: program_id.clone(),
+            amount: claim_amount,
+            recipient: schedule.recipient.clone(),
+            released_at: now,
+            released_by: env.current_contract_address(),
+            release_type: ReleaseType::Automatic,
+            fee_amount: fee_amount + program_fee_amount,
+        };
+
+        let mut history: Vec<ProgramReleaseHistory> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ReleaseHistory(program_id.clone()))
+            .unwrap_or(Vec::new(&env));
+        history.push_back(history_entry);
+
+        // Store updates
+        env.storage().persistent().set(
+            &DataKey::ReleaseSchedule(program_id.clone(), schedule_id),
+            &schedule,
+        );
+        env.storage().instance().set(&program_key, &program_data);
+        env.storage()
+            .persistent()
+            .set(&DataKey::ReleaseHistory(program_id.clone()), &history);
+
+        let receipt_id = Self::increment_receipt_id(&env);
+
+        // Emit events
+        env.events().publish(
+            (PROG_SCHEDULE_RELEASED,),
+            ProgramScheduleReleased {
+                program_id: program_id.clone(),
+                schedule_id,
+                amount: net_amount,
+                recipient: schedule.recipient.clone(),
+                released_at: now,
+                released_by: env.current_contract_address(),
+                release_type: ReleaseType::Automatic,
+            },
+        );
+
+        env.events().publish(
+            (PAYOUT,),
+            PayoutEvent {
+                version: EVENT_VERSION_V2,
+                program_id: program_data.program_id.clone(),
+                recipient: schedule.recipient.clone(),
+                amount: net_amount,
+                remaining_balance: program_data.remaining_balance,
+                receipt_id,
+            },
+        );
+
+        // Track performance
+        let duration = env.ledger().timestamp().saturating_sub(start);
+        monitoring::emit_performance(&env, symbol_short!("rel_auto"), duration);
+    }
+
+    /// Recipient-initiated pull of whatever has vested on a vesting
+    /// schedule since the last claim - the counterpart to
+    /// `release_prog_schedule_automatic`'s keeper-cranked push, gated by
+    /// the recipient's own signature instead of being callable by anyone.
+    /// Reuses the same `VestingTerms`/`vested_amount` accounting
+    /// `create_vesting_release_schedule` sets up, so a vesting schedule can
+    /// be driven by either entry point interchangeably.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `program_id` - The program containing the schedule
+    /// * `schedule_id` - The vesting schedule to claim against
+    ///
+    /// # Panics
+    /// * If program or schedule doesn't exist
+    /// * If the schedule isn't a vesting schedule
+    /// * If the schedule was cancelled
+    /// * If nothing is currently claimable (before the cliff, or already
+    ///   claimed everything vested so far - repeated calls within the same
+    ///   ledger are a no-op that panics rather than transferring zero)
+    ///
+    /// # Authorization
+    /// - Only the schedule's recipient can call this function
+    pub fn claim_vested(env: Env, program_id: String, schedule_id: u64) -> ProgramData {
+        if Self::is_paused_internal(&env) {
+            panic!("Contract is paused");
+        }
+
+        let program_key = DataKey::Program(program_id.clone());
+        let mut program_data: ProgramData = env
+            .storage()
+            .instance()
+            .get(&program_key)
+            .unwrap_or_else(|| panic!("Program not found"));
+
+        let schedule_key = DataKey::ReleaseSchedule(program_id.clone(), schedule_id);
+        let mut schedule: ProgramReleaseSchedule = env
+            .storage()
+            .persistent()
+            .get(&schedule_key)
+            .unwrap_or_else(|| panic!("Schedule not found"));
+
+        if schedule.cancelled {
+            panic!("Schedule was cancelled");
+        }
+
+        if schedule.disputed {
+            panic!("Schedule is under dispute");
+        }
+
+        schedule.recipient.require_auth();
+
+        let mut terms = schedule
+            .vesting
+            .clone()
+            .unwrap_or_else(|| panic!("Schedule is not a vesting schedule"));
+
+        Self::enforce_realizor(&env, &program_id, &schedule.recipient, terms.total_amount);
+
+        let now = env.ledger().timestamp();
+        let vested = vested_amount(&terms, now);
+        let claimable = vested - terms.released_amount;
+        if claimable <= 0 {
+            panic!("Nothing is currently claimable");
+        }
+
+        let contract_address = env.current_contract_address();
+        let token_client = token::Client::new(&env, &program_data.token_address);
+        let (_, fee_amount) = transfer_with_program_fee(
+            &env,
+            &program_id,
+            &token_client,
+            &contract_address,
+            &schedule.recipient,
+            claimable,
+        );
+
+        terms.released_amount += claimable;
+        schedule.vesting = Some(terms.clone());
+        schedule.released = terms.released_amount == terms.total_amount;
+        if schedule.released {
+            schedule.released_at = Some(now);
+            schedule.released_by = Some(schedule.recipient.clone());
+        }
+        env.storage().persistent().set(&schedule_key, &schedule);
+
+        program_data.remaining_balance -= claimable;
+        program_data.reserved_balance -= claimable;
+        env.storage().instance().set(&program_key, &program_data);
+
+        let mut history: Vec<ProgramReleaseHistory> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ReleaseHistory(program_id.clone()))
+            .unwrap_or(Vec::new(&env));
+        history.push_back(ProgramReleaseHistory {
+            schedule_id,
+            program_id: program_id.clone(),
+            amount: claimable,
+            recipient: schedule.recipient.clone(),
+            released_at: now,
+            released_by: schedule.recipient.clone(),
+            release_type: ReleaseType::Vesting,
+            fee_amount,
+        });
+        env.storage()
+            .persistent()
+            .set(&DataKey::ReleaseHistory(program_id.clone()), &history);
+
+        let receipt_id = Self::increment_receipt_id(&env);
+        env.events().publish(
+            (PROG_SCHEDULE_RELEASED,),
+            ProgramScheduleReleased {
+                program_id: program_id.clone(),
+                schedule_id,
+                amount: claimable,
+                recipient: schedule.recipient.clone(),
+                released_at: now,
+                released_by: schedule.recipient.clone(),
+                release_type: ReleaseType::Vesting,
+            },
+        );
+
+        program_data
+    }
+
+    /// Crank that releases every due-and-unreleased schedule for a program
+    /// in one call, instead of requiring a separate
+    /// `release_prog_schedule_automatic` per schedule id. Walks schedule ids
+    /// starting just after `start_after` (or from `1` the first time),
+    /// skipping anything not yet due, already released, or not yet past its
+    /// vesting cliff, and stops once `max_count` schedules have actually
+    /// been released. Mirrors `batch_payout`'s single summary event instead
+    /// of one event per schedule.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `program_id` - The program whose schedules to scan
+    /// * `start_after` - Resume the scan just after this schedule id, or
+    ///   `None` to start from the beginning
+    /// * `max_count` - Stop once this many schedules have been released
+    ///
+    /// # Returns
+    /// How many schedules were released, the total net amount transferred,
+    /// and the last schedule id looked at - pass that back in as
+    /// `start_after` on the next call to continue the scan.
+    ///
+    /// # Panics
+    /// * If the program doesn't exist
+    /// * If the contract (or this operation) is paused
+    pub fn release_due_schedules(
+        env: Env,
+        program_id: String,
+        start_after: Option<u64>,
+        max_count: u32,
+    ) -> DueScheduleReleaseResult {
+        // Reentrancy guard: Check and set
+        reentrancy_guard::check_not_entered(&env);
+        reentrancy_guard::set_entered(&env);
+
+        if Self::check_paused(&env, symbol_short!("release"), None) {
+            reentrancy_guard::clear_entered(&env);
+            panic!("Funds Paused");
+        }
+
+        let program_key = DataKey::Program(program_id.clone());
+        let mut program_data: ProgramData = env
+            .storage()
+            .instance()
+            .get(&program_key)
+            .unwrap_or_else(|| {
+                reentrancy_guard::clear_entered(&env);
+                panic!("Program not found")
+            });
+
+        Self::assert_dependencies_satisfied(&env, &program_id);
+
+        let next_id: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::NextScheduleId(program_id.clone()))
+            .unwrap_or(1);
+
+        let now = env.ledger().timestamp();
+        let contract_address = env.current_contract_address();
+        let token_client = token::Client::new(&env, &program_data.token_address);
+        let fee_config = Self::get_fee_config_internal(&env);
+
+        let mut released_count: u32 = 0;
+        let mut total_amount: i128 = 0;
+        let mut last_processed: Option<u64> = None;
+        let mut history: Vec<ProgramReleaseHistory> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ReleaseHistory(program_id.clone()))
+            .unwrap_or(Vec::new(&env));
+
+        let start = start_after.map(|id| id + 1).unwrap_or(1);
+
+        for schedule_id in start..next_id {
+            if released_count >= max_count {
+                break;
+            }
+            last_processed = Some(schedule_id);
+
+            let schedule_key = DataKey::ReleaseSchedule(program_id.clone(), schedule_id);
+            let mut schedule: ProgramReleaseSchedule = match env.storage().persistent().get(&schedule_key) {
+                Some(s) => s,
+                None => continue,
+            };
+
+            if schedule.released || schedule.cancelled || schedule.disputed {
+                continue;
+            }
+
+            let claim_amount = if let Some(terms) = schedule.vesting.clone() {
+                if now < terms.cliff_ts {
+                    continue;
+                }
+                let vested = vested_amount(&terms, now);
+                let claimable = vested - terms.released_amount;
+                if claimable <= 0 {
+                    continue;
+                }
+                claimable
+            } else {
+                if now < schedule.release_timestamp {
+                    continue;
+                }
+                schedule.amount
+            };
+
+            // Block on the program's realizor, if one is configured
+            Self::enforce_realizor(&env, &program_id, &schedule.recipient, claim_amount);
+
+            let fee_amount = if fee_config.fee_enabled && fee_config.payout_fee_rate > 0 {
+                Self::calculate_fee(claim_amount, fee_config.payout_fee_rate)
+            } else {
+                0
+            };
+            let net_amount = claim_amount - fee_amount;
+
+            token_client.transfer(&contract_address, &schedule.recipient, &net_amount);
+            if fee_amount > 0 {
+                token_client.transfer(&contract_address, &fee_config.fee_recipient, &fee_amount);
+                let fee_receipt_id = Self::increment_receipt_id(&env);
+                record_fee_ledger_entry(
+                    &env,
+                    &program_id,
+                    FeeKind::ScheduledRelease,
+                    claim_amount,
+                    fee_amount,
+                    fee_config.payout_fee_rate,
+                    &fee_config.fee_recipient,
+                    fee_receipt_id,
+                );
+            }
+
+            if let Some(terms) = schedule.vesting.as_mut() {
+                terms.released_amount += claim_amount;
+                schedule.released = terms.released_amount == terms.total_amount;
+            } else {
+                schedule.released = true;
+            }
+            schedule.released_at = Some(now);
+            schedule.released_by = Some(env.current_contract_address());
+
+            env.storage().persistent().set(&schedule_key, &schedule);
+
+            history.push_back(ProgramReleaseHistory {
+                schedule_id,
+                program_id: program_id.clone(),
+                amount: claim_amount,
+                recipient: schedule.recipient.clone(),
+                released_at: now,
+                released_by: env.current_contract_address(),
+                release_type: ReleaseType::Automatic,
+                fee_amount: 0,
+            });
+
+            program_data.remaining_balance -= claim_amount;
+            program_data.reserved_balance -= claim_amount;
+            total_amount += net_amount;
+            released_count += 1;
+
+            // Record outflow for threshold monitoring
+            threshold_monitor::record_outflow(&env, claim_amount);
+        }
+
+        env.storage().instance().set(&program_key, &program_data);
+        env.storage()
+            .persistent()
+            .set(&DataKey::ReleaseHistory(program_id.clone()), &history);
+
+        let receipt_id = Self::increment_receipt_id(&env);
+        env.events().publish(
+            (BATCH_PAYOUT,),
+            BatchPayoutEvent {
+                version: EVENT_VERSION_V2,
+                program_id: program_id.clone(),
+                recipient_count: released_count,
+                total_amount,
+                remaining_balance: program_data.remaining_balance,
+                receipt_id,
+            },
+        );
+
+        reentrancy_guard::clear_entered(&env);
+
+        DueScheduleReleaseResult {
+            released_count,
+            total_amount,
+            last_processed_schedule_id: last_processed,
+        }
+    }
+
+    /// Authorized-key crank over `get_due_program_schedules`, releasing up
+    /// to `max_count` of them in one call instead of one
+    /// `release_program_schedule_manual` per schedule. Unlike
+    /// `release_due_schedules`, this always pays a due schedule's full
+    /// `amount` (matching `release_program_schedule_manual`'s semantics,
+    /// not the vesting-partial-claim semantics `release_prog_schedule_automatic`
+    /// uses) and rate-limits the authorized key once for the whole crank
+    /// rather than once per schedule.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `program_id` - The program to release due schedules for
+    /// * `max_count` - Stop once this many schedules have been released
+    ///
+    /// # Returns
+    /// The schedule ids actually released, in the order processed.
+    ///
+    /// # Panics
+    /// * If the program doesn't exist
+    /// * If the caller is not the authorized payout key
+    ///
+    /// # Authorization
+    /// - Only authorized payout key can call this function
+    pub fn release_due_program_schedules(env: Env, program_id: String, max_count: u32) -> Vec<u64> {
+        let start = env.ledger().timestamp();
+
+        let program_key = DataKey::Program(program_id.clone());
+        let mut program_data: ProgramData = env
+            .storage()
+            .instance()
+            .get(&program_key)
+            .unwrap_or_else(|| panic!("Program not found"));
+
+        // Rate limit once for the whole crank, not once per schedule
+        anti_abuse::check_rate_limit(&env, program_data.authorized_payout_key.clone());
+        program_data.authorized_payout_key.require_auth();
+
+        let due = Self::get_due_program_schedules(env.clone(), program_id.clone());
+
+        let now = env.ledger().timestamp();
+        let contract_address = env.current_contract_address();
+        let token_client = token::Client::new(&env, &program_data.token_address);
+
+        let mut history: Vec<ProgramReleaseHistory> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ReleaseHistory(program_id.clone()))
+            .unwrap_or(Vec::new(&env));
+
+        let mut released_ids: Vec<u64> = Vec::new(&env);
+        let mut total_released: i128 = 0;
+
+        for due_schedule in due.iter() {
+            if released_ids.len() >= max_count {
+                break;
+            }
+
+            let schedule_key = DataKey::ReleaseSchedule(program_id.clone(), due_schedule.schedule_id);
+            let mut schedule: ProgramReleaseSchedule = match env.storage().persistent().get(&schedule_key) {
+                Some(s) => s,
+                None => continue,
+            };
+
+            // Skip, don't panic on, anything that became released or
+            // cancelled mid-loop (e.g. via a concurrent manual release)
+            if schedule.released || schedule.cancelled || schedule.disputed {
+                continue;
+            }
+
+            // Stop cleanly so the releases already processed this call
+            // still commit, instead of reverting the whole crank
+            if schedule.amount > program_data.remaining_balance {
+                break;
+            }
+
+            Self::enforce_realizor(&env, &program_id, &schedule.recipient, schedule.amount);
+
+            token_client.transfer(&contract_address, &schedule.recipient, &schedule.amount);
+
+            schedule.released = true;
+            schedule.released_at = Some(now);
+            schedule.released_by = Some(program_data.authorized_payout_key.clone());
+            env.storage().persistent().set(&schedule_key, &schedule);
+            Self::record_settlement_time(&env, schedule.release_timestamp, now);
+
+            program_data.remaining_balance -= schedule.amount;
+            program_data.reserved_balance -= schedule.amount;
+            total_released += schedule.amount;
+
+            history.push_back(ProgramReleaseHistory {
+                schedule_id: schedule.schedule_id,
+                program_id: program_id.clone(),
+                amount: schedule.amount,
+                recipient: schedule.recipient.clone(),
+                released_at: now,
+                released_by: program_data.authorized_payout_key.clone(),
+                release_type: ReleaseType::Manual,
+                fee_amount: 0,
+            });
+
+            env.events().publish(
+                (PROG_SCHEDULE_RELEASED,),
+                ProgramScheduleReleased {
+                    program_id: program_id.clone(),
+                    schedule_id: schedule.schedule_id,
+                    amount: schedule.amount,
+                    recipient: schedule.recipient.clone(),
+                    released_at: now,
+                    released_by: program_data.authorized_payout_key.clone(),
+                    release_type: ReleaseType::Manual,
+                },
+            );
+
+            released_ids.push_back(schedule.schedule_id);
+        }
+
+        env.storage().instance().set(&program_key, &program_data);
+        env.storage()
+            .persistent()
+            .set(&DataKey::ReleaseHistory(program_id.clone()), &history);
+
+        let receipt_id = Self::increment_receipt_id(&env);
+        env.events().publish(
+            (BATCH_PAYOUT,),
+            BatchPayoutEvent {
+                version: EVENT_VERSION_V2,
+                program_id: program_id.clone(),
+                recipient_count: released_ids.len() as u32,
+                total_amount: total_released,
+                remaining_balance: program_data.remaining_balance,
+                receipt_id,
+            },
+        );
+
+        let duration = env.ledger().timestamp().saturating_sub(start);
+        monitoring::emit_performance(&env, symbol_short!("rel_dprg"), duration);
+
+        released_ids
+    }
+
+    /// Manually releases funds for a program schedule (authorized payout key only).
+    /// Can be called before the release timestamp by authorized key.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `program_id` - The program containing the schedule
+    /// * `schedule_id` - The schedule to release
+    ///
+    /// # Panics
+    /// * If program doesn't exist
+    /// * If caller is not authorized payout key
+    /// * If schedule doesn't exist
+    /// * If schedule is already released
+    ///
+    /// # State Changes
+    /// - Transfers tokens to recipient
+    /// - Updates schedule status to released
+    /// - Adds to release history
+    /// - Updates program remaining balance
+    /// - Emits ScheduleReleased event
+    ///
+    /// # Authorization
+    /// - Only authorized payout key can call this function
+    ///
+    /// # Example
+    /// ```rust
+    /// // Authorized key can release early
+    /// escrow_client.release_program_schedule_manual(&"Hackathon2024", &1);
+    /// ```
+    pub fn release_program_schedule_manual(env: Env, program_id: String, schedule_id: u64) {
+        // `FEATURE_MANUAL_RELEASE_PAUSE` lets the admin schedule, ahead of
+        // time, the ledger timestamp at which manual releases start
+        // respecting PAUSE_RELEASE like the other release paths already do.
+        if feature_gate::is_feature_active(&env, FEATURE_MANUAL_RELEASE_PAUSE)
+            && Self::get_pause_flags(&env).is_set(PAUSE_RELEASE)
+        {
+            panic!("Contract is paused");
+        }
+
+        // Get program data
+        let program_key = DataKey::Program(program_id.clone());
+        let program_data: ProgramData = env
+            .storage()
+            .instance()
+            .get(&program_key)
+            .unwrap_or_else(|| panic!("Program not found"));
+
+        // A program under multisig governance can no longer be drained by
+        // its single authorized key - releases must go through
+        // `approve_release` until `threshold` distinct signers agree.
+        if env
+            .storage()
+            .instance()
+            .has(&DataKey::ProgramSigners(program_id.clone()))
+        {
+            panic!("Multisig approval required; use approve_release");
+        }
+
+        // Apply rate limiting to the authorized payout key
+        anti_abuse::check_rate_limit(&env, program_data.authorized_payout_key.clone());
+
+        // Verify authorization
+        program_data.authorized_payout_key.require_auth();
+
+        let released_by = program_data.authorized_payout_key.clone();
+        Self::execute_manual_release(&env, &program_id, schedule_id, released_by);
+    }
+
+    /// The actual transfer-and-bookkeeping behind a manual release, shared
+    /// by the single-key path (`release_program_schedule_manual`) and the
+    /// multisig path (`approve_release`, once `threshold` is reached) -
+    /// only who gets to trigger it differs; what happens once triggered
+    /// does not.
+    fn execute_manual_release(env: &Env, program_id: &String, schedule_id: u64, released_by: Address) {
+        let start = env.ledger().timestamp();
+
+        let program_key = DataKey::Program(program_id.clone());
+        let program_data: ProgramData = env
+            .storage()
+            .instance()
+            .get(&program_key)
+            .unwrap_or_else(|| panic!("Program not found"));
+
+        // Get schedule
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::ReleaseSchedule(program_id.clone(), schedule_id))
+        {
+            panic!("Schedule not found");
+        }
+
+        let mut schedule: ProgramReleaseSchedule = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ReleaseSchedule(program_id.clone(), schedule_id))
+            .unwrap();
+
+        // Check if already released
+        if schedule.released {
+            panic!("Schedule already released");
+        }
+
+        // Check if cancelled
+        if schedule.cancelled {
+            panic!("Schedule was cancelled");
+        }
+
+        // Check if disputed
+        if schedule.disputed {
+            panic!("Schedule is under dispute");
+        }
+
+        // If this schedule has its own realizor attached, it must report
+        // the schedule realized before any transfer happens - a milestone-
+        // or compliance-gated condition scoped to this schedule alone,
+        // distinct from the program-wide `enforce_realizor` gate.
+        if let Some(schedule_realizor) = schedule.realizor.clone() {
+            if !realizor::is_schedule_realized(
+                env,
+                &schedule_realizor,
+                program_id,
+                schedule_id,
+                &schedule.recipient,
+            ) {
+                env.events().publish(
+                    (SCHEDULE_REALIZOR_BLOCKED,),
+                    ScheduleRealizorBlockedEvent {
+                        program_id: program_id.clone(),
+                        schedule_id,
+                        recipient: schedule.recipient.clone(),
+                    },
+                );
+                panic!("Release condition not met");
+            }
+        }
+
+        // Get token client
+        let contract_address = env.current_contract_address();
+        let token_client = token::Client::new(env, &program_data.token_address);
+
+        // Transfer funds, net of any per-program platform fee
+        let (_, fee_amount) = transfer_with_program_fee(
+            env,
+            program_id,
+            &token_client,
+            &contract_address,
+            &schedule.recipient,
+            schedule.amount,
+        );
+
+        // Update schedule
+        let now = env.ledger().timestamp();
+        schedule.released = true;
+        schedule.released_at = Some(now);
+        schedule.released_by = Some(released_by.clone());
+        Self::record_settlement_time(env, schedule.release_timestamp, now);
+
+        // Update program data
+        let mut updated_data = program_data.clone();
+        updated_data.remaining_balance -= schedule.amount;
+        updated_data.reserved_balance -= schedule.amount;
+
+        // Add to release history
+        let history_entry = ProgramReleaseHistory {
+            schedule_id,
+            program_id: program_id.clone(),
+            amount: schedule.amount,
+            recipient: schedule.recipient.clone(),
+            released_at: now,
+            released_by: released_by.clone(),
+            release_type: ReleaseType::Manual,
+            fee_amount,
+        };
+
+        let mut history: Vec<ProgramReleaseHistory> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ReleaseHistory(program_id.clone()))
+            .unwrap_or(vec![env]);
+        history.push_back(history_entry);
+
+        // Store updates
+        env.storage().persistent().set(
+            &DataKey::ReleaseSchedule(program_id.clone(), schedule_id),
+            &schedule,
+        );
+        env.storage().instance().set(&program_key, &updated_data);
+        env.storage()
+            .persistent()
+            .set(&DataKey::ReleaseHistory(program_id.clone()), &history);
+
+        // Remove any multisig approvals now that the schedule is released.
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ReleaseApprovals(program_id.clone(), schedule_id));
+
+        // Emit program schedule released event
+        env.events().publish(
+            (PROG_SCHEDULE_RELEASED,),
+            ProgramScheduleReleased {
+                program_id: program_id.clone(),
+                schedule_id,
+                amount: schedule.amount,
+                recipient: schedule.recipient.clone(),
+                released_at: now,
+                released_by: released_by.clone(),
+                release_type: ReleaseType::Manual,
+            },
+        );
+
+        // Track successful operation
+        monitoring::track_operation(env, symbol_short!("rel_man"), released_by, true);
+
+        // Track performance
+        let duration = env.ledger().timestamp().saturating_sub(start);
+        monitoring::emit_performance(env, symbol_short!("rel_man"), duration);
+    }
+
+    /// Releases as many of `schedule_ids` as are currently eligible in one
+    /// invocation, instead of requiring a separate `release_program_schedule_manual`
+    /// call per winner at the end of a program. Reuses the same
+    /// `execute_manual_release` helper (and therefore the same transfer,
+    /// history, and event behavior) as the single-schedule path - only the
+    /// dispatch loop and its skip-not-abort error handling are new.
+    ///
+    /// An ineligible entry (not found, already released, cancelled,
+    /// disputed, gated behind multisig, blocked by a schedule realizor, or
+    /// would exceed the program's remaining balance) is recorded as
+    /// `Skipped` with a reason and the batch continues; it never aborts the
+    /// whole call the way a single `release_program_schedule_manual` panic
+    /// would.
+    ///
+    /// # Authorization
+    /// - Only the program's authorized payout key can call this function,
+    ///   same as `release_program_schedule_manual`
+    pub fn release_pending_batch(
+        env: Env,
+        program_id: String,
+        schedule_ids: Vec<u64>,
+    ) -> Vec<BatchReleaseResult> {
+        let start = env.ledger().timestamp();
+
+        let program_key = DataKey::Program(program_id.clone());
+        let mut program_data: ProgramData = env
+            .storage()
+            .instance()
+            .get(&program_key)
+            .unwrap_or_else(|| panic!("Program not found"));
+
+        // Rate limit once for the whole batch, not once per schedule
+        anti_abuse::check_rate_limit(&env, program_data.authorized_payout_key.clone());
+        program_data.authorized_payout_key.require_auth();
+
+        let under_multisig = env
+            .storage()
+            .instance()
+            .has(&DataKey::ProgramSigners(program_id.clone()));
+
+        let mut results: Vec<BatchReleaseResult> = Vec::new(&env);
+        let mut released_count: u32 = 0;
+        let mut total_amount: i128 = 0;
+
+        for schedule_id in schedule_ids.iter() {
+            let schedule_key = DataKey::ReleaseSchedule(program_id.clone(), schedule_id);
+            let schedule: Option<ProgramReleaseSchedule> = env.storage().persistent().get(&schedule_key);
+            let schedule = match schedule {
+                Some(s) => s,
+                None => {
+                    results.push_back(BatchReleaseResult {
+                        schedule_id,
+                        outcome: BatchReleaseOutcome::Skipped(String::from_str(&env, "schedule not found")),
+                    });
+                    continue;
+                }
+            };
+
+            let realizor_blocked = if let Some(schedule_realizor) = schedule.realizor.clone() {
+                !realizor::is_schedule_realized(
+                    &env,
+                    &schedule_realizor,
+                    &program_id,
+                    schedule_id,
+                    &schedule.recipient,
+                )
+            } else {
+                false
+            };
+
+            let skip_reason = if schedule.released {
+                Some("already released")
+            } else if schedule.cancelled {
+                Some("schedule was cancelled")
+            } else if schedule.disputed {
+                Some("schedule is under dispute")
+            } else if under_multisig {
+                Some("multisig approval required; use approve_release")
+            } else if schedule.amount > program_data.remaining_balance {
+                Some("would exceed remaining program balance")
+            } else if realizor_blocked {
+                Some("release condition not met")
+            } else {
+                None
+            };
+
+            if let Some(reason) = skip_reason {
+                results.push_back(BatchReleaseResult {
+                    schedule_id,
+                    outcome: BatchReleaseOutcome::Skipped(String::from_str(&env, reason)),
+                });
+                continue;
+            }
+
+            Self::execute_manual_release(
+                &env,
+                &program_id,
+                schedule_id,
+                program_data.authorized_payout_key.clone(),
+            );
+
+            // `execute_manual_release` persisted the updated balance; reload
+            // it so later iterations see the reduced remaining balance.
+            program_data = env.storage().instance().get(&program_key).unwrap();
+
+            released_count += 1;
+            total_amount += schedule.amount;
+            results.push_back(BatchReleaseResult {
+                schedule_id,
+                outcome: BatchReleaseOutcome::Released,
+            });
+        }
+
+        let receipt_id = Self::increment_receipt_id(&env);
+        env.events().publish(
+            (BATCH_PAYOUT,),
+            BatchPayoutEvent {
+                version: EVENT_VERSION_V2,
+                program_id: program_id.clone(),
+                recipient_count: released_count,
+                total_amount,
+                remaining_balance: program_data.remaining_balance,
+                receipt_id,
+            },
+        );
+
+        let duration = env.ledger().timestamp().saturating_sub(start);
+        monitoring::emit_performance(&env, symbol_short!("rel_batch"), duration);
+
+        results
+    }
+
+    /// Configures M-of-N multisig governance for `program_id`'s manual
+    /// releases (admin-gated via the program's own authorized payout key,
+    /// same as every other program-level config change). Once set,
+    /// `release_program_schedule_manual` refuses to run directly - every
+    /// release must go through `approve_release` until `threshold` distinct
+    /// signers have approved it.
+    ///
+    /// # Panics
+    /// * If `signers` is empty
+    /// * If `threshold` is zero or exceeds `signers.len()`
+    pub fn set_program_signers(env: Env, program_id: String, signers: Vec<Address>, threshold: u32) {
+        let program_key = DataKey::Program(program_id.clone());
+        let program_data: ProgramData = env
+            .storage()
+            .instance()
+            .get(&program_key)
+            .unwrap_or_else(|| panic!("Program not found"));
+        program_data.authorized_payout_key.require_auth();
+
+        if signers.is_empty() {
+            panic!("At least one signer is required");
+        }
+        if threshold == 0 || threshold > signers.len() as u32 {
+            panic!("Threshold must be between 1 and the number of signers");
+        }
+
+        env.storage().instance().set(
+            &DataKey::ProgramSigners(program_id),
+            &ProgramSigners { signers, threshold },
+        );
+    }
+
+    /// Records `signer`'s authenticated approval of releasing
+    /// `schedule_id`. Once distinct approvals reach the program's
+    /// configured `threshold`, the release executes immediately in the same
+    /// call that tips it over - there is no separate "finalize" step.
+    ///
+    /// # Panics
+    /// * If the program has no multisig configured
+    /// * If `signer` is not one of the configured signers
+    /// * If `signer` has already approved this schedule
+    pub fn approve_release(env: Env, program_id: String, schedule_id: u64, signer: Address) -> Vec<Address> {
+        signer.require_auth();
+
+        let signers_key = DataKey::ProgramSigners(program_id.clone());
+        let signers_config: ProgramSigners = env
+            .storage()
+            .instance()
+            .get(&signers_key)
+            .unwrap_or_else(|| panic!("Multisig not configured for this program"));
+
+        if !signers_config.signers.contains(&signer) {
+            panic!("Not an authorized signer");
+        }
+
+        let approvals_key = DataKey::ReleaseApprovals(program_id.clone(), schedule_id);
+        let mut approvals: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&approvals_key)
+            .unwrap_or(Vec::new(&env));
+
+        if approvals.contains(&signer) {
+            panic!("Signer has already approved this schedule");
+        }
+        approvals.push_back(signer.clone());
+        env.storage().persistent().set(&approvals_key, &approvals);
+
+        if approvals.len() >= signers_config.threshold {
+            Self::execute_manual_release(&env, &program_id, schedule_id, signer);
+            return Vec::new(&env);
+        }
+
+        approvals
+    }
+
+    /// Lists the signers who have approved releasing `schedule_id` so far.
+    /// Returns empty once the schedule has actually released (approvals are
+    /// cleared by `execute_manual_release`).
+    pub fn get_release_approvals(env: Env, program_id: String, schedule_id: u64) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ReleaseApprovals(program_id, schedule_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// The unspent allowance `spender` holds over `program_id`'s releases,
+    /// `0` if none was ever granted or it has expired.
+    pub fn get_release_allowance(env: Env, program_id: String, spender: Address) -> i128 {
+        let allowance: Option<ReleaseAllowance> = env
+            .storage()
+            .instance()
+            .get(&DataKey::ReleaseAllowance(program_id, spender));
+        match allowance {
+            Some(a) if a.expiration_ledger >= env.ledger().sequence() => a.amount,
+            _ => 0,
+        }
+    }
+
+    /// Grants (or tops up) `spender`'s allowance to call
+    /// `release_with_allowance` on `program_id`'s behalf, up to a
+    /// cumulative `amount`, expiring at `expiration_ledger`. Mirrors the
+    /// standard token `increase_allowance`: if the prior allowance hasn't
+    /// expired yet, `amount` is added on top of what's left; otherwise it
+    /// replaces it outright. Either way `expiration_ledger` becomes the new
+    /// expiry.
+    ///
+    /// # Panics
+    /// * If caller is not the program's authorized payout key
+    /// * If `amount` is not positive
+    pub fn increase_release_allowance(
+        env: Env,
+        program_id: String,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32,
+    ) {
+        let program_data: ProgramData = env
+            .storage()
+            .instance()
+            .get(&DataKey::Program(program_id.clone()))
+            .unwrap_or_else(|| panic!("Program not found"));
+        program_data.authorized_payout_key.require_auth();
+
+        if amount <= 0 {
+            panic!("Amount must be greater than zero");
+        }
+
+        let key = DataKey::ReleaseAllowance(program_id, spender);
+        let existing: Option<ReleaseAllowance> = env.storage().instance().get(&key);
+        let carried_over = match existing {
+            Some(a) if a.expiration_ledger >= env.ledger().sequence() => a.amount,
+            _ => 0,
+        };
+
+        env.storage().instance().set(
+            &key,
+            &ReleaseAllowance {
+                amount: carried_over + amount,
+                expiration_ledger,
+            },
+        );
+    }
+
+    /// Reduces `spender`'s allowance over `program_id`'s releases by
+    /// `amount`, floored at zero - mirrors the standard token
+    /// `decrease_allowance`. Leaves `expiration_ledger` untouched.
+    ///
+    /// # Panics
+    /// * If caller is not the program's authorized payout key
+    pub fn decrease_release_allowance(env: Env, program_id: String, spender: Address, amount: i128) {
+        let program_data: ProgramData = env
+            .storage()
+            .instance()
+            .get(&DataKey::Program(program_id.clone()))
+            .unwrap_or_else(|| panic!("Program not found"));
+        program_data.authorized_payout_key.require_auth();
+
+        let key = DataKey::ReleaseAllowance(program_id, spender);
+        let existing: Option<ReleaseAllowance> = env.storage().instance().get(&key);
+        if let Some(mut allowance) = existing {
+            allowance.amount = if amount >= allowance.amount {
+                0
+            } else {
+                allowance.amount - amount
+            };
+            env.storage().instance().set(&key, &allowance);
+        }
+    }
+
+    /// Releases `schedule_id` using `spender`'s delegated allowance instead
+    /// of the authorized payout key's own signature. Decrements the
+    /// allowance by the schedule's `amount` and records `spender` as
+    /// `released_by`.
+    ///
+    /// # Panics
+    /// * If the program is under multisig governance (`set_program_signers`)
+    /// * If `spender` has no unexpired allowance covering the schedule amount
+    pub fn release_with_allowance(env: Env, program_id: String, schedule_id: u64, spender: Address) {
+        spender.require_auth();
+
+        if env
+            .storage()
+            .instance()
+            .has(&DataKey::ProgramSigners(program_id.clone()))
+        {
+            panic!("Multisig approval required; use approve_release");
+        }
+
+        let schedule: ProgramReleaseSchedule = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ReleaseSchedule(program_id.clone(), schedule_id))
+            .unwrap_or_else(|| panic!("Schedule not found"));
+
+        let allowance_key = DataKey::ReleaseAllowance(program_id.clone(), spender.clone());
+        let mut allowance: ReleaseAllowance = env
+            .storage()
+            .instance()
+            .get(&allowance_key)
+            .unwrap_or_else(|| panic!("No release allowance for this spender"));
+
+        if allowance.expiration_ledger < env.ledger().sequence() {
+            panic!("Release allowance has expired");
+        }
+        if allowance.amount < schedule.amount {
+            panic!("Amount exceeds remaining release allowance");
+        }
+
+        allowance.amount -= schedule.amount;
+        env.storage().instance().set(&allowance_key, &allowance);
+
+        Self::execute_manual_release(&env, &program_id, schedule_id, spender);
+    }
+
+    /// Marks a not-yet-released schedule disputed, blocking every release
+    /// path (`release_program_schedule_manual`, the automatic/crank paths,
+    /// and `claim_vested`) until `resolve_dispute` clears it. Bumps the
+    /// program's `dispute_count`, which feeds the penalty
+    /// `get_program_reputation` applies to `overall_score_bps`.
+    ///
+    /// # Authorization
+    /// - Only the schedule's own recipient can open a dispute on it
+    ///
+    /// # Panics
+    /// * If the program or schedule doesn't exist
+    /// * If the schedule is already released, cancelled, or already disputed
+    pub fn open_dispute(env: Env, program_id: String, schedule_id: u64, reason: String) -> ProgramReleaseSchedule {
+        let program_key = DataKey::Program(program_id.clone());
+        let mut program_data: ProgramData = env
+            .storage()
+            .instance()
+            .get(&program_key)
+            .unwrap_or_else(|| panic!("Program not found"));
+
+        let schedule_key = DataKey::ReleaseSchedule(program_id.clone(), schedule_id);
+        let mut schedule: ProgramReleaseSchedule = env
+            .storage()
+            .persistent()
+            .get(&schedule_key)
+            .unwrap_or_else(|| panic!("Schedule not found"));
+
+        schedule.recipient.require_auth();
+
+        if schedule.released {
+            panic!("Schedule already released");
+        }
+        if schedule.cancelled {
+            panic!("Schedule was cancelled");
+        }
+        if schedule.disputed {
+            panic!("Schedule already disputed");
+        }
+
+        schedule.disputed = true;
+        schedule.dispute_reason = Some(reason.clone());
+        env.storage().persistent().set(&schedule_key, &schedule);
+
+        program_data.dispute_count += 1;
+        env.storage().instance().set(&program_key, &program_data);
+
+        env.events().publish(
+            (DISPUTE_OPENED,),
+            DisputeOpenedEvent {
+                program_id,
+                schedule_id,
+                opened_by: schedule.recipient.clone(),
+                reason,
+            },
+        );
+
+        schedule
+    }
+
+    /// Resolves a dispute opened via `open_dispute`. `uphold = true` keeps
+    /// the schedule blocked (expected to be followed by
+    /// `refund_program_schedule`); `uphold = false` clears it and restores
+    /// the schedule to its normal releasable state.
+    ///
+    /// # Authorization
+    /// - Admin only (`anti_abuse::get_admin`)
+    ///
+    /// # Panics
+    /// * If the program or schedule doesn't exist
+    /// * If the schedule is not currently disputed
+    pub fn resolve_dispute(env: Env, program_id: String, schedule_id: u64, uphold: bool) -> ProgramReleaseSchedule {
+        let admin = anti_abuse::get_admin(&env).expect("Admin not set");
+        admin.require_auth();
+
+        let schedule_key = DataKey::ReleaseSchedule(program_id.clone(), schedule_id);
+        let mut schedule: ProgramReleaseSchedule = env
+            .storage()
+            .persistent()
+            .get(&schedule_key)
+            .unwrap_or_else(|| panic!("Schedule not found"));
+
+        if !schedule.disputed {
+            panic!("Schedule is not disputed");
+        }
+
+        if !uphold {
+            schedule.disputed = false;
+            schedule.dispute_reason = None;
+            env.storage().persistent().set(&schedule_key, &schedule);
+        }
+
+        env.events().publish(
+            (DISPUTE_RESOLVED,),
+            DisputeResolvedEvent {
+                program_id,
+                schedule_id,
+                upheld: uphold,
+                resolved_by: admin,
+            },
+        );
+
+        schedule
+    }
+
+    /// Returns a disputed, unreleased schedule's full amount to the
+    /// program's `funder`, tombstones it (`cancelled = true`, same as
+    /// `cancel_program_release_schedule`) so it can never be released
+    /// afterwards, and records a `ReleaseType::Refund` history entry.
+    /// Bumps `refund_count`, which `get_program_reputation` reports; its
+    /// `overall_score_bps` penalty is computed from `dispute_count` alone.
+    ///
+    /// # Authorization
+    /// - Admin only (`anti_abuse::get_admin`)
+    ///
+    /// # Panics
+    /// * If the program or schedule doesn't exist
+    /// * If the schedule is not currently disputed, or is already released/cancelled
+    pub fn refund_program_schedule(env: Env, program_id: String, schedule_id: u64) -> ProgramData {
+        let admin = anti_abuse::get_admin(&env).expect("Admin not set");
+        admin.require_auth();
+
+        let program_key = DataKey::Program(program_id.clone());
+        let mut program_data: ProgramData = env
+            .storage()
+            .instance()
+            .get(&program_key)
+            .unwrap_or_else(|| panic!("Program not found"));
+
+        let schedule_key = DataKey::ReleaseSchedule(program_id.clone(), schedule_id);
+        let mut schedule: ProgramReleaseSchedule = env
+            .storage()
+            .persistent()
+            .get(&schedule_key)
+            .unwrap_or_else(|| panic!("Schedule not found"));
+
+        if schedule.released {
+            panic!("Schedule already released");
+        }
+        if schedule.cancelled {
+            panic!("Schedule was cancelled");
+        }
+        if !schedule.disputed {
+            panic!("Schedule is not disputed");
+        }
+
+        let contract_address = env.current_contract_address();
+        let token_client = token::Client::new(&env, &program_data.token_address);
+        token_client.transfer(&contract_address, &program_data.funder, &schedule.amount);
+
+        let now = env.ledger().timestamp();
+        schedule.cancelled = true;
+        schedule.disputed = false;
+        env.storage().persistent().set(&schedule_key, &schedule);
+
+        program_data.reserved_balance -= schedule.amount;
+        program_data.refund_count += 1;
+        env.storage().instance().set(&program_key, &program_data);
+
+        let mut history: Vec<ProgramReleaseHistory> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ReleaseHistory(program_id.clone()))
+            .unwrap_or(Vec::new(&env));
+        history.push_back(ProgramReleaseHistory {
+            schedule_id,
+            program_id: program_id.clone(),
+            amount: schedule.amount,
+            recipient: program_data.funder.clone(),
+            released_at: now,
+            released_by: admin,
+            release_type: ReleaseType::Refund,
+            fee_amount: 0,
+        });
+        env.storage()
+            .persistent()
+            .set(&DataKey::ReleaseHistory(program_id.clone()), &history);
+
+        env.events().publish(
+            (SCHEDULE_REFUNDED,),
+            ScheduleRefundedEvent {
+                program_id,
+                schedule_id,
+                amount: schedule.amount,
+                refunded_to: program_data.funder.clone(),
+            },
+        );
+
+        program_data
+    }
+
+    /// Admin-initiated early termination of a vesting schedule: pays out
+    /// whatever has vested so far (same accounting `claim_vested` uses),
+    /// then tombstones the schedule and frees the unvested remainder back
+    /// to the program's reservation, mirroring how
+    /// `cancel_program_release_schedule` returns an unclaimed reservation -
+    /// except here the recipient keeps the portion already earned instead
+    /// of losing it outright. Intended for the foundation-controlled case
+    /// where a grantee's vesting must stop (e.g. they leave the program)
+    /// without clawing back funds they've already vested.
+    ///
+    /// # Authorization
+    /// - Admin only (`anti_abuse::get_admin`), same as `refund_program_schedule`
+    ///
+    /// # Panics
+    /// * If the program or schedule doesn't exist
+    /// * If the schedule is not a vesting schedule
+    /// * If the schedule is already released or cancelled
+    pub fn terminate_vesting(env: Env, program_id: String, schedule_id: u64) -> ProgramData {
+        let admin = anti_abuse::get_admin(&env).expect("Admin not set");
+        admin.require_auth();
+
+        let program_key = DataKey::Program(program_id.clone());
+        let mut program_data: ProgramData = env
+            .storage()
+            .instance()
+            .get(&program_key)
+            .unwrap_or_else(|| panic!("Program not found"));
+
+        let schedule_key = DataKey::ReleaseSchedule(program_id.clone(), schedule_id);
+        let mut schedule: ProgramReleaseSchedule = env
+            .storage()
+            .persistent()
+            .get(&schedule_key)
+            .unwrap_or_else(|| panic!("Schedule not found"));
+
+        if schedule.released {
+            panic!("Schedule already released");
+        }
+        if schedule.cancelled {
+            panic!("Schedule was cancelled");
+        }
+
+        let mut terms = schedule
+            .vesting
+            .clone()
+            .unwrap_or_else(|| panic!("Schedule is not a vesting schedule"));
+
+        let now = env.ledger().timestamp();
+        let vested = vested_amount(&terms, now);
+        let claimable = vested - terms.released_amount;
+        let unvested_amount = terms.total_amount - vested;
+
+        if claimable > 0 {
+            let contract_address = env.current_contract_address();
+            let token_client = token::Client::new(&env, &program_data.token_address);
+            let (_, fee_amount) = transfer_with_program_fee(
+                &env,
+                &program_id,
+                &token_client,
+                &contract_address,
+                &schedule.recipient,
+                claimable,
+            );
+
+            program_data.remaining_balance -= claimable;
+
+            let mut history: Vec<ProgramReleaseHistory> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::ReleaseHistory(program_id.clone()))
+                .unwrap_or(Vec::new(&env));
+            history.push_back(ProgramReleaseHistory {
+                schedule_id,
+                program_id: program_id.clone(),
+                amount: claimable,
+                recipient: schedule.recipient.clone(),
+                released_at: now,
+                released_by: admin.clone(),
+                release_type: ReleaseType::Vesting,
+                fee_amount,
+            });
+            env.storage()
+                .persistent()
+                .set(&DataKey::ReleaseHistory(program_id.clone()), &history);
+
+            terms.released_amount = vested;
+        }
+
+        schedule.vesting = Some(terms);
+        schedule.cancelled = true;
+        env.storage().persistent().set(&schedule_key, &schedule);
+
+        program_data.reserved_balance -= unvested_amount + claimable.max(0);
+        env.storage().instance().set(&program_key, &program_data);
+
+        env.events().publish(
+            (VESTING_TERMINATED,),
+            VestingTerminatedEvent {
+                program_id,
+                schedule_id,
+                vested_amount: claimable.max(0),
+                unvested_amount,
+                recipient: schedule.recipient.clone(),
+                terminated_by: admin,
+            },
+        );
+
+        program_data
+    }
+
+    /// Cancels a not-yet-released schedule, freeing its reservation back to
+    /// the available pool instead of leaving it stuck forever. Unlike
+    /// release, cancellation never moves tokens - the schedule's amount was
+    /// never debited from `remaining_balance`, only held against it via
+    /// `reserved_balance` - so cancelling just shrinks that reservation.
+    ///
+    /// The schedule is tombstoned (`cancelled = true`) rather than deleted:
+    /// its `schedule_id` can never be released or cancelled again, the same
+    /// way `Tombstone(program_id)` keeps a closed program's id from being
+    /// reused.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `program_id` - The program containing the schedule
+    /// * `schedule_id` - The schedule to cancel
+    ///
+    /// # Returns
+    /// * `ProgramData` - Updated program data with the reservation freed
+    ///
+    /// # Panics
+    /// * If program doesn't exist
+    /// * If caller is not authorized payout key
+    /// * If schedule doesn't exist
+    /// * If schedule is already released or already cancelled
+    ///
+    /// # Authorization
+    /// - Only authorized payout key can call this function
+    pub fn cancel_program_release_schedule(
+        env: Env,
+        program_id: String,
+        schedule_id: u64,
+    ) -> ProgramData {
+        let program_key = DataKey::Program(program_id.clone());
+        let mut program_data: ProgramData = env
+            .storage()
+            .instance()
+            .get(&program_key)
+            .unwrap_or_else(|| panic!("Program not found"));
+
+        program_data.authorized_payout_key.require_auth();
+
+        let schedule_key = DataKey::ReleaseSchedule(program_id.clone(), schedule_id);
+        let mut schedule: ProgramReleaseSchedule = env
+            .storage()
+            .persistent()
+            .get(&schedule_key)
+            .unwrap_or_else(|| panic!("Schedule not found"));
+
+        if schedule.released {
+            panic!("Schedule already released");
+        }
+        if schedule.cancelled {
+            panic!("Schedule already cancelled");
+        }
+
+        // For a vesting schedule, only the unclaimed remainder is still
+        // reserved; for a single-shot schedule, the whole amount is.
+        let amount_returned = match schedule.vesting.as_ref() {
+            Some(terms) => terms.total_amount - terms.released_amount,
+            None => schedule.amount,
+        };
+
+        schedule.cancelled = true;
+        env.storage().persistent().set(&schedule_key, &schedule);
+
+        program_data.reserved_balance -= amount_returned;
+        env.storage().instance().set(&program_key, &program_data);
+
+        let now = env.ledger().timestamp();
+        env.events().publish(
+            (PROG_SCHEDULE_CANCELLED,),
+            ProgramScheduleCancelled {
+                program_id,
+                schedule_id,
+                amount_returned,
+                cancelled_by: program_data.authorized_payout_key.clone(),
+                cancelled_at: now,
+            },
+        );
+
+        program_data
+    }
+
+    /// Attaches (or replaces) a realizor gating this specific schedule's
+    /// manual release - see [`ProgramReleaseSchedule::realizor`].
+    ///
+    /// # Authorization
+    /// - Only authorized payout key can call this function
+    ///
+    /// # Panics
+    /// * If the program or schedule doesn't exist
+    pub fn attach_schedule_realizor(
+        env: Env,
+        program_id: String,
+        schedule_id: u64,
+        realizor: Address,
+    ) -> ProgramReleaseSchedule {
+        Self::set_schedule_realizor_internal(&env, &program_id, schedule_id, Some(realizor))
+    }
+
+    /// Clears a schedule's realizor, letting it release unconditionally again.
+    ///
+    /// # Authorization
+    /// - Only authorized payout key can call this function
+    ///
+    /// # Panics
+    /// * If the program or schedule doesn't exist
+    pub fn clear_schedule_realizor(env: Env, program_id: String, schedule_id: u64) -> ProgramReleaseSchedule {
+        Self::set_schedule_realizor_internal(&env, &program_id, schedule_id, None)
+    }
+
+    fn set_schedule_realizor_internal(
+        env: &Env,
+        program_id: &String,
+        schedule_id: u64,
+        realizor: Option<Address>,
+    ) -> ProgramReleaseSchedule {
+        let program_key = DataKey::Program(program_id.clone());
+        let program_data: ProgramData = env
+            .storage()
+            .instance()
+            .get(&program_key)
+            .unwrap_or_else(|| panic!("Program not found"));
+        program_data.authorized_payout_key.require_auth();
+
+        let schedule_key = DataKey::ReleaseSchedule(program_id.clone(), schedule_id);
+        let mut schedule: ProgramReleaseSchedule = env
+            .storage()
+            .persistent()
+            .get(&schedule_key)
+            .unwrap_or_else(|| panic!("Schedule not found"));
+
+        schedule.realizor = realizor;
+        env.storage().persistent().set(&schedule_key, &schedule);
+        schedule
+    }
+
+    /// Keeper-callable top-up of a schedule's persistent-storage TTL, for
+    /// long-dated schedules that would otherwise expire before their
+    /// `release_timestamp`. Unlike the automatic extension
+    /// `create_program_release_schedule`/`create_vesting_release_schedule`
+    /// perform at creation time, this can be called repeatedly by anyone to
+    /// push the expiry further out - optionally paying a small maintenance
+    /// fee out of the program's balance, using the same fee-config
+    /// plumbing `release_prog_schedule_automatic` charges its payout fee
+    /// from.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `program_id` - The program containing the schedule
+    /// * `schedule_id` - The schedule to extend
+    /// * `ledgers` - How many additional ledgers to guarantee the entry survives
+    ///
+    /// # Returns
+    /// * `u64` - The ledger sequence the entry is now guaranteed to survive until
+    ///
+    /// # Panics
+    /// * If program or schedule doesn't exist
+    /// * If `ledgers` is zero
+    /// * If the maintenance fee is enabled but exceeds the remaining balance
+    pub fn extend_schedule_ttl(env: Env, program_id: String, schedule_id: u64, ledgers: u32) -> u64 {
+        if ledgers == 0 {
+            panic!("ledgers must be greater than zero");
+        }
+
+        let program_key = DataKey::Program(program_id.clone());
+        let mut program_data: ProgramData = env
+            .storage()
+            .instance()
+            .get(&program_key)
+            .unwrap_or_else(|| panic!("Program not found"));
+
+        let schedule_key = DataKey::ReleaseSchedule(program_id.clone(), schedule_id);
+        let schedule: ProgramReleaseSchedule = env
+            .storage()
+            .persistent()
+            .get(&schedule_key)
+            .unwrap_or_else(|| panic!("Schedule not found"));
+
+        // Optional small maintenance fee, reusing the payout fee-config plumbing
+        let fee_config = Self::get_fee_config_internal(&env);
+        let fee_amount = if fee_config.fee_enabled && fee_config.payout_fee_rate > 0 {
+            Self::calculate_fee(schedule.amount, fee_config.payout_fee_rate)
+        } else {
+            0
+        };
+        if fee_amount > 0 {
+            if fee_amount > program_data.remaining_balance {
+                panic!("Insufficient balance for TTL maintenance fee");
+            }
+            program_data.remaining_balance -= fee_amount;
+            env.storage().instance().set(&program_key, &program_data);
+
+            let contract_address = env.current_contract_address();
+            let token_client = token::Client::new(&env, &program_data.token_address);
+            token_client.transfer(&contract_address, &fee_config.fee_recipient, &fee_amount);
+
+            let fee_receipt_id = Self::increment_receipt_id(&env);
+            record_fee_ledger_entry(
+                &env,
+                &program_id,
+                FeeKind::ScheduleMaintenance,
+                schedule.amount,
+                fee_amount,
+                fee_config.payout_fee_rate,
+                &fee_config.fee_recipient,
+                fee_receipt_id,
+            );
+        }
+
+        extend_schedule_storage_ttl(&env, &program_id, schedule_id, ledgers)
+    }
+
+    // ========================================================================
+    // View Functions (Read-only)
+    // ========================================================================
+
+
+
+    /// Retrieves the remaining balance for a specific program.
+    ///
+    /// # Arguments
+    /// * `program_id` - The program ID to query
+    ///
+    /// # Returns
+    /// * `i128` - Remaining balance
+    ///
+    /// # Panics
+    /// * If program doesn't exist
+    pub fn get_remaining_balance(env: Env, program_id: String) -> i128 {
+        let program_key = DataKey::Program(program_id);
+        let program_data: ProgramData = env
+            .storage()
+            .instance()
+            .get(&program_key)
+            .unwrap_or_else(|| panic!("Program not found"));
+
+        program_data.remaining_balance
+    }
+
+    /// Update fee configuration (admin only - uses authorized_payout_key)
+    ///
+    /// # Arguments
+    /// * `lock_fee_rate` - Optional new lock fee rate (basis points)
+    /// * `payout_fee_rate` - Optional new payout fee rate (basis points)
+    /// * `fee_recipient` - Optional new fee recipient address
+    /// * `fee_enabled` - Optional fee enable/disable flag
+    pub fn update_fee_config(
+        env: Env,
+        lock_fee_rate: Option<i128>,
+        payout_fee_rate: Option<i128>,
+        fee_recipient: Option<Address>,
+        fee_enabled: Option<bool>,
+    ) {
+        let admin = anti_abuse::get_admin(&env).expect("Admin not set");
+        admin.require_auth();
+
+        // Once staged and active, `FEATURE_STRICT_FEE_CAP` tightens the
+        // allowed range without an abrupt flag flip - the admin schedules
+        // the stricter cap ahead of time and it takes effect on its own.
+        let max_fee_rate = if feature_gate::is_feature_active(&env, FEATURE_STRICT_FEE_CAP) {
+            MAX_FEE_RATE / 2
+        } else {
+            MAX_FEE_RATE
+        };
+
+        let mut fee_config = Self::get_fee_config_internal(&env);
+
+        if let Some(rate) = lock_fee_rate {
+            if rate < 0 || rate > max_fee_rate {
+                panic!(
+                    "Invalid lock fee rate: must be between 0 and {}",
+                    max_fee_rate
+                );
+            }
+            fee_config.lock_fee_rate = rate;
+        }
+
+        if let Some(rate) = payout_fee_rate {
+            if rate < 0 || rate > max_fee_rate {
+                panic!(
+                    "Invalid payout fee rate: must be between 0 and {}",
+                    max_fee_rate
+                );
+            }
+            fee_config.payout_fee_rate = rate;
+        }
+
+        if let Some(recipient) = fee_recipient {
+            fee_config.fee_recipient = recipient;
+        }
+
+        if let Some(enabled) = fee_enabled {
+            fee_config.fee_enabled = enabled;
+        }
+
+        env.storage().instance().set(&FEE_CONFIG, &fee_config);
+
+        // Emit fee config updated event
+        env.events().publish(
+            (symbol_short!("fee_cfg"),),
+            (
+                fee_config.lock_fee_rate,
+                fee_config.payout_fee_rate,
+                fee_config.fee_recipient,
+                fee_config.fee_enabled,
+            ),
+        );
+    }
+
+    /// Get current fee configuration (view function)
+    pub fn get_fee_config(env: Env) -> FeeConfig {
+        Self::get_fee_config_internal(&env)
+    }
+
+    /// Configures (or clears, with `fee_bps: 0`) a per-program percentage
+    /// platform fee withheld from releases and sent to `treasury` - see
+    /// `ProgramFeeConfig` (for which release paths withhold it) and
+    /// `transfer_with_program_fee`.
+    /// Overwrites any fixed fee previously set via `set_program_fee_fixed`.
+    ///
+    /// # Panics
+    /// * If caller is not the program's authorized payout key
+    /// * If `fee_bps` exceeds 10,000 (100%)
+    pub fn set_program_fee(env: Env, program_id: String, fee_bps: u32, treasury: Address) {
+        let program_data: ProgramData = env
+            .storage()
+            .instance()
+            .get(&DataKey::Program(program_id.clone()))
+            .unwrap_or_else(|| panic!("Program not found"));
+        program_data.authorized_payout_key.require_auth();
+
+        if fee_bps > 10_000 {
+            panic!("fee_bps cannot exceed 10000");
+        }
+
+        env.storage().instance().set(
+            &DataKey::ProgramFee(program_id),
+            &ProgramFeeConfig {
+                fee_mode: FeeMode::Percentage,
+                fee_bps,
+                fixed_fee_amount: 0,
+                treasury,
+            },
+        );
+    }
+
+    /// Configures a per-program flat-fee platform fee: every release path
+    /// that withholds the program fee (see `ProgramFeeConfig`) pays
+    /// exactly `fixed_fee_amount` to `treasury` instead of a percentage of
+    /// the payout - see `ProgramFeeConfig` and `transfer_with_program_fee`.
+    /// A payout smaller than `fixed_fee_amount` is rejected at release time
+    /// rather than charging more than the recipient would have received.
+    /// Overwrites any percentage fee previously set via `set_program_fee`.
+    ///
+    /// # Panics
+    /// * If caller is not the program's authorized payout key
+    /// * If `fixed_fee_amount` is negative
+    pub fn set_program_fee_fixed(env: Env, program_id: String, fixed_fee_amount: i128, treasury: Address) {
+        let program_data: ProgramData = env
+            .storage()
+            .instance()
+            .get(&DataKey::Program(program_id.clone()))
+            .unwrap_or_else(|| panic!("Program not found"));
+        program_data.authorized_payout_key.require_auth();
+
+        if fixed_fee_amount < 0 {
+            panic!("fixed_fee_amount cannot be negative");
+        }
+
+        env.storage().instance().set(
+            &DataKey::ProgramFee(program_id),
+            &ProgramFeeConfig {
+                fee_mode: FeeMode::Fixed,
+                fee_bps: 0,
+                fixed_fee_amount,
+                treasury,
+            },
+        );
+    }
+
+    /// Sets how long `single_payout`/`batch_payout` remember a `request_id`
+    /// for `program_id` before it becomes reclaimable - see `idempotency`.
+    /// Admin only.
+    ///
+    /// # Panics
+    /// * If `window_secs` is zero
+    pub fn set_idempotency_window(env: Env, program_id: String, window_secs: u64) {
+        let admin = anti_abuse::get_admin(&env).expect("Admin not set");
+        idempotency::set_idempotency_window(&env, &admin, &program_id, window_secs);
+    }
+
+    /// Total platform fee collected so far for `program_id` via
+    /// `set_program_fee` or `set_program_fee_fixed`, `0` if none has ever
+    /// been configured or charged.
+    pub fn get_accumulated_fees(env: Env, program_id: String) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::AccumulatedProgramFees(program_id))
+            .unwrap_or(0)
+    }
+
+    /// Stages a feature gate to flip `Active` at `activation_ts` (admin only).
+    /// See `feature_gate` for the `Inactive`/`Pending`/`Active` lifecycle.
+    pub fn stage_feature(env: Env, id: Symbol, activation_ts: u64) {
+        let admin = anti_abuse::get_admin(&env).expect("Admin not set");
+        feature_gate::stage_feature(&env, &admin, id, activation_ts);
+    }
+
+    /// Cancels a staged or active feature gate, reverting it to `Inactive` (admin only).
+    pub fn cancel_feature(env: Env, id: Symbol) {
+        let admin = anti_abuse::get_admin(&env).expect("Admin not set");
+        feature_gate::cancel_feature(&env, &admin, id);
+    }
+
+    /// Current status of feature `id` (view function).
+    pub fn feature_status(env: Env, id: Symbol) -> feature_gate::FeatureStatus {
+        feature_gate::feature_status(&env, id)
+    }
+
+    /// Enables `feature_id` (a single bit position) for `program_id`,
+    /// setting it in the program's feature bitmap. A separate, per-program
+    /// counterpart to the contract-wide, time-staged `feature_gate` module
+    /// above: this flips on immediately rather than at a future
+    /// `activation_ts`, and is scoped to one program rather than the whole
+    /// contract - intended for shipping new or risky behavior (new payout
+    /// modes, fixed-fee mode, etc.) dark and turning it on per-program
+    /// without a redeploy. Guarded call sites consult `is_feature_enabled`
+    /// and fall back to their legacy behavior when it's off.
+    ///
+    /// # Authorization
+    /// - Admin only (`anti_abuse::get_admin`)
+    pub fn enable_feature(env: Env, program_id: String, feature_id: u32) -> u64 {
+        Self::set_program_feature_bit(&env, program_id, feature_id, true)
+    }
+
+    /// Disables `feature_id` for `program_id` - the inverse of `enable_feature`.
+    ///
+    /// # Authorization
+    /// - Admin only (`anti_abuse::get_admin`)
+    pub fn disable_feature(env: Env, program_id: String, feature_id: u32) -> u64 {
+        Self::set_program_feature_bit(&env, program_id, feature_id, false)
+    }
+
+    fn set_program_feature_bit(env: &Env, program_id: String, feature_id: u32, enabled: bool) -> u64 {
+        let admin = anti_abuse::get_admin(env).expect("Admin not set");
+        admin.require_auth();
+
+        let key = DataKey::ProgramFeatures(program_id.clone());
+        let mut mask: u64 = env.storage().instance().get(&key).unwrap_or(0);
+        if enabled {
+            mask |= 1u64 << feature_id;
+        } else {
+            mask &= !(1u64 << feature_id);
+        }
+        env.storage().instance().set(&key, &mask);
+
+        env.events().publish(
+            (FEATURE_ACTIVATED,),
+            FeatureActivatedEvent {
+                program_id,
+                feature_id,
+                enabled,
+                admin,
+                activated_at: env.ledger().timestamp(),
+            },
+        );
+
+        mask
+    }
+
+    /// Whether `feature_id` is currently enabled for `program_id` (view function).
+    pub fn is_feature_enabled(env: Env, program_id: String, feature_id: u32) -> bool {
+        let mask: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ProgramFeatures(program_id))
+            .unwrap_or(0);
+        mask & (1u64 << feature_id) != 0
+    }
+
+    /// Feeds one settlement-time sample - `released_at` minus the
+    /// schedule's `release_timestamp` - into the contract-wide p50/p95
+    /// estimators. A negative gap (an early manual release, before
+    /// `release_timestamp`) is clamped to zero; there's no such thing as
+    /// negative settlement latency.
+    fn record_settlement_time(env: &Env, release_timestamp: u64, released_at: u64) {
+        let gap = released_at.saturating_sub(release_timestamp) as i128;
+
+        let p50_key = DataKey::SettlementP50Estimator;
+        let mut p50: percentile::P2Estimator = env
+            .storage()
+            .instance()
+            .get(&p50_key)
+            .unwrap_or_else(|| percentile::P2Estimator::new(env, 5_000));
+        p50.observe(env, gap);
+        env.storage().instance().set(&p50_key, &p50);
+
+        let p95_key = DataKey::SettlementP95Estimator;
+        let mut p95: percentile::P2Estimator = env
+            .storage()
+            .instance()
+            .get(&p95_key)
+            .unwrap_or_else(|| percentile::P2Estimator::new(env, 9_500));
+        p95.observe(env, gap);
+        env.storage().instance().set(&p95_key, &p95);
+    }
+
+    /// Streaming p50/p95 settlement-time estimates across every release so
+    /// far (view function) - see `SettlementTimePercentiles`.
+    pub fn get_settlement_time_percentiles(env: Env) -> SettlementTimePercentiles {
+        let p50: Option<percentile::P2Estimator> = env.storage().instance().get(&DataKey::SettlementP50Estimator);
+        let p95: Option<percentile::P2Estimator> = env.storage().instance().get(&DataKey::SettlementP95Estimator);
+        SettlementTimePercentiles {
+            p50_settlement_secs: p50.map(|e| e.estimate()).unwrap_or(0),
+            p95_settlement_secs: p95.map(|e| e.estimate()).unwrap_or(0),
+        }
+    }
+
+    /// Serializes every registered program's metadata, balances, and
+    /// pending (vesting/conditional/single-shot) release schedules, plus
+    /// the contract-wide fee config and pause state, into a
+    /// [`FullStateSnapshot`] an operator can later hand to `import_snapshot`
+    /// on a different instance - see `FullStateSnapshot` for exactly what
+    /// is and isn't covered.
+    ///
+    /// # Authorization
+    /// - Admin only (`anti_abuse::get_admin`)
+    pub fn export_snapshot(env: Env) -> FullStateSnapshot {
+        let admin = anti_abuse::get_admin(&env).expect("Admin not set");
+        admin.require_auth();
+
+        let registry: Vec<String> = env
+            .storage()
+            .instance()
+            .get(&PROGRAM_REGISTRY)
+            .unwrap_or(vec![&env]);
+
+        let mut programs: Vec<ProgramSnapshotEntry> = Vec::new(&env);
+        for program_id in registry.iter() {
+            let program_data: ProgramData = match env.storage().instance().get(&DataKey::Program(program_id.clone())) {
+                Some(data) => data,
+                None => continue,
+            };
+            let schedules = Self::get_all_prog_release_schedules(env.clone(), program_id.clone());
+            let fee_config: Option<ProgramFeeConfig> =
+                env.storage().instance().get(&DataKey::ProgramFee(program_id.clone()));
+
+            programs.push_back(ProgramSnapshotEntry {
+                program_id,
+                program_data,
+                schedules,
+                fee_config,
+            });
+        }
+
+        FullStateSnapshot {
+            snapshot_version: SNAPSHOT_VERSION,
+            exported_at: env.ledger().timestamp(),
+            fee_config: Self::get_fee_config_internal(&env),
+            pause_flags: Self::get_pause_flags(&env),
+            programs,
+        }
+    }
+
+    /// Rehydrates a [`FullStateSnapshot`] taken via `export_snapshot` onto
+    /// this instance. Only usable on a freshly-initialized instance - one
+    /// with no programs registered yet - so an import can never silently
+    /// clobber state that was already accumulating here.
+    ///
+    /// # Authorization
+    /// - Admin only (`anti_abuse::get_admin`)
+    ///
+    /// # Panics
+    /// * If this instance already has at least one registered program
+    /// * If `snapshot.snapshot_version` is newer than `SNAPSHOT_VERSION` or
+    ///   older than `MIN_READABLE_SNAPSHOT_VERSION`
+    pub fn import_snapshot(env: Env, snapshot: FullStateSnapshot) {
+        let admin = anti_abuse::get_admin(&env).expect("Admin not set");
+        admin.require_auth();
+
+        if env.storage().instance().has(&PROGRAM_REGISTRY) {
+            panic!("Instance already initialized; import_snapshot requires a fresh instance");
+        }
+
+        if snapshot.snapshot_version > SNAPSHOT_VERSION
+            || snapshot.snapshot_version < MIN_READABLE_SNAPSHOT_VERSION
+        {
+            panic!("Unsupported snapshot_version");
+        }
+
+        env.storage().instance().set(&FEE_CONFIG, &snapshot.fee_config);
+        env.storage()
+            .instance()
+            .set(&DataKey::PauseFlags, &snapshot.pause_flags);
+
+        let mut registry: Vec<String> = Vec::new(&env);
+        for entry in snapshot.programs.iter() {
+            env.storage()
+                .instance()
+                .set(&DataKey::Program(entry.program_id.clone()), &entry.program_data);
+
+            let mut next_schedule_id: u64 = 1;
+            for schedule in entry.schedules.iter() {
+                env.storage().persistent().set(
+                    &DataKey::ReleaseSchedule(entry.program_id.clone(), schedule.schedule_id),
+                    &schedule,
+                );
+                next_schedule_id = next_schedule_id.max(schedule.schedule_id + 1);
+            }
+            env.storage().persistent().set(
+                &DataKey::NextScheduleId(entry.program_id.clone()),
+                &next_schedule_id,
+            );
+
+            if let Some(fee_config) = entry.fee_config.clone() {
+                env.storage()
+                    .instance()
+                    .set(&DataKey::ProgramFee(entry.program_id.clone()), &fee_config);
+            }
+
+            registry.push_back(entry.program_id.clone());
+        }
+        env.storage().instance().set(&PROGRAM_REGISTRY, &registry);
+
+        env.events().publish(
+            (SNAPSHOT_IMPORTED,),
+            SnapshotImportedEvent {
+                source_version: snapshot.snapshot_version,
+                program_count: registry.len(),
+                imported_at: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    /// Gets the total number of programs registered.
+    ///
+    /// # Returns
+    /// * `u32` - Count of registered programs
+    pub fn get_program_count(env: Env) -> u32 {
+        let registry: Vec<String> = env
+            .storage()
+            .instance()
+            .get(&PROGRAM_REGISTRY)
+            .unwrap_or(vec![&env]);
+
+        registry.len()
+    }
+
+    // ========================================================================
+    // Program Lifecycle (Freeze / Settle)
+    // ========================================================================
+
+    /// Freezes `program_id`: locking, payouts, and new schedule creation are
+    /// blocked from here on, but already-created `ProgramReleaseSchedule`s
+    /// may still be released via `release_program_schedule_manual` so
+    /// in-flight obligations can drain. Computes and stores an immutable
+    /// `ProgramSettlement` snapshot of the program's accounting at the
+    /// moment of freezing, emitting `PROGRAM_FROZEN`. Admin-gated, like
+    /// `update_fee_config`.
+    pub fn freeze_program(env: Env, program_id: String) -> ProgramSettlement {
+        let admin = anti_abuse::get_admin(&env).expect("Admin not set");
+        admin.require_auth();
 
-        // Get program data
         let program_key = DataKey::Program(program_id.clone());
-        let mut program_data: ProgramData = env
+        let program_data: ProgramData = env
             .storage()
             .instance()
             .get(&program_key)
             .unwrap_or_else(|| panic!("Program not found"));
 
-        Self::assert_dependencies_satisfied(&env, &program_id);
+        if get_program_phase_internal(&env, &program_id) != ProgramPhase::Open {
+            panic!("Program already frozen or settled");
+        }
 
-        // Get schedule
-        if !env
-            .storage()
-            .persistent()
-            .has(&DataKey::ReleaseSchedule(program_id.clone(), schedule_id))
-        {
-            panic!("Schedule not found");
+        let mut total_paid_out: i128 = 0;
+        for record in program_data.payout_history.iter() {
+            total_paid_out += record.amount;
         }
 
-        let mut schedule: ProgramReleaseSchedule = env
-            .storage()
-            .persistent()
-            .get(&DataKey::ReleaseSchedule(program_id.clone(), schedule_id))
-            .unwrap();
+        let unreleased_schedule_count =
+            Self::get_pending_program_schedules(env.clone(), program_id.clone()).len();
 
-        // Check if already released
-        if schedule.released {
-            panic!("Schedule already released");
-        }
+        let settlement = ProgramSettlement {
+            program_id: program_id.clone(),
+            total_funds: program_data.total_funds,
+            total_paid_out,
+            remaining_balance: program_data.remaining_balance,
+            unreleased_schedule_count,
+            settled_at: env.ledger().timestamp(),
+        };
 
-        let now = env.ledger().timestamp();
-        if now < schedule.release_timestamp {
-            panic!("Schedule not yet due for release");
-        }
+        env.storage()
+            .instance()
+            .set(&DataKey::ProgramPhase(program_id.clone()), &ProgramPhase::Frozen);
+        env.storage()
+            .instance()
+            .set(&DataKey::ProgramSettlement(program_id.clone()), &settlement);
 
-        // Get token client
-        let contract_address = env.current_contract_address();
-        let token_client = token::Client::new(&env, &program_data.token_address);
+        env.events().publish(
+            (PROGRAM_FROZEN,),
+            ProgramFrozenEvent {
+                program_id,
+                total_funds: settlement.total_funds,
+                total_paid_out: settlement.total_paid_out,
+                remaining_balance: settlement.remaining_balance,
+                unreleased_schedule_count: settlement.unreleased_schedule_count,
+            },
+        );
 
-        // Transfer funds
-        token_client.transfer(&contract_address, &schedule.recipient, &schedule.amount);
+        settlement
+    }
 
-        // Update schedule
-        schedule.released = true;
-        schedule.released_at = Some(now);
-        schedule.released_by = Some(env.current_contract_address());
+    /// Settles an already-`Frozen` program: requires every schedule to have
+    /// released and refunds the live `remaining_balance` to
+    /// `refund_address` via the program's token, then marks the phase
+    /// `Settled`. The frozen `ProgramSettlement` snapshot from
+    /// `freeze_program` is left untouched - it records what the program
+    /// looked like at freeze time, not at settlement time. Admin-gated.
+    pub fn settle_program(env: Env, program_id: String, refund_address: Address) {
+        let admin = anti_abuse::get_admin(&env).expect("Admin not set");
+        admin.require_auth();
 
-        // Update program data
-        program_data.remaining_balance -= schedule.amount;
+        if get_program_phase_internal(&env, &program_id) != ProgramPhase::Frozen {
+            panic!("Program must be frozen before it can be settled");
+        }
 
-        // Add to release history
-        let history_entry = ProgramReleaseHistory {
-            schedule_id,
-            program_id: program_id.clone(),
-            amount: schedule.amount,
-            recipient: schedule.recipient.clone(),
-            released_at: now,
-            released_by: env.current_contract_address(),
-            release_type: ReleaseType::Automatic,
-        };
+        let pending = Self::get_pending_program_schedules(env.clone(), program_id.clone());
+        if !pending.is_empty() {
+            panic!("Cannot settle: unreleased schedules remain");
+        }
 
-        let mut history: Vec<ProgramReleaseHistory> = env
+        let program_key = DataKey::Program(program_id.clone());
+        let mut program_data: ProgramData = env
             .storage()
-            .persistent()
-            .get(&DataKey::ReleaseHistory(program_id.clone()))
-            .unwrap_or(Vec::new(&env));
-        history.push_back(history_entry);
-
-        // Store updates
-        env.storage().persistent().set(
-            &DataKey::ReleaseSchedule(program_id.clone(), schedule_id),
-            &schedule,
-        );
-        env.storage().instance().set(&program_key, &program_data);
-        env.storage()
-            .persistent()
-            .set(&DataKey::ReleaseHistory(program_id.clone()), &history);
+            .instance()
+            .get(&program_key)
+            .unwrap_or_else(|| panic!("Program not found"));
 
-        let receipt_id = Self::increment_receipt_id(&env);
+        let refunded_amount = program_data.remaining_balance;
+        if refunded_amount > 0 {
+            let contract_address = env.current_contract_address();
+            let token_client = token::Client::new(&env, &program_data.token_address);
+            token_client.transfer(&contract_address, &refund_address, &refunded_amount);
+            program_data.remaining_balance = 0;
+            env.storage().instance().set(&program_key, &program_data);
+        }
 
-        // Emit events
-        env.events().publish(
-            (PROG_SCHEDULE_RELEASED,),
-            ProgramScheduleReleased {
-                program_id: program_id.clone(),
-                schedule_id,
-                amount: schedule.amount,
-                recipient: schedule.recipient.clone(),
-                released_at: now,
-                released_by: env.current_contract_address(),
-                release_type: ReleaseType::Automatic,
-            },
-        );
+        env.storage()
+            .instance()
+            .set(&DataKey::ProgramPhase(program_id.clone()), &ProgramPhase::Settled);
 
         env.events().publish(
-            (PAYOUT,),
-            PayoutEvent {
-                version: EVENT_VERSION_V2,
-                program_id: program_data.program_id.clone(),
-                recipient: schedule.recipient.clone(),
-                amount: schedule.amount,
-                remaining_balance: program_data.remaining_balance,
-                receipt_id,
+            (PROGRAM_SETTLED,),
+            ProgramSettledEvent {
+                program_id,
+                refund_address,
+                refunded_amount,
             },
         );
+    }
 
-        // Track performance
-        let duration = env.ledger().timestamp().saturating_sub(start);
-        monitoring::emit_performance(&env, symbol_short!("rel_auto"), duration);
+    /// Read-only lookup of the `ProgramSettlement` snapshot `freeze_program`
+    /// captured, if the program has been frozen.
+    pub fn get_settlement(env: Env, program_id: String) -> ProgramSettlement {
+        env.storage()
+            .instance()
+            .get(&DataKey::ProgramSettlement(program_id))
+            .unwrap_or_else(|| panic!("Program has not been frozen"))
     }
 
-    /// Manually releases funds for a program schedule (authorized payout key only).
-    /// Can be called before the release timestamp by authorized key.
-    ///
-    /// # Arguments
-    /// * `env` - The contract environment
-    /// * `program_id` - The program containing the schedule
-    /// * `schedule_id` - The schedule to release
-    ///
-    /// # Panics
-    /// * If program doesn't exist
-    /// * If caller is not authorized payout key
-    /// * If schedule doesn't exist
-    /// * If schedule is already released
-    ///
-    /// # State Changes
-    /// - Transfers tokens to recipient
-    /// - Updates schedule status to released
-    /// - Adds to release history
-    /// - Updates program remaining balance
-    /// - Emits ScheduleReleased event
-    ///
-    /// # Authorization
-    /// - Only authorized payout key can call this function
-    ///
-    /// # Example
-    /// ```rust
-    /// // Authorized key can release early
-    /// escrow_client.release_program_schedule_manual(&"Hackathon2024", &1);
-    /// ```
-    pub fn release_program_schedule_manual(env: Env, program_id: String, schedule_id: u64) {
-        let start = env.ledger().timestamp();
+    /// Current lifecycle phase of `program_id` (view function).
+    pub fn get_program_phase(env: Env, program_id: String) -> ProgramPhase {
+        get_program_phase_internal(&env, &program_id)
+    }
 
-        // Get program data
+    /// Permanently retires a drained program: replaces its `DataKey::Program`
+    /// entry with a `ProgramTombstone` and drops it from `PROGRAM_REGISTRY`,
+    /// so `program_id` can never be handed to `initialize_program` /
+    /// `batch_initialize_programs` again. Requires `remaining_balance == 0` -
+    /// this is a terminal audit record, not a way to discard an active
+    /// program - and, like `freeze_program`/`settle_program`, is gated by the
+    /// program's own authorized payout key rather than the contract admin,
+    /// since it's the organizer finalizing their own program.
+    pub fn close_program(env: Env, program_id: String) -> ProgramTombstone {
         let program_key = DataKey::Program(program_id.clone());
         let program_data: ProgramData = env
             .storage()
@@ -2150,209 +6966,340 @@ impl ProgramEscrowContract {
             .get(&program_key)
             .unwrap_or_else(|| panic!("Program not found"));
 
-        // Apply rate limiting to the authorized payout key
-        anti_abuse::check_rate_limit(&env, program_data.authorized_payout_key.clone());
-
-        // Verify authorization
         program_data.authorized_payout_key.require_auth();
 
-        // Get schedule
-        if !env
-            .storage()
-            .persistent()
-            .has(&DataKey::ReleaseSchedule(program_id.clone(), schedule_id))
-        {
-            panic!("Schedule not found");
-        }
-
-        let mut schedule: ProgramReleaseSchedule = env
-            .storage()
-            .persistent()
-            .get(&DataKey::ReleaseSchedule(program_id.clone(), schedule_id))
-            .unwrap();
-
-        // Check if already released
-        if schedule.released {
-            panic!("Schedule already released");
+        if program_data.remaining_balance != 0 {
+            panic!("Program must have a zero remaining balance to close");
         }
 
-        // Get token client
-        let contract_address = env.current_contract_address();
-        let token_client = token::Client::new(&env, &program_data.token_address);
-
-        // Transfer funds
-        token_client.transfer(&contract_address, &schedule.recipient, &schedule.amount);
-
-        // Update schedule
-        let now = env.ledger().timestamp();
-        schedule.released = true;
-        schedule.released_at = Some(now);
-        schedule.released_by = Some(program_data.authorized_payout_key.clone());
-
-        // Update program data
-        let mut updated_data = program_data.clone();
-        updated_data.remaining_balance -= schedule.amount;
-
-        // Add to release history
-        let history_entry = ProgramReleaseHistory {
-            schedule_id,
+        let receipt_id = Self::increment_receipt_id(&env);
+        let closed_at = env.ledger().timestamp();
+        let tombstone = ProgramTombstone {
             program_id: program_id.clone(),
-            amount: schedule.amount,
-            recipient: schedule.recipient.clone(),
-            released_at: now,
-            released_by: program_data.authorized_payout_key.clone(),
-            release_type: ReleaseType::Manual,
+            closed_at,
+            final_receipt_id: receipt_id,
+            reference_hash: program_data.reference_hash.clone(),
         };
 
-        let mut history: Vec<ProgramReleaseHistory> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::ReleaseHistory(program_id.clone()))
-            .unwrap_or(vec![&env]);
-        history.push_back(history_entry);
-
-        // Store updates
-        env.storage().persistent().set(
-            &DataKey::ReleaseSchedule(program_id.clone(), schedule_id),
-            &schedule,
-        );
-        env.storage().instance().set(&program_key, &updated_data);
+        env.storage().instance().remove(&program_key);
         env.storage()
             .persistent()
-            .set(&DataKey::ReleaseHistory(program_id.clone()), &history);
+            .set(&DataKey::Tombstone(program_id.clone()), &tombstone);
+
+        let mut registry: Vec<String> = env
+            .storage()
+            .instance()
+            .get(&PROGRAM_REGISTRY)
+            .unwrap_or(Vec::new(&env));
+        let mut trimmed: Vec<String> = Vec::new(&env);
+        for entry in registry.iter() {
+            if entry != program_id {
+                trimmed.push_back(entry);
+            }
+        }
+        registry = trimmed;
+        env.storage().instance().set(&PROGRAM_REGISTRY, &registry);
 
-        // Emit program schedule released event
         env.events().publish(
-            (PROG_SCHEDULE_RELEASED,),
-            ProgramScheduleReleased {
-                program_id: program_id.clone(),
-                schedule_id,
-                amount: schedule.amount,
-                recipient: schedule.recipient.clone(),
-                released_at: now,
-                released_by: program_data.authorized_payout_key.clone(),
-                release_type: ReleaseType::Manual,
+            (PROGRAM_CLOSED,),
+            ProgramClosedEvent {
+                program_id,
+                final_receipt_id: receipt_id,
+                closed_at,
             },
         );
 
-        // Track successful operation
-        monitoring::track_operation(
-            &env,
-            symbol_short!("rel_man"),
-            program_data.authorized_payout_key,
-            true,
-        );
-
-        // Track performance
-        let duration = env.ledger().timestamp().saturating_sub(start);
-        monitoring::emit_performance(&env, symbol_short!("rel_man"), duration);
+        tombstone
     }
 
-    // ========================================================================
-    // View Functions (Read-only)
-    // ========================================================================
-
-
-
-    /// Retrieves the remaining balance for a specific program.
-    ///
-    /// # Arguments
-    /// * `program_id` - The program ID to query
-    ///
-    /// # Returns
-    /// * `i128` - Remaining balance
-    ///
-    /// # Panics
-    /// * If program doesn't exist
-    pub fn get_remaining_balance(env: Env, program_id: String) -> i128 {
-        let program_key = DataKey::Program(program_id);
+    /// Read-only lookup of a closed program's `ProgramTombstone`.
+    pub fn get_tombstone(env: Env, program_id: String) -> ProgramTombstone {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Tombstone(program_id))
+            .unwrap_or_else(|| panic!("Program has not been closed"))
+    }
+
+    /// Compares `program_id`'s token's live contract balance against the sum
+    /// of `remaining_balance` recorded across every registered program that
+    /// shares that token. Returns the `ReconcileReport` when the two agree
+    /// within `tolerance`; when they diverge beyond it, auto-pauses new fund
+    /// locking (mirroring how a node halts on detected state corruption) and
+    /// returns `EscrowError::BalanceDrift` instead, so the caller still
+    /// learns drift happened even though it doesn't get the report back.
+    pub fn reconcile(env: Env, program_id: String, tolerance: i128) -> Result<ReconcileReport, EscrowError> {
         let program_data: ProgramData = env
             .storage()
             .instance()
-            .get(&program_key)
-            .unwrap_or_else(|| panic!("Program not found"));
+            .get(&DataKey::Program(program_id))
+            .ok_or(EscrowError::ProgramNotFound)?;
 
-        program_data.remaining_balance
+        let registry: Vec<String> = env
+            .storage()
+            .instance()
+            .get(&PROGRAM_REGISTRY)
+            .unwrap_or(Vec::new(&env));
+
+        let mut recorded: i128 = 0;
+        for other_id in registry.iter() {
+            let other_data: Option<ProgramData> = env.storage().instance().get(&DataKey::Program(other_id));
+            if let Some(other_data) = other_data {
+                if other_data.token_address == program_data.token_address {
+                    recorded += other_data.remaining_balance;
+                }
+            }
+        }
+
+        let token_client = token::Client::new(&env, &program_data.token_address);
+        let on_chain = token_client.balance(&env.current_contract_address());
+        let drift = on_chain - recorded;
+
+        if drift.abs() > tolerance {
+            let mut flags = Self::get_pause_flags(&env);
+            if !flags.is_set(PAUSE_LOCK) {
+                flags.set_flag(PAUSE_LOCK, true);
+                flags.pause_reason = Some(String::from_str(&env, "Balance drift detected by reconcile"));
+                flags.paused_at = env.ledger().timestamp();
+                env.storage().instance().set(&DataKey::PauseFlags, &flags);
+
+                let receipt_id = Self::increment_receipt_id(&env);
+                env.events().publish(
+                    (PAUSE_STATE_CHANGED,),
+                    PauseStateChanged {
+                        operation: symbol_short!("lock"),
+                        paused: true,
+                        admin: env.current_contract_address(),
+                        reason: flags.pause_reason.clone(),
+                        timestamp: flags.paused_at,
+                        receipt_id,
+                    },
+                );
+            }
+            return Err(EscrowError::BalanceDrift);
+        }
+
+        Ok(ReconcileReport {
+            token_address: program_data.token_address,
+            recorded,
+            on_chain,
+            drift,
+        })
     }
 
-    /// Update fee configuration (admin only - uses authorized_payout_key)
-    ///
-    /// # Arguments
-    /// * `lock_fee_rate` - Optional new lock fee rate (basis points)
-    /// * `payout_fee_rate` - Optional new payout fee rate (basis points)
-    /// * `fee_recipient` - Optional new fee recipient address
-    /// * `fee_enabled` - Optional fee enable/disable flag
-    pub fn update_fee_config(
-        env: Env,
-        lock_fee_rate: Option<i128>,
-        payout_fee_rate: Option<i128>,
-        fee_recipient: Option<Address>,
-        fee_enabled: Option<bool>,
-    ) {
+    /// Reclaims up to `max` empty programs (admin only): zero
+    /// `remaining_balance`, no pending release schedules, no unfired
+    /// conditional payout plan, and no in-flight multisig approval. Deletes
+    /// the program's own per-program storage entries (`DataKey::Program`,
+    /// `MultisigConfig`, release schedules/history, fee ledger, and
+    /// conditional-payout state) and drops it from `PROGRAM_REGISTRY`,
+    /// emitting `ProgramReclaimed`. Programs that are `Frozen`/`Settled`
+    /// (see `freeze_program`/`settle_program`) or already tombstoned (see
+    /// `close_program`) are left alone - those lifecycles already keep their
+    /// own permanent audit record, and reclaiming here would destroy it.
+    /// Never touches a program that still has any balance, schedule, plan,
+    /// or approval referencing it.
+    pub fn sweep_empty_programs(env: Env, max: u32) -> u32 {
         let admin = anti_abuse::get_admin(&env).expect("Admin not set");
         admin.require_auth();
 
-        let mut fee_config = Self::get_fee_config_internal(&env);
+        let max = if max == 0 { 1 } else { max };
+        let registry: Vec<String> = env
+            .storage()
+            .instance()
+            .get(&PROGRAM_REGISTRY)
+            .unwrap_or(Vec::new(&env));
 
-        if let Some(rate) = lock_fee_rate {
-            if rate < 0 || rate > MAX_FEE_RATE {
-                panic!(
-                    "Invalid lock fee rate: must be between 0 and {}",
-                    MAX_FEE_RATE
-                );
+        let mut kept: Vec<String> = Vec::new(&env);
+        let mut reclaimed = 0u32;
+
+        for program_id in registry.iter() {
+            if reclaimed >= max {
+                kept.push_back(program_id);
+                continue;
             }
-            fee_config.lock_fee_rate = rate;
-        }
 
-        if let Some(rate) = payout_fee_rate {
-            if rate < 0 || rate > MAX_FEE_RATE {
-                panic!(
-                    "Invalid payout fee rate: must be between 0 and {}",
-                    MAX_FEE_RATE
-                );
+            let program_data: Option<ProgramData> =
+                env.storage().instance().get(&DataKey::Program(program_id.clone()));
+            let program_data = match program_data {
+                Some(data) => data,
+                None => {
+                    kept.push_back(program_id);
+                    continue;
+                }
+            };
+
+            let reclaimable = program_data.remaining_balance == 0
+                && get_program_phase_internal(&env, &program_id) == ProgramPhase::Open
+                && Self::get_pending_program_schedules(env.clone(), program_id.clone()).is_empty()
+                && !has_active_conditional_plans(&env, &program_id)
+                && !has_pending_multisig_approval(&env, &program_id);
+
+            if !reclaimable {
+                kept.push_back(program_id);
+                continue;
             }
-            fee_config.payout_fee_rate = rate;
-        }
 
-        if let Some(recipient) = fee_recipient {
-            fee_config.fee_recipient = recipient;
-        }
+            let next_schedule_id: u64 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::NextScheduleId(program_id.clone()))
+                .unwrap_or(1);
+            for schedule_id in 1..next_schedule_id {
+                env.storage()
+                    .persistent()
+                    .remove(&DataKey::ReleaseSchedule(program_id.clone(), schedule_id));
+            }
+            env.storage()
+                .persistent()
+                .remove(&DataKey::NextScheduleId(program_id.clone()));
+            env.storage()
+                .persistent()
+                .remove(&DataKey::ReleaseHistory(program_id.clone()));
 
-        if let Some(enabled) = fee_enabled {
-            fee_config.fee_enabled = enabled;
+            let next_plan_id: u64 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::NextPlanId(program_id.clone()))
+                .unwrap_or(1);
+            for plan_id in 1..next_plan_id {
+                env.storage()
+                    .persistent()
+                    .remove(&DataKey::ConditionalPlan(program_id.clone(), plan_id));
+                env.storage()
+                    .persistent()
+                    .remove(&DataKey::PlanWitnesses(program_id.clone(), plan_id));
+            }
+            env.storage()
+                .persistent()
+                .remove(&DataKey::NextPlanId(program_id.clone()));
+
+            env.storage()
+                .persistent()
+                .remove(&DataKey::MultisigConfig(program_id.clone()));
+            env.storage()
+                .instance()
+                .remove(&DataKey::FeeLedger(program_id.clone()));
+            env.storage()
+                .instance()
+                .remove(&DataKey::ApprovalNonce(program_id.clone()));
+            env.storage()
+                .instance()
+                .remove(&DataKey::ProgramPhase(program_id.clone()));
+            env.storage()
+                .instance()
+                .remove(&DataKey::ProgramParent(program_id.clone()));
+            env.storage()
+                .instance()
+                .remove(&DataKey::Program(program_id.clone()));
+
+            reclaimed += 1;
+            let receipt_id = Self::increment_receipt_id(&env);
+            env.events().publish(
+                (PROGRAM_RECLAIMED,),
+                ProgramReclaimedEvent {
+                    program_id,
+                    receipt_id,
+                },
+            );
         }
 
-        env.storage().instance().set(&FEE_CONFIG, &fee_config);
+        env.storage().instance().set(&PROGRAM_REGISTRY, &kept);
 
-        // Emit fee config updated event
-        env.events().publish(
-            (symbol_short!("fee_cfg"),),
-            (
-                fee_config.lock_fee_rate,
-                fee_config.payout_fee_rate,
-                fee_config.fee_recipient,
-                fee_config.fee_enabled,
-            ),
-        );
+        reclaimed
     }
 
-    /// Get current fee configuration (view function)
-    pub fn get_fee_config(env: Env) -> FeeConfig {
-        Self::get_fee_config_internal(&env)
-    }
+    // ========================================================================
+    // Fee Ledger & Accounting
+    // ========================================================================
 
-    /// Gets the total number of programs registered.
-    ///
-    /// # Returns
-    /// * `u32` - Count of registered programs
-    pub fn get_program_count(env: Env) -> u32 {
-        let registry: Vec<String> = env
+    /// Itemized fee history for `program_id` plus rolled-up totals: fees by
+    /// `FeeKind`, a grand total, and an effective average rate in basis
+    /// points (grand total over the total gross amount fees were charged
+    /// against, `0` if nothing has been collected yet).
+    pub fn get_fee_report(env: Env, program_id: String) -> FeeReport {
+        let entries: Vec<FeeLedgerEntry> = env
             .storage()
             .instance()
-            .get(&PROGRAM_REGISTRY)
+            .get(&DataKey::FeeLedger(program_id))
             .unwrap_or(vec![&env]);
 
-        registry.len()
+        let mut total_lock_fees: i128 = 0;
+        let mut total_payout_fees: i128 = 0;
+        let mut total_batch_payout_fees: i128 = 0;
+        let mut total_scheduled_release_fees: i128 = 0;
+        let mut grand_total: i128 = 0;
+        let mut total_gross: i128 = 0;
+
+        for entry in entries.iter() {
+            match entry.kind {
+                FeeKind::Lock => total_lock_fees += entry.fee_amount,
+                FeeKind::Payout => total_payout_fees += entry.fee_amount,
+                FeeKind::BatchPayout => total_batch_payout_fees += entry.fee_amount,
+                FeeKind::ScheduledRelease => total_scheduled_release_fees += entry.fee_amount,
+                // `FeeReport` has no per-kind total for TTL maintenance
+                // fees; they count toward `grand_total` only.
+                FeeKind::ScheduleMaintenance => {}
+            }
+            grand_total += entry.fee_amount;
+            total_gross += entry.gross_amount;
+        }
+
+        let effective_rate_bps = if total_gross > 0 {
+            (grand_total * BASIS_POINTS) / total_gross
+        } else {
+            0
+        };
+
+        FeeReport {
+            entries,
+            total_lock_fees,
+            total_payout_fees,
+            total_batch_payout_fees,
+            total_scheduled_release_fees,
+            grand_total,
+            effective_rate_bps,
+        }
+    }
+
+    /// Merges `ProgramAggregateStats` with `get_fee_report` so an organizer
+    /// can reconcile locked-vs-paid-vs-fees for `program_id` in one call.
+    pub fn get_program_accounting(env: Env, program_id: String) -> ProgramAccountingReport {
+        let program_key = DataKey::Program(program_id.clone());
+        let program_data: ProgramData = env
+            .storage()
+            .instance()
+            .get(&program_key)
+            .unwrap_or_else(|| panic!("Program not found"));
+
+        let mut total_paid_out: i128 = 0;
+        for record in program_data.payout_history.iter() {
+            total_paid_out += record.amount;
+        }
+
+        let all_schedules = Self::get_all_prog_release_schedules(env.clone(), program_id.clone());
+        let scheduled_count = all_schedules.len();
+        let mut released_count: u32 = 0;
+        for schedule in all_schedules.iter() {
+            if schedule.released {
+                released_count += 1;
+            }
+        }
+
+        let stats = ProgramAggregateStats {
+            total_funds: program_data.total_funds,
+            remaining_balance: program_data.remaining_balance,
+            total_paid_out,
+            authorized_payout_key: program_data.authorized_payout_key.clone(),
+            payout_history: program_data.payout_history.clone(),
+            token_address: program_data.token_address.clone(),
+            payout_count: program_data.payout_history.len(),
+            scheduled_count,
+            released_count,
+        };
+
+        let fee_report = Self::get_fee_report(env, program_id);
+
+        ProgramAccountingReport { stats, fee_report }
     }
 
     // ========================================================================
@@ -2445,6 +7392,11 @@ impl ProgramEscrowContract {
             .unwrap_or(0)
             + 1;
 
+        let parent_id: Option<u64> = env
+            .storage()
+            .instance()
+            .get(&ConfigSnapshotKey::SnapshotCounter);
+
         let snapshot = ConfigSnapshot {
             id: next_id,
             timestamp: env.ledger().timestamp(),
@@ -2452,6 +7404,8 @@ impl ProgramEscrowContract {
             anti_abuse_config: anti_abuse::get_config(&env),
             anti_abuse_admin: anti_abuse::get_admin(&env),
             is_paused: Self::is_paused_internal(&env),
+            parent_id,
+            frozen: false,
         };
 
         env.storage()
@@ -2515,7 +7469,106 @@ impl ProgramEscrowContract {
         snapshots
     }
 
+    /// Freezes a snapshot (admin-only), marking it "rooted": immutable and
+    /// the only kind `restore_config_snapshot` will accept. Mirrors the
+    /// bank-chain's open -> frozen -> rooted progression, except frozen and
+    /// rooted collapse into one flag here since nothing else ever mutates
+    /// a stored `ConfigSnapshot` once written.
+    pub fn freeze_config_snapshot(env: Env, snapshot_id: u64) {
+        let admin = anti_abuse::get_admin(&env).expect("Admin not set");
+        admin.require_auth();
+
+        let mut snapshot: ConfigSnapshot = env
+            .storage()
+            .instance()
+            .get(&ConfigSnapshotKey::Snapshot(snapshot_id))
+            .unwrap_or_else(|| panic!("Snapshot not found"));
+
+        snapshot.frozen = true;
+        env.storage()
+            .instance()
+            .set(&ConfigSnapshotKey::Snapshot(snapshot_id), &snapshot);
+
+        env.events().publish(
+            (symbol_short!("cfg_snap"), symbol_short!("freeze")),
+            snapshot_id,
+        );
+    }
+
+    /// Reports exactly which tracked fields differ between two snapshots,
+    /// walking their stored values rather than the live chain - each
+    /// `ConfigSnapshot` already embeds a full config copy, so no
+    /// intermediate snapshot needs to be read to produce the diff.
+    pub fn diff_config_snapshots(env: Env, from_id: u64, to_id: u64) -> ConfigSnapshotDiff {
+        let from: ConfigSnapshot = env
+            .storage()
+            .instance()
+            .get(&ConfigSnapshotKey::Snapshot(from_id))
+            .unwrap_or_else(|| panic!("Snapshot not found"));
+        let to: ConfigSnapshot = env
+            .storage()
+            .instance()
+            .get(&ConfigSnapshotKey::Snapshot(to_id))
+            .unwrap_or_else(|| panic!("Snapshot not found"));
+
+        ConfigSnapshotDiff {
+            from_id,
+            to_id,
+            lock_fee_rate: if from.fee_config.lock_fee_rate != to.fee_config.lock_fee_rate {
+                Some(to.fee_config.lock_fee_rate)
+            } else {
+                None
+            },
+            payout_fee_rate: if from.fee_config.payout_fee_rate != to.fee_config.payout_fee_rate {
+                Some(to.fee_config.payout_fee_rate)
+            } else {
+                None
+            },
+            fee_recipient: if from.fee_config.fee_recipient != to.fee_config.fee_recipient {
+                Some(to.fee_config.fee_recipient.clone())
+            } else {
+                None
+            },
+            fee_enabled: if from.fee_config.fee_enabled != to.fee_config.fee_enabled {
+                Some(to.fee_config.fee_enabled)
+            } else {
+                None
+            },
+            anti_abuse_window_size: if from.anti_abuse_config.window_size
+                != to.anti_abuse_config.window_size
+            {
+                Some(to.anti_abuse_config.window_size)
+            } else {
+                None
+            },
+            anti_abuse_max_operations: if from.anti_abuse_config.max_operations
+                != to.anti_abuse_config.max_operations
+            {
+                Some(to.anti_abuse_config.max_operations)
+            } else {
+                None
+            },
+            anti_abuse_cooldown_period: if from.anti_abuse_config.cooldown_period
+                != to.anti_abuse_config.cooldown_period
+            {
+                Some(to.anti_abuse_config.cooldown_period)
+            } else {
+                None
+            },
+            admin_changed: from.anti_abuse_admin != to.anti_abuse_admin,
+            new_admin: to.anti_abuse_admin.clone(),
+            is_paused: if from.is_paused != to.is_paused {
+                Some(to.is_paused)
+            } else {
+                None
+            },
+        }
+    }
+
     /// Restores contract configuration from a prior snapshot (admin-only).
+    /// Restricted to frozen ("rooted") snapshots, so only a snapshot an
+    /// operator has explicitly vetted via `freeze_config_snapshot` (after
+    /// reviewing it with `diff_config_snapshots`) can be restored from.
     pub fn restore_config_snapshot(env: Env, snapshot_id: u64) {
         let admin = anti_abuse::get_admin(&env).expect("Admin not set");
         admin.require_auth();
@@ -2526,6 +7579,10 @@ impl ProgramEscrowContract {
             .get(&ConfigSnapshotKey::Snapshot(snapshot_id))
             .unwrap_or_else(|| panic!("Snapshot not found"));
 
+        if !snapshot.frozen {
+            panic!("Snapshot is not frozen");
+        }
+
         env.storage()
             .instance()
             .set(&FEE_CONFIG, &snapshot.fee_config);
@@ -2721,18 +7778,30 @@ impl ProgramEscrowContract {
             10_000
         };
 
-        let overall_score_bps: u32 =
+        let base_score_bps: u32 =
             (completion_rate_bps as u64 * 60 + payout_fulfillment_rate_bps as u64 * 40) as u32
                 / 100;
 
+        // Contested payouts drag the score down proportional to how much
+        // of the program's scheduled volume they touched, instead of a
+        // flat per-dispute penalty that would hit a tiny program as hard
+        // as a huge one.
+        let dispute_penalty_bps: u32 = if total_scheduled > 0 {
+            ((program_data.dispute_count as u64 * BASIS_POINTS as u64) / total_scheduled as u64)
+                as u32
+        } else {
+            0
+        };
+        let overall_score_bps: u32 = base_score_bps.saturating_sub(dispute_penalty_bps);
+
         ProgramReputationScore {
             total_payouts,
             total_scheduled,
             completed_releases,
             pending_releases,
             overdue_releases,
-            dispute_count: 0,
-            refund_count: 0,
+            dispute_count: program_data.dispute_count,
+            refund_count: program_data.refund_count,
             total_funds_locked,
             total_funds_distributed,
             completion_rate_bps,
@@ -2740,6 +7809,148 @@ impl ProgramEscrowContract {
             overall_score_bps,
         }
     }
+
+    // ── payout_splits entrypoints ────────────────────────────────────────
+    // Thin wrappers over `payout_splits` - see that module for the actual
+    // validation/authorization/accounting logic. Each function there
+    // already self-authorizes (`authorized_payout_key`/`admin`/beneficiary
+    // `require_auth`), so these just forward arguments and return values.
+
+    /// Set (or replace) `program_id`'s beneficiary split configuration. See
+    /// `payout_splits::set_split_config`.
+    pub fn set_split_config(
+        env: Env,
+        program_id: String,
+        beneficiaries: Vec<payout_splits::BeneficiarySplit>,
+        min_payout: Option<i128>,
+        dust_mode: Option<payout_splits::DustMode>,
+        vesting: Option<payout_splits::VestingSchedule>,
+        payout_mode: Option<payout_splits::PayoutMode>,
+    ) -> Result<payout_splits::SplitConfig, payout_splits::EscrowError> {
+        payout_splits::set_split_config(
+            &env,
+            &program_id,
+            beneficiaries,
+            min_payout,
+            dust_mode,
+            vesting,
+            payout_mode,
+        )
+    }
+
+    /// Current split configuration for `program_id`, if any (view function).
+    /// See `payout_splits::get_split_config`.
+    pub fn get_split_config(env: Env, program_id: String) -> Option<payout_splits::SplitConfig> {
+        payout_splits::get_split_config(&env, &program_id)
+    }
+
+    /// Confirm `recipient` as accepting their beneficiary slot in
+    /// `program_id`'s split config. See `payout_splits::confirm_beneficiary`.
+    pub fn confirm_beneficiary(env: Env, program_id: String, recipient: Address) {
+        payout_splits::confirm_beneficiary(&env, &program_id, &recipient)
+    }
+
+    /// Force-activate `program_id`'s split config regardless of outstanding
+    /// confirmations. See `payout_splits::force_activate_split_config`.
+    pub fn force_activate_split_config(env: Env, program_id: String) {
+        payout_splits::force_activate_split_config(&env, &program_id)
+    }
+
+    /// Deactivate `program_id`'s split config. See
+    /// `payout_splits::disable_split_config`.
+    pub fn disable_split_config(env: Env, program_id: String) -> Result<(), payout_splits::EscrowError> {
+        payout_splits::disable_split_config(&env, &program_id)
+    }
+
+    /// Execute a split payout of `total_amount` for `program_id`. See
+    /// `payout_splits::execute_split_payout`.
+    pub fn execute_split_payout(
+        env: Env,
+        program_id: String,
+        total_amount: i128,
+        drain: bool,
+        min_remaining_balance: Option<i128>,
+    ) -> Result<payout_splits::SplitPayoutResult, payout_splits::EscrowError> {
+        payout_splits::execute_split_payout(&env, &program_id, total_amount, drain, min_remaining_balance)
+    }
+
+    /// Execute a split payout of `total_amount` for `program_id` in batches
+    /// of up to `batch_size` beneficiaries per call. See
+    /// `payout_splits::execute_split_payout_partitioned`.
+    pub fn execute_split_payout_partitioned(
+        env: Env,
+        program_id: String,
+        total_amount: i128,
+        batch_size: u32,
+    ) -> Result<payout_splits::SplitPayoutBatchResult, payout_splits::EscrowError> {
+        payout_splits::execute_split_payout_partitioned(&env, &program_id, total_amount, batch_size)
+    }
+
+    /// Withdraw `beneficiary`'s accrued `PayoutMode::Pull` balance for
+    /// `program_id`. See `payout_splits::claim_split`.
+    pub fn claim_split(env: Env, program_id: String, beneficiary: Address) -> Result<i128, payout_splits::EscrowError> {
+        payout_splits::claim_split(&env, &program_id, &beneficiary)
+    }
+
+    /// Every beneficiary's outstanding pull-mode claimable balance for
+    /// `program_id` (view function). See
+    /// `payout_splits::preview_unclaimed_split`.
+    pub fn preview_unclaimed_split(
+        env: Env,
+        program_id: String,
+    ) -> Result<Vec<payout_splits::SplitPreviewEntry>, payout_splits::EscrowError> {
+        payout_splits::preview_unclaimed_split(&env, &program_id)
+    }
+
+    /// Hypothetical per-beneficiary split of `total_amount` for
+    /// `program_id`, without executing transfers (view function). See
+    /// `payout_splits::preview_split`.
+    pub fn preview_split(
+        env: Env,
+        program_id: String,
+        total_amount: i128,
+    ) -> Result<Vec<payout_splits::SplitPreviewEntry>, payout_splits::EscrowError> {
+        payout_splits::preview_split(&env, &program_id, total_amount)
+    }
+
+    /// Smallest `total_amount` that clears every beneficiary's `min_payout`
+    /// for `program_id` (view function). See
+    /// `payout_splits::min_viable_payout`.
+    pub fn min_viable_payout(env: Env, program_id: String) -> i128 {
+        payout_splits::min_viable_payout(&env, &program_id)
+    }
+
+    /// Currently vested-but-unreleased amount for `program_id`'s split
+    /// config (view function). See `payout_splits::vested_amount`.
+    pub fn vested_amount(env: Env, program_id: String) -> i128 {
+        payout_splits::vested_amount(&env, &program_id)
+    }
+
+    /// Every beneficiary's currently claimable (vested but unreleased)
+    /// amount for `program_id` (view function). See
+    /// `payout_splits::preview_claimable_split`.
+    pub fn preview_claimable_split(
+        env: Env,
+        program_id: String,
+    ) -> Result<Vec<payout_splits::SplitPreviewEntry>, payout_splits::EscrowError> {
+        payout_splits::preview_claimable_split(&env, &program_id)
+    }
+
+    /// Validate `program_id`'s split config's recipients are receivable
+    /// (view function). See `payout_splits::validate_split_recipients`.
+    pub fn validate_split_recipients(env: Env, program_id: String) -> Result<(), payout_splits::EscrowError> {
+        payout_splits::validate_split_recipients(&env, &program_id)
+    }
+
+    /// Admin-only early termination of `program_id`'s split config. See
+    /// `payout_splits::terminate_split_config`.
+    pub fn terminate_split_config(
+        env: Env,
+        program_id: String,
+        recovery_address: Option<Address>,
+    ) -> Result<payout_splits::SplitTerminationResult, payout_splits::EscrowError> {
+        payout_splits::terminate_split_config(&env, &program_id, recovery_address)
+    }
 }
 
 /// Helper function to calculate total scheduled amount for a program.
@@ -1,33 +1,90 @@
 // ============================================================
 // FILE: contracts/program-escrow/src/payout_splits.rs
 //
-// This module implements multi-beneficiary payout splits for Issue #[issue_id].
-//
-// Enables a single escrow to distribute funds across multiple recipients
-// using predefined share ratios, avoiding the need for multiple escrows.
+// Multi-beneficiary payout splits: lets a single escrow program distribute
+// funds across multiple recipients using predefined share ratios, avoiding
+// the need to stand up a separate program per recipient.
 //
 // ## Design
 //
 // - Shares are expressed in basis points (1 bp = 0.01%), summing to 10_000 (100%)
-// - Dust (remainder after integer division) is awarded to the first beneficiary
+// - Dust (remainder after integer division) is awarded per `DustMode`
 // - Splits are stored per-program and validated at creation time
 // - Both partial releases and full releases honour the ratio
-//
-// ## Integration (lib.rs)
-//
-//   mod payout_splits;
-//   pub use payout_splits::{BeneficiarySplit, SplitConfig};
-//
-// Add the following DataKey variants if not already present:
-//
-//   SplitConfig(String),   // program_id -> SplitConfig
-//
-// Expose the public functions inside the `ProgramEscrowContract` impl block.
 // ============================================================
 
-use soroban_sdk::{contracttype, symbol_short, token, Address, Env, String, Symbol, Vec};
+use soroban_sdk::{contracterror, contracttype, symbol_short, token, Address, Env, String, Symbol, Vec};
 use crate::{DataKey, ProgramData, PayoutRecord, PROGRAM_DATA};
 
+/// Typed failure reasons for the `payout_splits` validation paths, returned
+/// via `Result` instead of a string panic so callers can branch on the exact
+/// cause instead of matching panic messages.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum EscrowError {
+    /// `SplitConfig::beneficiaries` was empty.
+    NoBeneficiaries = 1,
+    /// More than 50 beneficiaries were supplied.
+    TooManyBeneficiaries = 2,
+    /// A beneficiary's `share_bps` was zero or negative.
+    ZeroShare = 3,
+    /// Beneficiary shares did not sum to `TOTAL_BASIS_POINTS`.
+    SharesDoNotSumTo10000 = 4,
+    /// `min_payout` was zero or negative.
+    NonPositiveMinPayout = 5,
+    /// No `SplitConfig` exists for the given program.
+    NoSplitConfig = 6,
+    /// The stored `SplitConfig` is not `active`.
+    SplitDisabled = 7,
+    /// `total_amount` was zero or negative.
+    NonPositiveAmount = 8,
+    /// `total_amount` exceeds `ProgramData::remaining_balance`.
+    InsufficientBalance = 9,
+    /// `drain` was true but `total_amount` did not equal the full remaining balance.
+    DrainAmountMismatch = 10,
+    /// `min_remaining_balance` was zero or negative.
+    NonPositiveMinRemainingBalance = 11,
+    /// A partial release would leave the escrow in the forbidden dust band.
+    ForbiddenDustBalance = 12,
+    /// The program has already been drained and its split config closed.
+    AlreadyDrained = 13,
+    /// A beneficiary's floor-divided share fell below `min_payout`.
+    BelowMinPayout = 14,
+    /// `cliff_ts` was earlier than `start_ts`, or `duration` was zero.
+    InvalidVestingSchedule = 15,
+    /// `total_amount` exceeds the currently vested-but-unreleased amount.
+    ExceedsVestedAmount = 16,
+    /// Two beneficiaries share the same recipient address.
+    DuplicateRecipient = 17,
+    /// A beneficiary's recipient is the escrow contract itself.
+    RecipientIsContract = 18,
+    /// A beneficiary's computed allocation for the release is not positive.
+    NonPositiveAllocation = 19,
+    /// `claim_split` was called with nothing accrued for the beneficiary.
+    NoClaimableBalance = 20,
+    /// No `ProgramData` exists for the given program id.
+    ProgramNotFound = 21,
+    /// The operation is currently blocked by an active pause flag.
+    OperationPaused = 22,
+    /// The operation requires the contract to be paused first.
+    ContractNotPaused = 23,
+    /// `initialize_program`/`batch_initialize_programs` was called with a
+    /// program id that is already in use.
+    ProgramAlreadyInitialized = 24,
+    /// `initialize_program` was called with a program id that has a
+    /// `ProgramTombstone` and can never be reused.
+    ProgramClosed = 25,
+    /// `reconcile` found the live token balance and the sum of recorded
+    /// `remaining_balance`s diverge by more than the caller's tolerance.
+    BalanceDrift = 26,
+    /// `execute_split_payout_partitioned` was called with a zero `batch_size`.
+    NonPositiveBatchSize = 27,
+    /// `execute_split_payout_partitioned` was called with a `total_amount`
+    /// that does not match the release already in progress for this program.
+    BatchAmountMismatch = 28,
+}
+
 // ---------------------------------------------------------------------------
 // Constants
 // ---------------------------------------------------------------------------
@@ -38,11 +95,57 @@ pub const TOTAL_BASIS_POINTS: i128 = 10_000;
 // Event symbols
 const SPLIT_CONFIG_SET: Symbol = symbol_short!("SplitCfg");
 const SPLIT_PAYOUT: Symbol = symbol_short!("SplitPay");
+const BENEFICIARY_CONFIRMED: Symbol = symbol_short!("SplitCnf");
+const SPLIT_ACTIVATED: Symbol = symbol_short!("SplitAct");
+const SPLIT_PAYOUT_BATCH: Symbol = symbol_short!("SplitBat");
+const SPLIT_TERMINATED: Symbol = symbol_short!("SplitTrm");
+const SPLIT_CLAIMED: Symbol = symbol_short!("SplitClm");
 
 // ---------------------------------------------------------------------------
 // Data types
 // ---------------------------------------------------------------------------
 
+/// How leftover dust from floor-division is apportioned across beneficiaries.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DustMode {
+    /// Award all dust to beneficiary index 0 (the original, simplest behaviour).
+    FirstIndex,
+    /// Distribute dust one unit at a time to the beneficiaries with the largest
+    /// fractional remainder, breaking ties by index order. Spreads rounding
+    /// error evenly across beneficiaries instead of always favouring index 0.
+    LargestRemainder,
+}
+
+/// How `execute_split_payout` settles a beneficiary's due amount.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PayoutMode {
+    /// Transfer each beneficiary's due amount directly (the original,
+    /// simplest behaviour). One unreachable recipient (frozen account,
+    /// missing trustline) reverts the whole release.
+    Push,
+    /// Credit each beneficiary's due amount into a per-recipient claim
+    /// ledger instead of transferring it. `remaining_balance` is still
+    /// decremented immediately; each beneficiary withdraws independently via
+    /// `claim_split`, so one unreachable recipient can't block the rest.
+    Pull,
+}
+
+/// Optional cliff + linear vesting schedule attached to a `SplitConfig`.
+///
+/// All timestamps are Unix seconds, read against `env.ledger().timestamp()`.
+/// Before `cliff_ts` nothing is releasable; from `cliff_ts` to
+/// `start_ts + duration` the releasable total grows linearly; at and after
+/// `start_ts + duration` the full `ProgramData::total_funds` is releasable.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingSchedule {
+    pub start_ts: u64,
+    pub cliff_ts: u64,
+    pub duration: u64,
+}
+
 /// One entry in a split configuration.
 ///
 /// `share_bps` is this beneficiary's portion expressed in basis points.
@@ -64,6 +167,48 @@ pub struct SplitConfig {
     pub beneficiaries: Vec<BeneficiarySplit>,
     /// Whether this config is currently active.
     pub active: bool,
+    /// Smallest share amount (after floor division) a beneficiary may be
+    /// paid in a single `execute_split_payout` call. Mirrors Solana's
+    /// rent-exempt / minimum-viable-balance check for stake split
+    /// destinations: rather than silently skip an under-floor share, the
+    /// release is rejected outright. Defaults to 1 (i.e. any non-zero share).
+    pub min_payout: i128,
+    /// Beneficiaries who have not yet called `confirm_beneficiary`. A freshly
+    /// set config starts with every beneficiary unconfirmed and `active: false`;
+    /// `execute_split_payout` refuses to run until this list is empty (or the
+    /// `authorized_payout_key` force-activates), guarding against releases to
+    /// recipients who never opted in and may lack a trustline for the token.
+    pub unconfirmed: Vec<Address>,
+    /// How leftover dust from floor-division is apportioned. Defaults to
+    /// `DustMode::FirstIndex`.
+    pub dust_mode: DustMode,
+    /// Optional cliff + linear vesting schedule. When set,
+    /// `execute_split_payout` rejects releases beyond `vested_amount`.
+    /// `None` means the full balance is releasable at any time.
+    pub vesting: Option<VestingSchedule>,
+    /// Cumulative amount actually paid to each beneficiary so far (parallel
+    /// to `beneficiaries`, in the same order). `execute_split_payout`
+    /// recomputes each beneficiary's target cumulative payout from the
+    /// lifetime total released and transfers only the difference from this,
+    /// so the bp ratio is held exactly over the life of the escrow instead of
+    /// drifting from repeated independent floor-divisions across partial
+    /// releases. Reset to all zeros whenever the config is replaced.
+    pub paid_so_far: Vec<i128>,
+    /// How `execute_split_payout` settles each beneficiary's due amount.
+    /// Defaults to `PayoutMode::Push`.
+    pub payout_mode: PayoutMode,
+}
+
+/// One beneficiary's computed amount in a `preview_split` result.
+///
+/// A dedicated type instead of repurposing `BeneficiarySplit.share_bps`
+/// (which would otherwise hold a basis-point share in config context but a
+/// raw token amount in preview context).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SplitPreviewEntry {
+    pub recipient: Address,
+    pub amount: i128,
 }
 
 /// Result returned from a split payout execution.
@@ -75,6 +220,62 @@ pub struct SplitPayoutResult {
     pub remaining_balance: i128,
 }
 
+/// Persisted cursor for a multi-call `execute_split_payout_partitioned` release.
+///
+/// A single release of `total_amount` against the program's `SplitConfig` may
+/// span many `execute_split_payout_partitioned` calls once the beneficiary
+/// list is too large to pay out in one transaction. `amounts` is computed
+/// once up front via `apportion` (honouring the config's `dust_mode`, e.g.
+/// `LargestRemainder`) so every batch transfers a fixed, already-settled
+/// figure rather than re-deriving dust placement call-by-call; `next_index`
+/// tracks how far through `SplitConfig::beneficiaries` (and `amounts`, which
+/// is parallel to it) the release has progressed, so a restart resumes from
+/// exactly where it left off instead of double-paying or skipping anyone.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SplitPayoutState {
+    pub program_id: String,
+    /// Gross amount being distributed across the whole (multi-batch) release.
+    pub total_amount: i128,
+    /// Per-beneficiary amount for this release, in `SplitConfig::beneficiaries`
+    /// order, computed once via `apportion` when the release began. Fixed for
+    /// the lifetime of the release so partition boundaries never reshuffle it.
+    pub amounts: Vec<i128>,
+    /// Sum of amounts actually transferred across completed batches so far.
+    pub distributed: i128,
+    /// Index into `SplitConfig::beneficiaries` (and `amounts`) of the next
+    /// unpaid entry.
+    pub next_index: u32,
+}
+
+/// Result of a single `execute_split_payout_partitioned` call.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SplitPayoutBatchResult {
+    pub batch_distributed: i128,
+    pub batch_recipient_count: u32,
+    pub total_distributed: i128,
+    pub next_index: u32,
+    pub remaining_balance: i128,
+    /// `true` if beneficiaries remain unpaid and another call is required.
+    pub more_batches_remaining: bool,
+}
+
+/// Result of `terminate_split_config`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SplitTerminationResult {
+    /// Total paid out to beneficiaries to bring them up to their
+    /// currently-vested entitlement.
+    pub released_to_beneficiaries: i128,
+    /// Unvested balance clawed back, either to `recovery_address` (if
+    /// provided) or left in `ProgramData::remaining_balance`.
+    pub recovered_amount: i128,
+    /// `true` if `recovered_amount` was transferred out to a recovery
+    /// address rather than left in the escrow.
+    pub recovered_to_external_address: bool,
+}
+
 // ---------------------------------------------------------------------------
 // Storage helpers
 // ---------------------------------------------------------------------------
@@ -83,6 +284,14 @@ fn split_key(program_id: &String) -> DataKey {
     DataKey::SplitConfig(program_id.clone())
 }
 
+fn payout_state_key(program_id: &String) -> DataKey {
+    DataKey::SplitPayoutState(program_id.clone())
+}
+
+fn claim_key(program_id: &String, beneficiary: &Address) -> DataKey {
+    DataKey::SplitClaimBalance(program_id.clone(), beneficiary.clone())
+}
+
 fn get_program(env: &Env) -> ProgramData {
     env.storage()
         .instance()
@@ -94,6 +303,142 @@ fn save_program(env: &Env, data: &ProgramData) {
     env.storage().instance().set(&PROGRAM_DATA, data);
 }
 
+fn get_admin(env: &Env) -> Address {
+    env.storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .unwrap_or_else(|| panic!("Not initialized"))
+}
+
+/// Maximum cumulative amount releasable under `config.vesting` as of `now`:
+/// zero before the cliff, a linear ramp from the cliff to `start_ts + duration`,
+/// and the full `total_funds` once fully vested. `None` vesting means the
+/// whole balance is always releasable.
+fn vested_total(program: &ProgramData, config: &SplitConfig, now: u64) -> i128 {
+    match &config.vesting {
+        None => program.total_funds,
+        Some(v) => {
+            if now < v.cliff_ts {
+                0
+            } else if now >= v.start_ts + v.duration {
+                program.total_funds
+            } else {
+                let elapsed = (now - v.start_ts) as i128;
+                (program.total_funds * elapsed) / v.duration as i128
+            }
+        }
+    }
+}
+
+/// Currently vested but not-yet-released amount: `vested_total` minus what has
+/// already been paid out of the program's total funds.
+fn vested_unreleased(program: &ProgramData, config: &SplitConfig, now: u64) -> i128 {
+    let already_paid = program.total_funds - program.remaining_balance;
+    vested_total(program, config, now) - already_paid
+}
+
+/// Split `total_amount` across `beneficiaries` using bp arithmetic, returning
+/// the per-beneficiary amount (in config order) with dust apportioned per
+/// `dust_mode`. The returned amounts always sum to exactly `total_amount`.
+fn apportion(
+    env: &Env,
+    beneficiaries: &Vec<BeneficiarySplit>,
+    total_amount: i128,
+    dust_mode: &DustMode,
+) -> soroban_sdk::Vec<i128> {
+    let n = beneficiaries.len();
+    let mut amounts: soroban_sdk::Vec<i128> = soroban_sdk::Vec::new(env);
+    let mut remainders: soroban_sdk::Vec<i128> = soroban_sdk::Vec::new(env);
+    let mut distributed: i128 = 0;
+
+    for i in 0..n {
+        let entry = beneficiaries.get(i).unwrap();
+        let product = total_amount
+            .checked_mul(entry.share_bps)
+            .unwrap_or_else(|| panic!("SplitPayout: arithmetic overflow"));
+        let share_amount = product / TOTAL_BASIS_POINTS;
+        amounts.push_back(share_amount);
+        remainders.push_back(product % TOTAL_BASIS_POINTS);
+        distributed = distributed
+            .checked_add(share_amount)
+            .unwrap_or_else(|| panic!("SplitPayout: sum overflow"));
+    }
+
+    let mut dust = total_amount - distributed;
+    if dust < 0 {
+        panic!("SplitPayout: internal accounting error");
+    }
+
+    match dust_mode {
+        DustMode::FirstIndex => {
+            let first_amount = amounts.get(0).unwrap() + dust;
+            amounts.set(0, first_amount);
+        }
+        DustMode::LargestRemainder => {
+            // dust is always < n (it's the floor-division remainder of a
+            // bp split), so each beneficiary receives at most one extra unit:
+            // award one unit at a time to whichever unclaimed index has the
+            // largest fractional remainder, ties broken by lowest index.
+            let mut used: soroban_sdk::Vec<bool> = soroban_sdk::Vec::new(env);
+            for _ in 0..n {
+                used.push_back(false);
+            }
+            while dust > 0 {
+                let mut best_idx: u32 = 0;
+                let mut best_remainder: i128 = -1;
+                for i in 0..n {
+                    if used.get(i).unwrap() {
+                        continue;
+                    }
+                    let r = remainders.get(i).unwrap();
+                    if r > best_remainder {
+                        best_remainder = r;
+                        best_idx = i;
+                    }
+                }
+                let amount = amounts.get(best_idx).unwrap() + 1;
+                amounts.set(best_idx, amount);
+                used.set(best_idx, true);
+                dust -= 1;
+            }
+        }
+    }
+
+    amounts
+}
+
+/// Preflight receivability checks shared by `execute_split_payout` and
+/// `validate_split_recipients`: no duplicate recipients, no recipient equal
+/// to the contract itself, and every entry of `amounts` (the actual per-
+/// beneficiary transfer amounts a caller is about to apply) strictly
+/// positive. Reports the first offending beneficiary's error.
+fn validate_recipients(
+    contract_addr: &Address,
+    beneficiaries: &Vec<BeneficiarySplit>,
+    amounts: &soroban_sdk::Vec<i128>,
+) -> Result<(), EscrowError> {
+    let n = beneficiaries.len();
+    for i in 0..n {
+        let entry = beneficiaries.get(i).unwrap();
+        if entry.recipient == *contract_addr {
+            return Err(EscrowError::RecipientIsContract);
+        }
+        for j in (i + 1)..n {
+            if beneficiaries.get(j).unwrap().recipient == entry.recipient {
+                return Err(EscrowError::DuplicateRecipient);
+            }
+        }
+    }
+
+    for i in 0..n {
+        if amounts.get(i).unwrap() <= 0 {
+            return Err(EscrowError::NonPositiveAllocation);
+        }
+    }
+
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // Public API
 // ---------------------------------------------------------------------------
@@ -104,25 +449,54 @@ fn save_program(env: &Env, data: &ProgramData) {
 /// * `program_id`     - The program this config applies to.
 /// * `beneficiaries`  - Ordered list of `BeneficiarySplit`. Index 0 receives dust.
 ///
+/// * `min_payout`     - Floor each beneficiary's share must clear for a given
+///                       `total_amount` (see `execute_split_payout`). Pass
+///                       `None` to use the default of 1.
+/// * `dust_mode`      - How leftover dust is apportioned. Pass `None` to use
+///                       `DustMode::FirstIndex`.
+/// * `vesting`        - Optional cliff + linear vesting schedule gating how
+///                       much of `total_funds` `execute_split_payout` may
+///                       release at a given ledger time. `None` leaves the
+///                       full balance releasable at any time.
+/// * `payout_mode`     - Push (direct transfer) or Pull (credit a claim
+///                       ledger for `claim_split` to withdraw from). Pass
+///                       `None` to use `PayoutMode::Push`.
+///
+/// The config is stored inactive: every beneficiary must call
+/// `confirm_beneficiary` (or the `authorized_payout_key` must call
+/// `force_activate_split_config`) before `execute_split_payout` will run.
+/// Setting a config also resets each beneficiary's `paid_so_far` to zero,
+/// since it wholesale replaces the beneficiary list the entitlement
+/// accounting is tracked against.
+///
+/// # Errors
+/// * `NoBeneficiaries` - `beneficiaries` is empty.
+/// * `TooManyBeneficiaries` - `beneficiaries` has more than 50 entries.
+/// * `ZeroShare` - an individual `share_bps` is zero or negative.
+/// * `SharesDoNotSumTo10000` - shares do not sum to exactly `TOTAL_BASIS_POINTS`.
+/// * `NonPositiveMinPayout` - `min_payout` is not positive.
+/// * `InvalidVestingSchedule` - `vesting` is set but `cliff_ts < start_ts` or `duration == 0`.
+///
 /// # Panics
 /// * If the caller is not the `authorized_payout_key`.
-/// * If `beneficiaries` is empty or has more than 50 entries.
-/// * If any individual `share_bps` is zero or negative.
-/// * If shares do not sum to exactly `TOTAL_BASIS_POINTS` (10 000).
 pub fn set_split_config(
     env: &Env,
     program_id: &String,
     beneficiaries: Vec<BeneficiarySplit>,
-) -> SplitConfig {
+    min_payout: Option<i128>,
+    dust_mode: Option<DustMode>,
+    vesting: Option<VestingSchedule>,
+    payout_mode: Option<PayoutMode>,
+) -> Result<SplitConfig, EscrowError> {
     let program = get_program(env);
     program.authorized_payout_key.require_auth();
 
     let n = beneficiaries.len();
     if n == 0 {
-        panic!("SplitConfig: must have at least one beneficiary");
+        return Err(EscrowError::NoBeneficiaries);
     }
     if n > 50 {
-        panic!("SplitConfig: maximum 50 beneficiaries");
+        return Err(EscrowError::TooManyBeneficiaries);
     }
 
     // Validate individual shares and compute total.
@@ -130,20 +504,44 @@ pub fn set_split_config(
     for i in 0..n {
         let entry = beneficiaries.get(i).unwrap();
         if entry.share_bps <= 0 {
-            panic!("SplitConfig: share_bps must be positive");
+            return Err(EscrowError::ZeroShare);
         }
         total = total
             .checked_add(entry.share_bps)
             .unwrap_or_else(|| panic!("SplitConfig: share overflow"));
     }
     if total != TOTAL_BASIS_POINTS {
-        panic!("SplitConfig: shares must sum to 10000 basis points");
+        return Err(EscrowError::SharesDoNotSumTo10000);
+    }
+
+    let min_payout = min_payout.unwrap_or(1);
+    if min_payout <= 0 {
+        return Err(EscrowError::NonPositiveMinPayout);
+    }
+
+    if let Some(v) = &vesting {
+        if v.duration == 0 || v.cliff_ts < v.start_ts {
+            return Err(EscrowError::InvalidVestingSchedule);
+        }
+    }
+
+    let mut unconfirmed: Vec<Address> = Vec::new(env);
+    let mut paid_so_far: Vec<i128> = Vec::new(env);
+    for i in 0..n {
+        unconfirmed.push_back(beneficiaries.get(i).unwrap().recipient);
+        paid_so_far.push_back(0);
     }
 
     let config = SplitConfig {
         program_id: program_id.clone(),
         beneficiaries: beneficiaries.clone(),
-        active: true,
+        active: false,
+        min_payout,
+        unconfirmed,
+        dust_mode: dust_mode.unwrap_or(DustMode::FirstIndex),
+        vesting,
+        paid_so_far,
+        payout_mode: payout_mode.unwrap_or(PayoutMode::Push),
     };
 
     env.storage()
@@ -155,7 +553,7 @@ pub fn set_split_config(
         (program_id.clone(), n as u32, env.ledger().timestamp()),
     );
 
-    config
+    Ok(config)
 }
 
 /// Retrieve the split configuration for a program.
@@ -167,10 +565,63 @@ pub fn get_split_config(env: &Env, program_id: &String) -> Option<SplitConfig> {
         .get(&split_key(program_id))
 }
 
-/// Deactivate the split configuration for a program.
+/// Record that `recipient` accepts being a beneficiary of the program's
+/// split config. Once every beneficiary has confirmed, the config flips to
+/// `active: true` and `execute_split_payout` may run.
+///
+/// # Arguments
+/// * `program_id` - The program whose config `recipient` is confirming.
+/// * `recipient`  - The beneficiary confirming; must authorize this call.
+///
+/// # Panics
+/// * If no split config exists for `program_id`.
+/// * If `recipient` is not a beneficiary of the stored config.
+pub fn confirm_beneficiary(env: &Env, program_id: &String, recipient: &Address) {
+    recipient.require_auth();
+
+    let key = split_key(program_id);
+    let mut config: SplitConfig = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| panic!("No split config found for program"));
+
+    let n = config.unconfirmed.len();
+    let mut found = false;
+    let mut remaining: Vec<Address> = Vec::new(env);
+    for i in 0..n {
+        let addr = config.unconfirmed.get(i).unwrap();
+        if addr == *recipient {
+            found = true;
+        } else {
+            remaining.push_back(addr);
+        }
+    }
+    if !found && !config.active {
+        panic!("SplitConfig: recipient is not an unconfirmed beneficiary");
+    }
+    config.unconfirmed = remaining;
+
+    if config.unconfirmed.is_empty() {
+        config.active = true;
+        env.events().publish(
+            (SPLIT_ACTIVATED,),
+            (program_id.clone(), env.ledger().timestamp()),
+        );
+    }
+
+    env.storage().persistent().set(&key, &config);
+
+    env.events().publish(
+        (BENEFICIARY_CONFIRMED,),
+        (program_id.clone(), recipient.clone(), env.ledger().timestamp()),
+    );
+}
+
+/// Force-activate a split config regardless of outstanding confirmations.
 ///
 /// Requires authorisation from the `authorized_payout_key`.
-pub fn disable_split_config(env: &Env, program_id: &String) {
+pub fn force_activate_split_config(env: &Env, program_id: &String) {
     let program = get_program(env);
     program.authorized_payout_key.require_auth();
 
@@ -181,51 +632,141 @@ pub fn disable_split_config(env: &Env, program_id: &String) {
         .get(&key)
         .unwrap_or_else(|| panic!("No split config found for program"));
 
+    config.active = true;
+    env.storage().persistent().set(&key, &config);
+
+    env.events().publish(
+        (SPLIT_ACTIVATED,),
+        (program_id.clone(), env.ledger().timestamp()),
+    );
+}
+
+/// Deactivate the split configuration for a program.
+///
+/// Requires authorisation from the `authorized_payout_key`.
+///
+/// # Errors
+/// * `NoSplitConfig` - no split config exists for `program_id`.
+pub fn disable_split_config(env: &Env, program_id: &String) -> Result<(), EscrowError> {
+    let program = get_program(env);
+    program.authorized_payout_key.require_auth();
+
+    let key = split_key(program_id);
+    let mut config: SplitConfig = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .ok_or(EscrowError::NoSplitConfig)?;
+
     config.active = false;
     env.storage().persistent().set(&key, &config);
+    Ok(())
 }
 
 /// Execute a split payout of `total_amount` according to the stored `SplitConfig`.
 ///
-/// The amount is divided proportionally using basis-point arithmetic.  Any
-/// remainder from integer division (dust) is added to the **first** beneficiary,
-/// ensuring the full `total_amount` is always distributed without drift.
+/// Each beneficiary's transfer is the difference between their target
+/// cumulative payout — `share_bps` of the lifetime total released including
+/// this call — and `paid_so_far`. This holds every beneficiary's true bp
+/// ratio exactly over the life of the escrow even when releases are chunked
+/// unevenly, rather than drifting from repeated independent floor-divisions
+/// of each call's own `total_amount`. `apportion`'s usual dust handling
+/// still applies to the lifetime target, so the final (`drain`) release
+/// always reconciles every beneficiary to their exact entitlement.
+///
+/// Mirrors Solana's full-drain vs. partial-split distinction: a `drain`
+/// release must exhaust the entire `remaining_balance` and closes the
+/// program's split config (no further split payouts may be executed),
+/// while a partial release must leave at least `min_remaining_balance` in
+/// the escrow — it can never land in the forbidden dust band strictly
+/// between zero and that floor.
 ///
 /// # Arguments
-/// * `program_id`   - The program whose config to use.
-/// * `total_amount` - Gross amount to distribute (must be ≤ remaining balance).
+/// * `program_id`           - The program whose config to use.
+/// * `total_amount`         - Gross amount to distribute (must be ≤ remaining balance).
+/// * `drain`                - If `true`, `total_amount` must equal the full
+///                             `remaining_balance` and the program is marked closed.
+///                             If `false`, the release must leave `remaining_balance`
+///                             at or above `min_remaining_balance`.
+/// * `min_remaining_balance` - Floor the post-release balance must clear for a
+///                             partial (`drain == false`) release. Ignored when
+///                             draining. `None` defaults to 1.
 ///
 /// # Returns
 /// `SplitPayoutResult` with totals and updated remaining balance.
 ///
+/// # Errors
+/// * `AlreadyDrained` - the program has already been drained and closed.
+/// * `NonPositiveAmount` - `total_amount` is ≤ 0.
+/// * `InsufficientBalance` - `total_amount` exceeds the remaining balance.
+/// * `DrainAmountMismatch` - `drain` is true but `total_amount` does not equal
+///   the full remaining balance.
+/// * `NonPositiveMinRemainingBalance` - `min_remaining_balance` is ≤ 0.
+/// * `ForbiddenDustBalance` - `drain` is false and the resulting
+///   `remaining_balance` would fall strictly between zero and `min_remaining_balance`.
+/// * `NoSplitConfig` - no split config exists for `program_id`.
+/// * `SplitDisabled` - the stored split config is not active.
+/// * `BelowMinPayout` - a beneficiary's actual due amount for this call would
+///   fall below `min_payout` (call `min_viable_payout` to size `total_amount`
+///   so this can't happen).
+/// * `ExceedsVestedAmount` - `total_amount` exceeds the currently vested but
+///   unreleased amount (call `vested_amount` to size `total_amount` instead).
+/// * `DuplicateRecipient` - two beneficiaries share the same recipient address.
+/// * `RecipientIsContract` - a beneficiary's recipient is the escrow contract itself.
+/// * `NonPositiveAllocation` - a beneficiary's computed allocation for
+///   `total_amount` is not positive (call `validate_split_recipients` to
+///   check a config before releasing).
+///
 /// # Panics
-/// * If no active split config exists.
-/// * If `total_amount` ≤ 0 or exceeds the remaining balance.
 /// * If caller is not the `authorized_payout_key`.
 pub fn execute_split_payout(
     env: &Env,
     program_id: &String,
     total_amount: i128,
-) -> SplitPayoutResult {
+    drain: bool,
+    min_remaining_balance: Option<i128>,
+) -> Result<SplitPayoutResult, EscrowError> {
     let mut program = get_program(env);
     program.authorized_payout_key.require_auth();
 
+    if env
+        .storage()
+        .persistent()
+        .get::<_, bool>(&DataKey::SplitClosed(program_id.clone()))
+        .unwrap_or(false)
+    {
+        return Err(EscrowError::AlreadyDrained);
+    }
+
     if total_amount <= 0 {
-        panic!("SplitPayout: amount must be greater than zero");
+        return Err(EscrowError::NonPositiveAmount);
     }
     if total_amount > program.remaining_balance {
-        panic!("SplitPayout: insufficient escrow balance");
+        return Err(EscrowError::InsufficientBalance);
+    }
+    if drain && total_amount != program.remaining_balance {
+        return Err(EscrowError::DrainAmountMismatch);
+    }
+    if !drain {
+        let min_remaining_balance = min_remaining_balance.unwrap_or(1);
+        if min_remaining_balance <= 0 {
+            return Err(EscrowError::NonPositiveMinRemainingBalance);
+        }
+        let post_balance = program.remaining_balance - total_amount;
+        if post_balance > 0 && post_balance < min_remaining_balance {
+            return Err(EscrowError::ForbiddenDustBalance);
+        }
     }
 
     // Load and validate config.
-    let config: SplitConfig = env
+    let mut config: SplitConfig = env
         .storage()
         .persistent()
         .get(&split_key(program_id))
-        .unwrap_or_else(|| panic!("SplitPayout: no split config found for program"));
+        .ok_or(EscrowError::NoSplitConfig)?;
 
     if !config.active {
-        panic!("SplitPayout: split config is disabled");
+        return Err(EscrowError::SplitDisabled);
     }
 
     let n = config.beneficiaries.len();
@@ -233,54 +774,82 @@ pub fn execute_split_payout(
     let token_client = token::Client::new(env, &program.token_address);
     let now = env.ledger().timestamp();
 
-    // Compute individual amounts using bp arithmetic; accumulate dust.
-    // dust = total_amount - sum(floor(total_amount * share_bps / 10_000))
-    let mut amounts: soroban_sdk::Vec<i128> = soroban_sdk::Vec::new(env);
-    let mut distributed: i128 = 0;
+    if config.vesting.is_some() {
+        let unlocked = vested_unreleased(&program, &config, now);
+        if total_amount > unlocked {
+            return Err(EscrowError::ExceedsVestedAmount);
+        }
+    }
 
+    // Recompute each beneficiary's target *cumulative* payout against the
+    // lifetime total released (including this call), then transfer only the
+    // difference from what they've already received. Because `apportion`
+    // always reconciles its dust so the returned amounts sum to exactly its
+    // `total_amount` argument, and `paid_so_far` sums to the prior lifetime
+    // total by the same invariant, the `due` amounts below always sum to
+    // exactly this call's `total_amount` - holding every beneficiary's true
+    // bp ratio over the life of the escrow instead of drifting from repeated
+    // independent floor-divisions across partial releases.
+    let cumulative_released = program.total_funds - (program.remaining_balance - total_amount);
+    let targets = apportion(env, &config.beneficiaries, cumulative_released, &config.dust_mode);
+    let mut due: soroban_sdk::Vec<i128> = soroban_sdk::Vec::new(env);
     for i in 0..n {
-        let entry = config.beneficiaries.get(i).unwrap();
-        let share_amount = total_amount
-            .checked_mul(entry.share_bps)
-            .and_then(|x| x.checked_div(TOTAL_BASIS_POINTS))
-            .unwrap_or_else(|| panic!("SplitPayout: arithmetic overflow"));
-        amounts.push_back(share_amount);
-        distributed = distributed
-            .checked_add(share_amount)
-            .unwrap_or_else(|| panic!("SplitPayout: sum overflow"));
+        due.push_back(targets.get(i).unwrap() - config.paid_so_far.get(i).unwrap());
     }
 
-    // Dust goes to index 0.
-    let dust = total_amount - distributed;
-    if dust < 0 {
-        panic!("SplitPayout: internal accounting error");
+    // Preflight every beneficiary for basic receivability before any transfer
+    // is issued, so a misconfigured split is caught atomically rather than
+    // after partial disbursement.
+    validate_recipients(&contract_addr, &config.beneficiaries, &due)?;
+
+    // Validate every beneficiary's actual due amount clears min_payout before
+    // transferring.
+    for i in 0..n {
+        if due.get(i).unwrap() < config.min_payout {
+            return Err(EscrowError::BelowMinPayout);
+        }
     }
-    let first_amount = amounts.get(0).unwrap() + dust;
-    amounts.set(0, first_amount);
 
-    // Transfer and record payouts.
+    // Settle and record payouts. Every share already cleared `min_payout`
+    // above, so none are skipped here. In `PayoutMode::Pull`, nothing is
+    // transferred yet - each beneficiary's due amount is credited to their
+    // claim ledger and withdrawn independently via `claim_split`, so one
+    // unreachable recipient can't revert the whole release.
     for i in 0..n {
         let entry = config.beneficiaries.get(i).unwrap();
-        let amount = amounts.get(i).unwrap();
+        let amount = due.get(i).unwrap();
 
-        if amount <= 0 {
-            // Edge case: a beneficiary with a very small share on a tiny payout.
-            // Skip transfer but still record so history is complete.
-            continue;
+        match config.payout_mode {
+            PayoutMode::Push => {
+                token_client.transfer(&contract_addr, &entry.recipient, &amount);
+                program.payout_history.push_back(PayoutRecord {
+                    recipient: entry.recipient.clone(),
+                    amount,
+                    timestamp: now,
+                });
+            }
+            PayoutMode::Pull => {
+                let key = claim_key(program_id, &entry.recipient);
+                let credited: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+                env.storage().persistent().set(&key, &(credited + amount));
+            }
         }
-
-        token_client.transfer(&contract_addr, &entry.recipient, &amount);
-
-        program.payout_history.push_back(PayoutRecord {
-            recipient: entry.recipient.clone(),
-            amount,
-            timestamp: now,
-        });
+        config.paid_so_far.set(i, targets.get(i).unwrap());
     }
 
+    env.storage()
+        .persistent()
+        .set(&split_key(program_id), &config);
+
     program.remaining_balance -= total_amount;
     save_program(env, &program);
 
+    if drain {
+        env.storage()
+            .persistent()
+            .set(&DataKey::SplitClosed(program_id.clone()), &true);
+    }
+
     env.events().publish(
         (SPLIT_PAYOUT,),
         (
@@ -292,57 +861,564 @@ pub fn execute_split_payout(
         ),
     );
 
-    SplitPayoutResult {
+    Ok(SplitPayoutResult {
         total_distributed: total_amount,
         recipient_count: n as u32,
         remaining_balance: program.remaining_balance,
+    })
+}
+
+/// Withdraw `beneficiary`'s accrued balance credited by a `PayoutMode::Pull`
+/// `execute_split_payout` release.
+///
+/// Transfers the full claimable balance and zeroes the ledger entry; a
+/// beneficiary with nothing credited gets `NoClaimableBalance` instead of a
+/// no-op success, so callers can distinguish "already claimed" from "claim
+/// succeeded for zero".
+///
+/// # Arguments
+/// * `program_id`  - The program whose pull-mode credit to withdraw from.
+/// * `beneficiary` - The claimant; must authorize this call.
+///
+/// # Returns
+/// The amount transferred.
+///
+/// # Errors
+/// * `NoClaimableBalance` - `beneficiary` has nothing credited for `program_id`.
+pub fn claim_split(
+    env: &Env,
+    program_id: &String,
+    beneficiary: &Address,
+) -> Result<i128, EscrowError> {
+    beneficiary.require_auth();
+
+    let key = claim_key(program_id, beneficiary);
+    let owed: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+    if owed <= 0 {
+        return Err(EscrowError::NoClaimableBalance);
+    }
+
+    let mut program = get_program(env);
+    let contract_addr = env.current_contract_address();
+    let token_client = token::Client::new(env, &program.token_address);
+    let now = env.ledger().timestamp();
+
+    token_client.transfer(&contract_addr, beneficiary, &owed);
+    env.storage().persistent().remove(&key);
+
+    program.payout_history.push_back(PayoutRecord {
+        recipient: beneficiary.clone(),
+        amount: owed,
+        timestamp: now,
+    });
+    save_program(env, &program);
+
+    env.events().publish(
+        (SPLIT_CLAIMED,),
+        (program_id.clone(), beneficiary.clone(), owed, now),
+    );
+
+    Ok(owed)
+}
+
+/// Per-beneficiary outstanding balance credited by `PayoutMode::Pull`
+/// releases but not yet withdrawn via `claim_split`.
+///
+/// Mirrors `preview_split`'s shape, but reads the claim ledger rather than
+/// computing a hypothetical apportionment.
+///
+/// # Errors
+/// * `NoSplitConfig` - no split config exists for `program_id`.
+pub fn preview_unclaimed_split(
+    env: &Env,
+    program_id: &String,
+) -> Result<Vec<SplitPreviewEntry>, EscrowError> {
+    let config: SplitConfig = env
+        .storage()
+        .persistent()
+        .get(&split_key(program_id))
+        .ok_or(EscrowError::NoSplitConfig)?;
+
+    let n = config.beneficiaries.len();
+    let mut preview: Vec<SplitPreviewEntry> = Vec::new(env);
+    for i in 0..n {
+        let entry = config.beneficiaries.get(i).unwrap();
+        let owed: i128 = env
+            .storage()
+            .persistent()
+            .get(&claim_key(program_id, &entry.recipient))
+            .unwrap_or(0);
+        preview.push_back(SplitPreviewEntry {
+            recipient: entry.recipient,
+            amount: owed,
+        });
     }
+
+    Ok(preview)
 }
 
 /// Calculate the hypothetical split amounts for `total_amount` without executing transfers.
 ///
-/// Useful for off-chain previews and tests.  Dust is awarded to index 0.
+/// Useful for off-chain previews and tests. Dust is apportioned per the
+/// config's `dust_mode`.
 ///
-/// Returns a `Vec` of `(recipient, amount)` pairs in config order.
+/// Returns a `Vec<SplitPreviewEntry>` in config order.
+///
+/// # Errors
+/// * `NoSplitConfig` - no split config exists for `program_id`.
 pub fn preview_split(
     env: &Env,
     program_id: &String,
     total_amount: i128,
-) -> Vec<BeneficiarySplit> {
+) -> Result<Vec<SplitPreviewEntry>, EscrowError> {
     let config: SplitConfig = env
         .storage()
         .persistent()
         .get(&split_key(program_id))
-        .unwrap_or_else(|| panic!("No split config found for program"));
+        .ok_or(EscrowError::NoSplitConfig)?;
 
     let n = config.beneficiaries.len();
-    let mut preview: Vec<BeneficiarySplit> = Vec::new(env);
-    let mut distributed: i128 = 0;
-    let mut computed: soroban_sdk::Vec<i128> = soroban_sdk::Vec::new(env);
+    let amounts = apportion(env, &config.beneficiaries, total_amount, &config.dust_mode);
 
+    let mut preview: Vec<SplitPreviewEntry> = Vec::new(env);
     for i in 0..n {
         let entry = config.beneficiaries.get(i).unwrap();
-        let share_amount = total_amount
-            .checked_mul(entry.share_bps)
-            .and_then(|x| x.checked_div(TOTAL_BASIS_POINTS))
-            .unwrap_or(0);
-        computed.push_back(share_amount);
-        distributed += share_amount;
+        preview.push_back(SplitPreviewEntry {
+            recipient: entry.recipient,
+            amount: amounts.get(i).unwrap(),
+        });
     }
 
-    let dust = total_amount - distributed;
+    Ok(preview)
+}
 
-    for i in 0..n {
+/// Smallest `total_amount` for which every beneficiary's floor-divided share
+/// meets the config's `min_payout`, i.e. the smallest release that
+/// `execute_split_payout` will accept without panicking.
+///
+/// This lets off-chain callers size releases correctly instead of
+/// discovering the floor via a failed (and reverted) transaction.
+///
+/// # Panics
+/// * If no split config exists for `program_id`.
+pub fn min_viable_payout(env: &Env, program_id: &String) -> i128 {
+    let config: SplitConfig = env
+        .storage()
+        .persistent()
+        .get(&split_key(program_id))
+        .unwrap_or_else(|| panic!("No split config found for program"));
+
+    let mut min_total: i128 = 0;
+    for i in 0..config.beneficiaries.len() {
         let entry = config.beneficiaries.get(i).unwrap();
-        let mut amount = computed.get(i).unwrap();
-        if i == 0 {
-            amount += dust;
+        // Smallest `total_amount` for which
+        // floor(total_amount * share_bps / TOTAL_BASIS_POINTS) >= min_payout
+        // is ceil(min_payout * TOTAL_BASIS_POINTS / share_bps).
+        let numerator = config.min_payout * TOTAL_BASIS_POINTS;
+        let required = (numerator + entry.share_bps - 1) / entry.share_bps;
+        if required > min_total {
+            min_total = required;
         }
-        preview.push_back(BeneficiarySplit {
+    }
+    min_total
+}
+
+/// Amount currently releasable under `program_id`'s vesting schedule that has
+/// not yet been paid out. If no vesting schedule is set, this is simply the
+/// program's `remaining_balance`.
+///
+/// # Panics
+/// * If no split config exists for `program_id`.
+pub fn vested_amount(env: &Env, program_id: &String) -> i128 {
+    let program = get_program(env);
+    let config: SplitConfig = env
+        .storage()
+        .persistent()
+        .get(&split_key(program_id))
+        .unwrap_or_else(|| panic!("No split config found for program"));
+
+    vested_unreleased(&program, &config, env.ledger().timestamp())
+}
+
+/// Per-beneficiary breakdown of `vested_amount`: what each beneficiary could
+/// claim right now under the config's vesting schedule given what they've
+/// already been paid (`paid_so_far`).
+///
+/// Mirrors `preview_split`, but sizes the release against the schedule's
+/// currently-vested total instead of a caller-supplied `total_amount`, so
+/// off-chain callers can show "claimable now" per recipient without first
+/// working out a `total_amount` that `execute_split_payout` would accept.
+/// Returns all zeros before the cliff and the full remaining entitlement
+/// once fully vested (or immediately, when no schedule is set).
+///
+/// # Errors
+/// * `NoSplitConfig` - no split config exists for `program_id`.
+pub fn preview_claimable_split(
+    env: &Env,
+    program_id: &String,
+) -> Result<Vec<SplitPreviewEntry>, EscrowError> {
+    let program = get_program(env);
+    let config: SplitConfig = env
+        .storage()
+        .persistent()
+        .get(&split_key(program_id))
+        .ok_or(EscrowError::NoSplitConfig)?;
+
+    let now = env.ledger().timestamp();
+    let cumulative_vested = vested_total(&program, &config, now);
+    let targets = apportion(env, &config.beneficiaries, cumulative_vested, &config.dust_mode);
+
+    let n = config.beneficiaries.len();
+    let mut preview: Vec<SplitPreviewEntry> = Vec::new(env);
+    for i in 0..n {
+        let entry = config.beneficiaries.get(i).unwrap();
+        let claimable = targets.get(i).unwrap() - config.paid_so_far.get(i).unwrap();
+        preview.push_back(SplitPreviewEntry {
             recipient: entry.recipient,
-            share_bps: amount, // repurposed field: holds computed amount in preview context
+            amount: claimable,
+        });
+    }
+
+    Ok(preview)
+}
+
+/// Check a program's split config for basic recipient receivability without
+/// releasing anything: no duplicate recipients, no recipient equal to the
+/// contract itself, and every beneficiary's due amount if `remaining_balance`
+/// were released in full right now (i.e. the largest single call any future
+/// `execute_split_payout` could make) is positive.
+///
+/// Callers about to release a specific `total_amount` smaller than the full
+/// remaining balance should prefer letting `execute_split_payout` perform its
+/// own preflight against that exact amount; this view is for catching a
+/// misconfigured split ahead of time against the largest amount it will ever
+/// need to handle.
+///
+/// # Errors
+/// * `NoSplitConfig` - no split config exists for `program_id`.
+/// * `DuplicateRecipient` - two beneficiaries share the same recipient address.
+/// * `RecipientIsContract` - a beneficiary's recipient is the escrow contract itself.
+/// * `NonPositiveAllocation` - a beneficiary's computed due amount is not positive.
+pub fn validate_split_recipients(env: &Env, program_id: &String) -> Result<(), EscrowError> {
+    let program = get_program(env);
+    let config: SplitConfig = env
+        .storage()
+        .persistent()
+        .get(&split_key(program_id))
+        .ok_or(EscrowError::NoSplitConfig)?;
+
+    let n = config.beneficiaries.len();
+    let targets = apportion(env, &config.beneficiaries, program.total_funds, &config.dust_mode);
+    let mut due: soroban_sdk::Vec<i128> = soroban_sdk::Vec::new(env);
+    for i in 0..n {
+        due.push_back(targets.get(i).unwrap() - config.paid_so_far.get(i).unwrap());
+    }
+
+    let contract_addr = env.current_contract_address();
+    validate_recipients(&contract_addr, &config.beneficiaries, &due)
+}
+
+/// Pay out the next `batch_size` beneficiaries of a `total_amount` release,
+/// resuming from wherever a prior call to this function left off.
+///
+/// Splits with dozens or hundreds of beneficiaries can exceed Soroban's
+/// per-transaction resource limits if paid in a single `execute_split_payout`
+/// call. This partitions the same release across as many calls as needed:
+/// the first call (when no `SplitPayoutState` exists for `program_id`)
+/// starts a new release for `total_amount` at `next_index = 0`; every
+/// subsequent call must pass the same `total_amount` and continues from the
+/// persisted cursor. `ProgramData::remaining_balance` is decremented only by
+/// what this batch actually transfers, so a partially-paid release never
+/// understates the escrow's true liability.
+///
+/// Per-beneficiary amounts are computed once, up front, exactly like
+/// `execute_split_payout`: from the cumulative entitlement at
+/// `program.total_funds - (remaining_balance - total_amount)` minus each
+/// beneficiary's `config.paid_so_far`, checked against the vesting cap,
+/// `validate_recipients`, and the `min_payout` floor before anything is
+/// transferred. The resulting per-beneficiary deltas are persisted in
+/// `SplitPayoutState::amounts`, and `config.paid_so_far` is advanced as each
+/// beneficiary is actually paid (even mid-batch-sequence), so this entrypoint
+/// can be freely interleaved with `execute_split_payout` without either one
+/// overpaying. Partition boundaries are a pure function of that fixed vector
+/// and the stored cursor, not of anything computed at call time, so a
+/// restart can never double-pay or skip a recipient.
+///
+/// # Arguments
+/// * `program_id` - The program whose config to pay out against.
+/// * `total_amount` - Gross amount for the whole (possibly multi-batch) release.
+/// * `batch_size` - Maximum number of beneficiaries to pay in this call.
+///
+/// # Returns
+/// `SplitPayoutBatchResult` describing this batch and whether more remain.
+///
+/// # Errors
+/// * `AlreadyDrained` - the program has already been drained and closed.
+/// * `NonPositiveBatchSize` - `batch_size` is zero.
+/// * `NonPositiveAmount` - `total_amount` is not positive.
+/// * `InsufficientBalance` - `total_amount` exceeds the remaining escrow balance.
+/// * `NoSplitConfig` - no split config exists for `program_id`.
+/// * `SplitDisabled` - the stored split config is not active.
+/// * `BatchAmountMismatch` - a release is already in progress for a different `total_amount`.
+/// * `ExceedsVestedAmount` - `total_amount` exceeds the currently vested-but-unreleased amount.
+/// * `DuplicateRecipient` / `RecipientIsContract` / `NonPositiveAllocation` - see `validate_recipients`.
+/// * `BelowMinPayout` - a beneficiary's due amount for this release falls below `config.min_payout`.
+///
+/// # Panics
+/// * If caller is not the `authorized_payout_key`.
+pub fn execute_split_payout_partitioned(
+    env: &Env,
+    program_id: &String,
+    total_amount: i128,
+    batch_size: u32,
+) -> Result<SplitPayoutBatchResult, EscrowError> {
+    let mut program = get_program(env);
+    program.authorized_payout_key.require_auth();
+
+    if env
+        .storage()
+        .persistent()
+        .get::<_, bool>(&DataKey::SplitClosed(program_id.clone()))
+        .unwrap_or(false)
+    {
+        return Err(EscrowError::AlreadyDrained);
+    }
+    if batch_size == 0 {
+        return Err(EscrowError::NonPositiveBatchSize);
+    }
+    if total_amount <= 0 {
+        return Err(EscrowError::NonPositiveAmount);
+    }
+    if total_amount > program.remaining_balance {
+        return Err(EscrowError::InsufficientBalance);
+    }
+
+    let mut config: SplitConfig = env
+        .storage()
+        .persistent()
+        .get(&split_key(program_id))
+        .ok_or(EscrowError::NoSplitConfig)?;
+    if !config.active {
+        return Err(EscrowError::SplitDisabled);
+    }
+
+    let n = config.beneficiaries.len();
+    let contract_addr = env.current_contract_address();
+    let now = env.ledger().timestamp();
+
+    let state_key = payout_state_key(program_id);
+    let mut state: SplitPayoutState = match env.storage().persistent().get(&state_key) {
+        Some(state) => state,
+        None => {
+            if config.vesting.is_some() {
+                let unlocked = vested_unreleased(&program, &config, now);
+                if total_amount > unlocked {
+                    return Err(EscrowError::ExceedsVestedAmount);
+                }
+            }
+
+            let cumulative_released = program.total_funds - (program.remaining_balance - total_amount);
+            let targets = apportion(env, &config.beneficiaries, cumulative_released, &config.dust_mode);
+            let mut due: Vec<i128> = Vec::new(env);
+            for i in 0..n {
+                due.push_back(targets.get(i).unwrap() - config.paid_so_far.get(i).unwrap());
+            }
+
+            validate_recipients(&contract_addr, &config.beneficiaries, &due)?;
+
+            for i in 0..n {
+                if due.get(i).unwrap() < config.min_payout {
+                    return Err(EscrowError::BelowMinPayout);
+                }
+            }
+
+            SplitPayoutState {
+                program_id: program_id.clone(),
+                total_amount,
+                amounts: due,
+                distributed: 0,
+                next_index: 0,
+            }
+        }
+    };
+
+    if state.total_amount != total_amount {
+        return Err(EscrowError::BatchAmountMismatch);
+    }
+
+    let token_client = token::Client::new(env, &program.token_address);
+
+    let batch_start = state.next_index;
+    let batch_end = core::cmp::min(batch_start + batch_size, n);
+    let mut batch_distributed: i128 = 0;
+    let mut batch_recipient_count: u32 = 0;
+
+    for i in batch_start..batch_end {
+        let entry = config.beneficiaries.get(i).unwrap();
+        let amount = state.amounts.get(i).unwrap();
+
+        token_client.transfer(&contract_addr, &entry.recipient, &amount);
+
+        program.payout_history.push_back(PayoutRecord {
+            recipient: entry.recipient.clone(),
+            amount,
+            timestamp: now,
+        });
+        config.paid_so_far.set(i, config.paid_so_far.get(i).unwrap() + amount);
+
+        batch_distributed += amount;
+        batch_recipient_count += 1;
+    }
+
+    state.distributed += batch_distributed;
+    state.next_index = batch_end;
+    program.remaining_balance -= batch_distributed;
+    save_program(env, &program);
+    env.storage().persistent().set(&split_key(program_id), &config);
+
+    let more_batches_remaining = state.next_index < n;
+    if more_batches_remaining {
+        env.storage().persistent().set(&state_key, &state);
+    } else {
+        // Release complete: drop the cursor so a later release can start fresh.
+        env.storage().persistent().remove(&state_key);
+    }
+
+    env.events().publish(
+        (SPLIT_PAYOUT_BATCH,),
+        (
+            program_id.clone(),
+            batch_start,
+            batch_end,
+            batch_distributed,
+            more_batches_remaining,
+            now,
+        ),
+    );
+
+    Ok(SplitPayoutBatchResult {
+        batch_distributed,
+        batch_recipient_count,
+        total_distributed: state.distributed,
+        next_index: state.next_index,
+        remaining_balance: program.remaining_balance,
+        more_batches_remaining,
+    })
+}
+
+/// Admin-only early termination of a program's split config.
+///
+/// Immediately releases each beneficiary's currently-vested-but-unpaid
+/// entitlement (same accounting `execute_split_payout` uses, so partial
+/// releases already taken are honoured exactly), then claws back whatever
+/// of `remaining_balance` is left over: to `recovery_address` if supplied,
+/// or simply left in the escrow's `remaining_balance` otherwise. The config
+/// is disabled and the program's split is marked closed, so no further
+/// `execute_split_payout` / `execute_split_payout_partitioned` calls may run
+/// against it.
+///
+/// Used when a grantee is removed or a program is wound down before its
+/// vesting schedule completes and the foundation needs to recover the
+/// unvested portion rather than let it keep streaming out.
+///
+/// # Arguments
+/// * `program_id`        - The program whose split config to terminate.
+/// * `recovery_address`  - Where to send the unvested balance. `None` leaves
+///                          it in `ProgramData::remaining_balance`.
+///
+/// # Errors
+/// * `NoSplitConfig` - no split config exists for `program_id`.
+/// * `SplitDisabled` - the stored split config is not active.
+/// * `AlreadyDrained` - the program has already been drained and closed.
+///
+/// # Panics
+/// * If no admin has been set, or the caller is not the admin.
+pub fn terminate_split_config(
+    env: &Env,
+    program_id: &String,
+    recovery_address: Option<Address>,
+) -> Result<SplitTerminationResult, EscrowError> {
+    let admin = get_admin(env);
+    admin.require_auth();
+
+    if env
+        .storage()
+        .persistent()
+        .get::<_, bool>(&DataKey::SplitClosed(program_id.clone()))
+        .unwrap_or(false)
+    {
+        return Err(EscrowError::AlreadyDrained);
+    }
+
+    let mut program = get_program(env);
+    let key = split_key(program_id);
+    let mut config: SplitConfig = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .ok_or(EscrowError::NoSplitConfig)?;
+
+    if !config.active {
+        return Err(EscrowError::SplitDisabled);
+    }
+
+    let now = env.ledger().timestamp();
+    let contract_addr = env.current_contract_address();
+    let token_client = token::Client::new(env, &program.token_address);
+
+    let cumulative_vested = vested_total(&program, &config, now);
+    let targets = apportion(env, &config.beneficiaries, cumulative_vested, &config.dust_mode);
+
+    let n = config.beneficiaries.len();
+    let mut released_to_beneficiaries: i128 = 0;
+    for i in 0..n {
+        let due = targets.get(i).unwrap() - config.paid_so_far.get(i).unwrap();
+        if due <= 0 {
+            continue;
+        }
+        let entry = config.beneficiaries.get(i).unwrap();
+        token_client.transfer(&contract_addr, &entry.recipient, &due);
+
+        program.payout_history.push_back(PayoutRecord {
+            recipient: entry.recipient.clone(),
+            amount: due,
+            timestamp: now,
         });
+        config.paid_so_far.set(i, targets.get(i).unwrap());
+        released_to_beneficiaries += due;
+    }
+
+    program.remaining_balance -= released_to_beneficiaries;
+    let recovered_amount = program.remaining_balance;
+    let recovered_to_external_address = recovery_address.is_some();
+    if let Some(recovery) = &recovery_address {
+        if recovered_amount > 0 {
+            token_client.transfer(&contract_addr, recovery, &recovered_amount);
+        }
+        program.remaining_balance = 0;
     }
 
-    preview
+    config.active = false;
+    env.storage().persistent().set(&key, &config);
+    save_program(env, &program);
+    env.storage()
+        .persistent()
+        .set(&DataKey::SplitClosed(program_id.clone()), &true);
+
+    env.events().publish(
+        (SPLIT_TERMINATED,),
+        (
+            program_id.clone(),
+            released_to_beneficiaries,
+            recovered_amount,
+            recovered_to_external_address,
+            now,
+        ),
+    );
+
+    Ok(SplitTerminationResult {
+        released_to_beneficiaries,
+        recovered_amount,
+        recovered_to_external_address,
+    })
 }
\ No newline at end of file
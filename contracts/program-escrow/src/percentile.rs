@@ -0,0 +1,266 @@
+//! # P² Streaming Quantile Estimator
+//!
+//! Soroban persistent storage charges per byte written, so keeping every
+//! settlement-time sample ever observed just to recompute a percentile on
+//! demand isn't viable. The P² ("Piecewise-Parabolic") algorithm estimates
+//! a quantile online in O(1) space: five markers track the estimated
+//! value (`q`) and position (`n`) of the minimum, the quantile itself, and
+//! the maximum (plus two interior support points), and each new sample
+//! nudges them towards the true quantile without ever storing the sample
+//! itself.
+//!
+//! All arithmetic here is integer - Soroban contracts have no floating
+//! point - so the quantile `p` and the markers' desired positions are
+//! carried as basis points (`p_bps`, `10_000` = 1.0) and as positions
+//! scaled by `10_000` (`np0..np4`) respectively. The marker heights
+//! (`q0..q4`) are plain `i128`, the same unit as the samples themselves
+//! (settlement time in seconds - see [`crate::get_settlement_time_percentiles`]).
+//!
+//! Fields are five flat `q0..q4`/`n0..n4`/`np0..np4` rather than
+//! `[T; 5]` arrays, matching every other `#[contracttype]` in this crate,
+//! none of which uses fixed-size arrays. `observe`/`estimate` copy them
+//! into a local `[T; 5]` for the duration of one call, since plain Rust
+//! arrays are fine as long as they never cross the contract-type boundary.
+//!
+//! [`P2Estimator::observe`] is the only mutating entry point; before the
+//! fifth sample it just buffers raw values in `init_samples` and seeds the
+//! five markers from their sorted order, exactly as the reference
+//! algorithm specifies.
+
+use soroban_sdk::{contracttype, Env, Vec};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct P2Estimator {
+    /// Target quantile in basis points, e.g. `5_000` = p50, `9_500` = p95.
+    pub p_bps: u32,
+    /// Raw samples collected before the five markers are seeded; cleared
+    /// once `observe` has seen its fifth sample.
+    pub init_samples: Vec<i128>,
+    /// `true` once the five markers have been seeded from the first five samples.
+    pub seeded: bool,
+    pub q0: i128,
+    pub q1: i128,
+    pub q2: i128,
+    pub q3: i128,
+    pub q4: i128,
+    pub n0: i64,
+    pub n1: i64,
+    pub n2: i64,
+    pub n3: i64,
+    pub n4: i64,
+    pub np0: i64,
+    pub np1: i64,
+    pub np2: i64,
+    pub np3: i64,
+    pub np4: i64,
+}
+
+impl P2Estimator {
+    pub fn new(env: &Env, p_bps: u32) -> Self {
+        P2Estimator {
+            p_bps,
+            init_samples: Vec::new(env),
+            seeded: false,
+            q0: 0,
+            q1: 0,
+            q2: 0,
+            q3: 0,
+            q4: 0,
+            n0: 0,
+            n1: 0,
+            n2: 0,
+            n3: 0,
+            n4: 0,
+            np0: 0,
+            np1: 0,
+            np2: 0,
+            np3: 0,
+            np4: 0,
+        }
+    }
+
+    fn q(&self) -> [i128; 5] {
+        [self.q0, self.q1, self.q2, self.q3, self.q4]
+    }
+
+    fn n(&self) -> [i64; 5] {
+        [self.n0, self.n1, self.n2, self.n3, self.n4]
+    }
+
+    fn np(&self) -> [i64; 5] {
+        [self.np0, self.np1, self.np2, self.np3, self.np4]
+    }
+
+    fn store_q(&mut self, q: [i128; 5]) {
+        self.q0 = q[0];
+        self.q1 = q[1];
+        self.q2 = q[2];
+        self.q3 = q[3];
+        self.q4 = q[4];
+    }
+
+    fn store_n(&mut self, n: [i64; 5]) {
+        self.n0 = n[0];
+        self.n1 = n[1];
+        self.n2 = n[2];
+        self.n3 = n[3];
+        self.n4 = n[4];
+    }
+
+    fn store_np(&mut self, np: [i64; 5]) {
+        self.np0 = np[0];
+        self.np1 = np[1];
+        self.np2 = np[2];
+        self.np3 = np[3];
+        self.np4 = np[4];
+    }
+
+    /// The desired-position increment for marker `i` (0-indexed), scaled
+    /// by `10_000`: `{0, p/2, p, (1+p)/2, 1}`.
+    fn dn(p_bps: u32, i: usize) -> i64 {
+        let p = p_bps as i64;
+        match i {
+            0 => 0,
+            1 => p / 2,
+            2 => p,
+            3 => (10_000 + p) / 2,
+            4 => 10_000,
+            _ => unreachable!(),
+        }
+    }
+
+    fn seed_from_init_samples(&mut self, env: &Env) {
+        let mut sorted: Vec<i128> = Vec::new(env);
+        for x in self.init_samples.iter() {
+            let mut inserted = false;
+            let mut i = 0u32;
+            while i < sorted.len() {
+                if x < sorted.get(i).unwrap() {
+                    sorted.insert(i, x);
+                    inserted = true;
+                    break;
+                }
+                i += 1;
+            }
+            if !inserted {
+                sorted.push_back(x);
+            }
+        }
+
+        let mut q = [0i128; 5];
+        let mut n = [0i64; 5];
+        let mut np = [0i64; 5];
+        for i in 0..5usize {
+            q[i] = sorted.get(i as u32).unwrap();
+            n[i] = (i + 1) as i64;
+            np[i] = (i as i64 + 1) * 10_000;
+        }
+        self.store_q(q);
+        self.store_n(n);
+        self.store_np(np);
+        self.init_samples = Vec::new(env);
+        self.seeded = true;
+    }
+
+    /// Feeds a new sample into the estimator, updating the marker heights
+    /// and positions per the P² algorithm.
+    pub fn observe(&mut self, env: &Env, x: i128) {
+        if !self.seeded {
+            self.init_samples.push_back(x);
+            if self.init_samples.len() == 5 {
+                self.seed_from_init_samples(env);
+            }
+            return;
+        }
+
+        let mut q = self.q();
+        let mut n = self.n();
+        let mut np = self.np();
+
+        if x < q[0] {
+            q[0] = x;
+        } else if x > q[4] {
+            q[4] = x;
+        }
+
+        // Find the cell k (0-indexed marker below x) and bump every
+        // marker position strictly above it.
+        let mut k: usize = 3;
+        for i in 0..4 {
+            if q[i] <= x && x < q[i + 1] {
+                k = i;
+                break;
+            }
+        }
+        for i in (k + 1)..5 {
+            n[i] += 1;
+        }
+        for (i, np_i) in np.iter_mut().enumerate() {
+            *np_i += Self::dn(self.p_bps, i);
+        }
+
+        for i in 1..4 {
+            let d = np[i] - n[i] * 10_000;
+            let sign: i64 = if d >= 10_000 {
+                1
+            } else if d <= -10_000 {
+                -1
+            } else {
+                0
+            };
+            if sign == 0 {
+                continue;
+            }
+            let would_move = n[i] + sign;
+            if would_move > n[i - 1] && would_move < n[i + 1] {
+                let new_q = Self::parabolic(&q, &n, i, sign);
+                q[i] = if new_q > q[i - 1] && new_q < q[i + 1] {
+                    new_q
+                } else {
+                    Self::linear(&q, &n, i, sign)
+                };
+                n[i] = would_move;
+            }
+        }
+
+        self.store_q(q);
+        self.store_n(n);
+        self.store_np(np);
+    }
+
+    fn parabolic(q: &[i128; 5], n: &[i64; 5], i: usize, d: i64) -> i128 {
+        let n_im1 = n[i - 1];
+        let n_i = n[i];
+        let n_ip1 = n[i + 1];
+        let q_im1 = q[i - 1];
+        let q_i = q[i];
+        let q_ip1 = q[i + 1];
+
+        let term1 = (n_i - n_im1 + d) as i128 * (q_ip1 - q_i) / (n_ip1 - n_i) as i128;
+        let term2 = (n_ip1 - n_i - d) as i128 * (q_i - q_im1) / (n_i - n_im1) as i128;
+        q_i + (d as i128) * (term1 + term2) / (n_ip1 - n_im1) as i128
+    }
+
+    fn linear(q: &[i128; 5], n: &[i64; 5], i: usize, d: i64) -> i128 {
+        let neighbor = if d > 0 { i + 1 } else { i - 1 };
+        q[i] + d as i128 * (q[neighbor] - q[i]) / (n[neighbor] - n[i]) as i128
+    }
+
+    /// The current quantile estimate: the middle marker's height once
+    /// seeded, or the closest available sample before then.
+    pub fn estimate(&self) -> i128 {
+        if self.seeded {
+            self.q2
+        } else if self.init_samples.is_empty() {
+            0
+        } else {
+            // Not enough samples yet to seed the markers - the best
+            // available estimate is the raw sample closest to the target
+            // quantile's relative position in what's been seen so far.
+            let len = self.init_samples.len();
+            let idx = (len as i64 - 1).max(0) as u32 * self.p_bps / 10_000;
+            self.init_samples.get(idx).unwrap()
+        }
+    }
+}
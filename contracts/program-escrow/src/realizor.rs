@@ -0,0 +1,213 @@
+//! # Realizor Gating (External Eligibility Check Before Release)
+//!
+//! Some grant programs need a payout to stay locked until a condition
+//! tracked in a *different* contract clears - e.g. a prize winner must have
+//! fully unstaked, or completed a milestone a separate tracker contract
+//! owns. Mirroring the delegated-provider pattern the sibling bounty-escrow
+//! contract already uses for participant eligibility
+//! (`external_filter::is_allowed_by_provider`), this lets a program point
+//! at a "realizor" contract implementing a known `is_realized(program_id,
+//! recipient, amount) -> bool` interface, and has `single_payout`,
+//! `batch_payout`, and `release_prog_schedule_automatic` defer to it before
+//! releasing funds.
+//!
+//! The realizor address lives under its own `DataKey::Realizor` entry
+//! rather than on `ProgramData` itself, so adding/removing the gate doesn't
+//! require touching every `ProgramData` construction site in `lib.rs`.
+//! [`is_realized`] fails closed: a trapping/misbehaving realizor or a plain
+//! `false` are both treated as "not realized yet," since a payout should
+//! never slip through just because the contract it was gated on broke.
+
+use soroban_sdk::{Address, Env, IntoVal, String, Symbol, Val, Vec};
+
+const IS_REALIZED: &str = "is_realized";
+
+/// Sets (or clears, with `None`) the realizor gating `program_id`'s
+/// payouts. Gated the same way `create_program_release_schedule` gates
+/// schedule creation: only the program's own authorized payout key.
+pub fn set_program_realizor(
+    env: &Env,
+    program_id: &String,
+    authorized_payout_key: &Address,
+    realizor: Option<Address>,
+) {
+    authorized_payout_key.require_auth();
+    let key = crate::DataKey::Realizor(program_id.clone());
+    match realizor {
+        Some(addr) => env.storage().instance().set(&key, &addr),
+        None => env.storage().instance().remove(&key),
+    }
+}
+
+/// The realizor currently gating `program_id`, if one has been set.
+pub fn get_program_realizor(env: &Env, program_id: &String) -> Option<Address> {
+    env.storage()
+        .instance()
+        .get(&crate::DataKey::Realizor(program_id.clone()))
+}
+
+/// Cross-contract call into `realizor`'s `is_realized(program_id,
+/// recipient, amount)`. Any outcome other than a clean `Ok(true)` -
+/// `Ok(false)`, a trap inside the realizor, or a missing/mismatched
+/// interface - is treated as "not realized."
+pub fn is_realized(env: &Env, realizor: &Address, program_id: &String, recipient: &Address, amount: i128) -> bool {
+    let func = Symbol::new(env, IS_REALIZED);
+    let args: Vec<Val> = Vec::from_array(
+        env,
+        [
+            program_id.into_val(env),
+            recipient.into_val(env),
+            amount.into_val(env),
+        ],
+    );
+
+    let result: Result<
+        Result<bool, soroban_sdk::Error>,
+        Result<soroban_sdk::InvokeError, soroban_sdk::InvokeError>,
+    > = env.try_invoke_contract(realizor, &func, args);
+
+    matches!(result, Ok(Ok(true)))
+}
+
+/// Cross-contract call into a per-schedule realizor's `is_realized(
+/// program_id, schedule_id, recipient)` - the same fail-closed contract as
+/// [`is_realized`], just scoped to one `ProgramReleaseSchedule` (via
+/// `attach_schedule_realizor` in `lib.rs`) instead of gating every payout
+/// in the program.
+pub fn is_schedule_realized(
+    env: &Env,
+    realizor: &Address,
+    program_id: &String,
+    schedule_id: u64,
+    recipient: &Address,
+) -> bool {
+    let func = Symbol::new(env, IS_REALIZED);
+    let args: Vec<Val> = Vec::from_array(
+        env,
+        [
+            program_id.into_val(env),
+            schedule_id.into_val(env),
+            recipient.into_val(env),
+        ],
+    );
+
+    let result: Result<
+        Result<bool, soroban_sdk::Error>,
+        Result<soroban_sdk::InvokeError, soroban_sdk::InvokeError>,
+    > = env.try_invoke_contract(realizor, &func, args);
+
+    matches!(result, Ok(Ok(true)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::{contract, contractimpl, testutils::Address as _};
+
+    #[contract]
+    struct RealizesAllProvider;
+
+    #[contractimpl]
+    impl RealizesAllProvider {
+        pub fn is_realized(_env: Env, _program_id: String, _recipient: Address, _amount: i128) -> bool {
+            true
+        }
+    }
+
+    #[contract]
+    struct RealizesNoneProvider;
+
+    #[contractimpl]
+    impl RealizesNoneProvider {
+        pub fn is_realized(_env: Env, _program_id: String, _recipient: Address, _amount: i128) -> bool {
+            false
+        }
+    }
+
+    #[contract]
+    struct TrappingRealizor;
+
+    #[contractimpl]
+    impl TrappingRealizor {
+        pub fn is_realized(_env: Env, _program_id: String, _recipient: Address, _amount: i128) -> bool {
+            panic!("realizor misbehaving")
+        }
+    }
+
+    #[test]
+    fn test_set_and_get_program_realizor_round_trips() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let authorized_key = Address::generate(&env);
+        let realizor = Address::generate(&env);
+        let program_id = String::from_str(&env, "Hackathon2024");
+
+        assert!(get_program_realizor(&env, &program_id).is_none());
+        set_program_realizor(&env, &program_id, &authorized_key, Some(realizor.clone()));
+        assert_eq!(get_program_realizor(&env, &program_id), Some(realizor));
+
+        set_program_realizor(&env, &program_id, &authorized_key, None);
+        assert!(get_program_realizor(&env, &program_id).is_none());
+    }
+
+    #[test]
+    fn test_is_realized_true() {
+        let env = Env::default();
+        let realizor_id = env.register_contract(None, RealizesAllProvider);
+        let program_id = String::from_str(&env, "Hackathon2024");
+        let recipient = Address::generate(&env);
+
+        assert!(is_realized(&env, &realizor_id, &program_id, &recipient, 1_000));
+    }
+
+    #[test]
+    fn test_is_realized_false() {
+        let env = Env::default();
+        let realizor_id = env.register_contract(None, RealizesNoneProvider);
+        let program_id = String::from_str(&env, "Hackathon2024");
+        let recipient = Address::generate(&env);
+
+        assert!(!is_realized(&env, &realizor_id, &program_id, &recipient, 1_000));
+    }
+
+    #[test]
+    fn test_is_realized_fails_closed_on_trap() {
+        let env = Env::default();
+        let realizor_id = env.register_contract(None, TrappingRealizor);
+        let program_id = String::from_str(&env, "Hackathon2024");
+        let recipient = Address::generate(&env);
+
+        assert!(!is_realized(&env, &realizor_id, &program_id, &recipient, 1_000));
+    }
+
+    #[contract]
+    struct RealizesScheduleProvider;
+
+    #[contractimpl]
+    impl RealizesScheduleProvider {
+        pub fn is_realized(_env: Env, _program_id: String, schedule_id: u64, _recipient: Address) -> bool {
+            schedule_id == 7
+        }
+    }
+
+    #[test]
+    fn test_is_schedule_realized_checks_schedule_id() {
+        let env = Env::default();
+        let realizor_id = env.register_contract(None, RealizesScheduleProvider);
+        let program_id = String::from_str(&env, "Hackathon2024");
+        let recipient = Address::generate(&env);
+
+        assert!(is_schedule_realized(&env, &realizor_id, &program_id, 7, &recipient));
+        assert!(!is_schedule_realized(&env, &realizor_id, &program_id, 8, &recipient));
+    }
+
+    #[test]
+    fn test_is_schedule_realized_fails_closed_on_trap() {
+        let env = Env::default();
+        let realizor_id = env.register_contract(None, TrappingRealizor);
+        let program_id = String::from_str(&env, "Hackathon2024");
+        let recipient = Address::generate(&env);
+
+        assert!(!is_schedule_realized(&env, &realizor_id, &program_id, 1, &recipient));
+    }
+}
@@ -0,0 +1,75 @@
+#![cfg(test)]
+
+use crate::{ProgramEscrowContract, ProgramEscrowContractClient};
+use soroban_sdk::{testutils::Address as _, token, Address, Env, String};
+
+fn create_token_contract<'a>(env: &Env, admin: &Address) -> token::Client<'a> {
+    let token_contract = env.register_stellar_asset_contract_v2(admin.clone());
+    token::Client::new(env, &token_contract.address())
+}
+
+fn setup_program<'a>(
+    env: &Env,
+    admin: &Address,
+    authorized_payout_key: &Address,
+) -> (ProgramEscrowContractClient<'a>, String, token::Client<'a>) {
+    let contract_id = env.register_contract(None, ProgramEscrowContract);
+    let client = ProgramEscrowContractClient::new(env, &contract_id);
+
+    client.initialize_contract(admin);
+
+    let token_admin = Address::generate(env);
+    let token_client = create_token_contract(env, &token_admin);
+
+    let program_id = String::from_str(env, "pause-prog");
+    client.init_program(
+        &program_id,
+        authorized_payout_key,
+        &token_client.address,
+        admin,
+        &None,
+        &None,
+    );
+
+    let token_admin_client = token::StellarAssetClient::new(env, &token_client.address);
+    let depositor = Address::generate(env);
+    token_admin_client.mint(&depositor, &5000);
+    token_client.transfer(&depositor, &contract_id, &5000);
+    client.lock_program_funds(&program_id, &5000i128);
+
+    (client, program_id, token_client)
+}
+
+#[test]
+#[should_panic(expected = "Funds Paused")]
+fn test_non_admin_release_blocked_while_paused() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let payout_key = Address::generate(&env);
+    let (client, program_id, _token) = setup_program(&env, &admin, &payout_key);
+
+    client.set_paused(&None, &Some(true), &None, &None);
+
+    let recipient = Address::generate(&env);
+    client.single_payout(&program_id, &recipient, &1000i128);
+}
+
+#[test]
+fn test_admin_bypasses_release_pause() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    // The authorized payout key for this program *is* the admin, so it can
+    // authenticate its way past an active release pause.
+    let admin = Address::generate(&env);
+    let (client, program_id, token) = setup_program(&env, &admin, &admin);
+
+    client.set_paused(&None, &Some(true), &None, &None);
+
+    let recipient = Address::generate(&env);
+    client.single_payout(&program_id, &recipient, &1000i128);
+
+    assert_eq!(token.balance(&recipient), 1000);
+}
@@ -0,0 +1,238 @@
+//! # Versioned Event Schemas
+//!
+//! `emit_jurisdiction_event` already publishes a fully structured,
+//! versioned `EscrowJurisdictionEvent`, but `submit_identity_claim`,
+//! `enforce_transaction_limit`, and `set_authorized_issuer` still publish
+//! loose tuples under ad-hoc topics (`"claim"`, `"limit"`, `"issuer"`) with
+//! no schema version, so an off-chain indexer has to special-case each one
+//! and has no way to tell an old shape from a new one if a field is ever
+//! added. This module gives every one of them the same treatment
+//! `EscrowJurisdictionEvent` already gets: a dedicated `#[contracttype]`
+//! struct, a `version` field, and `env.ledger().timestamp()` stamped in by
+//! the constructor rather than by each call site.
+//!
+//! [`EventBuilder`] just carries the schema `version` so every `emit::*`
+//! constructor in this module stamps the same one without repeating a
+//! magic number at each call site; bump [`EventBuilder::CURRENT_VERSION`]
+//! the day any of these structs' fields change shape.
+
+use soroban_sdk::{symbol_short, Address, Env, Symbol};
+
+use crate::EscrowStatus;
+
+/// Schema version stamped on every event this module emits. Bump this (and
+/// document the change here) whenever a struct below gains, loses, or
+/// reinterprets a field.
+pub struct EventBuilder;
+
+impl EventBuilder {
+    pub const CURRENT_VERSION: u32 = 1;
+}
+
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LimitCheckedEvent {
+    pub version: u32,
+    pub address: Address,
+    pub passed: bool,
+    pub amount: i128,
+    pub effective_limit: i128,
+    pub timestamp: u64,
+}
+
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimSubmittedEvent {
+    pub version: u32,
+    pub address: Address,
+    pub tier: crate::IdentityTier,
+    pub risk_score: u32,
+    pub expiry: u64,
+    pub timestamp: u64,
+}
+
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IssuerChangedEvent {
+    pub version: u32,
+    pub issuer: Address,
+    pub authorized: bool,
+    pub timestamp: u64,
+}
+
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FundsLockedEvent {
+    pub version: u32,
+    pub bounty_id: u64,
+    pub depositor: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FundsReleasedEvent {
+    pub version: u32,
+    pub bounty_id: u64,
+    pub contributor: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FundsRefundedEvent {
+    pub version: u32,
+    pub bounty_id: u64,
+    pub depositor: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+/// Announces an escrow's `remaining_amount` immediately after it changes,
+/// so an indexer can track liquidity without replaying and diffing
+/// jurisdiction events. Published even when `previous_remaining ==
+/// new_remaining` but `status` changed, so a Locked->Released/Refunded
+/// transition is always visible as its own event.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowBalanceEvent {
+    pub version: u32,
+    pub bounty_id: u64,
+    pub previous_remaining: i128,
+    pub new_remaining: i128,
+    pub status: EscrowStatus,
+    pub timestamp: u64,
+}
+
+/// Typed constructors, one per event this crate publishes outside of
+/// `emit_jurisdiction_event`. Each stamps [`EventBuilder::CURRENT_VERSION`]
+/// and the current ledger timestamp so call sites never have to.
+pub mod emit {
+    use super::*;
+
+    pub fn limit_checked(
+        env: &Env,
+        address: Address,
+        passed: bool,
+        amount: i128,
+        effective_limit: i128,
+    ) {
+        let topic: Symbol = if passed {
+            symbol_short!("pass")
+        } else {
+            symbol_short!("exceed")
+        };
+        env.events().publish(
+            (symbol_short!("limit"), address.clone(), topic),
+            LimitCheckedEvent {
+                version: EventBuilder::CURRENT_VERSION,
+                address,
+                passed,
+                amount,
+                effective_limit,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    pub fn claim_submitted(
+        env: &Env,
+        address: Address,
+        tier: crate::IdentityTier,
+        risk_score: u32,
+        expiry: u64,
+    ) {
+        env.events().publish(
+            (symbol_short!("claim"), address.clone(), symbol_short!("ok")),
+            ClaimSubmittedEvent {
+                version: EventBuilder::CURRENT_VERSION,
+                address,
+                tier,
+                risk_score,
+                expiry,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    /// `reason` is the short tag the caller already publishes today
+    /// (`"expired"`/`"unauth"`) so existing off-chain topic filters keep
+    /// matching while the payload gains a stable, versioned shape.
+    pub fn claim_rejected(env: &Env, address: Address, reason: Symbol) {
+        env.events()
+            .publish((symbol_short!("claim"), address, reason), ());
+    }
+
+    pub fn issuer_changed(env: &Env, issuer: Address, authorized: bool) {
+        env.events().publish(
+            (symbol_short!("issuer"), issuer.clone()),
+            IssuerChangedEvent {
+                version: EventBuilder::CURRENT_VERSION,
+                issuer,
+                authorized,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    pub fn funds_locked(env: &Env, bounty_id: u64, depositor: Address, amount: i128) {
+        env.events().publish(
+            (symbol_short!("lock"), bounty_id),
+            FundsLockedEvent {
+                version: EventBuilder::CURRENT_VERSION,
+                bounty_id,
+                depositor,
+                amount,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    pub fn funds_released(env: &Env, bounty_id: u64, contributor: Address, amount: i128) {
+        env.events().publish(
+            (symbol_short!("release"), bounty_id),
+            FundsReleasedEvent {
+                version: EventBuilder::CURRENT_VERSION,
+                bounty_id,
+                contributor,
+                amount,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    pub fn funds_refunded(env: &Env, bounty_id: u64, depositor: Address, amount: i128) {
+        env.events().publish(
+            (symbol_short!("refund"), bounty_id),
+            FundsRefundedEvent {
+                version: EventBuilder::CURRENT_VERSION,
+                bounty_id,
+                depositor,
+                amount,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    pub fn escrow_balance_changed(
+        env: &Env,
+        bounty_id: u64,
+        previous_remaining: i128,
+        new_remaining: i128,
+        status: EscrowStatus,
+    ) {
+        env.events().publish(
+            (symbol_short!("balance"), bounty_id),
+            EscrowBalanceEvent {
+                version: EventBuilder::CURRENT_VERSION,
+                bounty_id,
+                previous_remaining,
+                new_remaining,
+                status,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+}
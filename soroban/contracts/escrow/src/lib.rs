@@ -7,10 +7,12 @@ use soroban_sdk::{
     String, Symbol, Vec,
 };
 
+mod events;
 mod identity;
 pub use identity::*;
 
 mod reentrancy_guard;
+mod storage_access;
 
 #[contracterror]
 #[derive(Clone, Debug, PartialEq)]
@@ -35,6 +37,9 @@ pub enum Error {
     JurisdictionPaused = 107,
     JurisdictionKycRequired = 108,
     JurisdictionAmountExceeded = 109,
+    StateCorrupt = 110,
+    TokenNotConfigured = 111,
+    SelectionTargetUnreachable = 112,
 }
 
 #[contracttype]
@@ -75,6 +80,10 @@ pub struct Escrow {
 pub struct EscrowSearchCriteria {
     pub status_filter: u32,
     pub depositor: Option<Address>,
+    /// Bounty ids to skip even if they otherwise match, so a caller can
+    /// request "the next matching escrow that isn't one of these" (e.g.
+    /// when stitching together results from several overlapping queries).
+    pub excluded_ids: Vec<u64>,
 }
 
 /// A single escrow record in search results (flattened).
@@ -89,16 +98,36 @@ pub struct EscrowRecord {
     pub deadline: u64,
 }
 
+/// Aggregate counts and totals over a set of escrows matching a given
+/// `EscrowSearchCriteria`, returned by `get_escrow_stats`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowStats {
+    pub locked_count: u32,
+    pub released_count: u32,
+    pub refunded_count: u32,
+    pub total_amount: i128,
+    pub total_remaining_amount: i128,
+}
+
 /// A single page of escrow search results.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct EscrowPage {
     /// Matched escrow records.
     pub records: Vec<EscrowRecord>,
-    /// Cursor for the next page (`None` if this is the last page).
+    /// Cursor for the next page (`None` if this is the last page). Kept
+    /// alongside `end_cursor` for existing forward-pagination callers;
+    /// unlike `end_cursor` it is `None` whenever `has_more` is `false`.
     pub next_cursor: Option<u64>,
     /// Whether more results exist beyond this page.
     pub has_more: bool,
+    /// `bounty_id` of the first record in this page (`None` if empty).
+    pub start_cursor: Option<u64>,
+    /// `bounty_id` of the last record in this page (`None` if empty).
+    pub end_cursor: Option<u64>,
+    /// Whether more results exist before this page.
+    pub has_previous: bool,
 }
 
 #[contracttype]
@@ -129,6 +158,11 @@ pub enum DataKey {
     EscrowJurisdiction(u64),
     /// Persistent Vec<u64> index of all bounty IDs.
     EscrowIndex,
+    /// Persistent Vec<u64> index of bounty IDs per depositor.
+    EscrowByDepositor(Address),
+    /// Persistent Vec<u64> index of bounty IDs per status code
+    /// (1=Locked, 2=Released, 3=Refunded; matches `EscrowSearchCriteria::status_filter`).
+    EscrowByStatus(u32),
     // Identity-related storage keys
     AddressIdentity(Address),
     AuthorizedIssuer(Address),
@@ -188,6 +222,77 @@ impl EscrowContract {
         );
     }
 
+    /// Convert an `EscrowStatus` to the u32 code `EscrowSearchCriteria`
+    /// and the `EscrowByStatus` index key use (1=Locked, 2=Released,
+    /// 3=Refunded).
+    fn status_code(status: &EscrowStatus) -> u32 {
+        match status {
+            EscrowStatus::Locked => 1,
+            EscrowStatus::Released => 2,
+            EscrowStatus::Refunded => 3,
+        }
+    }
+
+    /// Convert `EscrowSearchCriteria::status_filter` (0=any, 1=Locked,
+    /// 2=Released, 3=Refunded) into the matching `EscrowStatus`, or `None`
+    /// for "match any".
+    fn status_match_for_criteria(status_filter: u32) -> Option<EscrowStatus> {
+        match status_filter {
+            1 => Some(EscrowStatus::Locked),
+            2 => Some(EscrowStatus::Released),
+            3 => Some(EscrowStatus::Refunded),
+            _ => None,
+        }
+    }
+
+    /// Pick the narrowest applicable secondary index for a search: the
+    /// depositor index when a depositor is set, otherwise the status index
+    /// when a status filter is set, otherwise the global index.
+    fn search_index_key(criteria: &EscrowSearchCriteria, status_match: &Option<EscrowStatus>) -> DataKey {
+        if let Some(ref depositor) = criteria.depositor {
+            DataKey::EscrowByDepositor(depositor.clone())
+        } else if let Some(ref status) = status_match {
+            DataKey::EscrowByStatus(Self::status_code(status))
+        } else {
+            DataKey::EscrowIndex
+        }
+    }
+
+    /// Append `bounty_id` to the persistent `Vec<u64>` index stored under `key`.
+    fn append_to_index(env: &Env, key: &DataKey, bounty_id: u64) {
+        let mut index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(key)
+            .unwrap_or_else(|| Vec::new(env));
+        index.push_back(bounty_id);
+        env.storage().persistent().set(key, &index);
+    }
+
+    /// Remove `bounty_id` from the persistent `Vec<u64>` index stored under `key`, if present.
+    fn remove_from_index(env: &Env, key: &DataKey, bounty_id: u64) {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(key)
+            .unwrap_or_else(|| Vec::new(env));
+        let mut filtered: Vec<u64> = Vec::new(env);
+        for i in 0..index.len() {
+            let id = index.get(i).unwrap();
+            if id != bounty_id {
+                filtered.push_back(id);
+            }
+        }
+        env.storage().persistent().set(key, &filtered);
+    }
+
+    /// Move `bounty_id` from the `from` status index to the `to` status
+    /// index. Called whenever an escrow's status actually changes.
+    fn move_status_index(env: &Env, bounty_id: u64, from: &EscrowStatus, to: &EscrowStatus) {
+        Self::remove_from_index(env, &DataKey::EscrowByStatus(Self::status_code(from)), bounty_id);
+        Self::append_to_index(env, &DataKey::EscrowByStatus(Self::status_code(to)), bounty_id);
+    }
+
     fn enforce_lock_jurisdiction(
         env: &Env,
         depositor: &Address,
@@ -287,15 +392,7 @@ impl EscrowContract {
             .persistent()
             .set(&DataKey::AuthorizedIssuer(issuer.clone()), &authorized);
 
-        // Emit event for issuer management
-        env.events().publish(
-            (soroban_sdk::symbol_short!("issuer"), issuer.clone()),
-            if authorized {
-                soroban_sdk::symbol_short!("add")
-            } else {
-                soroban_sdk::symbol_short!("remove")
-            },
-        );
+        events::emit::issuer_changed(&env, issuer, authorized);
 
         Ok(())
     }
@@ -372,10 +469,7 @@ impl EscrowContract {
 
         // Check if claim has expired
         if identity::is_claim_expired(&env, claim.expiry) {
-            env.events().publish(
-                (soroban_sdk::symbol_short!("claim"), claim.address.clone()),
-                soroban_sdk::symbol_short!("expired"),
-            );
+            events::emit::claim_rejected(&env, claim.address.clone(), symbol_short!("expired"));
             return Err(Error::ClaimExpired);
         }
 
@@ -387,10 +481,7 @@ impl EscrowContract {
             .unwrap_or(false);
 
         if !is_authorized {
-            env.events().publish(
-                (soroban_sdk::symbol_short!("claim"), claim.address.clone()),
-                soroban_sdk::symbol_short!("unauth"),
-            );
+            events::emit::claim_rejected(&env, claim.address.clone(), symbol_short!("unauth"));
             return Err(Error::UnauthorizedIssuer);
         }
 
@@ -411,11 +502,7 @@ impl EscrowContract {
             &identity_data,
         );
 
-        // Emit event for successful claim submission
-        env.events().publish(
-            (soroban_sdk::symbol_short!("claim"), claim.address.clone()),
-            (claim.tier, claim.risk_score, claim.expiry),
-        );
+        events::emit::claim_submitted(&env, claim.address, claim.tier, claim.risk_score, claim.expiry);
 
         Ok(())
     }
@@ -478,23 +565,11 @@ impl EscrowContract {
         let effective_limit = Self::get_effective_limit(env.clone(), address.clone());
 
         if amount > effective_limit {
-            // Emit event for limit enforcement failure
-            env.events().publish(
-                (soroban_sdk::symbol_short!("limit"), address.clone()),
-                (
-                    soroban_sdk::symbol_short!("exceed"),
-                    amount,
-                    effective_limit,
-                ),
-            );
+            events::emit::limit_checked(env, address.clone(), false, amount, effective_limit);
             return Err(Error::TransactionExceedsLimit);
         }
 
-        // Emit event for successful limit check
-        env.events().publish(
-            (soroban_sdk::symbol_short!("limit"), address.clone()),
-            (soroban_sdk::symbol_short!("pass"), amount, effective_limit),
-        );
+        events::emit::limit_checked(env, address.clone(), true, amount, effective_limit);
 
         Ok(())
     }
@@ -526,64 +601,67 @@ impl EscrowContract {
         // GUARD: acquire reentrancy lock
         reentrancy_guard::acquire(&env);
 
-        depositor.require_auth();
-        if !env.storage().instance().has(&DataKey::Admin) {
-            return Err(Error::NotInitialized);
-        }
-        if amount <= 0 {
-            return Err(Error::InsufficientBalance);
-        }
-        if env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
-            return Err(Error::BountyExists);
-        }
+        let result = (|| -> Result<(), Error> {
+            depositor.require_auth();
+            storage_access::load_admin(&env)?;
+            if amount <= 0 {
+                return Err(Error::InsufficientBalance);
+            }
+            if env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+                return Err(Error::BountyExists);
+            }
 
-        Self::enforce_lock_jurisdiction(&env, &depositor, amount, &jurisdiction)?;
+            Self::enforce_lock_jurisdiction(&env, &depositor, amount, &jurisdiction)?;
 
-        // EFFECTS: write escrow state before external call
-        let escrow = Escrow {
-            depositor: depositor.clone(),
-            amount,
-            remaining_amount: amount,
-            status: EscrowStatus::Locked,
-            deadline,
-        };
-        env.storage()
-            .persistent()
-            .set(&DataKey::Escrow(bounty_id), &escrow);
-
-        // Store jurisdiction config separately (avoids Option<ContractType> XDR issue)
-        if let Some(ref juris) = jurisdiction {
+            // EFFECTS: write escrow state before external call
+            let escrow = Escrow {
+                depositor: depositor.clone(),
+                amount,
+                remaining_amount: amount,
+                status: EscrowStatus::Locked,
+                deadline,
+            };
             env.storage()
                 .persistent()
-                .set(&DataKey::EscrowJurisdiction(bounty_id), juris);
-        }
+                .set(&DataKey::Escrow(bounty_id), &escrow);
+            events::emit::escrow_balance_changed(&env, bounty_id, 0, amount, escrow.status.clone());
+
+            // Store jurisdiction config separately (avoids Option<ContractType> XDR issue)
+            if let Some(ref juris) = jurisdiction {
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::EscrowJurisdiction(bounty_id), juris);
+            }
 
-        // Append bounty_id to the global index for paginated queries
-        let mut index: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::EscrowIndex)
-            .unwrap_or_else(|| Vec::new(&env));
-        index.push_back(bounty_id);
-        env.storage()
-            .persistent()
-            .set(&DataKey::EscrowIndex, &index);
+            // Append bounty_id to the global index and the narrower
+            // depositor/status secondary indexes for paginated queries.
+            Self::append_to_index(&env, &DataKey::EscrowIndex, bounty_id);
+            Self::append_to_index(
+                &env,
+                &DataKey::EscrowByDepositor(depositor.clone()),
+                bounty_id,
+            );
+            Self::append_to_index(
+                &env,
+                &DataKey::EscrowByStatus(Self::status_code(&EscrowStatus::Locked)),
+                bounty_id,
+            );
 
-        // INTERACTION: external token transfer is last
-        let token = env
-            .storage()
-            .instance()
-            .get::<_, Address>(&DataKey::Token)
-            .unwrap();
-        let contract = env.current_contract_address();
-        let token_client = token::Client::new(&env, &token);
-        token_client.transfer(&depositor, &contract, &amount);
+            // INTERACTION: external token transfer is last
+            let token = storage_access::load_token(&env)?;
+            let contract = env.current_contract_address();
+            let token_client = token::Client::new(&env, &token);
+            token_client.transfer(&depositor, &contract, &amount);
 
-        Self::emit_jurisdiction_event(&env, bounty_id, symbol_short!("lock"), &jurisdiction);
+            Self::emit_jurisdiction_event(&env, bounty_id, symbol_short!("lock"), &jurisdiction);
+            events::emit::funds_locked(&env, bounty_id, depositor.clone(), amount);
 
-        // GUARD: release reentrancy lock
+            Ok(())
+        })();
+
+        // GUARD: release reentrancy lock on every exit path, not just success
         reentrancy_guard::release(&env);
-        Ok(())
+        result
     }
 
     /// Release funds to contributor. Admin must be authorized. Fails if already released or refunded.
@@ -595,64 +673,188 @@ impl EscrowContract {
         // GUARD: acquire reentrancy lock
         reentrancy_guard::acquire(&env);
 
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        admin.require_auth();
-        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
-            return Err(Error::BountyNotFound);
-        }
+        let result = (|| -> Result<(), Error> {
+            let admin = storage_access::load_admin(&env)?;
+            admin.require_auth();
 
-        let mut escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Escrow(bounty_id))
-            .unwrap();
-        if escrow.status != EscrowStatus::Locked {
-            return Err(Error::FundsNotLocked);
-        }
-        if escrow.remaining_amount <= 0 {
-            return Err(Error::InsufficientBalance);
-        }
+            let mut escrow = storage_access::load_escrow(&env, bounty_id)?;
+            if escrow.status != EscrowStatus::Locked {
+                return Err(Error::FundsNotLocked);
+            }
+            if escrow.remaining_amount <= 0 {
+                return Err(Error::InsufficientBalance);
+            }
 
-        let jurisdiction: Option<EscrowJurisdictionConfig> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::EscrowJurisdiction(bounty_id));
-
-        Self::enforce_release_jurisdiction(
-            &env,
-            &contributor,
-            escrow.remaining_amount,
-            &jurisdiction,
-        )?;
-
-        // EFFECTS: update state before external call (CEI)
-        let release_amount = escrow.remaining_amount;
-        escrow.remaining_amount = 0;
-        escrow.status = EscrowStatus::Released;
-        env.storage()
-            .persistent()
-            .set(&DataKey::Escrow(bounty_id), &escrow);
+            let jurisdiction: Option<EscrowJurisdictionConfig> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::EscrowJurisdiction(bounty_id));
+
+            Self::enforce_release_jurisdiction(
+                &env,
+                &contributor,
+                escrow.remaining_amount,
+                &jurisdiction,
+            )?;
+
+            // EFFECTS: update state before external call (CEI)
+            let previous_remaining = escrow.remaining_amount;
+            let release_amount = escrow.remaining_amount;
+            escrow.remaining_amount = 0;
+            escrow.status = EscrowStatus::Released;
+            env.storage()
+                .persistent()
+                .set(&DataKey::Escrow(bounty_id), &escrow);
+            Self::move_status_index(&env, bounty_id, &EscrowStatus::Locked, &escrow.status);
+            events::emit::escrow_balance_changed(
+                &env,
+                bounty_id,
+                previous_remaining,
+                escrow.remaining_amount,
+                escrow.status.clone(),
+            );
 
-        // INTERACTION: external token transfer is last
-        let token = env
-            .storage()
-            .instance()
-            .get::<_, Address>(&DataKey::Token)
-            .unwrap();
-        let contract = env.current_contract_address();
-        let token_client = token::Client::new(&env, &token);
-        token_client.transfer(&contract, &contributor, &release_amount);
-
-        Self::emit_jurisdiction_event(
-            &env,
-            bounty_id,
-            symbol_short!("release"),
-            &jurisdiction,
-        );
+            // INTERACTION: external token transfer is last
+            let token = storage_access::load_token(&env)?;
+            let contract = env.current_contract_address();
+            let token_client = token::Client::new(&env, &token);
+            token_client.transfer(&contract, &contributor, &release_amount);
+
+            Self::emit_jurisdiction_event(
+                &env,
+                bounty_id,
+                symbol_short!("release"),
+                &jurisdiction,
+            );
+            events::emit::funds_released(&env, bounty_id, contributor.clone(), release_amount);
+
+            Ok(())
+        })();
 
-        // GUARD: release reentrancy lock
+        // GUARD: release reentrancy lock on every exit path, not just success
         reentrancy_guard::release(&env);
-        Ok(())
+        result
+    }
+
+    /// Release a single milestone tranche of `amount` to `contributor`,
+    /// leaving the escrow `Locked` with a reduced `remaining_amount` unless
+    /// this tranche exhausts it. A thin wrapper over [`Self::release_many`]
+    /// with a single-entry payout list, so both paths share one
+    /// journal-and-commit implementation.
+    ///
+    /// # Reentrancy
+    /// Protected by reentrancy guard. Escrow state is updated before the
+    /// outbound token transfer (CEI pattern).
+    pub fn release_partial(
+        env: Env,
+        bounty_id: u64,
+        contributor: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        let payouts = soroban_sdk::vec![&env, (contributor, amount)];
+        Self::release_many(env, bounty_id, payouts)
+    }
+
+    /// Pay out several contributors from one escrow in a single call.
+    ///
+    /// Builds an in-memory journal of the proposed deductions first: it
+    /// walks `payouts` accumulating the running total and checking every
+    /// recipient against [`Self::enforce_release_jurisdiction`] before any
+    /// storage is touched or any token moves. If the running total would
+    /// exceed `remaining_amount`, or any recipient fails its jurisdiction
+    /// check, the whole batch is rejected and nothing is written - a
+    /// partial batch never leaves partial state behind.
+    ///
+    /// Only once every entry in the journal has cleared does this commit:
+    /// `remaining_amount` is decremented and the `Escrow` record written
+    /// (CEI), and the token transfers fire last, one per journal entry, in
+    /// `payouts` order. The escrow stays `Locked` until `remaining_amount`
+    /// reaches zero, at which point it flips to `Released`.
+    ///
+    /// # Reentrancy
+    /// Protected by reentrancy guard. Escrow state is updated before the
+    /// outbound token transfers (CEI pattern).
+    pub fn release_many(
+        env: Env,
+        bounty_id: u64,
+        payouts: Vec<(Address, i128)>,
+    ) -> Result<(), Error> {
+        // GUARD: acquire reentrancy lock
+        reentrancy_guard::acquire(&env);
+
+        let result = (|| -> Result<(), Error> {
+            let admin = storage_access::load_admin(&env)?;
+            admin.require_auth();
+
+            let mut escrow = storage_access::load_escrow(&env, bounty_id)?;
+            if escrow.status != EscrowStatus::Locked {
+                return Err(Error::FundsNotLocked);
+            }
+
+            let jurisdiction: Option<EscrowJurisdictionConfig> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::EscrowJurisdiction(bounty_id));
+
+            // JOURNAL: validate every payout and accrue the running total
+            // before writing or transferring anything.
+            let mut journaled_total: i128 = 0;
+            for (contributor, amount) in payouts.iter() {
+                if amount <= 0 {
+                    return Err(Error::InsufficientBalance);
+                }
+                journaled_total += amount;
+                if journaled_total > escrow.remaining_amount {
+                    return Err(Error::InsufficientBalance);
+                }
+                Self::enforce_release_jurisdiction(&env, &contributor, amount, &jurisdiction)?;
+            }
+
+            // EFFECTS: commit the journal before any external call (CEI)
+            let previous_remaining = escrow.remaining_amount;
+            escrow.remaining_amount -= journaled_total;
+            if escrow.remaining_amount == 0 {
+                escrow.status = EscrowStatus::Released;
+            }
+            env.storage()
+                .persistent()
+                .set(&DataKey::Escrow(bounty_id), &escrow);
+            if escrow.status == EscrowStatus::Released {
+                Self::move_status_index(&env, bounty_id, &EscrowStatus::Locked, &escrow.status);
+            }
+            events::emit::escrow_balance_changed(
+                &env,
+                bounty_id,
+                previous_remaining,
+                escrow.remaining_amount,
+                escrow.status.clone(),
+            );
+
+            // INTERACTION: external token transfers fire last, one per
+            // journal entry, in payout order.
+            let token = storage_access::load_token(&env)?;
+            let contract = env.current_contract_address();
+            let token_client = token::Client::new(&env, &token);
+            for (contributor, amount) in payouts.iter() {
+                token_client.transfer(&contract, &contributor, &amount);
+            }
+
+            Self::emit_jurisdiction_event(
+                &env,
+                bounty_id,
+                symbol_short!("release"),
+                &jurisdiction,
+            );
+            for (contributor, amount) in payouts.iter() {
+                events::emit::funds_released(&env, bounty_id, contributor, amount);
+            }
+
+            Ok(())
+        })();
+
+        // GUARD: release reentrancy lock on every exit path, not just success
+        reentrancy_guard::release(&env);
+        result
     }
 
     /// Refund remaining funds to depositor. Allowed after deadline.
@@ -664,60 +866,62 @@ impl EscrowContract {
         // GUARD: acquire reentrancy lock
         reentrancy_guard::acquire(&env);
 
-        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
-            return Err(Error::BountyNotFound);
-        }
+        let result = (|| -> Result<(), Error> {
+            let mut escrow = storage_access::load_escrow(&env, bounty_id)?;
+            if escrow.status != EscrowStatus::Locked {
+                return Err(Error::FundsNotLocked);
+            }
+            let now = env.ledger().timestamp();
+            if now < escrow.deadline {
+                return Err(Error::DeadlineNotPassed);
+            }
+            if escrow.remaining_amount <= 0 {
+                return Err(Error::InsufficientBalance);
+            }
+            let jurisdiction: Option<EscrowJurisdictionConfig> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::EscrowJurisdiction(bounty_id));
+            Self::enforce_refund_jurisdiction(&env, &escrow.depositor, &jurisdiction)?;
+
+            // EFFECTS: update state before external call (CEI)
+            let previous_remaining = escrow.remaining_amount;
+            let amount = escrow.remaining_amount;
+            let depositor = escrow.depositor.clone();
+            escrow.remaining_amount = 0;
+            escrow.status = EscrowStatus::Refunded;
+            env.storage()
+                .persistent()
+                .set(&DataKey::Escrow(bounty_id), &escrow);
+            Self::move_status_index(&env, bounty_id, &EscrowStatus::Locked, &escrow.status);
+            events::emit::escrow_balance_changed(
+                &env,
+                bounty_id,
+                previous_remaining,
+                escrow.remaining_amount,
+                escrow.status.clone(),
+            );
 
-        let mut escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Escrow(bounty_id))
-            .unwrap();
-        if escrow.status != EscrowStatus::Locked {
-            return Err(Error::FundsNotLocked);
-        }
-        let now = env.ledger().timestamp();
-        if now < escrow.deadline {
-            return Err(Error::DeadlineNotPassed);
-        }
-        if escrow.remaining_amount <= 0 {
-            return Err(Error::InsufficientBalance);
-        }
-        let jurisdiction: Option<EscrowJurisdictionConfig> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::EscrowJurisdiction(bounty_id));
-        Self::enforce_refund_jurisdiction(&env, &escrow.depositor, &jurisdiction)?;
-
-        // EFFECTS: update state before external call (CEI)
-        let amount = escrow.remaining_amount;
-        let depositor = escrow.depositor.clone();
-        escrow.remaining_amount = 0;
-        escrow.status = EscrowStatus::Refunded;
-        env.storage()
-            .persistent()
-            .set(&DataKey::Escrow(bounty_id), &escrow);
+            // INTERACTION: external token transfer is last
+            let token = storage_access::load_token(&env)?;
+            let contract = env.current_contract_address();
+            let token_client = token::Client::new(&env, &token);
+            token_client.transfer(&contract, &depositor, &amount);
 
-        // INTERACTION: external token transfer is last
-        let token = env
-            .storage()
-            .instance()
-            .get::<_, Address>(&DataKey::Token)
-            .unwrap();
-        let contract = env.current_contract_address();
-        let token_client = token::Client::new(&env, &token);
-        token_client.transfer(&contract, &depositor, &amount);
-
-        Self::emit_jurisdiction_event(
-            &env,
-            bounty_id,
-            symbol_short!("refund"),
-            &jurisdiction,
-        );
+            Self::emit_jurisdiction_event(
+                &env,
+                bounty_id,
+                symbol_short!("refund"),
+                &jurisdiction,
+            );
+            events::emit::funds_refunded(&env, bounty_id, depositor, amount);
 
-        // GUARD: release reentrancy lock
+            Ok(())
+        })();
+
+        // GUARD: release reentrancy lock on every exit path, not just success
         reentrancy_guard::release(&env);
-        Ok(())
+        result
     }
 
     /// Read escrow state (for tests).
@@ -752,51 +956,313 @@ impl EscrowContract {
         index.len()
     }
 
-    /// Paginated search over escrows.
+    /// Connection-style paginated search over escrows, supporting paging
+    /// both forward and backward.
     ///
     /// * `criteria` – `status_filter`: 0=any, 1=Locked, 2=Released, 3=Refunded.
     ///                `depositor`: optional address filter.
-    /// * `cursor`   – pass the `next_cursor` from a previous `EscrowPage` to continue;
-    ///                `None` starts from the beginning.
-    /// * `limit`    – max results per page (capped at `MAX_PAGE_SIZE`).
+    /// * `after`    – forward cursor: pass a previous page's `end_cursor`/`next_cursor`
+    ///                to continue past it. Ignored when paging backward (see below).
+    /// * `before`   – backward cursor: pass a previous page's `start_cursor` to fetch
+    ///                the matching records immediately preceding it.
+    /// * `first`    – max results when paging forward (capped at `MAX_PAGE_SIZE`,
+    ///                defaults to `MAX_PAGE_SIZE` when `None` or `0`).
+    /// * `last`     – max results when paging backward (same capping/default as `first`).
     ///
-    /// Returns an `EscrowPage` with matching records, the next cursor, and a
-    /// `has_more` flag.
+    /// Paging direction is chosen by whether `before`/`last` are set: if
+    /// either is `Some`, this pages backward from `before` (or from the end
+    /// of the matching set if `before` is `None`); otherwise it pages
+    /// forward from `after` (or from the start if `after` is `None`).
+    ///
+    /// Returns an `EscrowPage` with matching records plus `start_cursor`,
+    /// `end_cursor`, `has_more` (more data after `end_cursor`), and
+    /// `has_previous` (more data before `start_cursor`).
     pub fn get_escrows(
         env: Env,
         criteria: EscrowSearchCriteria,
-        cursor: Option<u64>,
-        limit: u32,
+        after: Option<u64>,
+        before: Option<u64>,
+        first: Option<u32>,
+        last: Option<u32>,
+    ) -> EscrowPage {
+        let status_match = Self::status_match_for_criteria(criteria.status_filter);
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&Self::search_index_key(&criteria, &status_match))
+            .unwrap_or_else(|| Vec::new(&env));
+
+        if before.is_some() || last.is_some() {
+            return Self::get_escrows_backward(&env, &criteria, &status_match, &index, before, last);
+        }
+        Self::get_escrows_forward(&env, &criteria, &status_match, &index, after, first)
+    }
+
+    fn get_escrows_forward(
+        env: &Env,
+        criteria: &EscrowSearchCriteria,
+        status_match: &Option<EscrowStatus>,
+        index: &Vec<u64>,
+        after: Option<u64>,
+        first: Option<u32>,
+    ) -> EscrowPage {
+        let effective_first = match first {
+            Some(n) if n > 0 && n <= MAX_PAGE_SIZE => n,
+            _ => MAX_PAGE_SIZE,
+        };
+
+        let mut records: Vec<EscrowRecord> = Vec::new(env);
+        let mut past_cursor = after.is_none();
+        let mut has_more = false;
+
+        for i in 0..index.len() {
+            let id = index.get(i).unwrap();
+
+            // Skip until we pass the cursor
+            if !past_cursor {
+                if Some(id) == after {
+                    past_cursor = true;
+                }
+                continue;
+            }
+
+            let escrow = match Self::matching_escrow(env, id, status_match, criteria) {
+                Some(e) => e,
+                None => continue,
+            };
+
+            // Check if we already have enough results
+            if records.len() >= effective_first {
+                has_more = true;
+                break;
+            }
+
+            records.push_back(EscrowRecord {
+                bounty_id: id,
+                depositor: escrow.depositor,
+                amount: escrow.amount,
+                remaining_amount: escrow.remaining_amount,
+                status: escrow.status,
+                deadline: escrow.deadline,
+            });
+        }
+
+        let start_cursor = records.first().map(|r| r.bounty_id);
+        let end_cursor = records.last().map(|r| r.bounty_id);
+        let next_cursor = if has_more { end_cursor } else { None };
+
+        EscrowPage {
+            records,
+            next_cursor,
+            has_more,
+            start_cursor,
+            end_cursor,
+            has_previous: after.is_some(),
+        }
+    }
+
+    fn get_escrows_backward(
+        env: &Env,
+        criteria: &EscrowSearchCriteria,
+        status_match: &Option<EscrowStatus>,
+        index: &Vec<u64>,
+        before: Option<u64>,
+        last: Option<u32>,
     ) -> EscrowPage {
+        // Collect every matching id, in ascending (index) order, so we can
+        // take a window from the end of it.
+        let mut all_matched: Vec<u64> = Vec::new(env);
+        for i in 0..index.len() {
+            let id = index.get(i).unwrap();
+            if Self::matching_escrow(env, id, status_match, criteria).is_some() {
+                all_matched.push_back(id);
+            }
+        }
+
+        // `before` is an exclusive upper bound: only ids that come strictly
+        // earlier than it are eligible. If it's absent (or not found among
+        // the matches) the eligible set is everything matched.
+        let mut boundary = all_matched.len();
+        if let Some(before_id) = before {
+            for i in 0..all_matched.len() {
+                if all_matched.get(i).unwrap() == before_id {
+                    boundary = i;
+                    break;
+                }
+            }
+        }
+
+        let effective_last = match last {
+            Some(n) if n > 0 && n <= MAX_PAGE_SIZE => n,
+            _ => MAX_PAGE_SIZE,
+        };
+        let take = if boundary < effective_last {
+            boundary
+        } else {
+            effective_last
+        };
+        let start = boundary - take;
+
+        let mut records: Vec<EscrowRecord> = Vec::new(env);
+        for i in start..boundary {
+            let id = all_matched.get(i).unwrap();
+            let escrow: Escrow = env.storage().persistent().get(&DataKey::Escrow(id)).unwrap();
+            records.push_back(EscrowRecord {
+                bounty_id: id,
+                depositor: escrow.depositor,
+                amount: escrow.amount,
+                remaining_amount: escrow.remaining_amount,
+                status: escrow.status,
+                deadline: escrow.deadline,
+            });
+        }
+
+        let has_previous = start > 0;
+        let has_more = boundary < all_matched.len();
+        let start_cursor = records.first().map(|r| r.bounty_id);
+        let end_cursor = records.last().map(|r| r.bounty_id);
+        let next_cursor = if has_more { end_cursor } else { None };
+
+        EscrowPage {
+            records,
+            next_cursor,
+            has_more,
+            start_cursor,
+            end_cursor,
+            has_previous,
+        }
+    }
+
+    /// Fetch escrow `id` and return it only if it passes `status_match`/`criteria.depositor`.
+    fn matching_escrow(
+        env: &Env,
+        id: u64,
+        status_match: &Option<EscrowStatus>,
+        criteria: &EscrowSearchCriteria,
+    ) -> Option<Escrow> {
+        let escrow: Escrow = env.storage().persistent().get(&DataKey::Escrow(id))?;
+        if let Some(ref status) = status_match {
+            if escrow.status != *status {
+                return None;
+            }
+        }
+        if let Some(ref depositor) = criteria.depositor {
+            if escrow.depositor != *depositor {
+                return None;
+            }
+        }
+        if criteria.excluded_ids.contains(&id) {
+            return None;
+        }
+        Some(escrow)
+    }
+
+    /// Choose a minimal set of `depositor`'s `Locked` escrows whose
+    /// `remaining_amount`s sum to at least `target_amount`, useful for
+    /// aggregating many small bounties into one settlement.
+    ///
+    /// Implemented as a largest-first greedy pass: the depositor's eligible
+    /// locked records (skipping any id in `excluded_ids`) are repeatedly
+    /// searched for the one with the largest `remaining_amount`, which is
+    /// taken next, until the running sum reaches `target_amount` or
+    /// `max_inputs` records have been chosen. If `max_inputs` is exhausted
+    /// first, or the depositor's total available locked balance can't
+    /// reach `target_amount` at all, returns `Error::SelectionTargetUnreachable`
+    /// rather than a partial set - callers are expected to settle the
+    /// returned records atomically, so a partial selection would be
+    /// actively misleading.
+    pub fn select_escrows(
+        env: Env,
+        depositor: Address,
+        target_amount: i128,
+        max_inputs: u32,
+        excluded_ids: Vec<u64>,
+    ) -> Result<Vec<EscrowRecord>, Error> {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowByDepositor(depositor.clone()))
+            .unwrap_or_else(|| Vec::new(&env));
+
+        let mut candidates: Vec<EscrowRecord> = Vec::new(&env);
+        for i in 0..index.len() {
+            let id = index.get(i).unwrap();
+            if excluded_ids.contains(&id) {
+                continue;
+            }
+            let escrow: Escrow = match env.storage().persistent().get(&DataKey::Escrow(id)) {
+                Some(e) => e,
+                None => continue,
+            };
+            if escrow.status != EscrowStatus::Locked {
+                continue;
+            }
+            candidates.push_back(EscrowRecord {
+                bounty_id: id,
+                depositor: escrow.depositor,
+                amount: escrow.amount,
+                remaining_amount: escrow.remaining_amount,
+                status: escrow.status,
+                deadline: escrow.deadline,
+            });
+        }
+
+        let mut selected: Vec<EscrowRecord> = Vec::new(&env);
+        let mut running_total: i128 = 0;
+        while running_total < target_amount
+            && !candidates.is_empty()
+            && selected.len() < max_inputs
+        {
+            let mut best_idx: u32 = 0;
+            let mut best_amount = candidates.get(0).unwrap().remaining_amount;
+            for i in 1..candidates.len() {
+                let amount = candidates.get(i).unwrap().remaining_amount;
+                if amount > best_amount {
+                    best_amount = amount;
+                    best_idx = i;
+                }
+            }
+            let record = candidates.get(best_idx).unwrap();
+            running_total += record.remaining_amount;
+            selected.push_back(record);
+            candidates.remove(best_idx);
+        }
+
+        if running_total < target_amount {
+            return Err(Error::SelectionTargetUnreachable);
+        }
+
+        Ok(selected)
+    }
+
+    /// Paginated search for `Locked` escrows whose `deadline` has passed
+    /// (i.e. is at or before `env.ledger().timestamp()`), so a keeper can
+    /// discover refund-eligible bounties without scanning and re-checking
+    /// every record client-side. Walks the `Locked` status index rather
+    /// than the global index, and pages the same way `get_escrows` does:
+    /// pass a previous page's `next_cursor` to continue, `None` to start
+    /// from the beginning.
+    pub fn get_expired_escrows(env: Env, cursor: Option<u64>, limit: u32) -> EscrowPage {
         let effective_limit = if limit == 0 || limit > MAX_PAGE_SIZE {
             MAX_PAGE_SIZE
         } else {
             limit
         };
 
-        // Convert u32 status code to EscrowStatus for matching
-        let status_match = match criteria.status_filter {
-            1 => Some(EscrowStatus::Locked),
-            2 => Some(EscrowStatus::Released),
-            3 => Some(EscrowStatus::Refunded),
-            _ => None, // 0 or anything else = match any
-        };
-
         let index: Vec<u64> = env
             .storage()
             .persistent()
-            .get(&DataKey::EscrowIndex)
+            .get(&DataKey::EscrowByStatus(Self::status_code(&EscrowStatus::Locked)))
             .unwrap_or_else(|| Vec::new(&env));
+        let now = env.ledger().timestamp();
 
         let mut records: Vec<EscrowRecord> = Vec::new(&env);
         let mut past_cursor = cursor.is_none();
-        let mut next_cursor: Option<u64> = None;
         let mut has_more = false;
 
         for i in 0..index.len() {
             let id = index.get(i).unwrap();
 
-            // Skip until we pass the cursor
             if !past_cursor {
                 if Some(id) == cursor {
                     past_cursor = true;
@@ -804,37 +1270,19 @@ impl EscrowContract {
                 continue;
             }
 
-            // Fetch the escrow record
-            let escrow_opt: Option<Escrow> = env
-                .storage()
-                .persistent()
-                .get(&DataKey::Escrow(id));
-            if escrow_opt.is_none() {
+            let escrow: Escrow = match env.storage().persistent().get(&DataKey::Escrow(id)) {
+                Some(e) => e,
+                None => continue,
+            };
+            if escrow.status != EscrowStatus::Locked || escrow.deadline > now {
                 continue;
             }
-            let escrow = escrow_opt.unwrap();
 
-            // Apply status filter
-            if let Some(ref status) = status_match {
-                if escrow.status != *status {
-                    continue;
-                }
-            }
-
-            // Apply depositor filter
-            if let Some(ref depositor) = criteria.depositor {
-                if escrow.depositor != *depositor {
-                    continue;
-                }
-            }
-
-            // Check if we already have enough results
             if records.len() >= effective_limit {
                 has_more = true;
                 break;
             }
 
-            next_cursor = Some(id);
             records.push_back(EscrowRecord {
                 bounty_id: id,
                 depositor: escrow.depositor,
@@ -845,15 +1293,59 @@ impl EscrowContract {
             });
         }
 
-        if !has_more {
-            next_cursor = None;
-        }
+        let start_cursor = records.first().map(|r| r.bounty_id);
+        let end_cursor = records.last().map(|r| r.bounty_id);
+        let next_cursor = if has_more { end_cursor } else { None };
 
         EscrowPage {
             records,
             next_cursor,
             has_more,
+            start_cursor,
+            end_cursor,
+            has_previous: cursor.is_some(),
+        }
+    }
+
+    /// Aggregate counts per `EscrowStatus` plus total `amount` and total
+    /// `remaining_amount` across every escrow matching `criteria` (the
+    /// same criteria shape `get_escrows` takes). Unlike `get_escrows` this
+    /// is an unbounded scan, but it still walks the narrowest applicable
+    /// secondary index and folds the aggregates in one pass, rather than
+    /// requiring a dashboard to page through every record and sum
+    /// client-side.
+    pub fn get_escrow_stats(env: Env, criteria: EscrowSearchCriteria) -> EscrowStats {
+        let status_match = Self::status_match_for_criteria(criteria.status_filter);
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&Self::search_index_key(&criteria, &status_match))
+            .unwrap_or_else(|| Vec::new(&env));
+
+        let mut stats = EscrowStats {
+            locked_count: 0,
+            released_count: 0,
+            refunded_count: 0,
+            total_amount: 0,
+            total_remaining_amount: 0,
+        };
+
+        for i in 0..index.len() {
+            let id = index.get(i).unwrap();
+            let escrow = match Self::matching_escrow(&env, id, &status_match, &criteria) {
+                Some(e) => e,
+                None => continue,
+            };
+            match escrow.status {
+                EscrowStatus::Locked => stats.locked_count += 1,
+                EscrowStatus::Released => stats.released_count += 1,
+                EscrowStatus::Refunded => stats.refunded_count += 1,
+            }
+            stats.total_amount += escrow.amount;
+            stats.total_remaining_amount += escrow.remaining_amount;
         }
+
+        stats
     }
 }
 
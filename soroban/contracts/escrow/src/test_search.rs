@@ -53,8 +53,9 @@ fn test_search_empty_contract() {
     let criteria = EscrowSearchCriteria {
         status_filter: 0,
         depositor: None,
+        excluded_ids: Vec::new(&env),
     };
-    let page = client.get_escrows(&criteria, &None, &10);
+    let page = client.get_escrows(&criteria, &None, &None, &10, &None);
     assert_eq!(page.records.len(), 0);
     assert_eq!(page.next_cursor, None);
     assert!(!page.has_more);
@@ -76,8 +77,9 @@ fn test_search_lists_all_escrows() {
     let criteria = EscrowSearchCriteria {
         status_filter: 0,
         depositor: None,
+        excluded_ids: Vec::new(&env),
     };
-    let page = client.get_escrows(&criteria, &None, &10);
+    let page = client.get_escrows(&criteria, &None, &None, &10, &None);
     assert_eq!(page.records.len(), 5);
     assert!(!page.has_more);
     assert_eq!(page.next_cursor, None);
@@ -99,10 +101,11 @@ fn test_search_pagination_basic() {
     let criteria = EscrowSearchCriteria {
         status_filter: 0,
         depositor: None,
+        excluded_ids: Vec::new(&env),
     };
 
     // First page: limit 2
-    let page1 = client.get_escrows(&criteria, &None, &2);
+    let page1 = client.get_escrows(&criteria, &None, &None, &2, &None);
     assert_eq!(page1.records.len(), 2);
     assert!(page1.has_more);
     assert!(page1.next_cursor.is_some());
@@ -110,14 +113,14 @@ fn test_search_pagination_basic() {
     assert_eq!(page1.records.get(1).unwrap().bounty_id, 2);
 
     // Second page: start after cursor
-    let page2 = client.get_escrows(&criteria, &page1.next_cursor, &2);
+    let page2 = client.get_escrows(&criteria, &page1.next_cursor, &None, &2, &None);
     assert_eq!(page2.records.len(), 2);
     assert!(page2.has_more);
     assert_eq!(page2.records.get(0).unwrap().bounty_id, 3);
     assert_eq!(page2.records.get(1).unwrap().bounty_id, 4);
 
     // Third page: last result
-    let page3 = client.get_escrows(&criteria, &page2.next_cursor, &2);
+    let page3 = client.get_escrows(&criteria, &page2.next_cursor, &None, &2, &None);
     assert_eq!(page3.records.len(), 1);
     assert!(!page3.has_more);
     assert_eq!(page3.next_cursor, None);
@@ -142,8 +145,9 @@ fn test_search_filter_by_status() {
     let locked_criteria = EscrowSearchCriteria {
         status_filter: 1,
         depositor: None,
+        excluded_ids: Vec::new(&env),
     };
-    let page = client.get_escrows(&locked_criteria, &None, &10);
+    let page = client.get_escrows(&locked_criteria, &None, &None, &10, &None);
     assert_eq!(page.records.len(), 2);
     assert_eq!(page.records.get(0).unwrap().bounty_id, 1);
     assert_eq!(page.records.get(1).unwrap().bounty_id, 3);
@@ -152,8 +156,9 @@ fn test_search_filter_by_status() {
     let released_criteria = EscrowSearchCriteria {
         status_filter: 2,
         depositor: None,
+        excluded_ids: Vec::new(&env),
     };
-    let page = client.get_escrows(&released_criteria, &None, &10);
+    let page = client.get_escrows(&released_criteria, &None, &None, &10, &None);
     assert_eq!(page.records.len(), 1);
     assert_eq!(page.records.get(0).unwrap().bounty_id, 2);
 }
@@ -177,16 +182,18 @@ fn test_search_filter_by_depositor() {
     let criteria = EscrowSearchCriteria {
         status_filter: 0,
         depositor: Some(depositor.clone()),
+        excluded_ids: Vec::new(&env),
     };
-    let page = client.get_escrows(&criteria, &None, &10);
+    let page = client.get_escrows(&criteria, &None, &None, &10, &None);
     assert_eq!(page.records.len(), 3);
 
     // Filter by a non-existent depositor returns empty
     let criteria_other = EscrowSearchCriteria {
         status_filter: 0,
         depositor: Some(depositor2.clone()),
+        excluded_ids: Vec::new(&env),
     };
-    let page_other = client.get_escrows(&criteria_other, &None, &10);
+    let page_other = client.get_escrows(&criteria_other, &None, &None, &10, &None);
     assert_eq!(page_other.records.len(), 0);
 }
 
@@ -206,10 +213,11 @@ fn test_search_page_size_cap() {
     let criteria = EscrowSearchCriteria {
         status_filter: 0,
         depositor: None,
+        excluded_ids: Vec::new(&env),
     };
 
     // Request 100 (exceeds cap), should return 20
-    let page = client.get_escrows(&criteria, &None, &100);
+    let page = client.get_escrows(&criteria, &None, &None, &100, &None);
     assert_eq!(page.records.len(), 20);
     assert!(page.has_more);
     assert!(page.next_cursor.is_some());
@@ -235,16 +243,298 @@ fn test_search_combined_criteria_pagination() {
     let criteria = EscrowSearchCriteria {
         status_filter: 1,
         depositor: Some(depositor.clone()),
+        excluded_ids: Vec::new(&env),
     };
 
-    let page1 = client.get_escrows(&criteria, &None, &2);
+    let page1 = client.get_escrows(&criteria, &None, &None, &2, &None);
     assert_eq!(page1.records.len(), 2);
     assert!(page1.has_more);
     assert_eq!(page1.records.get(0).unwrap().bounty_id, 1);
     assert_eq!(page1.records.get(1).unwrap().bounty_id, 3);
 
-    let page2 = client.get_escrows(&criteria, &page1.next_cursor, &2);
+    let page2 = client.get_escrows(&criteria, &page1.next_cursor, &None, &2, &None);
     assert_eq!(page2.records.len(), 1);
     assert!(!page2.has_more);
     assert_eq!(page2.records.get(0).unwrap().bounty_id, 5);
 }
+
+// ==================== BACKWARD PAGINATION ====================
+
+#[test]
+fn test_search_pagination_backward() {
+    let env = Env::default();
+    let (client, _cid, _admin, depositor, _contributor, _tc) = setup_search(&env, 100_000);
+
+    let deadline = env.ledger().timestamp() + 1000;
+    for id in 1..=5u64 {
+        client.lock_funds(&depositor, &id, &1_000, &deadline);
+    }
+
+    let criteria = EscrowSearchCriteria {
+        status_filter: 0,
+        depositor: None,
+        excluded_ids: Vec::new(&env),
+    };
+
+    // Last page: the final 2 records
+    let page1 = client.get_escrows(&criteria, &None, &None, &None, &2);
+    assert_eq!(page1.records.len(), 2);
+    assert!(page1.has_previous);
+    assert!(!page1.has_more);
+    assert_eq!(page1.records.get(0).unwrap().bounty_id, 4);
+    assert_eq!(page1.records.get(1).unwrap().bounty_id, 5);
+
+    // Page before that, anchored on the first page's start_cursor
+    let page2 = client.get_escrows(&criteria, &None, &page1.start_cursor, &None, &2);
+    assert_eq!(page2.records.len(), 2);
+    assert!(page2.has_previous);
+    assert!(page2.has_more);
+    assert_eq!(page2.records.get(0).unwrap().bounty_id, 2);
+    assert_eq!(page2.records.get(1).unwrap().bounty_id, 3);
+
+    // Final page walking backward: the first record, nothing before it
+    let page3 = client.get_escrows(&criteria, &None, &page2.start_cursor, &None, &2);
+    assert_eq!(page3.records.len(), 1);
+    assert!(!page3.has_previous);
+    assert!(page3.has_more);
+    assert_eq!(page3.records.get(0).unwrap().bounty_id, 1);
+}
+
+#[test]
+fn test_search_forward_and_backward_cursors_agree() {
+    let env = Env::default();
+    let (client, _cid, _admin, depositor, _contributor, _tc) = setup_search(&env, 100_000);
+
+    let deadline = env.ledger().timestamp() + 1000;
+    for id in 1..=5u64 {
+        client.lock_funds(&depositor, &id, &1_000, &deadline);
+    }
+
+    let criteria = EscrowSearchCriteria {
+        status_filter: 0,
+        depositor: None,
+        excluded_ids: Vec::new(&env),
+    };
+
+    let forward_page = client.get_escrows(&criteria, &None, &None, &3, &None);
+    let backward_page = client.get_escrows(&criteria, &None, &None, &None, &3);
+
+    // Forward page [1,2,3], backward page [3,4,5]: they share bounty_id 3.
+    assert_eq!(forward_page.end_cursor, Some(3));
+    assert_eq!(backward_page.start_cursor, Some(3));
+}
+
+// ==================== EXCLUDED IDS ====================
+
+#[test]
+fn test_search_excluded_ids_are_skipped() {
+    let env = Env::default();
+    let (client, _cid, _admin, depositor, _contributor, _tc) = setup_search(&env, 100_000);
+
+    let deadline = env.ledger().timestamp() + 1000;
+    for id in 1..=5u64 {
+        client.lock_funds(&depositor, &id, &1_000, &deadline);
+    }
+
+    let mut excluded_ids = Vec::new(&env);
+    excluded_ids.push_back(2u64);
+    excluded_ids.push_back(4u64);
+    let criteria = EscrowSearchCriteria {
+        status_filter: 0,
+        depositor: None,
+        excluded_ids,
+    };
+
+    let page = client.get_escrows(&criteria, &None, &None, &10, &None);
+    assert_eq!(page.records.len(), 3);
+    assert_eq!(page.records.get(0).unwrap().bounty_id, 1);
+    assert_eq!(page.records.get(1).unwrap().bounty_id, 3);
+    assert_eq!(page.records.get(2).unwrap().bounty_id, 5);
+    assert_eq!(page.next_cursor, None);
+    assert!(!page.has_more);
+}
+
+#[test]
+fn test_search_excluded_ids_do_not_advance_cursor_onto_them() {
+    let env = Env::default();
+    let (client, _cid, _admin, depositor, _contributor, _tc) = setup_search(&env, 100_000);
+
+    let deadline = env.ledger().timestamp() + 1000;
+    for id in 1..=3u64 {
+        client.lock_funds(&depositor, &id, &1_000, &deadline);
+    }
+
+    let mut excluded_ids = Vec::new(&env);
+    excluded_ids.push_back(3u64);
+    let criteria = EscrowSearchCriteria {
+        status_filter: 0,
+        depositor: None,
+        excluded_ids,
+    };
+
+    // Limit 2 over escrows [1,2,3] with 3 excluded: only 1 and 2 can ever
+    // match, so the page is complete and the cursor must not land on the
+    // excluded id.
+    let page = client.get_escrows(&criteria, &None, &None, &2, &None);
+    assert_eq!(page.records.len(), 2);
+    assert_eq!(page.next_cursor, None);
+    assert!(!page.has_more);
+}
+
+// ==================== ESCROW SELECTION ====================
+
+#[test]
+fn test_select_escrows_largest_first_greedy() {
+    let env = Env::default();
+    let (client, _cid, _admin, depositor, _contributor, _tc) = setup_search(&env, 100_000);
+
+    let deadline = env.ledger().timestamp() + 1000;
+    client.lock_funds(&depositor, &1, &100, &deadline);
+    client.lock_funds(&depositor, &2, &500, &deadline);
+    client.lock_funds(&depositor, &3, &300, &deadline);
+
+    // Target 700: largest-first picks 500 then 300, covering it in 2 inputs.
+    let selected = client
+        .select_escrows(&depositor, &700, &10, &Vec::new(&env))
+        .unwrap();
+    assert_eq!(selected.len(), 2);
+    assert_eq!(selected.get(0).unwrap().bounty_id, 2);
+    assert_eq!(selected.get(1).unwrap().bounty_id, 3);
+}
+
+#[test]
+fn test_select_escrows_rejects_when_max_inputs_hit_before_target() {
+    let env = Env::default();
+    let (client, _cid, _admin, depositor, _contributor, _tc) = setup_search(&env, 100_000);
+
+    let deadline = env.ledger().timestamp() + 1000;
+    client.lock_funds(&depositor, &1, &100, &deadline);
+    client.lock_funds(&depositor, &2, &100, &deadline);
+    client.lock_funds(&depositor, &3, &100, &deadline);
+
+    let result = client.try_select_escrows(&depositor, &700, &2, &Vec::new(&env));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_select_escrows_rejects_when_available_balance_insufficient() {
+    let env = Env::default();
+    let (client, _cid, _admin, depositor, _contributor, _tc) = setup_search(&env, 100_000);
+
+    let deadline = env.ledger().timestamp() + 1000;
+    client.lock_funds(&depositor, &1, &100, &deadline);
+    client.lock_funds(&depositor, &2, &200, &deadline);
+
+    let result = client.try_select_escrows(&depositor, &1_000, &10, &Vec::new(&env));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_select_escrows_skips_excluded_and_released() {
+    let env = Env::default();
+    let (client, _cid, _admin, depositor, contributor, _tc) = setup_search(&env, 100_000);
+
+    let deadline = env.ledger().timestamp() + 1000;
+    client.lock_funds(&depositor, &1, &500, &deadline);
+    client.lock_funds(&depositor, &2, &500, &deadline);
+    client.lock_funds(&depositor, &3, &500, &deadline);
+    client.release_funds(&1, &contributor);
+
+    let mut excluded_ids = Vec::new(&env);
+    excluded_ids.push_back(2u64);
+
+    // #1 is Released (ineligible), #2 is excluded: only #3 can be picked.
+    let selected = client
+        .select_escrows(&depositor, &500, &10, &excluded_ids)
+        .unwrap();
+    assert_eq!(selected.len(), 1);
+    assert_eq!(selected.get(0).unwrap().bounty_id, 3);
+}
+
+// ==================== EXPIRED ESCROWS ====================
+
+#[test]
+fn test_get_expired_escrows_only_returns_locked_past_deadline() {
+    let env = Env::default();
+    let (client, _cid, _admin, depositor, contributor, _tc) = setup_search(&env, 100_000);
+
+    let now = env.ledger().timestamp();
+    client.lock_funds(&depositor, &1, &100, &(now + 1000)); // not yet expired
+    client.lock_funds(&depositor, &2, &100, &now); // expired (deadline == now)
+    client.lock_funds(&depositor, &3, &100, &(now + 1)); // will expire after advancing
+    client.release_funds(&1, &contributor); // no longer Locked, excluded regardless
+
+    env.ledger().with_mut(|l| l.timestamp = now + 2);
+
+    let page = client.get_expired_escrows(&None, &10);
+    assert_eq!(page.records.len(), 2);
+    assert_eq!(page.records.get(0).unwrap().bounty_id, 2);
+    assert_eq!(page.records.get(1).unwrap().bounty_id, 3);
+    assert!(!page.has_more);
+}
+
+#[test]
+fn test_get_expired_escrows_paginates() {
+    let env = Env::default();
+    let (client, _cid, _admin, depositor, _contributor, _tc) = setup_search(&env, 100_000);
+
+    let now = env.ledger().timestamp();
+    for id in 1..=4u64 {
+        client.lock_funds(&depositor, &id, &100, &now);
+    }
+
+    let page1 = client.get_expired_escrows(&None, &2);
+    assert_eq!(page1.records.len(), 2);
+    assert!(page1.has_more);
+
+    let page2 = client.get_expired_escrows(&page1.next_cursor, &2);
+    assert_eq!(page2.records.len(), 2);
+    assert!(!page2.has_more);
+}
+
+// ==================== AGGREGATE STATS ====================
+
+#[test]
+fn test_get_escrow_stats_counts_and_totals() {
+    let env = Env::default();
+    let (client, _cid, _admin, depositor, contributor, _tc) = setup_search(&env, 100_000);
+
+    let deadline = env.ledger().timestamp() + 1000;
+    client.lock_funds(&depositor, &1, &1_000, &deadline);
+    client.lock_funds(&depositor, &2, &2_000, &deadline);
+    client.lock_funds(&depositor, &3, &3_000, &deadline);
+    client.release_funds(&2, &contributor);
+
+    let criteria = EscrowSearchCriteria {
+        status_filter: 0,
+        depositor: None,
+        excluded_ids: Vec::new(&env),
+    };
+    let stats = client.get_escrow_stats(&criteria);
+    assert_eq!(stats.locked_count, 2);
+    assert_eq!(stats.released_count, 1);
+    assert_eq!(stats.refunded_count, 0);
+    assert_eq!(stats.total_amount, 6_000);
+    // #2's remaining_amount is zeroed out by release_funds.
+    assert_eq!(stats.total_remaining_amount, 1_000 + 0 + 3_000);
+}
+
+#[test]
+fn test_get_escrow_stats_respects_depositor_filter() {
+    let env = Env::default();
+    let (client, _cid, _admin, depositor, _contributor, _tc) = setup_search(&env, 100_000);
+
+    let depositor2 = Address::generate(&env);
+    let deadline = env.ledger().timestamp() + 1000;
+    client.lock_funds(&depositor, &1, &1_000, &deadline);
+    client.lock_funds(&depositor, &2, &2_000, &deadline);
+
+    let criteria = EscrowSearchCriteria {
+        status_filter: 0,
+        depositor: Some(depositor2.clone()),
+        excluded_ids: Vec::new(&env),
+    };
+    let stats = client.get_escrow_stats(&criteria);
+    assert_eq!(stats.locked_count, 0);
+    assert_eq!(stats.total_amount, 0);
+}
@@ -34,13 +34,24 @@ macro_rules! setup_search {
 #[test]
 fn test_search_empty_contract() {
     setup_search!(
-        env, client, _contract_id, _admin, _program_admin,
-        _token_client, _token_admin, 0i128
+        env,
+        client,
+        _contract_id,
+        _admin,
+        _program_admin,
+        _token_client,
+        _token_admin,
+        0i128
     );
 
     let criteria = ProgramSearchCriteria {
         status_filter: 0,
         admin: None,
+        min_funding: None,
+        max_funding: None,
+        name_prefix: None,
+        jurisdiction: None,
+        sort: ProgramSortOrder::IdAscending,
     };
     let page = client.get_programs(&criteria, &None, &10);
     assert_eq!(page.records.len(), 0);
@@ -54,8 +65,14 @@ fn test_search_empty_contract() {
 #[test]
 fn test_search_lists_all_programs() {
     setup_search!(
-        env, client, _contract_id, _admin, program_admin,
-        _token_client, _token_admin, 100_000i128
+        env,
+        client,
+        _contract_id,
+        _admin,
+        program_admin,
+        _token_client,
+        _token_admin,
+        100_000i128
     );
 
     for id in 1..=5u64 {
@@ -70,6 +87,11 @@ fn test_search_lists_all_programs() {
     let criteria = ProgramSearchCriteria {
         status_filter: 0,
         admin: None,
+        min_funding: None,
+        max_funding: None,
+        name_prefix: None,
+        jurisdiction: None,
+        sort: ProgramSortOrder::IdAscending,
     };
     let page = client.get_programs(&criteria, &None, &10);
     assert_eq!(page.records.len(), 5);
@@ -83,8 +105,14 @@ fn test_search_lists_all_programs() {
 #[test]
 fn test_search_pagination_basic() {
     setup_search!(
-        env, client, _contract_id, _admin, program_admin,
-        _token_client, _token_admin, 100_000i128
+        env,
+        client,
+        _contract_id,
+        _admin,
+        program_admin,
+        _token_client,
+        _token_admin,
+        100_000i128
     );
 
     for id in 1..=5u64 {
@@ -99,6 +127,11 @@ fn test_search_pagination_basic() {
     let criteria = ProgramSearchCriteria {
         status_filter: 0,
         admin: None,
+        min_funding: None,
+        max_funding: None,
+        name_prefix: None,
+        jurisdiction: None,
+        sort: ProgramSortOrder::IdAscending,
     };
 
     // First page: limit 2
@@ -129,8 +162,14 @@ fn test_search_pagination_basic() {
 #[test]
 fn test_search_filter_by_status() {
     setup_search!(
-        env, client, _contract_id, _admin, program_admin,
-        _token_client, _token_admin, 100_000i128
+        env,
+        client,
+        _contract_id,
+        _admin,
+        program_admin,
+        _token_client,
+        _token_admin,
+        100_000i128
     );
 
     // Register 3 programs — all Active by default
@@ -147,6 +186,11 @@ fn test_search_filter_by_status() {
     let active_criteria = ProgramSearchCriteria {
         status_filter: 1,
         admin: None,
+        min_funding: None,
+        max_funding: None,
+        name_prefix: None,
+        jurisdiction: None,
+        sort: ProgramSortOrder::IdAscending,
     };
     let page = client.get_programs(&active_criteria, &None, &10);
     assert_eq!(page.records.len(), 3);
@@ -155,6 +199,11 @@ fn test_search_filter_by_status() {
     let completed_criteria = ProgramSearchCriteria {
         status_filter: 2,
         admin: None,
+        min_funding: None,
+        max_funding: None,
+        name_prefix: None,
+        jurisdiction: None,
+        sort: ProgramSortOrder::IdAscending,
     };
     let page = client.get_programs(&completed_criteria, &None, &10);
     assert_eq!(page.records.len(), 0);
@@ -165,8 +214,14 @@ fn test_search_filter_by_status() {
 #[test]
 fn test_search_filter_by_admin() {
     setup_search!(
-        env, client, _contract_id, _admin, program_admin,
-        _token_client, token_admin, 100_000i128
+        env,
+        client,
+        _contract_id,
+        _admin,
+        program_admin,
+        _token_client,
+        token_admin,
+        100_000i128
     );
 
     let other_admin = Address::generate(&env);
@@ -196,6 +251,11 @@ fn test_search_filter_by_admin() {
     let criteria = ProgramSearchCriteria {
         status_filter: 0,
         admin: Some(program_admin.clone()),
+        min_funding: None,
+        max_funding: None,
+        name_prefix: None,
+        jurisdiction: None,
+        sort: ProgramSortOrder::IdAscending,
     };
     let page = client.get_programs(&criteria, &None, &10);
     assert_eq!(page.records.len(), 2);
@@ -206,6 +266,11 @@ fn test_search_filter_by_admin() {
     let criteria_other = ProgramSearchCriteria {
         status_filter: 0,
         admin: Some(other_admin.clone()),
+        min_funding: None,
+        max_funding: None,
+        name_prefix: None,
+        jurisdiction: None,
+        sort: ProgramSortOrder::IdAscending,
     };
     let page_other = client.get_programs(&criteria_other, &None, &10);
     assert_eq!(page_other.records.len(), 1);
@@ -217,8 +282,14 @@ fn test_search_filter_by_admin() {
 #[test]
 fn test_search_page_size_cap() {
     setup_search!(
-        env, client, _contract_id, _admin, program_admin,
-        _token_client, _token_admin, 1_000_000i128
+        env,
+        client,
+        _contract_id,
+        _admin,
+        program_admin,
+        _token_client,
+        _token_admin,
+        1_000_000i128
     );
 
     // Create 25 programs
@@ -234,6 +305,11 @@ fn test_search_page_size_cap() {
     let criteria = ProgramSearchCriteria {
         status_filter: 0,
         admin: None,
+        min_funding: None,
+        max_funding: None,
+        name_prefix: None,
+        jurisdiction: None,
+        sort: ProgramSortOrder::IdAscending,
     };
 
     // Request 100 (exceeds cap), should return 20
@@ -248,8 +324,14 @@ fn test_search_page_size_cap() {
 #[test]
 fn test_search_batch_registered_programs() {
     setup_search!(
-        env, client, _contract_id, _admin, program_admin,
-        _token_client, _token_admin, 100_000i128
+        env,
+        client,
+        _contract_id,
+        _admin,
+        program_admin,
+        _token_client,
+        _token_admin,
+        100_000i128
     );
 
     let items = vec![
@@ -279,6 +361,11 @@ fn test_search_batch_registered_programs() {
     let criteria = ProgramSearchCriteria {
         status_filter: 0,
         admin: None,
+        min_funding: None,
+        max_funding: None,
+        name_prefix: None,
+        jurisdiction: None,
+        sort: ProgramSortOrder::IdAscending,
     };
     let page = client.get_programs(&criteria, &None, &10);
     assert_eq!(page.records.len(), 3);
@@ -287,3 +374,166 @@ fn test_search_batch_registered_programs() {
     assert_eq!(page.records.get(2).unwrap().program_id, 30);
     assert_eq!(client.get_program_count(), 3);
 }
+
+// ==================== FILTER BY FUNDING RANGE ====================
+
+#[test]
+fn test_search_filter_by_funding_range() {
+    setup_search!(
+        env,
+        client,
+        _contract_id,
+        _admin,
+        program_admin,
+        _token_client,
+        _token_admin,
+        100_000i128
+    );
+
+    let fundings = [500i128, 1_000, 1_500, 2_000, 2_500];
+    for (i, amount) in fundings.iter().enumerate() {
+        client.register_program(
+            &(i as u64 + 1),
+            &program_admin,
+            &String::from_str(&env, "Program"),
+            amount,
+        );
+    }
+
+    let criteria = ProgramSearchCriteria {
+        status_filter: 0,
+        admin: None,
+        min_funding: Some(1_000),
+        max_funding: Some(2_000),
+        name_prefix: None,
+        jurisdiction: None,
+        sort: ProgramSortOrder::IdAscending,
+    };
+    let page = client.get_programs(&criteria, &None, &10);
+    assert_eq!(page.records.len(), 3);
+    assert_eq!(page.records.get(0).unwrap().program_id, 2);
+    assert_eq!(page.records.get(1).unwrap().program_id, 3);
+    assert_eq!(page.records.get(2).unwrap().program_id, 4);
+}
+
+// ==================== FILTER BY NAME PREFIX ====================
+
+#[test]
+fn test_search_filter_by_name_prefix() {
+    setup_search!(
+        env,
+        client,
+        _contract_id,
+        _admin,
+        program_admin,
+        _token_client,
+        _token_admin,
+        100_000i128
+    );
+
+    client.register_program(
+        &1,
+        &program_admin,
+        &String::from_str(&env, "Alpha Fund"),
+        &1_000,
+    );
+    client.register_program(
+        &2,
+        &program_admin,
+        &String::from_str(&env, "Alpine Grant"),
+        &1_000,
+    );
+    client.register_program(
+        &3,
+        &program_admin,
+        &String::from_str(&env, "Beta Fund"),
+        &1_000,
+    );
+
+    let criteria = ProgramSearchCriteria {
+        status_filter: 0,
+        admin: None,
+        min_funding: None,
+        max_funding: None,
+        name_prefix: Some(String::from_str(&env, "Al")),
+        jurisdiction: None,
+        sort: ProgramSortOrder::IdAscending,
+    };
+    let page = client.get_programs(&criteria, &None, &10);
+    assert_eq!(page.records.len(), 2);
+    assert_eq!(page.records.get(0).unwrap().program_id, 1);
+    assert_eq!(page.records.get(1).unwrap().program_id, 2);
+
+    // A prefix longer than every name matches nothing.
+    let too_long = ProgramSearchCriteria {
+        status_filter: 0,
+        admin: None,
+        min_funding: None,
+        max_funding: None,
+        name_prefix: Some(String::from_str(&env, "Alpha Fund Extended")),
+        jurisdiction: None,
+        sort: ProgramSortOrder::IdAscending,
+    };
+    let page = client.get_programs(&too_long, &None, &10);
+    assert_eq!(page.records.len(), 0);
+}
+
+// ==================== SORT ORDER ====================
+
+#[test]
+fn test_search_sort_by_funding_stable_pagination() {
+    setup_search!(
+        env,
+        client,
+        _contract_id,
+        _admin,
+        program_admin,
+        _token_client,
+        _token_admin,
+        100_000i128
+    );
+
+    // program_id ascending is the reverse of funding ascending here.
+    let fundings = [(1u64, 500i128), (2, 1_500), (3, 1_000), (4, 2_000)];
+    for (id, amount) in fundings.iter() {
+        client.register_program(
+            id,
+            &program_admin,
+            &String::from_str(&env, "Program"),
+            amount,
+        );
+    }
+
+    let criteria = ProgramSearchCriteria {
+        status_filter: 0,
+        admin: None,
+        min_funding: None,
+        max_funding: None,
+        name_prefix: None,
+        jurisdiction: None,
+        sort: ProgramSortOrder::FundingAscending,
+    };
+
+    let page1 = client.get_programs(&criteria, &None, &2);
+    assert_eq!(page1.records.len(), 2);
+    assert_eq!(page1.records.get(0).unwrap().program_id, 1); // funding 500
+    assert_eq!(page1.records.get(1).unwrap().program_id, 3); // funding 1000
+    assert!(page1.has_more);
+
+    let page2 = client.get_programs(&criteria, &page1.next_cursor, &2);
+    assert_eq!(page2.records.len(), 2);
+    assert_eq!(page2.records.get(0).unwrap().program_id, 2); // funding 1500
+    assert_eq!(page2.records.get(1).unwrap().program_id, 4); // funding 2000
+    assert!(!page2.has_more);
+
+    // FundingDescending should reverse the full order.
+    let desc_criteria = ProgramSearchCriteria {
+        sort: ProgramSortOrder::FundingDescending,
+        ..criteria
+    };
+    let desc_page = client.get_programs(&desc_criteria, &None, &10);
+    assert_eq!(desc_page.records.get(0).unwrap().program_id, 4);
+    assert_eq!(desc_page.records.get(1).unwrap().program_id, 2);
+    assert_eq!(desc_page.records.get(2).unwrap().program_id, 3);
+    assert_eq!(desc_page.records.get(3).unwrap().program_id, 1);
+}